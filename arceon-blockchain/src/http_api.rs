@@ -0,0 +1,109 @@
+//! Read-only HTTP query API over a running `BlockchainManager`.
+//!
+//! Every route serializes the same structs the in-process calls return:
+//!
+//! - `GET /block/:epoch` → `FinalizedBlock` (404 if the epoch has no block)
+//! - `GET /stats` → `BlockchainStats`
+//! - `GET /balance/:account` → token type → balance for that account
+//! - `GET /saves` → `Vec<BlockchainSaveInfo>`
+
+use crate::{BlockchainManager, BlockchainSaveInfo, BlockchainStats, FinalizedBlock};
+use anyhow::Result;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{info, error};
+use uuid::Uuid;
+
+type Shared = State<Arc<BlockchainManager>>;
+
+/// Routes of the query API, for embedding in a larger server
+pub fn router(manager: Arc<BlockchainManager>) -> Router {
+    Router::new()
+        .route("/block/:epoch", get(block))
+        .route("/stats", get(stats))
+        .route("/balance/:account", get(balance))
+        .route("/saves", get(saves))
+        .with_state(manager)
+}
+
+/// Bind `http_bind_address` from the manager's config and serve the query API in the
+/// background. Returns the address actually bound, so a port of 0 can be used.
+pub async fn serve(manager: Arc<BlockchainManager>) -> Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = tokio::net::TcpListener::bind(&manager.config.http_bind_address).await?;
+    let addr = listener.local_addr()?;
+    let app = router(manager);
+
+    let handle = tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("HTTP query API stopped: {}", e);
+        }
+    });
+
+    info!("🌐 Blockchain query API listening on http://{}", addr);
+    Ok((addr, handle))
+}
+
+async fn block(State(manager): Shared, Path(epoch): Path<u64>) -> Result<Json<FinalizedBlock>, StatusCode> {
+    manager.get_block(epoch).await.map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn stats(State(manager): Shared) -> Json<BlockchainStats> {
+    Json(manager.get_blockchain_stats().await)
+}
+
+async fn balance(State(manager): Shared, Path(account): Path<Uuid>) -> Result<Json<HashMap<String, u64>>, StatusCode> {
+    manager.get_all_balances(account).await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+async fn saves(State(manager): Shared) -> Result<Json<Vec<BlockchainSaveInfo>>, StatusCode> {
+    manager.list_blockchain_saves().await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Plain HTTP/1.1 GET; returns the status line and body
+    async fn http_get(addr: SocketAddr, path: &str) -> (String, String) {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", path, addr);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap().to_string(), body.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_stats_endpoint_serves_blockchain_stats() {
+        let mut config = arceon_core::config::Config::default().blockchain;
+        config.http_bind_address = "127.0.0.1:0".to_string();
+        let mut manager = BlockchainManager::new(&config).await.unwrap();
+        manager.start(Uuid::new_v4(), true, 1_000).await.unwrap();
+        let manager = Arc::new(manager);
+
+        let (addr, server) = serve(manager.clone()).await.unwrap();
+        let (status, body) = http_get(addr, "/stats").await;
+
+        assert!(status.contains("200"), "{}", status);
+        let served: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let expected = serde_json::to_value(manager.get_blockchain_stats().await).unwrap();
+        assert_eq!(served, expected);
+
+        let (status, _) = http_get(addr, "/block/999999").await;
+        assert!(status.contains("404"), "{}", status);
+        server.abort();
+    }
+}
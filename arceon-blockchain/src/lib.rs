@@ -1,15 +1,20 @@
 use anyhow::Result;
+use arceon_core::channel::{self, ConsensusSender};
 use arceon_core::config::BlockchainConfig;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, BTreeMap, VecDeque};
+use std::collections::{HashMap, BTreeMap, BTreeSet, VecDeque};
 use std::time::SystemTime;
-use tokio::sync::{RwLock, mpsc};
-use tracing::{info, warn, error, debug};
+use tokio::sync::{broadcast, RwLock, mpsc};
+use tracing::{info, warn, error, debug, info_span, Instrument};
 use uuid::Uuid;
 use std::sync::Arc;
 use sha2::{Sha256, Digest};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 
 // Import our new blockchain modules
+pub use arceon_crypto::merkle;
+#[cfg(feature = "http-api")]
+pub mod http_api;
 pub mod nft_system;
 pub mod token_economy;
 
@@ -19,6 +24,37 @@ pub use token_economy::{TokenEconomySystem, TokenDefinition, TokenTransaction, T
 // Consensus types integrated into blockchain module
 pub type BlockHash = [u8; 32];
 
+/// Prefix marking deflate-compressed block data in saves (format version 1)
+const COMPRESSED_BLOCKS_MAGIC: &[u8] = b"ARCZ\x01";
+
+/// Finalized blocks buffered per subscriber before the slowest one starts missing blocks
+const BLOCK_SUBSCRIPTION_CAPACITY: usize = 64;
+
+/// Length of one uptime sample; a node seen at any point in an interval was up for all of it
+const ACTIVITY_INTERVAL_SECS: u64 = 300;
+
+/// Uptime is measured over this many of the most recent activity intervals (30 days)
+const UPTIME_WINDOW_INTERVALS: u64 = 30 * 24 * 60 * 60 / ACTIVITY_INTERVAL_SECS;
+
+/// Areas present in every world at genesis; part of the genesis hash
+pub const GENESIS_AREAS: [&str; 3] = ["Central Plains", "Alderheart", "Silverleaf Enclave"];
+
+/// Format of the `PortableWorldExport` files this build writes. Files with the same major
+/// version are migrated up on import; any other major version is rejected.
+pub const CURRENT_EXPORT_VERSION: &str = "1.1";
+
+/// Rewrites the raw JSON of an export in place to the next minor version
+type ExportMigration = fn(&mut serde_json::Value) -> Result<()>;
+
+/// Upgrades from one minor export version to the next, applied in order to the raw JSON
+/// of an older export before it is deserialized
+const EXPORT_MIGRATIONS: &[(&str, &str, ExportMigration)] = &[
+    ("1.0", "1.1", migrate_export_1_0_to_1_1),
+];
+
+/// Account holding tokens locked by outgoing bridge transfers until they complete or are refunded
+pub const BRIDGE_ESCROW_ACCOUNT: Uuid = Uuid::from_u128(0x4252_4944_4745);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WorldChange {
     PlayerAction {
@@ -68,6 +104,10 @@ pub struct FinalizedBlock {
     pub validator_signatures: HashMap<Uuid, String>,
     pub merkle_root: String,
     pub previous_hash: Option<BlockHash>,
+    /// `(initiator, nonce)` of each transaction the block finalizes, so every node advances
+    /// nonces from the block itself rather than from whatever its own mempool held
+    #[serde(default)]
+    pub transaction_nonces: Vec<(Uuid, u64)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +118,7 @@ pub enum ConsensusMessage {
     ValidatorJoin {
         node_id: Uuid,
         stake_amount: u64,
+        public_key: [u8; 32], // ed25519 key the validator signs finalized blocks with
         timestamp: SystemTime,
     },
     ValidatorLeave {
@@ -182,10 +223,18 @@ pub enum ConsensusStep {
 pub struct BlockchainManager {
     config: BlockchainConfig,
     consensus_enabled: bool,
+    node_id: Uuid,
     blockchain_storage: Arc<RwLock<BlockchainStorage>>,
     world_state: Arc<RwLock<WorldState>>,
-    message_sender: Option<mpsc::UnboundedSender<ConsensusMessage>>,
-    message_receiver: Option<mpsc::UnboundedReceiver<ConsensusMessage>>,
+    pending_proposals: Arc<RwLock<HashMap<Uuid, WorldStateProposal>>>, // received from peers, awaiting votes
+    message_sender: Option<ConsensusSender<ConsensusMessage>>,
+    message_receiver: Option<mpsc::Receiver<ConsensusMessage>>,
+    // Transaction signing
+    transaction_keys: HashMap<Uuid, VerifyingKey>, // initiator -> registered public key
+    local_signers: HashMap<Uuid, SigningKey>,      // keys for actors hosted on this node
+    validator_keys: Arc<RwLock<HashMap<Uuid, VerifyingKey>>>, // keys validator block signatures are checked against
+    validators: Arc<RwLock<HashMap<Uuid, ValidatorInfo>>>, // registry kept current by join/leave messages
+    block_events: broadcast::Sender<FinalizedBlock>, // every applied block, for subscribers
     // Enhanced blockchain systems
     pub nft_system: Arc<RwLock<NFTSystem>>,
     pub token_economy: Arc<RwLock<TokenEconomySystem>>,
@@ -194,11 +243,87 @@ pub struct BlockchainManager {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockchainStorage {
     pub blocks: BTreeMap<u64, FinalizedBlock>, // epoch -> block
-    pub world_snapshots: HashMap<u64, WorldStateSnapshot>, // epoch -> snapshot
+    pub world_snapshots: HashMap<u64, WorldStateSnapshot>, // epoch -> full snapshot
+    #[serde(default)]
+    pub world_deltas: BTreeMap<u64, WorldStateDelta>, // epoch -> changes, for epochs without a full snapshot
     pub pending_transactions: VecDeque<WorldTransaction>,
     pub last_finalized_epoch: u64,
     pub genesis_block: Option<FinalizedBlock>,
     pub blockchain_saves: Option<HashMap<String, BlockchainSaveSnapshot>>, // save_name -> snapshot
+    #[serde(default)]
+    pub bridge_transfers: HashMap<String, BridgeTransfer>, // bridge_id -> transfer
+    #[serde(default)]
+    pub reward_ledger: HashMap<Uuid, u64>, // node -> total rewards distributed to it
+    #[serde(default)]
+    pub activity_samples: HashMap<Uuid, BTreeSet<u64>>, // node -> activity intervals it was seen in
+    #[serde(skip)]
+    block_hash_index: HashMap<BlockHash, u64>, // block hash -> epoch, rebuilt from `blocks` on restore
+}
+
+impl BlockchainStorage {
+    /// Store `block` under its epoch, keeping the hash index in step
+    fn insert_block(&mut self, block: FinalizedBlock) {
+        if let Some(replaced) = self.blocks.get(&block.epoch) {
+            self.block_hash_index.remove(&replaced.block_hash);
+        }
+        self.block_hash_index.insert(block.block_hash, block.epoch);
+        self.blocks.insert(block.epoch, block);
+    }
+
+    /// Drop blocks and world history below `epoch`, keeping the genesis block and the
+    /// newest full snapshot at or below `epoch` so later deltas can still be replayed.
+    /// Returns how many blocks were dropped.
+    fn prune_before(&mut self, epoch: u64) -> usize {
+        let pruned: Vec<u64> = self.blocks.range(1..epoch.max(1)).map(|(epoch, _)| *epoch).collect();
+        for pruned_epoch in &pruned {
+            if let Some(block) = self.blocks.remove(pruned_epoch) {
+                self.block_hash_index.remove(&block.block_hash);
+            }
+        }
+
+        let anchor = self.world_snapshots.keys().filter(|snapshot| **snapshot <= epoch).max().copied();
+        if let Some(anchor) = anchor {
+            self.world_snapshots.retain(|snapshot, _| *snapshot >= anchor);
+            self.world_deltas = self.world_deltas.split_off(&(anchor + 1));
+        }
+        pruned.len()
+    }
+
+    /// Note that `node_id` was active at `at`, forgetting samples that have aged out of
+    /// the uptime window
+    fn record_activity(&mut self, node_id: Uuid, at: SystemTime) {
+        let interval = activity_interval(at);
+        let samples = self.activity_samples.entry(node_id).or_default();
+        samples.insert(interval);
+        let newest = samples.last().copied().unwrap_or(interval);
+        *samples = samples.split_off(&newest.saturating_sub(UPTIME_WINDOW_INTERVALS - 1));
+    }
+
+    /// Share of the uptime window ending at `now` in which `node_id` was seen, counted
+    /// from its first sample so new nodes aren't penalised for time before they joined.
+    /// The interval in progress only counts once the node has shown up in it.
+    fn uptime_percentage(&self, node_id: Uuid, now: SystemTime) -> f64 {
+        let Some(samples) = self.activity_samples.get(&node_id) else {
+            return 0.0;
+        };
+        let current = activity_interval(now);
+        let window_start = current.saturating_sub(UPTIME_WINDOW_INTERVALS - 1);
+        let mut observed = samples.range(window_start..=current);
+        let Some(first) = observed.next().copied() else {
+            return 0.0;
+        };
+
+        let active = 1 + observed.count() as u64;
+        let total = current - first + u64::from(samples.contains(&current));
+        active as f64 / total as f64 * 100.0
+    }
+
+    fn rebuild_block_hash_index(&mut self) {
+        self.block_hash_index = self.blocks
+            .iter()
+            .map(|(epoch, block)| (block.block_hash, *epoch))
+            .collect();
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,6 +336,26 @@ pub struct WorldState {
     pub skill_discoveries: HashMap<String, SkillDiscovery>,
     pub world_time: u64,
     pub last_update: SystemTime,
+    /// Highest transaction nonce finalized for each initiator
+    #[serde(default)]
+    pub account_nonces: HashMap<Uuid, u64>,
+}
+
+impl WorldState {
+    /// The world before any block has changed it, as it stands at genesis
+    fn empty(last_update: SystemTime) -> Self {
+        Self {
+            current_epoch: 0,
+            players: HashMap::new(),
+            areas: HashMap::new(),
+            npcs: HashMap::new(),
+            global_events: Vec::new(),
+            skill_discoveries: HashMap::new(),
+            world_time: 0,
+            last_update,
+            account_nonces: HashMap::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -308,9 +453,37 @@ pub struct SkillDiscovery {
     pub discovery_method: String,
     pub discovery_time: SystemTime,
     pub validation_votes: HashMap<Uuid, bool>,
+    #[serde(default)]
+    pub vote_tally: u32, // consensus votes accumulated across every block that carried this skill
     pub confirmed: bool,
 }
 
+/// `AreaUpdate` type under which new structures are recorded
+pub const STRUCTURE_CONSTRUCTION: &str = "StructureConstruction";
+
+/// Votes a skill discovery needs before it is confirmed
+const SKILL_CONFIRMATION_VOTES: u32 = 5;
+
+impl SkillDiscovery {
+    fn total_votes(&self) -> u32 {
+        let approvals = self.validation_votes.values().filter(|approved| **approved).count() as u32;
+        self.vote_tally.saturating_add(approvals)
+    }
+}
+
+/// The changes one epoch made, enough to replay it on top of an earlier snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldStateDelta {
+    pub epoch: u64,
+    pub world_changes: Vec<WorldChange>,
+    pub block_hash: BlockHash,
+    pub timestamp: SystemTime,
+    pub validator_signatures: HashMap<Uuid, String>,
+    /// Nonces this epoch's transactions advanced, per initiator
+    #[serde(default)]
+    pub nonces: HashMap<Uuid, u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldStateSnapshot {
     pub epoch: u64,
@@ -325,6 +498,9 @@ pub struct WorldTransaction {
     pub transaction_id: Uuid,
     pub transaction_type: TransactionType,
     pub initiator: Uuid,
+    /// Per-initiator sequence number; must be exactly one above the initiator's last
+    #[serde(default)]
+    pub nonce: u64,
     pub timestamp: SystemTime,
     pub data: serde_json::Value,
     pub signature: Option<String>,
@@ -346,24 +522,23 @@ impl BlockchainManager {
         let blockchain_storage = BlockchainStorage {
             blocks: BTreeMap::new(),
             world_snapshots: HashMap::new(),
+            world_deltas: BTreeMap::new(),
             pending_transactions: VecDeque::new(),
             last_finalized_epoch: 0,
             genesis_block: None,
             blockchain_saves: None,
+            bridge_transfers: HashMap::new(),
+            reward_ledger: HashMap::new(),
+            activity_samples: HashMap::new(),
+            block_hash_index: HashMap::new(),
         };
 
-        let world_state = WorldState {
-            current_epoch: 0,
-            players: HashMap::new(),
-            areas: HashMap::new(),
-            npcs: HashMap::new(),
-            global_events: Vec::new(),
-            skill_discoveries: HashMap::new(),
-            world_time: 0,
-            last_update: SystemTime::now(),
-        };
+        let world_state = WorldState::empty(SystemTime::now());
 
-        let (sender, receiver) = mpsc::unbounded_channel();
+        let (sender, receiver) = channel::consensus_channel(
+            config.consensus_queue_capacity,
+            std::time::Duration::from_millis(config.consensus_send_timeout_ms),
+        );
 
         // Initialize enhanced blockchain systems
         let nft_config = NFTConfig {
@@ -376,6 +551,7 @@ impl BlockchainManager {
             evolution_requirements_multiplier: 1.0,
             cross_chain_enabled: false,
             supported_chains: vec!["arceon".to_string()],
+            ..NFTConfig::default()
         };
         let nft_system = NFTSystem::new(nft_config).await?;
         let token_economy = TokenEconomySystem::new().await?;
@@ -383,21 +559,39 @@ impl BlockchainManager {
         Ok(Self {
             config: config.clone(),
             consensus_enabled: false,
+            node_id: Uuid::nil(),
             blockchain_storage: Arc::new(RwLock::new(blockchain_storage)),
             world_state: Arc::new(RwLock::new(world_state)),
+            pending_proposals: Arc::new(RwLock::new(HashMap::new())),
             message_sender: Some(sender),
             message_receiver: Some(receiver),
+            transaction_keys: HashMap::new(),
+            local_signers: HashMap::new(),
+            validator_keys: Arc::new(RwLock::new(HashMap::new())),
+            validators: Arc::new(RwLock::new(HashMap::new())),
+            block_events: broadcast::channel(BLOCK_SUBSCRIPTION_CAPACITY).0,
             nft_system: Arc::new(RwLock::new(nft_system)),
             token_economy: Arc::new(RwLock::new(token_economy)),
         })
     }
     
     /// Initialize blockchain with consensus manager
-    pub async fn start(&mut self, _node_id: Uuid, is_masternode: bool, _stake_amount: u64) -> Result<()> {
+    pub async fn start(&mut self, node_id: Uuid, is_masternode: bool, stake_amount: u64) -> Result<()> {
         info!("🔗 Initializing blockchain system with consensus");
 
         // Enable consensus for decentralized mode
         self.consensus_enabled = true;
+        self.node_id = node_id;
+
+        // This node signs the records it submits about itself
+        let mut secret = [0u8; 32];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut secret);
+        let node_key = SigningKey::from_bytes(&secret);
+        if is_masternode {
+            self.register_validator(node_id, stake_amount).await;
+            self.register_validator_key(node_id, node_key.verifying_key()).await;
+        }
+        self.add_local_signer(node_id, node_key);
 
         // Create genesis block if this is the first node
         if is_masternode {
@@ -416,35 +610,38 @@ impl BlockchainManager {
         info!("🌱 Creating genesis block");
 
         let genesis_world_state = self.world_state.read().await.clone();
+        let genesis_time = self.genesis_time();
+        let genesis_hash = self.calculate_genesis_hash();
         let genesis_changes = vec![
             WorldChange::WorldEvent {
-                event_id: Uuid::new_v4(),
+                event_id: Uuid::from_slice(&genesis_hash[..16])?,
                 event_type: "GENESIS".to_string(),
-                timestamp: SystemTime::now(),
-                affected_areas: vec!["Central Plains".to_string()],
+                timestamp: genesis_time,
+                affected_areas: vec![GENESIS_AREAS[0].to_string()],
                 data: serde_json::json!({
                     "description": "The birth of Arceon's decentralized world",
-                    "initial_areas": ["Central Plains", "Alderheart", "Silverleaf Enclave"],
+                    "initial_areas": GENESIS_AREAS,
                     "network_mode": "decentralized"
                 }),
             }
         ];
 
         let genesis_block = FinalizedBlock {
-            block_hash: self.calculate_genesis_hash()?,
+            block_hash: genesis_hash,
             epoch: 0,
             round: 0,
-            proposer: Uuid::new_v4(), // Genesis proposer
-            timestamp: SystemTime::now(),
+            proposer: Uuid::nil(), // Genesis has no proposer
+            timestamp: genesis_time,
             world_changes: genesis_changes,
             validator_signatures: HashMap::new(),
             merkle_root: "genesis".to_string(),
             previous_hash: None,
+            transaction_nonces: Vec::new(),
         };
 
         // Store genesis block
         let mut storage = self.blockchain_storage.write().await;
-        storage.blocks.insert(0, genesis_block.clone());
+        storage.insert_block(genesis_block.clone());
         storage.genesis_block = Some(genesis_block.clone());
         storage.last_finalized_epoch = 0;
 
@@ -464,15 +661,25 @@ impl BlockchainManager {
     }
 
     /// Calculate genesis block hash
-    fn calculate_genesis_hash(&self) -> Result<BlockHash> {
+    /// Derived only from network identity so every node computes the same genesis
+    fn calculate_genesis_hash(&self) -> BlockHash {
         let mut hasher = Sha256::new();
         hasher.update(b"ARCEON_GENESIS_BLOCK");
-        hasher.update(SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_secs().to_be_bytes());
+        hasher.update(self.config.network_name.as_bytes());
+        hasher.update(self.config.genesis_timestamp.to_be_bytes());
+        for area in GENESIS_AREAS {
+            hasher.update((area.len() as u64).to_be_bytes());
+            hasher.update(area.as_bytes());
+        }
         
         let result = hasher.finalize();
         let mut hash = [0u8; 32];
         hash.copy_from_slice(&result);
-        Ok(hash)
+        hash
+    }
+
+    fn genesis_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(self.config.genesis_timestamp)
     }
 
     /// Start message processing loop
@@ -480,15 +687,25 @@ impl BlockchainManager {
         if let Some(mut receiver) = self.message_receiver.take() {
             let blockchain_storage = self.blockchain_storage.clone();
             let world_state = self.world_state.clone();
+            let pending_proposals = self.pending_proposals.clone();
+            let validators = self.validators.clone();
+            let validator_keys = self.validator_keys.clone();
+            let node_id = self.node_id;
 
             tokio::spawn(async move {
                 while let Some(message) = receiver.recv().await {
+                    // Several nodes can share a process; the span says whose log line this is
+                    let current_epoch = world_state.read().await.current_epoch;
+                    let span = info_span!("consensus_message", %node_id, current_epoch);
                     if let Err(e) = Self::process_consensus_message(
                         message,
                         blockchain_storage.clone(),
                         world_state.clone(),
-                    ).await {
-                        error!("Error processing consensus message: {}", e);
+                        pending_proposals.clone(),
+                        validators.clone(),
+                        validator_keys.clone(),
+                    ).instrument(span.clone()).await {
+                        span.in_scope(|| error!("Error processing consensus message: {}", e));
                     }
                 }
             });
@@ -500,16 +717,20 @@ impl BlockchainManager {
     /// Process incoming consensus messages
     async fn process_consensus_message(
         message: ConsensusMessage,
-        _blockchain_storage: Arc<RwLock<BlockchainStorage>>,
+        blockchain_storage: Arc<RwLock<BlockchainStorage>>,
         _world_state: Arc<RwLock<WorldState>>,
+        pending_proposals: Arc<RwLock<HashMap<Uuid, WorldStateProposal>>>,
+        validators: Arc<RwLock<HashMap<Uuid, ValidatorInfo>>>,
+        validator_keys: Arc<RwLock<HashMap<Uuid, VerifyingKey>>>,
     ) -> Result<()> {
         debug!("🔗 Processing consensus message: {:?}", std::mem::discriminant(&message));
         
         // Simplified consensus processing for Phase 2
         // Full consensus implementation would go here
         match message {
-            ConsensusMessage::Proposal(_) => {
-                debug!("Received world state proposal");
+            ConsensusMessage::Proposal(proposal) => {
+                debug!("Received world state proposal {} from {}", proposal.proposal_id, proposal.proposer);
+                pending_proposals.write().await.insert(proposal.proposal_id, proposal);
             }
             ConsensusMessage::Vote(_) => {
                 debug!("Received consensus vote");
@@ -517,6 +738,19 @@ impl BlockchainManager {
             ConsensusMessage::ViewChange(_) => {
                 debug!("Received view change request");
             }
+            ConsensusMessage::ValidatorJoin { node_id, stake_amount, public_key, timestamp } => {
+                let public_key = VerifyingKey::from_bytes(&public_key)
+                    .map_err(|e| anyhow::anyhow!("Rejected join from validator {}: invalid public key: {}", node_id, e))?;
+                info!("Validator {} joined with stake {}", node_id, stake_amount);
+                validator_keys.write().await.insert(node_id, public_key);
+                Self::insert_validator(&mut *validators.write().await, node_id, stake_amount, timestamp);
+                blockchain_storage.write().await.record_activity(node_id, timestamp);
+            }
+            ConsensusMessage::ValidatorLeave { node_id, .. } => {
+                info!("Validator {} left", node_id);
+                validator_keys.write().await.remove(&node_id);
+                Self::remove_validator(&mut *validators.write().await, node_id);
+            }
             _ => {
                 debug!("Received other consensus message");
             }
@@ -526,47 +760,284 @@ impl BlockchainManager {
 
     /// Apply finalized block to world state
     pub async fn apply_finalized_block(&mut self, block: FinalizedBlock) -> Result<()> {
+        let current_epoch = self.world_state.read().await.current_epoch;
+        let span = info_span!("apply_block", node_id = %self.node_id, current_epoch, block_epoch = block.epoch);
+        self.apply_block(block).instrument(span).await
+    }
+
+    async fn apply_block(&mut self, block: FinalizedBlock) -> Result<()> {
         info!("📝 Applying finalized block for epoch {}", block.epoch);
 
-        // Update world state based on finalized changes
+        self.verify_finalized_block(&block).await?;
+        self.commit_block(block).await
+    }
+
+    /// Apply the blocks `consensus` has confirmed since the last call, in epoch order, and
+    /// return how many were applied. Consensus has already required 2/3 of the stake to
+    /// precommit each of them, and its signatures are over those votes rather than over
+    /// the block, so only the contents are checked here, not block signatures. A block
+    /// already applied for its epoch is skipped, while a different block already held for
+    /// that epoch (such as a locally created genesis block) is a conflict. The first block
+    /// that fails stops the batch, leaving the rest of it unapplied.
+    pub async fn apply_confirmed_blocks(&mut self, consensus: &arceon_consensus::ConsensusManager) -> Result<usize> {
+        let mut applied = 0;
+        for confirmed in consensus.take_confirmed_blocks().await {
+            let block = Self::from_consensus_block(confirmed)?;
+            let stored = self.blockchain_storage.read().await.blocks.get(&block.epoch).map(|stored| stored.block_hash);
+            match stored {
+                Some(hash) if hash == block.block_hash => {
+                    debug!("Block for epoch {} is already applied", block.epoch);
+                    continue;
+                }
+                Some(_) => {
+                    return Err(anyhow::anyhow!("Rejected confirmed block {}: conflicts with the block already applied for that epoch", block.epoch));
+                }
+                None => {}
+            }
+
+            let current_epoch = self.world_state.read().await.current_epoch;
+            let span = info_span!("apply_block", node_id = %self.node_id, current_epoch, block_epoch = block.epoch);
+            async {
+                info!("📝 Applying confirmed block for epoch {}", block.epoch);
+                self.verify_block_contents(&block).await?;
+                self.commit_block(block).await
+            }.instrument(span).await?;
+            applied += 1;
+        }
+        Ok(applied)
+    }
+
+    /// The consensus crate's block, as stored here. Both share one wire format; consensus
+    /// blocks carry no transaction nonces, so none advance.
+    fn from_consensus_block(block: arceon_consensus::FinalizedBlock) -> Result<FinalizedBlock> {
+        Ok(serde_json::from_value(serde_json::to_value(block)?)?)
+    }
+
+    /// Apply a block that has passed verification
+    async fn commit_block(&mut self, block: FinalizedBlock) -> Result<()> {
         let mut world_state = self.world_state.write().await;
-        world_state.current_epoch = block.epoch;
-        world_state.last_update = block.timestamp;
+        let mut storage = self.blockchain_storage.write().await;
+        let nonces = Self::block_nonces(&world_state.account_nonces, &block)?;
+
+        // Pending transactions this block includes
+        let included = |tx: &WorldTransaction| block.world_changes.iter().any(|change| {
+            serde_json::to_value(change).is_ok_and(|v| v == tx.data)
+        });
 
-        for change in &block.world_changes {
-            self.apply_world_change(&mut world_state, change).await?;
+        // Update world state based on finalized changes
+        self.advance_world_state(&mut world_state, block.epoch, block.timestamp, &block.world_changes).await?;
+        Self::record_nonces(&mut world_state, &nonces);
+
+        // The proposer and every signer were demonstrably up when the block was made
+        for node_id in std::iter::once(&block.proposer).chain(block.validator_signatures.keys()) {
+            storage.record_activity(*node_id, block.timestamp);
         }
 
         // Store block and create snapshot
-        let mut storage = self.blockchain_storage.write().await;
-        storage.blocks.insert(block.epoch, block.clone());
+        storage.insert_block(block.clone());
         storage.last_finalized_epoch = block.epoch;
 
-        // Create world state snapshot
-        let snapshot = WorldStateSnapshot {
-            epoch: block.epoch,
-            world_state: world_state.clone(),
-            block_hash: block.block_hash,
-            timestamp: block.timestamp,
-            validator_signatures: block.validator_signatures.clone(),
-        };
+        // Full snapshots only every `snapshot_interval` epochs; in between, keep the changes
+        let interval = self.config.snapshot_interval.max(1);
+        if block.epoch.is_multiple_of(interval) {
+            let snapshot = WorldStateSnapshot {
+                epoch: block.epoch,
+                world_state: world_state.clone(),
+                block_hash: block.block_hash,
+                timestamp: block.timestamp,
+                validator_signatures: block.validator_signatures.clone(),
+            };
+            storage.world_snapshots.insert(block.epoch, snapshot);
+        } else {
+            let delta = WorldStateDelta {
+                epoch: block.epoch,
+                world_changes: block.world_changes.clone(),
+                block_hash: block.block_hash,
+                timestamp: block.timestamp,
+                validator_signatures: block.validator_signatures.clone(),
+                nonces,
+            };
+            storage.world_deltas.insert(block.epoch, delta);
+        }
 
-        storage.world_snapshots.insert(block.epoch, snapshot);
+        // Clear processed transactions, keeping those that weren't included in this block
+        storage.pending_transactions.retain(|tx| !included(tx));
 
-        // Clear processed transactions
-        storage.pending_transactions.retain(|tx| {
-            // Keep transactions that weren't included in this block
-            !block.world_changes.iter().any(|change| {
-                serde_json::to_value(change).map_or(false, |v| v == tx.data)
-            })
-        });
+        if self.config.max_history_epochs > 0 {
+            storage.prune_before((block.epoch + 1).saturating_sub(self.config.max_history_epochs));
+        }
+
+        drop(storage);
+        drop(world_state);
 
         info!("✅ Block {} applied successfully with {} changes", 
             block.epoch, block.world_changes.len());
 
+        // Nobody listening is fine
+        let _ = self.block_events.send(block);
+
+        Ok(())
+    }
+
+    /// Reject a block whose merkle root doesn't match its changes, whose transaction
+    /// nonces don't follow on from the finalized ones, or whose valid validator
+    /// signatures carry less than 2/3 of the stake of every known validator.
+    /// A validator whose key this node hasn't learned still counts toward the total.
+    ///
+    /// A node that knows of no validators at all (a follower that hasn't heard a join
+    /// yet) has no stake to weigh signatures against, so it follows the blocks it is
+    /// given on their merkle root alone; the 2/3 rule applies once any validator is known.
+    async fn verify_finalized_block(&self, block: &FinalizedBlock) -> Result<()> {
+        self.verify_block_contents(block).await?;
+
+        let validators = self.validators.read().await;
+        if validators.is_empty() {
+            warn!("No validators known yet; accepting block {} without a stake check", block.epoch);
+            return Ok(());
+        }
+
+        let payload = Self::block_signing_payload(block)?;
+        let validator_keys = self.validator_keys.read().await;
+        let mut total_stake = 0u64;
+        let mut signed_stake = 0u64;
+        for (validator, info) in validators.iter() {
+            total_stake = total_stake.saturating_add(info.stake_amount);
+
+            let signed = validator_keys.get(validator).zip(block.validator_signatures.get(validator))
+                .is_some_and(|(public_key, encoded)| Self::signature_is_valid(public_key, &payload, encoded));
+            if signed {
+                signed_stake = signed_stake.saturating_add(info.stake_amount);
+            }
+        }
+
+        if total_stake == 0 || (signed_stake as u128) * 3 < (total_stake as u128) * 2 {
+            return Err(anyhow::anyhow!("Rejected block {}: valid signatures cover {} of {} validator stake, 2/3 required",
+                block.epoch, signed_stake, total_stake));
+        }
+        Ok(())
+    }
+
+    /// The checks that need no signatures: the merkle root matches the changes and the
+    /// transaction nonces follow on from the finalized ones
+    async fn verify_block_contents(&self, block: &FinalizedBlock) -> Result<()> {
+        let merkle_root = self.calculate_merkle_root(&block.world_changes)?;
+        if merkle_root != block.merkle_root {
+            return Err(anyhow::anyhow!("Rejected block {}: merkle root {} does not match its changes ({})",
+                block.epoch, block.merkle_root, merkle_root));
+        }
+        Self::block_nonces(&self.world_state.read().await.account_nonces, block)?;
+        Ok(())
+    }
+
+    /// What validators sign: the change count is bound in alongside the merkle root, so a
+    /// relay can't pad or trim the change list even where two lists would share a root
+    fn block_signing_payload(block: &FinalizedBlock) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&(
+            "arceon-finalized-block",
+            block.block_hash,
+            block.epoch,
+            block.round,
+            &block.merkle_root,
+            block.world_changes.len() as u64,
+            block.previous_hash,
+            &block.transaction_nonces,
+        ))?)
+    }
+
+    /// The highest nonce `block` finalizes for each initiator it carries. Each initiator's
+    /// nonces must run on, one at a time, from its last finalized nonce, so a replayed or
+    /// skipped transaction rejects the block.
+    fn block_nonces(account_nonces: &HashMap<Uuid, u64>, block: &FinalizedBlock) -> Result<HashMap<Uuid, u64>> {
+        let mut nonces: HashMap<Uuid, u64> = HashMap::new();
+        for (initiator, nonce) in &block.transaction_nonces {
+            let last = nonces.get(initiator).or(account_nonces.get(initiator)).copied().unwrap_or(0);
+            if *nonce != last + 1 {
+                return Err(anyhow::anyhow!("Rejected block {}: nonce {} from {} but expected {}",
+                    block.epoch, nonce, initiator, last + 1));
+            }
+            nonces.insert(*initiator, *nonce);
+        }
+        Ok(nonces)
+    }
+
+    /// Add `validator`'s signature over `block`, as collected when the block is finalized
+    pub fn sign_block(block: &mut FinalizedBlock, validator: Uuid, key: &SigningKey) -> Result<()> {
+        use base64::Engine;
+
+        let signature = key.sign(&Self::block_signing_payload(block)?);
+        block.validator_signatures.insert(validator, base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()));
+        Ok(())
+    }
+
+    /// Register the key `validator` signs finalized blocks with
+    pub async fn register_validator_key(&self, validator: Uuid, public_key: VerifyingKey) {
+        self.validator_keys.write().await.insert(validator, public_key);
+    }
+
+    /// Receive every block applied from now on. A subscriber that falls more than
+    /// `BLOCK_SUBSCRIPTION_CAPACITY` blocks behind skips the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<FinalizedBlock> {
+        self.block_events.subscribe()
+    }
+
+    /// Receive the world events of `event_type` (e.g. `TOKEN_TRANSFER`) in each block
+    /// applied from now on, in block order
+    pub fn subscribe_events(&self, event_type: &str) -> mpsc::UnboundedReceiver<WorldChange> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut blocks = self.subscribe();
+        let event_type = event_type.to_string();
+
+        tokio::spawn(async move {
+            loop {
+                let block = match blocks.recv().await {
+                    Ok(block) => block,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Event subscriber for {} missed {} blocks", event_type, skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let matching = block.world_changes.into_iter().filter(|change| {
+                    matches!(change, WorldChange::WorldEvent { event_type: kind, .. } if *kind == event_type)
+                });
+                for change in matching {
+                    if sender.send(change).is_err() {
+                        return; // subscriber went away
+                    }
+                }
+            }
+        });
+
+        receiver
+    }
+
+    /// Move `world_state` to `epoch` by applying that epoch's changes
+    async fn advance_world_state(&self, world_state: &mut WorldState, epoch: u64, timestamp: SystemTime, changes: &[WorldChange]) -> Result<()> {
+        world_state.current_epoch = epoch;
+        world_state.last_update = timestamp;
+        world_state.world_time = Self::world_time_at(timestamp);
+
+        for change in changes {
+            self.apply_world_change(world_state, change).await?;
+        }
         Ok(())
     }
 
+    fn record_nonces(world_state: &mut WorldState, nonces: &HashMap<Uuid, u64>) {
+        for (initiator, nonce) in nonces {
+            let last = world_state.account_nonces.entry(*initiator).or_insert(0);
+            *last = (*last).max(*nonce);
+        }
+    }
+
+    /// Event id derived from the change itself, so replaying a change reproduces the same state
+    fn change_event_id(change: &WorldChange) -> Uuid {
+        let digest = Sha256::digest(merkle::canonical_json(change).unwrap_or_default());
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        Uuid::from_bytes(bytes)
+    }
+
     /// Apply individual world change to state
     async fn apply_world_change(&self, world_state: &mut WorldState, change: &WorldChange) -> Result<()> {
         match change {
@@ -583,7 +1054,7 @@ impl BlockchainManager {
                 // Add to area events
                 if let Some(area) = world_state.areas.get_mut(area_id) {
                     area.events.push(AreaEvent {
-                        event_id: Uuid::new_v4(),
+                        event_id: Self::change_event_id(change),
                         event_type: action_type.clone(),
                         description: format!("Player {} performed {}", player_id, action_type),
                         timestamp: *timestamp,
@@ -606,7 +1077,7 @@ impl BlockchainManager {
                 // Add to area events
                 if let Some(area) = world_state.areas.get_mut(area_id) {
                     area.events.push(AreaEvent {
-                        event_id: Uuid::new_v4(),
+                        event_id: Self::change_event_id(change),
                         event_type: action_type.clone(),
                         description: format!("NPC {} performed {}", npc_id, action_type),
                         timestamp: *timestamp,
@@ -622,13 +1093,16 @@ impl BlockchainManager {
                 
                 if let Some(area) = world_state.areas.get_mut(area_id) {
                     area.events.push(AreaEvent {
-                        event_id: Uuid::new_v4(),
+                        event_id: Self::change_event_id(change),
                         event_type: update_type.clone(),
                         description: format!("Area update: {}", update_type),
                         timestamp: *timestamp,
                         participants: Vec::new(),
                         data: data.clone(),
                     });
+                    if update_type == STRUCTURE_CONSTRUCTION {
+                        Self::place_structure(area, data, *timestamp);
+                    }
                     area.last_update = *timestamp;
                 }
             }
@@ -636,16 +1110,21 @@ impl BlockchainManager {
             WorldChange::SkillEvolution { skill_name, evolution_type, timestamp, discoverer, consensus_votes } => {
                 debug!("Applying skill evolution: {} via {}", skill_name, evolution_type);
                 
-                let discovery = SkillDiscovery {
-                    skill_name: skill_name.clone(),
-                    discoverer: discoverer.unwrap_or(Uuid::new_v4()),
-                    discovery_method: evolution_type.clone(),
-                    discovery_time: *timestamp,
-                    validation_votes: HashMap::new(),
-                    confirmed: *consensus_votes >= 5, // Require 5+ votes for confirmation
-                };
+                // Keep the original discoverer and any votes already tallied in earlier blocks
+                let discovery = world_state.skill_discoveries
+                    .entry(skill_name.clone())
+                    .or_insert_with(|| SkillDiscovery {
+                        skill_name: skill_name.clone(),
+                        discoverer: discoverer.unwrap_or_else(|| Self::change_event_id(change)),
+                        discovery_method: evolution_type.clone(),
+                        discovery_time: *timestamp,
+                        validation_votes: HashMap::new(),
+                        vote_tally: 0,
+                        confirmed: false,
+                    });
 
-                world_state.skill_discoveries.insert(skill_name.clone(), discovery);
+                discovery.vote_tally = discovery.vote_tally.saturating_add(*consensus_votes);
+                discovery.confirmed = discovery.total_votes() >= SKILL_CONFIRMATION_VOTES;
             }
 
             WorldChange::WorldEvent { event_id, event_type, timestamp, affected_areas, data } => {
@@ -668,30 +1147,190 @@ impl BlockchainManager {
         Ok(())
     }
 
-    /// Add a world change to pending transactions
-    pub async fn submit_world_change(&mut self, change: WorldChange) -> Result<()> {
+    /// Queue a world change for consensus, charging the initiator the scheduled fee for its
+    /// transaction type. Records this node submits about itself are fee-exempt. The
+    /// transaction is signed if the initiator's key is held locally.
+    pub async fn submit_world_change(&mut self, initiator: Uuid, change: WorldChange) -> Result<()> {
         if self.consensus_enabled {
-            // Add to pending transactions for consensus processing
-            let mut storage = self.blockchain_storage.write().await;
-            let transaction = WorldTransaction {
+            let mut transaction = WorldTransaction {
                 transaction_id: Uuid::new_v4(),
-                transaction_type: match change {
-                    WorldChange::PlayerAction { .. } => TransactionType::PlayerAction,
-                    WorldChange::NPCAction { .. } => TransactionType::NPCAction,
-                    WorldChange::AreaUpdate { .. } => TransactionType::AreaUpdate,
-                    WorldChange::SkillEvolution { .. } => TransactionType::SkillDiscovery,
-                    WorldChange::WorldEvent { .. } => TransactionType::GlobalEvent,
-                },
-                initiator: Uuid::new_v4(), // Would be actual initiator
+                transaction_type: Self::transaction_type_of(&change),
+                initiator,
+                nonce: self.get_nonce(initiator).await + 1,
                 timestamp: SystemTime::now(),
                 data: serde_json::to_value(&change)?,
                 signature: None,
             };
-            storage.pending_transactions.push_back(transaction);
+            if let Some(key) = self.local_signers.get(&initiator) {
+                Self::sign_transaction(&mut transaction, key)?;
+            }
+            self.submit_transaction(transaction).await?;
+        }
+        Ok(())
+    }
+
+    /// Queue a transaction built (and possibly signed) elsewhere. Its nonce must follow
+    /// the initiator's last one, and when signatures are required, unsigned or
+    /// badly-signed transactions are rejected here.
+    pub async fn submit_transaction(&mut self, transaction: WorldTransaction) -> Result<()> {
+        if !self.consensus_enabled {
+            return Ok(());
+        }
+
+        let last_nonce = self.get_nonce(transaction.initiator).await;
+        if transaction.nonce != last_nonce + 1 {
+            return Err(anyhow::anyhow!("Rejected {:?} from {}: nonce {} but expected {}",
+                transaction.transaction_type, transaction.initiator, transaction.nonce, last_nonce + 1));
+        }
+
+        if self.config.require_transaction_signatures && !self.verify_transaction(&transaction)? {
+            return Err(anyhow::anyhow!("Rejected {:?} from {}: missing or invalid signature",
+                transaction.transaction_type, transaction.initiator));
+        }
+
+        let fee = self.world_change_fee(&transaction.transaction_type);
+        if fee > 0 && transaction.initiator != self.node_id {
+            self.token_economy.read().await.charge_fee(transaction.initiator, fee).await
+                .map_err(|e| anyhow::anyhow!("Rejected {:?} from {}: {}", transaction.transaction_type, transaction.initiator, e))?;
+        }
+
+        // Add to pending transactions for consensus processing
+        self.blockchain_storage.write().await.pending_transactions.push_back(transaction);
+        Ok(())
+    }
+
+    /// Last nonce used by `account`, counting transactions still awaiting a block; the
+    /// next transaction from `account` must carry this plus one
+    pub async fn get_nonce(&self, account: Uuid) -> u64 {
+        let finalized = self.world_state.read().await.account_nonces.get(&account).copied().unwrap_or(0);
+        let pending = self.blockchain_storage.read().await.pending_transactions
+            .iter()
+            .filter(|tx| tx.initiator == account)
+            .map(|tx| tx.nonce)
+            .max()
+            .unwrap_or(0);
+        finalized.max(pending)
+    }
+
+    /// Register the public key transactions from `initiator` must be signed with
+    pub fn register_transaction_key(&mut self, initiator: Uuid, public_key: VerifyingKey) {
+        self.transaction_keys.insert(initiator, public_key);
+    }
+
+    /// Hold `initiator`'s key on this node so its world changes are signed on submission
+    pub fn add_local_signer(&mut self, initiator: Uuid, key: SigningKey) {
+        self.register_transaction_key(initiator, key.verifying_key());
+        self.local_signers.insert(initiator, key);
+    }
+
+    fn transaction_signing_payload(transaction: &WorldTransaction) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&(
+            "arceon-world-transaction",
+            transaction.transaction_id,
+            &transaction.transaction_type,
+            transaction.initiator,
+            transaction.nonce,
+            transaction.timestamp,
+            &transaction.data,
+        ))?)
+    }
+
+    /// Sign `transaction` with `key`, storing the base64 signature on it
+    pub fn sign_transaction(transaction: &mut WorldTransaction, key: &SigningKey) -> Result<()> {
+        use base64::Engine;
+
+        let signature = key.sign(&Self::transaction_signing_payload(transaction)?);
+        transaction.signature = Some(base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()));
+        Ok(())
+    }
+
+    /// Whether `transaction` carries a valid signature from its initiator's registered key
+    pub fn verify_transaction(&self, transaction: &WorldTransaction) -> Result<bool> {
+        let (Some(encoded), Some(public_key)) = (&transaction.signature, self.transaction_keys.get(&transaction.initiator)) else {
+            return Ok(false);
+        };
+        Ok(Self::signature_is_valid(public_key, &Self::transaction_signing_payload(transaction)?, encoded))
+    }
+
+    /// Check a base64 ed25519 signature over `payload`
+    fn signature_is_valid(public_key: &VerifyingKey, payload: &[u8], encoded: &str) -> bool {
+        use base64::Engine;
+
+        let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(encoded) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(&bytes) else {
+            return false;
+        };
+        public_key.verify(payload, &signature).is_ok()
+    }
+
+    /// Add the structure a `StructureConstruction` update describes to `area`, once
+    fn place_structure(area: &mut AreaState, data: &serde_json::Value, built_at: SystemTime) {
+        let field = |name: &str| data.get(name).and_then(|value| value.as_str()).unwrap_or_default();
+        let Ok(structure_id) = Uuid::parse_str(field("structure_id")) else {
+            warn!("Structure construction in {} has no valid structure id", area.area_id);
+            return;
+        };
+        if area.structures.iter().any(|structure| structure.structure_id == structure_id) {
+            return;
+        }
+
+        area.structures.push(Structure {
+            structure_id,
+            name: field("name").to_string(),
+            structure_type: field("structure_type").to_string(),
+            builder: Uuid::parse_str(field("builder")).unwrap_or_default(),
+            location: (0.0, 0.0),
+            interior_area_id: None,
+            build_time: built_at,
+            materials_used: Vec::new(),
+        });
+    }
+
+    fn transaction_type_of(change: &WorldChange) -> TransactionType {
+        match change {
+            WorldChange::PlayerAction { .. } => TransactionType::PlayerAction,
+            WorldChange::NPCAction { .. } => TransactionType::NPCAction,
+            WorldChange::AreaUpdate { update_type, .. } if update_type == STRUCTURE_CONSTRUCTION => TransactionType::StructureConstruction,
+            WorldChange::AreaUpdate { .. } => TransactionType::AreaUpdate,
+            WorldChange::SkillEvolution { .. } => TransactionType::SkillDiscovery,
+            WorldChange::WorldEvent { .. } => TransactionType::GlobalEvent,
+        }
+    }
+
+    /// Fail early if `initiator` can't cover the fee for recording a `transaction_type`
+    /// change on top of `reserved` native tokens the operation itself will spend, so an
+    /// operation is never carried out and then left unrecorded
+    async fn ensure_fee_payable(&self, initiator: Uuid, transaction_type: TransactionType, reserved: u64) -> Result<()> {
+        let fee = self.world_change_fee(&transaction_type);
+        if !self.consensus_enabled || initiator == self.node_id || fee == 0 {
+            return Ok(());
+        }
+
+        let token_economy = self.token_economy.read().await;
+        let balance = token_economy.get_balance(initiator, token_economy.config.native_token_symbol.clone()).await;
+        if balance < fee.saturating_add(reserved) {
+            return Err(anyhow::anyhow!("Rejected {:?} from {}: {} needed for the fee, {} available",
+                transaction_type, initiator, fee.saturating_add(reserved), balance));
         }
         Ok(())
     }
 
+    /// Base fee for a transaction type from the configured schedule
+    pub fn world_change_fee(&self, transaction_type: &TransactionType) -> u64 {
+        let schedule = &self.config.fee_schedule;
+        match transaction_type {
+            TransactionType::PlayerAction => schedule.player_action,
+            TransactionType::NPCAction => schedule.npc_action,
+            TransactionType::AreaUpdate => schedule.area_update,
+            TransactionType::SkillDiscovery => schedule.skill_discovery,
+            TransactionType::GlobalEvent => schedule.global_event,
+            TransactionType::StructureConstruction => schedule.structure_construction,
+            TransactionType::ItemTransfer => schedule.item_transfer,
+        }
+    }
+
     /// Get current world state
     pub async fn get_world_state(&self) -> WorldState {
         self.world_state.read().await.clone()
@@ -725,45 +1364,211 @@ impl BlockchainManager {
         storage.blocks.get(&epoch).cloned()
     }
 
-    /// Get world state snapshot by epoch
-    pub async fn get_world_snapshot(&self, epoch: u64) -> Option<WorldStateSnapshot> {
+    /// Drop blocks and snapshots below `epoch`; see `max_history_epochs` for doing this
+    /// automatically. The genesis block always survives.
+    pub async fn prune_before(&self, epoch: u64) -> usize {
+        let pruned = self.blockchain_storage.write().await.prune_before(epoch);
+        if pruned > 0 {
+            info!("✂️ Pruned {} blocks below epoch {}", pruned, epoch);
+        }
+        pruned
+    }
+
+    /// Get block by its hash
+    pub async fn get_block_by_hash(&self, hash: BlockHash) -> Option<FinalizedBlock> {
         let storage = self.blockchain_storage.read().await;
-        storage.world_snapshots.get(&epoch).cloned()
+        let epoch = storage.block_hash_index.get(&hash)?;
+        storage.blocks.get(epoch).cloned()
     }
 
-    /// Sync with other nodes (request missing blocks)
-    pub async fn sync_with_network(&mut self, from_epoch: u64) -> Result<()> {
-        if self.consensus_enabled {
-            info!("🔄 Initiating blockchain sync from epoch {}", from_epoch);
-            
-            // Create sync request message
-            let sync_request = ConsensusMessage::SyncRequest {
-                requester: Uuid::new_v4(), // Would be actual node ID
-                from_epoch,
-                to_epoch: None,
-            };
-            
-            // Send sync request through message channel
-            if let Some(sender) = &self.message_sender {
-                let _ = sender.send(sync_request);
-            }
+    /// Get the blocks from epoch `from` to `to` inclusive, in epoch order
+    pub async fn get_block_range(&self, from: u64, to: u64) -> Vec<FinalizedBlock> {
+        if from > to {
+            return Vec::new();
         }
-        Ok(())
+        let storage = self.blockchain_storage.read().await;
+        storage.blocks.range(from..=to).map(|(_, block)| block.clone()).collect()
     }
 
-    /// Save complete world state to blockchain storage (decentralized mode)
-    pub async fn save_world_state_to_blockchain(&mut self, save_name: String) -> Result<String> {
-        info!("💾 Saving world state '{}' to blockchain", save_name);
+    /// Recompute the world state from the stored blocks alone: start from the empty
+    /// genesis state and apply every later block's changes in epoch order, as
+    /// `apply_block` did. The genesis block's own changes only announce the world and
+    /// were never applied, so they are skipped here too.
+    ///
+    /// Nonces are advanced from each block's own `transaction_nonces`. Fails if the
+    /// history has a gap, e.g. after pruning.
+    pub async fn replay_from_genesis(&self) -> Result<WorldState> {
+        self.replay_with(|_, _| {}).await
+    }
 
-        if !self.consensus_enabled {
-            return Err(anyhow::anyhow!("Blockchain save requires consensus mode"));
+    /// `replay_from_genesis`, showing `inspect` the state after each block, genesis included
+    async fn replay_with(&self, mut inspect: impl FnMut(&FinalizedBlock, &WorldState)) -> Result<WorldState> {
+        let blocks: Vec<FinalizedBlock> = self.blockchain_storage.read().await.blocks.values().cloned().collect();
+
+        let Some(genesis) = blocks.first().filter(|block| block.epoch == 0) else {
+            return Err(anyhow::anyhow!("Cannot replay: the genesis block is not stored"));
+        };
+        let mut world_state = WorldState::empty(genesis.timestamp);
+        inspect(genesis, &world_state);
+        for (expected_epoch, block) in (0u64..).zip(&blocks).skip(1) {
+            if block.epoch != expected_epoch {
+                return Err(anyhow::anyhow!("Cannot replay: no block stored for epoch {}", expected_epoch));
+            }
+            let nonces = Self::block_nonces(&world_state.account_nonces, block)?;
+            self.advance_world_state(&mut world_state, block.epoch, block.timestamp, &block.world_changes).await?;
+            Self::record_nonces(&mut world_state, &nonces);
+            inspect(block, &world_state);
         }
+        Ok(world_state)
+    }
 
-        let world_state = self.world_state.read().await.clone();
-        let storage = self.blockchain_storage.read().await;
-        
-        // Create comprehensive save data
-        let save_data = BlockchainSaveData {
+    /// Check the stored history against itself: every block's `previous_hash` must name
+    /// the block before it, and every full snapshot must have the same world state merkle
+    /// root as the state replayed from the blocks up to its epoch. Epochs kept as deltas
+    /// have no stored state to compare. A gap in the blocks stops the replay; the report
+    /// says where.
+    pub async fn verify_chain_integrity(&self) -> IntegrityReport {
+        let mut report = IntegrityReport::default();
+        let stored_roots: HashMap<u64, String> = {
+            let storage = self.blockchain_storage.read().await;
+            let mut previous: Option<&FinalizedBlock> = None;
+            for block in storage.blocks.values() {
+                // Across a pruned gap there is no predecessor to check against
+                if let Some(previous) = previous.filter(|previous| previous.epoch + 1 == block.epoch) {
+                    if block.previous_hash != Some(previous.block_hash) {
+                        report.broken_links.push(block.epoch);
+                    }
+                }
+                previous = Some(block);
+            }
+            storage.world_snapshots.iter()
+                .map(|(epoch, snapshot)| (*epoch, Self::world_state_root(&snapshot.world_state)))
+                .collect()
+        };
+
+        let replay = self.replay_with(|block, world_state| {
+            report.epochs_replayed += 1;
+            let Some(stored_root) = stored_roots.get(&block.epoch) else {
+                return;
+            };
+            report.snapshots_checked += 1;
+            let replayed_root = Self::world_state_root(world_state);
+            if replayed_root != *stored_root {
+                report.divergent_snapshots.push(SnapshotDivergence {
+                    epoch: block.epoch,
+                    stored_root: stored_root.clone(),
+                    replayed_root,
+                });
+            }
+        }).await;
+        if let Err(e) = replay {
+            report.replay_error = Some(e.to_string());
+        }
+
+        if !report.is_intact() {
+            warn!("🩺 Chain integrity check failed: {} divergent snapshot(s), {} broken link(s){}",
+                report.divergent_snapshots.len(), report.broken_links.len(),
+                report.replay_error.as_ref().map_or(String::new(), |e| format!(", replay stopped: {}", e)));
+        }
+        report
+    }
+
+    /// Get world state snapshot by epoch. Epochs stored as deltas are rebuilt by replaying
+    /// their changes forward from the nearest earlier full snapshot.
+    pub async fn get_world_snapshot(&self, epoch: u64) -> Option<WorldStateSnapshot> {
+        let (base, deltas) = {
+            let storage = self.blockchain_storage.read().await;
+            if let Some(snapshot) = storage.world_snapshots.get(&epoch) {
+                return Some(snapshot.clone());
+            }
+            let delta = storage.world_deltas.get(&epoch)?;
+            let base = storage.world_snapshots.values()
+                .filter(|snapshot| snapshot.epoch < epoch)
+                .max_by_key(|snapshot| snapshot.epoch)?
+                .clone();
+            let deltas: Vec<WorldStateDelta> = storage.world_deltas
+                .range(base.epoch + 1..=epoch)
+                .map(|(_, delta)| delta.clone())
+                .collect();
+
+            // A gap in the deltas means the epoch can't be rebuilt faithfully
+            if deltas.len() as u64 != epoch - base.epoch || deltas.last()?.epoch != delta.epoch {
+                warn!("Cannot rebuild snapshot for epoch {}: deltas since epoch {} are incomplete", epoch, base.epoch);
+                return None;
+            }
+            (base, deltas)
+        };
+
+        let mut world_state = base.world_state;
+        for delta in &deltas {
+            if let Err(e) = self.advance_world_state(&mut world_state, delta.epoch, delta.timestamp, &delta.world_changes).await {
+                warn!("Failed to replay epoch {}: {}", delta.epoch, e);
+                return None;
+            }
+            Self::record_nonces(&mut world_state, &delta.nonces);
+        }
+
+        let last = deltas.last()?;
+        Some(WorldStateSnapshot {
+            epoch,
+            world_state,
+            block_hash: last.block_hash,
+            timestamp: last.timestamp,
+            validator_signatures: last.validator_signatures.clone(),
+        })
+    }
+
+    /// Sender for consensus messages arriving from the network; they are processed
+    /// by this manager's message loop once `start` has run
+    pub fn consensus_sender(&self) -> Option<ConsensusSender<ConsensusMessage>> {
+        self.message_sender.clone()
+    }
+
+    /// Consensus messages dropped because the message loop's queue stayed full
+    pub fn consensus_queue_saturation(&self) -> u64 {
+        self.message_sender.as_ref().map_or(0, ConsensusSender::saturated)
+    }
+
+    /// A proposal received from a peer that has not been finalized yet
+    pub async fn get_pending_proposal(&self, proposal_id: Uuid) -> Option<WorldStateProposal> {
+        self.pending_proposals.read().await.get(&proposal_id).cloned()
+    }
+
+    /// Sync with other nodes (request missing blocks)
+    pub async fn sync_with_network(&mut self, from_epoch: u64) -> Result<()> {
+        if self.consensus_enabled {
+            info!("🔄 Initiating blockchain sync from epoch {}", from_epoch);
+            
+            // Create sync request message
+            let sync_request = ConsensusMessage::SyncRequest {
+                requester: Uuid::new_v4(), // Would be actual node ID
+                from_epoch,
+                to_epoch: None,
+            };
+            
+            // Send sync request through message channel
+            if let Some(sender) = &self.message_sender {
+                if let Err(e) = sender.send(sync_request).await {
+                    warn!("Sync request not queued: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Save complete world state to blockchain storage (decentralized mode)
+    pub async fn save_world_state_to_blockchain(&mut self, save_name: String) -> Result<String> {
+        info!("💾 Saving world state '{}' to blockchain", save_name);
+
+        if !self.consensus_enabled {
+            return Err(anyhow::anyhow!("Blockchain save requires consensus mode"));
+        }
+
+        let world_state = self.world_state.read().await.clone();
+        let storage = self.blockchain_storage.read().await;
+        
+        // Create comprehensive save data
+        let save_data = BlockchainSaveData {
             save_id: Uuid::new_v4(),
             save_name: save_name.clone(),
             timestamp: SystemTime::now(),
@@ -772,7 +1577,7 @@ impl BlockchainManager {
                 total_blocks: storage.blocks.len(),
                 last_finalized_epoch: storage.last_finalized_epoch,
                 genesis_hash: storage.genesis_block.as_ref().map(|b| b.block_hash),
-                validator_count: 0, // Will be filled by consensus layer
+                validator_count: self.validators.read().await.len(),
                 network_id: self.calculate_network_id()?,
             },
             integrity_hash: String::new(), // Will be calculated
@@ -823,7 +1628,7 @@ impl BlockchainManager {
         };
 
         // Submit to consensus for network-wide recording
-        self.submit_world_change(save_change).await?;
+        self.submit_world_change(self.node_id, save_change).await?;
 
         info!("✅ World state '{}' saved to blockchain with integrity hash: {}", 
             save_name, integrity_hash);
@@ -860,9 +1665,13 @@ impl BlockchainManager {
         // Decompress and restore blockchain data
         self.restore_blockchain_data(save_snapshot.compressed_blocks).await?;
 
+        // Reconcile world time with the restored block history
+        let mut restored_world_state = save_snapshot.save_data.world_state;
+        self.reconcile_world_time(&mut restored_world_state).await;
+
         // Restore world state
         let mut world_state = self.world_state.write().await;
-        *world_state = save_snapshot.save_data.world_state;
+        *world_state = restored_world_state;
 
         // Update blockchain storage metadata
         let mut storage = self.blockchain_storage.write().await;
@@ -886,12 +1695,40 @@ impl BlockchainManager {
         drop(world_state);
 
         // Submit to consensus for network-wide recording
-        self.submit_world_change(load_change).await?;
+        self.submit_world_change(self.node_id, load_change).await?;
 
         info!("✅ World state '{}' loaded successfully from blockchain", save_name);
         Ok(())
     }
 
+    /// Derive world time from the highest applied block, correcting snapshots taken mid-tick
+    async fn reconcile_world_time(&self, world_state: &mut WorldState) {
+        let storage = self.blockchain_storage.read().await;
+        let highest_block = storage.blocks.range(..=world_state.current_epoch)
+            .next_back()
+            .map(|(_, block)| block);
+
+        if let Some(block) = highest_block {
+            let block_world_time = Self::world_time_from_block(block);
+            if block_world_time != world_state.world_time {
+                warn!("⚠️ Snapshot world_time {} disagrees with block {} (world_time {}) - reconciling to block history",
+                    world_state.world_time, block.epoch, block_world_time);
+                world_state.world_time = block_world_time;
+            }
+        }
+    }
+
+    /// World time recorded by a block (seconds since the Unix epoch)
+    fn world_time_from_block(block: &FinalizedBlock) -> u64 {
+        Self::world_time_at(block.timestamp)
+    }
+
+    fn world_time_at(timestamp: SystemTime) -> u64 {
+        timestamp.duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
     /// List all available blockchain saves
     pub async fn list_blockchain_saves(&self) -> Result<Vec<BlockchainSaveInfo>> {
         let storage = self.blockchain_storage.read().await;
@@ -930,8 +1767,9 @@ impl BlockchainManager {
 
         // Create portable export data
         let export_data = PortableWorldExport {
-            version: "1.0".to_string(),
+            version: CURRENT_EXPORT_VERSION.to_string(),
             export_timestamp: SystemTime::now(),
+            epoch: save_snapshot.save_data.world_state.current_epoch,
             original_save: save_snapshot,
             network_compatibility: NetworkCompatibility {
                 network_id: self.calculate_network_id()?,
@@ -953,9 +1791,67 @@ impl BlockchainManager {
     pub async fn import_world_state(&mut self, import_path: String, save_name: String) -> Result<()> {
         info!("📥 Importing world state from {} as '{}'", import_path, save_name);
 
-        // Read and deserialize export file
-        let file_content = std::fs::read_to_string(&import_path)?;
-        let export_data: PortableWorldExport = serde_json::from_str(&file_content)?;
+        let export_data = self.read_verified_export(&import_path)?;
+
+        // Store imported save
+        let mut storage = self.blockchain_storage.write().await;
+        if storage.blockchain_saves.is_none() {
+            storage.blockchain_saves = Some(HashMap::new());
+        }
+        
+        storage.blockchain_saves.as_mut().unwrap()
+            .insert(save_name.clone(), export_data.original_save);
+
+        info!("✅ World state imported successfully as '{}'", save_name);
+        Ok(())
+    }
+
+    /// Restore the named players from an export into the current world, leaving every
+    /// other player as it is. Nothing is merged unless all of them are in the export.
+    pub async fn import_players(&mut self, import_path: String, player_ids: &[Uuid]) -> Result<()> {
+        info!("📥 Restoring {} players from {}", player_ids.len(), import_path);
+
+        let export_data = self.read_verified_export(&import_path)?;
+        let exported = &export_data.original_save.save_data.world_state.players;
+        let restored = player_ids.iter()
+            .map(|id| exported.get(id).cloned()
+                .ok_or_else(|| anyhow::anyhow!("Player {} is not in {}", id, import_path)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut world_state = self.world_state.write().await;
+        for player in restored {
+            world_state.players.insert(player.player_id, player);
+        }
+
+        info!("✅ Restored {} players", player_ids.len());
+        Ok(())
+    }
+
+    /// Restore the named areas from an export into the current world, leaving every
+    /// other area as it is. Nothing is merged unless all of them are in the export.
+    pub async fn import_areas(&mut self, import_path: String, area_ids: &[String]) -> Result<()> {
+        info!("📥 Restoring {} areas from {}", area_ids.len(), import_path);
+
+        let export_data = self.read_verified_export(&import_path)?;
+        let exported = &export_data.original_save.save_data.world_state.areas;
+        let restored = area_ids.iter()
+            .map(|id| exported.get(id).cloned()
+                .ok_or_else(|| anyhow::anyhow!("Area '{}' is not in {}", id, import_path)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut world_state = self.world_state.write().await;
+        for area in restored {
+            world_state.areas.insert(area.area_id.clone(), area);
+        }
+
+        info!("✅ Restored {} areas", area_ids.len());
+        Ok(())
+    }
+
+    /// Read an export file and check it belongs to this network and is intact
+    fn read_verified_export(&self, import_path: &str) -> Result<PortableWorldExport> {
+        let file_content = std::fs::read_to_string(import_path)?;
+        let export_data = read_portable_export(&file_content)?;
 
         // Verify network compatibility
         let current_network_id = self.calculate_network_id()?;
@@ -969,28 +1865,24 @@ impl BlockchainManager {
             return Err(anyhow::anyhow!("Import integrity verification failed"));
         }
 
-        // Store imported save
-        let mut storage = self.blockchain_storage.write().await;
-        if storage.blockchain_saves.is_none() {
-            storage.blockchain_saves = Some(HashMap::new());
-        }
-        
-        storage.blockchain_saves.as_mut().unwrap()
-            .insert(save_name.clone(), export_data.original_save);
-
-        info!("✅ World state imported successfully as '{}'", save_name);
-        Ok(())
+        Ok(export_data)
     }
 
     /// Create automatic checkpoint of current world state
     pub async fn create_automatic_checkpoint(&mut self) -> Result<String> {
-        let checkpoint_name = format!("auto_checkpoint_{}", 
-            SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_secs());
+        // Nanosecond names keep back-to-back checkpoints from overwriting each other
+        let mut stamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_nanos();
+        let mut checkpoint_name = format!("auto_checkpoint_{}", stamp);
+        while self.blockchain_storage.read().await.blockchain_saves.as_ref()
+            .is_some_and(|saves| saves.contains_key(&checkpoint_name)) {
+            stamp += 1;
+            checkpoint_name = format!("auto_checkpoint_{}", stamp);
+        }
         
         info!("🔄 Creating automatic checkpoint: {}", checkpoint_name);
         self.save_world_state_to_blockchain(checkpoint_name.clone()).await?;
         
-        // Cleanup old checkpoints (keep last 10)
+        // Cleanup old checkpoints beyond the configured retention
         self.cleanup_old_checkpoints().await?;
         
         Ok(checkpoint_name)
@@ -1006,11 +1898,17 @@ impl BlockchainManager {
                 .map(|(name, snapshot)| (name.clone(), snapshot.save_data.timestamp))
                 .collect();
             
-            // Sort by timestamp (newest first)
-            auto_checkpoints.sort_by(|a, b| b.1.cmp(&a.1));
+            // Retention of 0 means keep every checkpoint
+            let retention = self.config.checkpoint_retention;
+            if retention == 0 {
+                return Ok(());
+            }
+            
+            // Sort by timestamp (newest first), then name for checkpoints taken in the same instant
+            auto_checkpoints.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
             
-            // Remove old checkpoints (keep last 10)
-            for (name, _) in auto_checkpoints.into_iter().skip(10) {
+            // Remove old checkpoints beyond the retention count
+            for (name, _) in auto_checkpoints.into_iter().skip(retention) {
                 saves.remove(&name);
                 debug!("🗑️ Removed old checkpoint: {}", name);
             }
@@ -1034,103 +1932,136 @@ impl BlockchainManager {
         hasher.update(save_data.save_name.as_bytes());
         hasher.update(save_data.timestamp.duration_since(SystemTime::UNIX_EPOCH)?.as_secs().to_be_bytes());
         
-        // Hash world state components
-        hasher.update(save_data.world_state.current_epoch.to_be_bytes());
-        hasher.update(save_data.world_state.world_time.to_be_bytes());
-        hasher.update(save_data.world_state.players.len().to_be_bytes());
-        hasher.update(save_data.world_state.areas.len().to_be_bytes());
-        hasher.update(save_data.world_state.npcs.len().to_be_bytes());
-        
+        // Hash world state contents, each length-prefixed so one part can't bleed into the next
+        let world_state = &save_data.world_state;
+        hasher.update(world_state.current_epoch.to_be_bytes());
+        hasher.update(world_state.world_time.to_be_bytes());
+        for part in [
+            merkle::canonical_json(&world_state.players)?,
+            merkle::canonical_json(&world_state.areas)?,
+            merkle::canonical_json(&world_state.npcs)?,
+            merkle::canonical_json(&world_state.global_events)?,
+        ] {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(&part);
+        }
+
         Ok(format!("{:x}", hasher.finalize()))
     }
 
     /// Compress blockchain data for storage
     async fn compress_blockchain_data(&self) -> Result<Vec<u8>> {
-        let storage = self.blockchain_storage.read().await;
-        let serialized = serde_json::to_vec(&*storage)?;
+        use std::io::Write;
+
+        // Earlier saves stay out: restoring never reads them, and each checkpoint would
+        // otherwise carry a copy of every checkpoint before it
+        let mut storage = self.blockchain_storage.write().await;
+        let saves = storage.blockchain_saves.take();
+        let serialized = serde_json::to_vec(&*storage);
+        storage.blockchain_saves = saves;
+        drop(storage);
+        let serialized = serialized?;
         
-        // Simple compression (in production, would use proper compression)
-        Ok(serialized)
+        let mut compressed = COMPRESSED_BLOCKS_MAGIC.to_vec();
+        let mut encoder = flate2::write::DeflateEncoder::new(&mut compressed, flate2::Compression::default());
+        encoder.write_all(&serialized)?;
+        encoder.finish()?;
+        Ok(compressed)
     }
 
     /// Restore blockchain data from compressed format
     async fn restore_blockchain_data(&mut self, compressed_data: Vec<u8>) -> Result<()> {
-        // Decompress and deserialize
-        let restored_storage: BlockchainStorage = serde_json::from_slice(&compressed_data)?;
+        use std::io::Read;
+
+        // Saves written before compression was added are bare JSON
+        let restored_storage: BlockchainStorage = match compressed_data.strip_prefix(COMPRESSED_BLOCKS_MAGIC) {
+            Some(deflated) => {
+                let mut serialized = Vec::new();
+                flate2::read::DeflateDecoder::new(deflated).read_to_end(&mut serialized)?;
+                serde_json::from_slice(&serialized)?
+            }
+            None => serde_json::from_slice(&compressed_data)?,
+        };
         
         let mut storage = self.blockchain_storage.write().await;
         storage.blocks = restored_storage.blocks;
+        storage.rebuild_block_hash_index();
         storage.world_snapshots = restored_storage.world_snapshots;
+        storage.world_deltas = restored_storage.world_deltas;
         storage.last_finalized_epoch = restored_storage.last_finalized_epoch;
         storage.genesis_block = restored_storage.genesis_block;
+        storage.bridge_transfers = restored_storage.bridge_transfers;
+        storage.reward_ledger = restored_storage.reward_ledger;
+        storage.activity_samples = restored_storage.activity_samples;
         
         Ok(())
     }
 
-    /// Generate merkle proof for world state
+    /// Generate merkle proof for world state.
+    ///
+    /// Leaves are the world header (epoch and world time) followed by every player,
+    /// area and NPC in key order; the proof shows the header is included under the root.
     async fn generate_world_state_merkle_proof(&self, world_state: &WorldState) -> Result<MerkleProof> {
-        let mut hasher = Sha256::new();
-        
-        // Hash all world state components
-        let players_hash = {
-            let mut h = Sha256::new();
-            for (id, player) in &world_state.players {
-                h.update(id.to_string().as_bytes());
-                h.update(player.name.as_bytes());
-                h.update(player.race.as_bytes());
-            }
-            format!("{:x}", h.finalize())
-        };
-        
-        let areas_hash = {
-            let mut h = Sha256::new();
-            for (id, area) in &world_state.areas {
-                h.update(id.as_bytes());
-                h.update(area.name.as_bytes());
-            }
-            format!("{:x}", h.finalize())
-        };
-        
-        let npcs_hash = {
-            let mut h = Sha256::new();
-            for (id, npc) in &world_state.npcs {
-                h.update(id.to_string().as_bytes());
-                h.update(npc.name.as_bytes());
-            }
-            format!("{:x}", h.finalize())
-        };
-        
-        hasher.update(players_hash.as_bytes());
-        hasher.update(areas_hash.as_bytes());
-        hasher.update(npcs_hash.as_bytes());
-        hasher.update(world_state.world_time.to_be_bytes());
-        
+        let tree = merkle::MerkleTree::from_leaves(Self::world_state_leaves(world_state));
+        let proof = tree.proof(0).expect("world state always has a header leaf");
+
         Ok(MerkleProof {
-            root_hash: format!("{:x}", hasher.finalize()),
-            proof_elements: vec![players_hash, areas_hash, npcs_hash],
+            root_hash: merkle::to_hex(&tree.root()),
+            proof_elements: proof.iter().map(|(sibling, _)| merkle::to_hex(sibling)).collect(),
             leaf_index: 0,
         })
     }
 
+    /// Hex merkle root over `world_state_leaves`, as carried in its merkle proofs
+    fn world_state_root(world_state: &WorldState) -> String {
+        merkle::to_hex(&merkle::MerkleTree::from_leaves(Self::world_state_leaves(world_state)).root())
+    }
+
+    fn world_state_leaves(world_state: &WorldState) -> Vec<merkle::Hash> {
+        let mut header = world_state.current_epoch.to_be_bytes().to_vec();
+        header.extend_from_slice(&world_state.world_time.to_be_bytes());
+        let mut leaves = vec![merkle::leaf_hash(&header)];
+
+        let players: BTreeMap<_, _> = world_state.players.iter().collect();
+        for (id, player) in players {
+            leaves.push(merkle::leaf_hash(format!("player:{}:{}:{}", id, player.name, player.race).as_bytes()));
+        }
+        let areas: BTreeMap<_, _> = world_state.areas.iter().collect();
+        for (id, area) in areas {
+            leaves.push(merkle::leaf_hash(format!("area:{}:{}", id, area.name).as_bytes()));
+        }
+        let npcs: BTreeMap<_, _> = world_state.npcs.iter().collect();
+        for (id, npc) in npcs {
+            leaves.push(merkle::leaf_hash(format!("npc:{}:{}", id, npc.name).as_bytes()));
+        }
+
+        leaves
+    }
+
     /// Verify world state merkle proof
     async fn verify_world_state_merkle_proof(&self, world_state: &WorldState, proof: &MerkleProof) -> Result<bool> {
-        let generated_proof = self.generate_world_state_merkle_proof(world_state).await?;
-        Ok(generated_proof.root_hash == proof.root_hash)
+        let leaves = Self::world_state_leaves(world_state);
+        let Some(leaf) = leaves.get(proof.leaf_index).copied() else {
+            return Ok(false);
+        };
+        let root = merkle::MerkleTree::from_leaves(leaves).root();
+        if merkle::from_hex(&proof.root_hash) != Some(root) {
+            return Ok(false);
+        }
+
+        let mut steps = Vec::with_capacity(proof.proof_elements.len());
+        for (level, element) in proof.proof_elements.iter().enumerate() {
+            let Some(sibling) = merkle::from_hex(element) else {
+                return Ok(false);
+            };
+            steps.push((sibling, (proof.leaf_index >> level) & 1 == 0));
+        }
+        Ok(merkle::verify(&root, &leaf, &steps))
     }
 
     /// Calculate merkle root for world changes
     fn calculate_merkle_root(&self, changes: &[WorldChange]) -> Result<String> {
-        if changes.is_empty() {
-            return Ok("0".repeat(64));
-        }
-
-        let mut hasher = Sha256::new();
-        for change in changes {
-            let serialized = serde_json::to_string(change)?;
-            hasher.update(serialized.as_bytes());
-        }
-        
-        Ok(format!("{:x}", hasher.finalize()))
+        Ok(merkle::to_hex(&merkle::MerkleTree::build(changes)?.root()))
     }
 
     /// Conflict resolution for concurrent world modifications
@@ -1145,52 +2076,115 @@ impl BlockchainManager {
             return Ok(conflicting_proposals.into_iter().next().unwrap());
         }
 
+        let preview = self.preview_conflict_resolution(&conflicting_proposals).await?;
+        if !preview.dropped.is_empty() {
+            info!("🔧 Dropped {} conflicting changes", preview.dropped.len());
+        }
+        let mut resolved_proposal = conflicting_proposals.iter()
+            .find(|proposal| proposal.proposal_id == preview.base_proposal)
+            .cloned()
+            .expect("base proposal is one of the inputs");
+
+        // Update the resolved proposal. Id and timestamp derive from the inputs rather than
+        // the local clock so independent nodes produce an identical proposal.
+        resolved_proposal.world_changes = preview.kept;
+        resolved_proposal.merkle_root = self.calculate_merkle_root(&resolved_proposal.world_changes)?;
+        resolved_proposal.proposal_id = Self::derive_resolved_proposal_id(&conflicting_proposals, &resolved_proposal.merkle_root);
+        resolved_proposal.timestamp = conflicting_proposals.iter()
+            .map(|proposal| proposal.timestamp)
+            .max()
+            .unwrap_or(resolved_proposal.timestamp);
+
+        info!("✅ Resolved conflicts into single proposal with {} changes", 
+            resolved_proposal.world_changes.len());
+
+        Ok(resolved_proposal)
+    }
+
+    /// Work out which changes resolving `proposals` would keep and which it would drop,
+    /// and why, without building a proposal. `resolve_world_state_conflicts` goes through
+    /// this, so the preview always matches what it would do.
+    pub async fn preview_conflict_resolution(&self, proposals: &[WorldStateProposal]) -> Result<ConflictPreview> {
+        let Some(first) = proposals.first() else {
+            return Err(anyhow::anyhow!("No proposals to resolve"));
+        };
+        if proposals.len() == 1 {
+            return Ok(ConflictPreview {
+                base_proposal: first.proposal_id,
+                kept: first.world_changes.clone(),
+                dropped: Vec::new(),
+            });
+        }
+
         // Group proposals by priority and timestamp
-        let mut prioritized_proposals = self.prioritize_proposals(&conflicting_proposals).await?;
+        let mut prioritized_proposals = self.prioritize_proposals(proposals).await?;
         
-        // Sort by priority (highest first), then by timestamp (earliest first)
+        // Sort by priority (highest first), then by timestamp (earliest first), then by id so
+        // every node picks the same base proposal regardless of arrival order
         prioritized_proposals.sort_by(|a, b| {
-            b.priority.partial_cmp(&a.priority).unwrap_or(std::cmp::Ordering::Equal)
+            b.priority.total_cmp(&a.priority)
                 .then_with(|| a.proposal.timestamp.cmp(&b.proposal.timestamp))
+                .then_with(|| a.proposal.proposal_id.cmp(&b.proposal.proposal_id))
         });
 
         // Start with the highest priority proposal as base
-        let mut resolved_proposal = prioritized_proposals[0].proposal.clone();
-        let mut resolved_changes = resolved_proposal.world_changes.clone();
+        let base = &prioritized_proposals[0].proposal;
+        let mut merged_changes = base.world_changes.clone();
+        let mut dropped = Vec::new();
 
-        // Merge compatible changes from other proposals
+        // Merge changes from other proposals that don't conflict with what is already in
         for prioritized in &prioritized_proposals[1..] {
-            let compatible_changes = self.find_compatible_changes(
-                &resolved_changes,
-                &prioritized.proposal.world_changes
-            ).await?;
-
-            for change in compatible_changes {
-                if !self.conflicts_with_existing(&resolved_changes, &change).await? {
-                    resolved_changes.push(change);
+            for change in &prioritized.proposal.world_changes {
+                match self.first_conflict(&merged_changes, change).await? {
+                    Some(kept_instead) => dropped.push(DroppedChange {
+                        change: change.clone(),
+                        kept_instead,
+                        reason: format!("conflicts with a change from higher-priority proposal {}", base.proposal_id),
+                    }),
+                    None => merged_changes.push(change.clone()),
                 }
             }
         }
 
         // Apply conflict resolution rules
-        resolved_changes = self.apply_conflict_resolution_rules(resolved_changes).await?;
+        let resolution = self.apply_conflict_resolution_rules(merged_changes).await?;
+        dropped.extend(resolution.dropped);
 
-        // Update the resolved proposal
-        resolved_proposal.world_changes = resolved_changes;
-        resolved_proposal.proposal_id = Uuid::new_v4(); // New ID for resolved proposal
-        resolved_proposal.merkle_root = self.calculate_merkle_root(&resolved_proposal.world_changes)?;
-        resolved_proposal.timestamp = SystemTime::now();
+        Ok(ConflictPreview {
+            base_proposal: base.proposal_id,
+            kept: resolution.kept,
+            dropped,
+        })
+    }
 
-        info!("✅ Resolved conflicts into single proposal with {} changes", 
-            resolved_proposal.world_changes.len());
+    /// Content-derived id for a resolved proposal: the same sources and outcome give the same id
+    fn derive_resolved_proposal_id(sources: &[WorldStateProposal], merkle_root: &str) -> Uuid {
+        let mut source_ids: Vec<Uuid> = sources.iter().map(|proposal| proposal.proposal_id).collect();
+        source_ids.sort();
 
-        Ok(resolved_proposal)
+        let mut hasher = Sha256::new();
+        for id in &source_ids {
+            hasher.update(id.as_bytes());
+        }
+        hasher.update(merkle_root.as_bytes());
+
+        let digest = hasher.finalize();
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&digest[..16]);
+        Uuid::from_bytes(bytes)
     }
 
     /// Prioritize proposals based on various factors
     async fn prioritize_proposals(&self, proposals: &[WorldStateProposal]) -> Result<Vec<PrioritizedProposal>> {
         let mut prioritized = Vec::new();
 
+        // Measure age against the newest proposal, not the local clock, so the score is the
+        // same on every node
+        let newest_timestamp = proposals.iter()
+            .map(|proposal| proposal.timestamp)
+            .max()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
         for proposal in proposals {
             let mut priority = 0.0;
 
@@ -1201,7 +2195,7 @@ impl BlockchainManager {
             }
 
             // Priority based on proposal timestamp (earlier = higher priority)
-            let age_seconds = SystemTime::now()
+            let age_seconds = newest_timestamp
                 .duration_since(proposal.timestamp)
                 .unwrap_or_default()
                 .as_secs();
@@ -1236,56 +2230,34 @@ impl BlockchainManager {
         priority / changes.len() as f64
     }
 
-    /// Find changes that don't conflict with existing changes
-    async fn find_compatible_changes(&self, existing: &[WorldChange], new: &[WorldChange]) -> Result<Vec<WorldChange>> {
-        let mut compatible = Vec::new();
-
-        for new_change in new {
-            let mut is_compatible = true;
-
-            for existing_change in existing {
-                if self.changes_conflict(existing_change, new_change).await? {
-                    is_compatible = false;
-                    break;
-                }
-            }
-
-            if is_compatible {
-                compatible.push(new_change.clone());
-            }
-        }
-
-        Ok(compatible)
-    }
-
-    /// Check if a new change conflicts with existing changes
-    async fn conflicts_with_existing(&self, existing: &[WorldChange], new_change: &WorldChange) -> Result<bool> {
+    /// First of `existing` that `new_change` conflicts with, if any
+    async fn first_conflict(&self, existing: &[WorldChange], new_change: &WorldChange) -> Result<Option<WorldChange>> {
         for existing_change in existing {
             if self.changes_conflict(existing_change, new_change).await? {
-                return Ok(true);
+                return Ok(Some(existing_change.clone()));
             }
         }
-        Ok(false)
+        Ok(None)
     }
 
     /// Determine if two world changes conflict with each other
     async fn changes_conflict(&self, change1: &WorldChange, change2: &WorldChange) -> Result<bool> {
-        match (change1, change2) {
-            // Player actions in the same area at similar times may conflict
-            (WorldChange::PlayerAction { player_id: id1, area_id: area1, timestamp: t1, .. },
-             WorldChange::PlayerAction { player_id: id2, area_id: area2, timestamp: t2, .. }) => {
-                if id1 == id2 {
-                    // Same player can't do two things simultaneously
-                    let time_diff = t1.duration_since(*t2).unwrap_or_else(|_| t2.duration_since(*t1).unwrap_or_default());
-                    return Ok(time_diff.as_secs() < 1); // Conflict if within 1 second
-                }
-                if area1 == area2 {
-                    // Check for area-specific conflicts (e.g., both trying to pick up same item)
-                    return Ok(self.check_area_specific_conflicts(change1, change2).await?);
-                }
-                Ok(false)
+        // Player and NPC actions collide the same way, in either pairing
+        if let (Some(actor1), Some(actor2)) = (ActorAction::of(change1), ActorAction::of(change2)) {
+            if actor1.is_npc == actor2.is_npc && actor1.actor_id == actor2.actor_id {
+                // Same actor can't do two things simultaneously
+                let (t1, t2) = (actor1.timestamp, actor2.timestamp);
+                let time_diff = t1.duration_since(t2).unwrap_or_else(|_| t2.duration_since(t1).unwrap_or_default());
+                return Ok(time_diff.as_secs() < 1); // Conflict if within 1 second
+            }
+            if actor1.area_id == actor2.area_id {
+                // Check for area-specific conflicts (e.g., both trying to pick up same item)
+                return self.check_area_specific_conflicts(change1, change2).await;
             }
+            return Ok(false);
+        }
 
+        match (change1, change2) {
             // Area updates in the same area may conflict
             (WorldChange::AreaUpdate { area_id: area1, .. },
              WorldChange::AreaUpdate { area_id: area2, .. }) => {
@@ -1309,10 +2281,11 @@ impl BlockchainManager {
         }
     }
 
-    /// Check for area-specific conflicts between player actions
+    /// Check for area-specific conflicts between player or NPC actions
     async fn check_area_specific_conflicts(&self, change1: &WorldChange, change2: &WorldChange) -> Result<bool> {
-        if let (WorldChange::PlayerAction { action_type: action1, data: data1, .. },
-                WorldChange::PlayerAction { action_type: action2, data: data2, .. }) = (change1, change2) {
+        if let (Some(actor1), Some(actor2)) = (ActorAction::of(change1), ActorAction::of(change2)) {
+            let (action1, data1) = (actor1.action_type, actor1.data);
+            let (action2, data2) = (actor2.action_type, actor2.data);
             
             // Check for item pickup conflicts
             if action1 == "pickup" && action2 == "pickup" {
@@ -1349,53 +2322,75 @@ impl BlockchainManager {
         Ok(false)
     }
 
-    /// Apply conflict resolution rules to a set of changes
-    async fn apply_conflict_resolution_rules(&self, mut changes: Vec<WorldChange>) -> Result<Vec<WorldChange>> {
-        // Sort changes by timestamp to process them in order
-        changes.sort_by(|a, b| self.get_change_timestamp(a).cmp(&self.get_change_timestamp(b)));
+    /// Apply conflict resolution rules to a set of changes. Conflict is treated as
+    /// transitive: if A conflicts with B and B with C, all three form one group and
+    /// only the group's winner survives.
+    async fn apply_conflict_resolution_rules(&self, mut changes: Vec<WorldChange>) -> Result<ResolvedChanges> {
+        // Sort changes by timestamp to process them in order, breaking ties on content
+        let mut keyed: Vec<(SystemTime, Vec<u8>, WorldChange)> = changes.drain(..)
+            .map(|change| {
+                let content = merkle::canonical_json(&change).unwrap_or_default();
+                (self.get_change_timestamp(&change), content, change)
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        changes = keyed.into_iter().map(|(_, _, change)| change).collect();
+
+        // Union-find over the conflict relation
+        let mut parent: Vec<usize> = (0..changes.len()).collect();
+        for i in 0..changes.len() {
+            for j in (i + 1)..changes.len() {
+                if self.changes_conflict(&changes[i], &changes[j]).await? {
+                    let (root_i, root_j) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                    // Keep the earlier index as root so groups stay in timestamp order
+                    parent[root_i.max(root_j)] = root_i.min(root_j);
+                }
+            }
+        }
 
-        let mut resolved_changes = Vec::new();
-        let mut processed_conflicts = std::collections::HashSet::new();
+        let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for i in 0..changes.len() {
+            let root = find_root(&mut parent, i);
+            groups.entry(root).or_default().push(i);
+        }
 
-        for (i, change) in changes.iter().enumerate() {
-            if processed_conflicts.contains(&i) {
-                continue; // Skip already processed conflicts
+        let mut resolved = ResolvedChanges::default();
+        for members in groups.values() {
+            if members.len() == 1 {
+                resolved.kept.push(changes[members[0]].clone());
+                continue;
             }
 
-            let mut conflicting_indices = vec![i];
-            
-            // Find all changes that conflict with this one
-            for (j, other_change) in changes.iter().enumerate().skip(i + 1) {
-                if processed_conflicts.contains(&j) {
-                    continue;
-                }
-                
-                if self.changes_conflict(change, other_change).await? {
-                    conflicting_indices.push(j);
-                }
-            }
+            // Resolve the conflict using priority rules
+            let group: Vec<&WorldChange> = members.iter().map(|&idx| &changes[idx]).collect();
+            let winner = self.resolve_single_conflict(&group).await?;
+            let winner_idx = winner.as_ref().and_then(|winner| {
+                let content = merkle::canonical_json(winner).unwrap_or_default();
+                members.iter().copied().find(|&idx| merkle::canonical_json(&changes[idx]).unwrap_or_default() == content)
+            });
 
-            if conflicting_indices.len() == 1 {
-                // No conflicts, add the change as-is
-                resolved_changes.push(change.clone());
-            } else {
-                // Resolve the conflict using priority rules
-                let resolved_change = self.resolve_single_conflict(
-                    &conflicting_indices.iter().map(|&idx| &changes[idx]).collect::<Vec<_>>()
-                ).await?;
-                
-                if let Some(resolved) = resolved_change {
-                    resolved_changes.push(resolved);
-                }
-                
-                // Mark all conflicting indices as processed
-                for &idx in &conflicting_indices {
-                    processed_conflicts.insert(idx);
+            for &idx in members {
+                if Some(idx) != winner_idx {
+                    debug!("🗑️ Dropping change at {:?}: lost a conflict", self.get_change_timestamp(&changes[idx]));
+                    let Some(winner) = &winner else { continue };
+                    let reason = if self.changes_conflict(winner, &changes[idx]).await? {
+                        "conflicts with an earlier change".to_string()
+                    } else {
+                        "conflicts, through other changes, with an earlier change".to_string()
+                    };
+                    resolved.dropped.push(DroppedChange {
+                        change: changes[idx].clone(),
+                        kept_instead: winner.clone(),
+                        reason,
+                    });
                 }
             }
+            if let Some(winner) = winner {
+                resolved.kept.push(winner);
+            }
         }
 
-        Ok(resolved_changes)
+        Ok(resolved)
     }
 
     /// Resolve a single conflict between multiple changes
@@ -1430,20 +2425,65 @@ impl BlockchainManager {
         }
     }
 
-    /// Get validator information for a node
+    /// Get validator information for a node; `None` unless it is a registered validator
     async fn get_validator_info(&self, node_id: Uuid) -> Option<ValidatorInfo> {
-        // This would interface with the consensus layer
-        // For now, return default validator info
-        Some(ValidatorInfo {
+        self.validators.read().await.get(&node_id).cloned()
+    }
+
+    /// Add `node_id` to the validator registry, or update its stake if already present
+    pub async fn register_validator(&self, node_id: Uuid, stake_amount: u64) {
+        let now = SystemTime::now();
+        Self::insert_validator(&mut *self.validators.write().await, node_id, stake_amount, now);
+        self.blockchain_storage.write().await.record_activity(node_id, now);
+    }
+
+    /// Count `node_id` as up at `at`; fed by joins and finalized blocks, and by callers
+    /// that see other signs of life such as network heartbeats
+    pub async fn record_validator_activity(&self, node_id: Uuid, at: SystemTime) {
+        if let Some(validator) = self.validators.write().await.get_mut(&node_id) {
+            validator.last_activity = validator.last_activity.max(at);
+        }
+        self.blockchain_storage.write().await.record_activity(node_id, at);
+    }
+
+    /// Remove `node_id` from the validator registry; its block signatures stop counting
+    pub async fn deregister_validator(&mut self, node_id: Uuid) -> Option<ValidatorInfo> {
+        self.validator_keys.write().await.remove(&node_id);
+        Self::remove_validator(&mut *self.validators.write().await, node_id)
+    }
+
+    fn insert_validator(validators: &mut HashMap<Uuid, ValidatorInfo>, node_id: Uuid, stake_amount: u64, joined_at: SystemTime) {
+        let validator = validators.entry(node_id).or_insert_with(|| ValidatorInfo {
             node_id,
-            stake_amount: 1000,
-            voting_power: 0.1,
+            stake_amount,
+            voting_power: 0.0,
             is_active: true,
-            last_activity: SystemTime::now(),
+            last_activity: joined_at,
             reputation_score: 100.0,
             blocks_produced: 0,
             slashing_count: 0,
-        })
+        });
+        validator.stake_amount = stake_amount;
+        validator.last_activity = validator.last_activity.max(joined_at);
+        Self::recompute_voting_power(validators);
+    }
+
+    fn remove_validator(validators: &mut HashMap<Uuid, ValidatorInfo>, node_id: Uuid) -> Option<ValidatorInfo> {
+        let removed = validators.remove(&node_id);
+        Self::recompute_voting_power(validators);
+        removed
+    }
+
+    /// Each validator's voting power is its share of the total registered stake
+    fn recompute_voting_power(validators: &mut HashMap<Uuid, ValidatorInfo>) {
+        let total_stake: u64 = validators.values().map(|validator| validator.stake_amount).sum();
+        for validator in validators.values_mut() {
+            validator.voting_power = if total_stake > 0 {
+                validator.stake_amount as f64 / total_stake as f64
+            } else {
+                0.0
+            };
+        }
     }
 
     /// Create conflict resolution report
@@ -1468,6 +2508,9 @@ impl BlockchainManager {
     pub async fn validate_masternode_eligibility(&self, node_id: Uuid, stake_amount: u64) -> Result<MasternodeEligibility> {
         info!("🏛️ Validating masternode eligibility for node: {}", node_id);
 
+        // A registered validator is judged on the stake it actually has bonded
+        let stake_amount = self.get_validator_info(node_id).await.map_or(stake_amount, |info| info.stake_amount);
+
         let mut eligibility = MasternodeEligibility {
             node_id,
             is_eligible: false,
@@ -1540,31 +2583,9 @@ impl BlockchainManager {
         Ok(eligibility)
     }
 
-    /// Calculate node uptime percentage
+    /// Calculate node uptime percentage over the last 30 days of activity samples
     async fn calculate_node_uptime(&self, node_id: Uuid) -> Result<f64> {
-        // This would check the last 30 days of activity
-        // For now, return a default based on validator info
-        if let Some(validator) = self.get_validator_info(node_id).await {
-            let time_since_activity = SystemTime::now()
-                .duration_since(validator.last_activity)
-                .unwrap_or_default()
-                .as_secs();
-            
-            // Calculate uptime based on recent activity (simplified)
-            let uptime = if time_since_activity < 300 { // 5 minutes
-                100.0
-            } else if time_since_activity < 3600 { // 1 hour
-                95.0
-            } else if time_since_activity < 86400 { // 1 day
-                80.0
-            } else {
-                50.0
-            };
-            
-            Ok(uptime)
-        } else {
-            Ok(0.0)
-        }
+        Ok(self.blockchain_storage.read().await.uptime_percentage(node_id, SystemTime::now()))
     }
 
     /// Get block production history for a node
@@ -1648,7 +2669,7 @@ impl BlockchainManager {
         };
 
         // Validate that the proposer is an eligible masternode
-        let proposer_stake = 10000; // Would get actual stake from consensus layer
+        let proposer_stake = self.get_validator_info(proposer).await.map_or(0, |info| info.stake_amount);
         let eligibility = self.validate_masternode_eligibility(proposer, proposer_stake).await?;
         
         if !eligibility.is_eligible {
@@ -1679,7 +2700,7 @@ impl BlockchainManager {
 
         // Validation rewards for all validators who signed the block
         for (validator_id, _signature) in &block_signatures {
-            let validator_stake = 5000; // Would get actual stake
+            let validator_stake = self.get_validator_info(*validator_id).await.map_or(0, |info| info.stake_amount);
             let validator_eligibility = self.validate_masternode_eligibility(*validator_id, validator_stake).await?;
             
             if validator_eligibility.is_eligible {
@@ -1762,7 +2783,12 @@ impl BlockchainManager {
 
         // Submit all reward changes for consensus
         for change in reward_changes {
-            self.submit_world_change(change).await?;
+            self.submit_world_change(self.node_id, change).await?;
+        }
+
+        let mut storage = self.blockchain_storage.write().await;
+        for reward in distribution.masternode_rewards.iter().chain(&distribution.validator_rewards) {
+            *storage.reward_ledger.entry(reward.node_id).or_insert(0) += reward.amount;
         }
 
         Ok(())
@@ -1799,13 +2825,104 @@ impl BlockchainManager {
         })
     }
 
-    /// Calculate total rewards earned by a masternode
-    async fn calculate_total_rewards_earned(&self, node_id: Uuid) -> Result<u64> {
-        // This would query historical reward records
-        // For now, estimate based on block production
-        let history = self.get_block_production_history(node_id).await?;
-        let base_reward = self.config.reward_amount;
-        Ok(history.len() as u64 * base_reward)
+    /// Everything an operator needs to know about a masternode in one place: whether it is
+    /// eligible and why not, how it has been performing and what it can expect to earn
+    pub async fn masternode_report(&self, node_id: Uuid) -> Result<MasternodeReport> {
+        let eligibility = self.validate_masternode_eligibility(node_id, 0).await?;
+        let performance = self.get_masternode_performance_stats(node_id).await?;
+
+        // Eligibility stops at the first stake shortfall, so estimate rewards here instead
+        let estimated_rewards = self.calculate_masternode_rewards(
+            eligibility.current_stake,
+            performance.reputation_score,
+            performance.uptime_percentage,
+        ).await?;
+
+        Ok(MasternodeReport {
+            node_id,
+            is_eligible: eligibility.is_eligible,
+            current_stake: eligibility.current_stake,
+            required_stake: eligibility.required_stake,
+            validation_errors: eligibility.validation_errors,
+            performance,
+            estimated_rewards,
+        })
+    }
+
+    /// Total rewards distributed to a masternode, from the reward ledger
+    async fn calculate_total_rewards_earned(&self, node_id: Uuid) -> Result<u64> {
+        let storage = self.blockchain_storage.read().await;
+        Ok(storage.reward_ledger.get(&node_id).copied().unwrap_or(0))
+    }
+
+    /// Compare each proposer's share of recent blocks with its stake-weighted expected share
+    pub async fn production_fairness_report(&self, window_epochs: u64) -> FairnessReport {
+        let (window_start_epoch, window_end_epoch, blocks_by_proposer) = {
+            let storage = self.blockchain_storage.read().await;
+            let window_end_epoch = storage.blocks.keys().next_back().copied().unwrap_or(0);
+            // Genesis has no real proposer, so it never counts towards production
+            let window_start_epoch = window_end_epoch.saturating_sub(window_epochs.saturating_sub(1)).max(1);
+
+            let mut blocks_by_proposer: BTreeMap<Uuid, u64> = BTreeMap::new();
+            for block in storage.blocks.range(window_start_epoch..=window_end_epoch).map(|(_, block)| block) {
+                *blocks_by_proposer.entry(block.proposer).or_insert(0) += 1;
+            }
+            (window_start_epoch, window_end_epoch, blocks_by_proposer)
+        };
+
+        let mut stakes = BTreeMap::new();
+        for node_id in blocks_by_proposer.keys() {
+            let stake = self.get_validator_info(*node_id).await.map(|v| v.stake_amount).unwrap_or(0);
+            stakes.insert(*node_id, stake);
+        }
+
+        let total_blocks: u64 = blocks_by_proposer.values().sum();
+        let total_stake: u64 = stakes.values().sum();
+        let max_deviation = self.config.fairness_max_deviation;
+
+        let proposers: Vec<ProposerShare> = blocks_by_proposer.iter()
+            .map(|(node_id, blocks_produced)| {
+                let actual_share = *blocks_produced as f64 / total_blocks.max(1) as f64;
+                let expected_share = if total_stake > 0 {
+                    stakes[node_id] as f64 / total_stake as f64
+                } else {
+                    1.0 / blocks_by_proposer.len() as f64
+                };
+                let deviation = if expected_share > 0.0 {
+                    actual_share / expected_share - 1.0
+                } else {
+                    f64::INFINITY
+                };
+
+                ProposerShare {
+                    node_id: *node_id,
+                    blocks_produced: *blocks_produced,
+                    actual_share,
+                    expected_share,
+                    deviation,
+                    over_producing: deviation > max_deviation,
+                }
+            })
+            .collect();
+
+        let flagged: Vec<Uuid> = proposers.iter()
+            .filter(|share| share.over_producing)
+            .map(|share| share.node_id)
+            .collect();
+
+        if !flagged.is_empty() {
+            warn!("⚖️ {} proposer(s) exceeded their fair block share in epochs {}..={}", 
+                flagged.len(), window_start_epoch, window_end_epoch);
+        }
+
+        FairnessReport {
+            window_start_epoch,
+            window_end_epoch,
+            total_blocks,
+            max_deviation,
+            proposers,
+            flagged,
+        }
     }
 
     /// Calculate performance rating
@@ -1845,6 +2962,8 @@ impl BlockchainManager {
     pub async fn create_blockchain_nft(&mut self, collection_id: String, owner_id: Uuid, attributes: Vec<NFTAttribute>) -> Result<Uuid> {
         info!("🎨 Creating blockchain-backed NFT in collection: {}", collection_id);
 
+        self.ensure_fee_payable(owner_id, TransactionType::GlobalEvent, 0).await?;
+
         let token_id = {
             let nft_system = self.nft_system.write().await;
             let mint_request = crate::nft_system::MintRequest {
@@ -1878,16 +2997,127 @@ impl BlockchainManager {
         };
 
         // Submit to consensus for network-wide recording
-        self.submit_world_change(nft_creation_change).await?;
+        self.submit_world_change(owner_id, nft_creation_change).await?;
 
         info!("✅ Blockchain NFT created with ID: {}", token_id);
         Ok(token_id)
     }
 
+    /// Transfer an NFT through blockchain consensus; only its current owner may send it
+    pub async fn transfer_blockchain_nft(&mut self, token_id: Uuid, from: Uuid, to: Uuid) -> Result<()> {
+        info!("🎨 Transferring blockchain NFT {} from {} to {}", token_id, from, to);
+
+        self.ensure_fee_payable(from, TransactionType::GlobalEvent, 0).await?;
+
+        // The NFT system verifies ownership and lock state before moving it
+        self.nft_system.read().await.transfer_nft(token_id, from, to).await?;
+
+        let nft_transfer_change = WorldChange::WorldEvent {
+            event_id: Uuid::new_v4(),
+            event_type: "NFT_TRANSFER".to_string(),
+            timestamp: SystemTime::now(),
+            affected_areas: vec!["GLOBAL".to_string()],
+            data: serde_json::json!({
+                "token_id": token_id,
+                "from": from,
+                "to": to,
+                "consensus_verified": true
+            }),
+        };
+
+        // Submit to consensus for network-wide recording
+        self.submit_world_change(from, nft_transfer_change).await?;
+
+        info!("✅ Blockchain NFT {} transferred", token_id);
+        Ok(())
+    }
+
+    /// Sell an NFT for `price` native tokens, paying the creator royalty and marketplace fee
+    /// out of the price before the seller receives the remainder
+    pub async fn sell_nft(&mut self, token_id: Uuid, seller: Uuid, buyer: Uuid, price: u64) -> Result<crate::nft_system::SaleBreakdown> {
+        info!("🛒 Selling NFT {} from {} to {} for {}", token_id, seller, buyer, price);
+
+        let breakdown = self.nft_system.read().await.sale_breakdown(token_id, seller, price).await?;
+
+        // The buyer initiates the purchase, so pays the price and the recording fee
+        self.ensure_fee_payable(buyer, TransactionType::GlobalEvent, price).await?;
+        let token_economy = self.token_economy.read().await;
+        let token_symbol = token_economy.config.native_token_symbol.clone();
+        let buyer_balance = token_economy.get_balance(buyer, token_symbol.clone()).await;
+        if buyer_balance < price {
+            return Err(anyhow::anyhow!("Buyer {} cannot afford {} {}: {} available", buyer, price, token_symbol, buyer_balance));
+        }
+
+        // Take payment first, since it can be handed back; if the NFT then refuses to move
+        // (cooldown, lock), the buyer is refunded and the sale fails with nothing changed
+        let payouts = [
+            (breakdown.creator_id, breakdown.royalty),
+            (breakdown.marketplace_account, breakdown.marketplace_fee),
+            (seller, breakdown.seller_proceeds),
+        ];
+        token_economy.distribute_payment(buyer, token_symbol.clone(), &payouts).await?;
+        if let Err(e) = self.nft_system.read().await.transfer_nft(token_id, seller, buyer).await {
+            token_economy.refund_payment(buyer, token_symbol.clone(), &payouts).await?;
+            return Err(e);
+        }
+        drop(token_economy);
+
+        let nft_sale_change = WorldChange::WorldEvent {
+            event_id: Uuid::new_v4(),
+            event_type: "NFT_SALE".to_string(),
+            timestamp: SystemTime::now(),
+            affected_areas: vec!["GLOBAL".to_string()],
+            data: serde_json::json!({
+                "token_id": token_id,
+                "seller": seller,
+                "buyer": buyer,
+                "token_type": token_symbol,
+                "breakdown": breakdown,
+                "consensus_verified": true
+            }),
+        };
+
+        // Submit to consensus for network-wide recording
+        self.submit_world_change(buyer, nft_sale_change).await?;
+
+        info!("✅ NFT {} sold: royalty {}, marketplace fee {}, seller proceeds {}",
+            token_id, breakdown.royalty, breakdown.marketplace_fee, breakdown.seller_proceeds);
+        Ok(breakdown)
+    }
+
+    /// Burn an NFT through blockchain consensus; only its current owner may burn it
+    pub async fn burn_blockchain_nft(&mut self, token_id: Uuid, owner: Uuid) -> Result<()> {
+        info!("🔥 Burning blockchain NFT {} owned by {}", token_id, owner);
+
+        self.ensure_fee_payable(owner, TransactionType::GlobalEvent, 0).await?;
+        self.nft_system.read().await.burn_nft(token_id, owner).await?;
+
+        let nft_burn_change = WorldChange::WorldEvent {
+            event_id: Uuid::new_v4(),
+            event_type: "NFT_BURN".to_string(),
+            timestamp: SystemTime::now(),
+            affected_areas: vec!["GLOBAL".to_string()],
+            data: serde_json::json!({
+                "token_id": token_id,
+                "owner_id": owner,
+                "consensus_verified": true
+            }),
+        };
+
+        // Submit to consensus for network-wide recording
+        self.submit_world_change(owner, nft_burn_change).await?;
+
+        info!("✅ Blockchain NFT {} burned", token_id);
+        Ok(())
+    }
+
     /// Transfer tokens through blockchain consensus
     pub async fn transfer_tokens(&mut self, from: Uuid, to: Uuid, token_type: String, amount: u64) -> Result<String> {
         info!("💰 Processing blockchain token transfer: {} {} from {} to {}", amount, token_type, from, to);
 
+        let native_amount = if token_type == self.token_economy.read().await.config.native_token_symbol { amount } else { 0 };
+        self.ensure_fee_payable(from, TransactionType::GlobalEvent, native_amount).await?;
+
         let token_economy = self.token_economy.read().await;
         let transaction_id = token_economy.transfer_tokens(from, to, token_type.clone(), amount).await?;
 
@@ -1911,12 +3141,24 @@ impl BlockchainManager {
         };
 
         // Submit to consensus for network-wide recording
-        self.submit_world_change(transfer_change).await?;
+        self.submit_world_change(from, transfer_change).await?;
 
         info!("✅ Blockchain token transfer completed: {}", transaction_id_str);
         Ok(transaction_id_str)
     }
 
+    /// Balance of `token_type` held by `owner`, including any transfer already processed
+    pub async fn get_balance(&self, owner: Uuid, token_type: &str) -> Result<u64> {
+        let token_economy = self.token_economy.read().await;
+        Ok(token_economy.get_balance(owner, token_type.to_string()).await)
+    }
+
+    /// Every token `owner` holds a non-zero balance of
+    pub async fn get_all_balances(&self, owner: Uuid) -> Result<HashMap<String, u64>> {
+        let token_economy = self.token_economy.read().await;
+        Ok(token_economy.get_balances(owner).await)
+    }
+
     /// Get comprehensive blockchain economy stats
     pub async fn get_economy_stats(&self) -> Result<BlockchainEconomyStats> {
         let nft_system = self.nft_system.read().await;
@@ -1945,21 +3187,27 @@ impl BlockchainManager {
     pub async fn process_defi_operation(&mut self, user_id: Uuid, operation: DeFiOperation) -> Result<String> {
         info!("🏦 Processing DeFi operation through blockchain consensus");
 
+        self.ensure_fee_payable(user_id, TransactionType::GlobalEvent, 0).await?;
+
         let token_economy = self.token_economy.read().await;
+        let mut withdrawal = None;
         let result = match &operation {
             DeFiOperation::AddLiquidity { pool_id, token_a_amount, token_b_amount, .. } => {
                 let pool_id_str = pool_id.to_string();
                 token_economy.add_liquidity(user_id, pool_id_str, *token_a_amount, *token_b_amount).await?.to_string()
             }
             DeFiOperation::RemoveLiquidity { pool_id, liquidity_amount, .. } => {
-                // For now, we'll simulate remove liquidity by swapping tokens
                 let pool_id_str = pool_id.to_string();
-                token_economy.swap_tokens(user_id, pool_id_str, "LP_TOKEN".to_string(), *liquidity_amount, 1).await?.to_string()
+                let withdrawn = token_economy.remove_liquidity(user_id, pool_id_str, *liquidity_amount).await?;
+                let result = format!("{} {} + {} {}", withdrawn.amount_a, withdrawn.token_a, withdrawn.amount_b, withdrawn.token_b);
+                withdrawal = Some(withdrawn);
+                result
+            }
+            DeFiOperation::Stake { token_type, amount, duration_days } => {
+                token_economy.stake(user_id, token_type.clone(), *amount, *duration_days).await?.to_string()
             }
-            DeFiOperation::Stake { token_type, amount, duration_days: _ } => {
-                // Simulate staking by creating a token transfer to a staking pool
-                let staking_pool_id = Uuid::new_v4();
-                token_economy.transfer_tokens(user_id, staking_pool_id, token_type.clone(), *amount).await?.to_string()
+            DeFiOperation::Unstake { stake_id } => {
+                token_economy.unstake(*stake_id).await?.to_string()
             }
             DeFiOperation::YieldFarm { farm_id: _, amount } => {
                 // Simulate yield farming by minting reward tokens
@@ -1988,52 +3236,151 @@ impl BlockchainManager {
                 "user_id": user_id,
                 "operation": operation,
                 "result": result,
+                "withdrawal": withdrawal,
                 "consensus_verified": true
             }),
         };
 
         // Submit to consensus for network-wide recording
-        self.submit_world_change(defi_change).await?;
+        self.submit_world_change(user_id, defi_change).await?;
 
         info!("✅ DeFi operation completed: {}", result);
         Ok(result)
     }
 
-    /// Cross-chain bridge operation through blockchain consensus
+    /// Cross-chain bridge operation through blockchain consensus. The tokens are locked
+    /// in escrow until the target chain's mint is proven or the transfer times out.
     pub async fn initiate_cross_chain_bridge(&mut self, user_id: Uuid, source_chain: String, target_chain: String, token_type: String, amount: u64) -> Result<String> {
         info!("🌉 Initiating cross-chain bridge operation");
 
-        // For now, simulate cross-chain bridge by transferring tokens to a bridge pool
-        let bridge_pool_id = Uuid::new_v4();
-        let token_economy = self.token_economy.read().await;
-        let transfer_id = token_economy.transfer_tokens(user_id, bridge_pool_id, token_type.clone(), amount).await?;
-        
-        let bridge_id = format!("bridge_{}", transfer_id);
-        drop(token_economy);
+        if amount == 0 {
+            return Err(anyhow::anyhow!("Bridge amount must be positive"));
+        }
+        let native_amount = if token_type == self.token_economy.read().await.config.native_token_symbol { amount } else { 0 };
+        self.ensure_fee_payable(user_id, TransactionType::GlobalEvent, native_amount).await?;
+
+        let transfer_id = self.token_economy.read().await
+            .distribute_payment(user_id, token_type.clone(), &[(BRIDGE_ESCROW_ACCOUNT, amount)]).await?
+            .into_iter()
+            .next()
+            .unwrap_or_else(Uuid::new_v4);
+
+        let transfer = BridgeTransfer {
+            bridge_id: format!("bridge_{}", transfer_id),
+            user_id,
+            source_chain,
+            target_chain,
+            token_type,
+            amount,
+            status: BridgeStatus::Locked,
+            locked_at: SystemTime::now(),
+            proof: None,
+        };
+        let bridge_id = transfer.bridge_id.clone();
+        self.blockchain_storage.write().await.bridge_transfers.insert(bridge_id.clone(), transfer.clone());
+
+        // Submit to consensus for network-wide recording
+        self.record_bridge_transition(user_id, &transfer).await?;
+
+        info!("✅ Cross-chain bridge initiated: {}", bridge_id);
+        Ok(bridge_id)
+    }
+
+    /// Finalize a locked transfer once the target chain has minted it; `proof` is the
+    /// target chain's attestation of the mint and is kept with the transfer
+    pub async fn complete_bridge_transfer(&mut self, bridge_id: &str, proof: String) -> Result<BridgeTransfer> {
+        if proof.trim().is_empty() {
+            return Err(anyhow::anyhow!("Bridge transfer {} needs a mint proof to complete", bridge_id));
+        }
+
+        let minted = self.transition_bridge_transfer(bridge_id, BridgeStatus::Locked, BridgeStatus::Minted, |transfer| {
+            transfer.proof = Some(proof);
+        }).await?;
+        self.record_bridge_transition(self.node_id, &minted).await?;
 
-        // Create blockchain record of bridge operation
+        let completed = self.transition_bridge_transfer(bridge_id, BridgeStatus::Minted, BridgeStatus::Completed, |_| {}).await?;
+        self.record_bridge_transition(self.node_id, &completed).await?;
+
+        info!("✅ Cross-chain bridge completed: {}", bridge_id);
+        Ok(completed)
+    }
+
+    /// Return a locked transfer's tokens to its owner after `bridge_refund_timeout_secs`
+    /// have passed without completion
+    pub async fn refund_bridge_transfer(&mut self, bridge_id: &str) -> Result<BridgeTransfer> {
+        let timeout = std::time::Duration::from_secs(self.config.bridge_refund_timeout_secs);
+        let transfer = self.blockchain_storage.read().await.bridge_transfers.get(bridge_id).cloned()
+            .ok_or_else(|| anyhow::anyhow!("Bridge transfer {} not found", bridge_id))?;
+        if transfer.locked_at.elapsed().unwrap_or_default() < timeout {
+            return Err(anyhow::anyhow!("Bridge transfer {} has not timed out yet", bridge_id));
+        }
+
+        let refunded = self.transition_bridge_transfer(bridge_id, BridgeStatus::Locked, BridgeStatus::Refunded, |_| {}).await?;
+        self.token_economy.read().await
+            .distribute_payment(BRIDGE_ESCROW_ACCOUNT, refunded.token_type.clone(), &[(refunded.user_id, refunded.amount)]).await?;
+        self.record_bridge_transition(self.node_id, &refunded).await?;
+
+        info!("↩️ Cross-chain bridge refunded: {}", bridge_id);
+        Ok(refunded)
+    }
+
+    pub async fn get_bridge_transfer(&self, bridge_id: &str) -> Option<BridgeTransfer> {
+        self.blockchain_storage.read().await.bridge_transfers.get(bridge_id).cloned()
+    }
+
+    /// Move a stored transfer from `from` to `to`, failing if it is in any other state
+    async fn transition_bridge_transfer(&self, bridge_id: &str, from: BridgeStatus, to: BridgeStatus, update: impl FnOnce(&mut BridgeTransfer)) -> Result<BridgeTransfer> {
+        let mut storage = self.blockchain_storage.write().await;
+        let transfer = storage.bridge_transfers.get_mut(bridge_id)
+            .ok_or_else(|| anyhow::anyhow!("Bridge transfer {} not found", bridge_id))?;
+        if transfer.status != from {
+            return Err(anyhow::anyhow!("Bridge transfer {} is {:?}, expected {:?}", bridge_id, transfer.status, from));
+        }
+        transfer.status = to;
+        update(transfer);
+        Ok(transfer.clone())
+    }
+
+    async fn record_bridge_transition(&mut self, initiator: Uuid, transfer: &BridgeTransfer) -> Result<()> {
         let bridge_change = WorldChange::WorldEvent {
             event_id: Uuid::new_v4(),
             event_type: "CROSS_CHAIN_BRIDGE".to_string(),
             timestamp: SystemTime::now(),
             affected_areas: vec!["GLOBAL".to_string()],
             data: serde_json::json!({
-                "bridge_id": bridge_id,
-                "user_id": user_id,
-                "source_chain": source_chain,
-                "target_chain": target_chain,
-                "token_type": token_type,
-                "amount": amount,
-                "status": "initiated"
+                "bridge_id": transfer.bridge_id,
+                "user_id": transfer.user_id,
+                "source_chain": transfer.source_chain,
+                "target_chain": transfer.target_chain,
+                "token_type": transfer.token_type,
+                "amount": transfer.amount,
+                "status": transfer.status,
+                "proof": transfer.proof,
             }),
         };
+        self.submit_world_change(initiator, bridge_change).await
+    }
+}
 
-        // Submit to consensus for network-wide recording
-        self.submit_world_change(bridge_change).await?;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeTransfer {
+    pub bridge_id: String,
+    pub user_id: Uuid,
+    pub source_chain: String,
+    pub target_chain: String,
+    pub token_type: String,
+    pub amount: u64,
+    pub status: BridgeStatus,
+    pub locked_at: SystemTime,
+    pub proof: Option<String>,
+}
 
-        info!("✅ Cross-chain bridge initiated: {}", bridge_id);
-        Ok(bridge_id)
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BridgeStatus {
+    Locked,    // tokens held in escrow on this chain
+    Minted,    // target chain has minted the equivalent
+    Completed, // settled; escrowed tokens stay locked behind the minted ones
+    Refunded,  // timed out and returned to the owner
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2087,6 +3434,8 @@ pub struct BlockchainSaveInfo {
 pub struct PortableWorldExport {
     pub version: String,
     pub export_timestamp: SystemTime,
+    /// Epoch of the exported save, readable without walking its world state (since 1.1)
+    pub epoch: u64,
     pub original_save: BlockchainSaveSnapshot,
     pub network_compatibility: NetworkCompatibility,
 }
@@ -2099,6 +3448,133 @@ pub struct NetworkCompatibility {
     pub min_stake: u64,
 }
 
+/// The parts of a player or NPC action that conflict checks compare
+struct ActorAction<'a> {
+    actor_id: Uuid,
+    is_npc: bool,
+    action_type: &'a str,
+    area_id: &'a str,
+    timestamp: SystemTime,
+    data: &'a serde_json::Value,
+}
+
+impl<'a> ActorAction<'a> {
+    fn of(change: &'a WorldChange) -> Option<Self> {
+        match change {
+            WorldChange::PlayerAction { player_id, action_type, area_id, timestamp, data } => Some(Self {
+                actor_id: *player_id,
+                is_npc: false,
+                action_type,
+                area_id,
+                timestamp: *timestamp,
+                data,
+            }),
+            WorldChange::NPCAction { npc_id, action_type, area_id, timestamp, data } => Some(Self {
+                actor_id: *npc_id,
+                is_npc: true,
+                action_type,
+                area_id,
+                timestamp: *timestamp,
+                data,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// `major.minor` of an export version string
+fn parse_export_version(version: &str) -> Result<(u64, u64)> {
+    let (major, minor) = version.split_once('.')
+        .ok_or_else(|| anyhow::anyhow!("Malformed export version '{}'", version))?;
+    Ok((major.parse()?, minor.parse()?))
+}
+
+/// Parse an export file, migrating older minor versions up to `CURRENT_EXPORT_VERSION`
+fn read_portable_export(file_content: &str) -> Result<PortableWorldExport> {
+    let mut export: serde_json::Value = serde_json::from_str(file_content)?;
+    let mut version = export.get("version")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Export file has no format version"))?
+        .to_string();
+
+    let (major, minor) = parse_export_version(&version)?;
+    let (current_major, current_minor) = parse_export_version(CURRENT_EXPORT_VERSION)?;
+    if major != current_major {
+        return Err(anyhow::anyhow!(
+            "Export format version {} is incompatible with this build (reads {}.x)",
+            version, current_major
+        ));
+    }
+    if minor > current_minor {
+        warn!("⚠️ Export format {} is newer than {}; unknown fields will be ignored", version, CURRENT_EXPORT_VERSION);
+    }
+
+    while parse_export_version(&version)? < (current_major, current_minor) {
+        let (_, to, migrate) = EXPORT_MIGRATIONS.iter()
+            .find(|(from, _, _)| *from == version)
+            .ok_or_else(|| anyhow::anyhow!("No migration from export format version {}", version))?;
+        migrate(&mut export)?;
+        debug!("Migrated export from format {} to {}", version, to);
+        version = to.to_string();
+        export["version"] = serde_json::Value::String(version.clone());
+    }
+
+    Ok(serde_json::from_value(export)?)
+}
+
+/// 1.1 added the top-level `epoch`; copy it from the save's world state
+fn migrate_export_1_0_to_1_1(export: &mut serde_json::Value) -> Result<()> {
+    let epoch = export.pointer("/original_save/save_data/world_state/current_epoch")
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("1.0 export has no world state epoch"))?;
+    export["epoch"] = epoch;
+    Ok(())
+}
+
+/// Index of the uptime sampling interval containing `at`
+fn activity_interval(at: SystemTime) -> u64 {
+    at.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs() / ACTIVITY_INTERVAL_SECS
+}
+
+/// Union-find root of `i`, compressing the path on the way
+fn find_root(parent: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut node = i;
+    while parent[node] != root {
+        let next = parent[node];
+        parent[node] = root;
+        node = next;
+    }
+    root
+}
+
+/// Outcome of conflict resolution: changes that survive, in timestamp order, and the
+/// ones that lost to another change in their conflict group
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedChanges {
+    pub kept: Vec<WorldChange>,
+    pub dropped: Vec<DroppedChange>,
+}
+
+/// A change conflict resolution throws away, with the change that survived in its place
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DroppedChange {
+    pub change: WorldChange,
+    pub kept_instead: WorldChange,
+    pub reason: String,
+}
+
+/// Dry run of `resolve_world_state_conflicts`, from `preview_conflict_resolution`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictPreview {
+    pub base_proposal: Uuid, // proposal the others are merged into
+    pub kept: Vec<WorldChange>, // in timestamp order
+    pub dropped: Vec<DroppedChange>,
+}
+
 #[derive(Debug, Clone)]
 pub struct PrioritizedProposal {
     pub proposal: WorldStateProposal,
@@ -2205,6 +3681,83 @@ pub struct MasternodePerformanceStats {
     pub performance_rating: PerformanceRating,
 }
 
+/// Combined eligibility, performance and reward estimate for one masternode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MasternodeReport {
+    pub node_id: Uuid,
+    pub is_eligible: bool,
+    pub current_stake: u64,
+    pub required_stake: u64,
+    pub validation_errors: Vec<String>,
+    pub performance: MasternodePerformanceStats,
+    pub estimated_rewards: MasternodeRewards,
+}
+
+impl std::fmt::Display for MasternodeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = if self.is_eligible { "eligible" } else { "NOT eligible" };
+        writeln!(f, "Masternode {} ({})", self.node_id, status)?;
+        writeln!(f, "  Stake:       {} / {} required", self.current_stake, self.required_stake)?;
+        writeln!(f, "  Reputation:  {:.1}", self.performance.reputation_score)?;
+        writeln!(f, "  Uptime:      {:.1}%", self.performance.uptime_percentage)?;
+        writeln!(f, "  Blocks:      {} produced ({:?})",
+            self.performance.total_blocks_produced, self.performance.performance_rating)?;
+        writeln!(f, "  Rewards:     {} earned, ~{}/day estimated",
+            self.performance.total_rewards_earned, self.estimated_rewards.estimated_daily_rewards)?;
+        if !self.validation_errors.is_empty() {
+            writeln!(f, "  Problems:")?;
+            for error in &self.validation_errors {
+                writeln!(f, "    - {}", error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What `verify_chain_integrity` found
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub epochs_replayed: u64,
+    pub snapshots_checked: usize,
+    pub divergent_snapshots: Vec<SnapshotDivergence>,
+    pub broken_links: Vec<u64>, // epochs whose previous_hash doesn't name the block before
+    pub replay_error: Option<String>, // why the replay stopped before the last block
+}
+
+impl IntegrityReport {
+    pub fn is_intact(&self) -> bool {
+        self.divergent_snapshots.is_empty() && self.broken_links.is_empty() && self.replay_error.is_none()
+    }
+}
+
+/// A stored snapshot whose world state doesn't match the replayed one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDivergence {
+    pub epoch: u64,
+    pub stored_root: String,
+    pub replayed_root: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FairnessReport {
+    pub window_start_epoch: u64,
+    pub window_end_epoch: u64,
+    pub total_blocks: u64,
+    pub max_deviation: f64,
+    pub proposers: Vec<ProposerShare>,
+    pub flagged: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposerShare {
+    pub node_id: Uuid,
+    pub blocks_produced: u64,
+    pub actual_share: f64,
+    pub expected_share: f64,
+    pub deviation: f64, // relative to expected share, 0.5 = 50% over
+    pub over_producing: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PerformanceRating {
     Excellent,
@@ -2214,7 +3767,7 @@ pub enum PerformanceRating {
     Critical,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BlockchainStats {
     pub total_blocks: usize,
     pub last_finalized_epoch: u64,
@@ -2259,6 +3812,9 @@ pub enum DeFiOperation {
         amount: u64,
         duration_days: u32,
     },
+    Unstake {
+        stake_id: Uuid,
+    },
     YieldFarm {
         farm_id: Uuid,
         amount: u64,
@@ -2275,3 +3831,1624 @@ pub enum DeFiOperation {
         collateral_amount: u64,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn test_config() -> BlockchainConfig {
+        arceon_core::config::Config::default().blockchain
+    }
+
+    fn test_block(epoch: u64, timestamp: SystemTime, world_changes: Vec<WorldChange>) -> FinalizedBlock {
+        FinalizedBlock {
+            block_hash: [epoch as u8; 32],
+            epoch,
+            round: 0,
+            proposer: Uuid::nil(),
+            timestamp,
+            world_changes,
+            validator_signatures: HashMap::new(),
+            merkle_root: String::new(),
+            previous_hash: None,
+            transaction_nonces: Vec::new(),
+        }
+    }
+
+    /// A block with a correct merkle root, signed by `manager`'s own validator key
+    fn signed_block(manager: &BlockchainManager, epoch: u64, timestamp: SystemTime, world_changes: Vec<WorldChange>) -> FinalizedBlock {
+        signed_block_with_nonces(manager, epoch, timestamp, world_changes, Vec::new())
+    }
+
+    /// `signed_block`, finalizing the given `(initiator, nonce)` pairs
+    fn signed_block_with_nonces(manager: &BlockchainManager, epoch: u64, timestamp: SystemTime, world_changes: Vec<WorldChange>, transaction_nonces: Vec<(Uuid, u64)>) -> FinalizedBlock {
+        let mut block = test_block(epoch, timestamp, world_changes);
+        block.transaction_nonces = transaction_nonces;
+        block.merkle_root = manager.calculate_merkle_root(&block.world_changes).unwrap();
+        BlockchainManager::sign_block(&mut block, manager.node_id, &manager.local_signers[&manager.node_id]).unwrap();
+        block
+    }
+
+    async fn started_manager() -> BlockchainManager {
+        started_manager_with(test_config()).await
+    }
+
+    async fn started_manager_with(config: BlockchainConfig) -> BlockchainManager {
+        let mut manager = BlockchainManager::new(&config).await.unwrap();
+        manager.start(Uuid::new_v4(), true, 1_000).await.unwrap();
+        manager
+    }
+
+    async fn fund(manager: &BlockchainManager, owner: Uuid, amount: u64) {
+        let token_economy = manager.token_economy.read().await;
+        let symbol = token_economy.config.native_token_symbol.clone();
+        token_economy.token_manager.write().await.balances.insert((owner, symbol), amount);
+    }
+
+    async fn native_balance(manager: &BlockchainManager, owner: Uuid) -> u64 {
+        let token_economy = manager.token_economy.read().await;
+        token_economy.get_balance(owner, token_economy.config.native_token_symbol.clone()).await
+    }
+
+    fn player_action(player_id: Uuid) -> WorldChange {
+        WorldChange::PlayerAction {
+            player_id,
+            action_type: "move".to_string(),
+            area_id: "town".to_string(),
+            timestamp: SystemTime::now(),
+            data: serde_json::json!({}),
+        }
+    }
+
+    fn world_event() -> WorldChange {
+        WorldChange::WorldEvent {
+            event_id: Uuid::new_v4(),
+            event_type: "FESTIVAL".to_string(),
+            timestamp: SystemTime::now(),
+            affected_areas: vec!["GLOBAL".to_string()],
+            data: serde_json::json!({}),
+        }
+    }
+
+    /// A span's name and its recorded fields
+    type RecordedSpan = (String, HashMap<String, String>);
+
+    /// Subscriber that keeps the name and fields of every span opened while it is the default
+    #[derive(Clone, Default)]
+    struct SpanRecorder {
+        spans: Arc<std::sync::Mutex<Vec<RecordedSpan>>>,
+        next_id: Arc<std::sync::atomic::AtomicU64>,
+    }
+
+    #[derive(Default)]
+    struct FieldRecorder(HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldRecorder {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut fields = FieldRecorder::default();
+            span.record(&mut fields);
+            self.spans.lock().unwrap().push((span.metadata().name().to_string(), fields.0));
+            tracing::span::Id::from_u64(self.next_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1)
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+        fn event(&self, _event: &tracing::Event<'_>) {}
+        fn enter(&self, _span: &tracing::span::Id) {}
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[tokio::test]
+    async fn test_block_application_span_carries_node_and_epoch() {
+        let recorder = SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let mut manager = started_manager().await;
+        manager.apply_finalized_block(signed_block(&manager, 1, SystemTime::now(), Vec::new())).await.unwrap();
+
+        let spans = recorder.spans.lock().unwrap();
+        let (_, fields) = spans.iter().find(|(name, _)| name == "apply_block").unwrap();
+        assert_eq!(fields["node_id"], manager.node_id.to_string());
+        assert_eq!(fields["current_epoch"], "0");
+        assert_eq!(fields["block_epoch"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_load_reconciles_world_time_with_blocks() {
+        let mut manager = started_manager().await;
+        let block_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        manager.apply_finalized_block(signed_block(&manager, 1, block_time, Vec::new())).await.unwrap();
+        manager.save_world_state_to_blockchain("mid_tick".to_string()).await.unwrap();
+
+        // Simulate a snapshot taken mid-tick whose world_time ran ahead of the blocks
+        {
+            let mut storage = manager.blockchain_storage.write().await;
+            let snapshot = storage.blockchain_saves.as_mut().unwrap().get_mut("mid_tick").unwrap();
+            snapshot.save_data.world_state.world_time += 7;
+            snapshot.save_data.integrity_hash = manager.calculate_save_integrity_hash(&snapshot.save_data).unwrap();
+            snapshot.world_state_merkle_proof = manager
+                .generate_world_state_merkle_proof(&snapshot.save_data.world_state).await.unwrap();
+        }
+
+        manager.load_world_state_from_blockchain("mid_tick".to_string()).await.unwrap();
+        assert_eq!(manager.get_world_state().await.world_time, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_world_change_fees_follow_schedule() {
+        let mut config = test_config();
+        config.fee_schedule.player_action = 3;
+        config.fee_schedule.global_event = 40;
+        let mut manager = started_manager_with(config).await;
+        let player = Uuid::new_v4();
+        fund(&manager, player, 100).await;
+
+        manager.submit_world_change(player, player_action(player)).await.unwrap();
+        assert_eq!(native_balance(&manager, player).await, 97);
+
+        manager.submit_world_change(player, world_event()).await.unwrap();
+        assert_eq!(native_balance(&manager, player).await, 57);
+        assert_eq!(manager.blockchain_storage.read().await.pending_transactions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unfunded_initiator_is_rejected() {
+        let mut manager = started_manager().await;
+        let player = Uuid::new_v4();
+
+        assert!(manager.submit_world_change(player, world_event()).await.is_err());
+        assert!(manager.blockchain_storage.read().await.pending_transactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fairness_report_flags_over_producer() {
+        let manager = started_manager().await;
+        let honest = [Uuid::from_u128(1), Uuid::from_u128(2)];
+        let greedy = Uuid::from_u128(3);
+        {
+            let mut storage = manager.blockchain_storage.write().await;
+            for epoch in 1..=20 {
+                // The greedy node produces 12 of 20 blocks despite an equal stake
+                let proposer = if epoch <= 12 { greedy } else { honest[(epoch % 2) as usize] };
+                let mut block = test_block(epoch, SystemTime::now(), Vec::new());
+                block.proposer = proposer;
+                storage.blocks.insert(epoch, block);
+            }
+        }
+
+        let report = manager.production_fairness_report(20).await;
+        assert_eq!(report.total_blocks, 20);
+        assert_eq!(report.flagged, vec![greedy]);
+        let greedy_share = report.proposers.iter().find(|share| share.node_id == greedy).unwrap();
+        assert!((greedy_share.actual_share - 0.6).abs() < 1e-9);
+        assert!((greedy_share.expected_share - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    fn test_proposal(id: u128, proposer: u128, timestamp: SystemTime, world_changes: Vec<WorldChange>) -> WorldStateProposal {
+        WorldStateProposal {
+            proposal_id: Uuid::from_u128(id),
+            proposer: Uuid::from_u128(proposer),
+            epoch: 1,
+            round: 0,
+            timestamp,
+            world_changes,
+            previous_block_hash: None,
+            merkle_root: String::new(),
+            signature: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conflict_resolution_is_identical_across_nodes() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(5_000);
+        let area_update = |update_type: &str| WorldChange::AreaUpdate {
+            area_id: "market".to_string(),
+            update_type: update_type.to_string(),
+            timestamp: t0,
+            data: serde_json::json!({}),
+        };
+        let action = |player: u128| WorldChange::PlayerAction {
+            player_id: Uuid::from_u128(player),
+            action_type: "craft".to_string(),
+            area_id: "forge".to_string(),
+            timestamp: t0,
+            data: serde_json::json!({}),
+        };
+        // Same priority and timestamp: only the fixed tie-breaks decide the outcome
+        let proposals = vec![
+            test_proposal(7, 1, t0, vec![area_update("flood"), action(10)]),
+            test_proposal(3, 2, t0, vec![area_update("fire"), action(11)]),
+        ];
+
+        let mut node_a = started_manager().await;
+        let mut node_b = started_manager().await;
+        let resolved_a = node_a.resolve_world_state_conflicts(proposals.clone()).await.unwrap();
+        let resolved_b = node_b.resolve_world_state_conflicts(proposals.into_iter().rev().collect()).await.unwrap();
+
+        assert_eq!(serde_json::to_vec(&resolved_a).unwrap(), serde_json::to_vec(&resolved_b).unwrap());
+        assert_eq!(resolved_a.proposer, Uuid::from_u128(2));
+        assert_eq!(resolved_a.world_changes.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_genesis_is_identical_for_same_config() {
+        let node_a = started_manager().await;
+        let node_b = started_manager().await;
+
+        let genesis_a = node_a.blockchain_storage.read().await.genesis_block.clone().unwrap();
+        let genesis_b = node_b.blockchain_storage.read().await.genesis_block.clone().unwrap();
+        assert_eq!(genesis_a.block_hash, genesis_b.block_hash);
+        assert_eq!(genesis_a.timestamp, genesis_b.timestamp);
+
+        let mut other_network = test_config();
+        other_network.network_name = "arceon_testnet".to_string();
+        let node_c = started_manager_with(other_network).await;
+        let genesis_c = node_c.blockchain_storage.read().await.genesis_block.clone().unwrap();
+        assert_ne!(genesis_a.block_hash, genesis_c.block_hash);
+    }
+
+    #[tokio::test]
+    async fn test_block_data_compression_round_trip() {
+        let manager = started_manager().await;
+        {
+            let mut storage = manager.blockchain_storage.write().await;
+            for epoch in 1..=300 {
+                let changes = vec![WorldChange::AreaUpdate {
+                    area_id: format!("area_{}", epoch % 12),
+                    update_type: "weather".to_string(),
+                    timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(epoch),
+                    data: serde_json::json!({ "condition": "rain", "intensity": epoch % 5 }),
+                }];
+                storage.blocks.insert(epoch, test_block(epoch, SystemTime::now(), changes));
+            }
+        }
+        let raw = serde_json::to_vec(&*manager.blockchain_storage.read().await).unwrap();
+
+        let compressed = manager.compress_blockchain_data().await.unwrap();
+        assert!(compressed.starts_with(COMPRESSED_BLOCKS_MAGIC));
+        assert!(compressed.len() * 4 < raw.len(), "{} vs {}", compressed.len(), raw.len());
+
+        let mut restored = started_manager().await;
+        restored.restore_blockchain_data(compressed).await.unwrap();
+        assert_eq!(restored.blockchain_storage.read().await.blocks.len(), 301);
+
+        // Legacy saves stored the JSON uncompressed
+        let mut legacy = started_manager().await;
+        legacy.restore_blockchain_data(raw).await.unwrap();
+        assert_eq!(legacy.blockchain_storage.read().await.blocks.len(), 301);
+    }
+
+    #[tokio::test]
+    async fn test_world_state_proof_rejects_tampered_sibling() {
+        let manager = started_manager().await;
+        let mut world_state = manager.get_world_state().await;
+        for name in GENESIS_AREAS {
+            world_state.areas.insert(name.to_string(), AreaState {
+                area_id: name.to_string(),
+                name: name.to_string(),
+                description: String::new(),
+                players: Vec::new(),
+                npcs: Vec::new(),
+                structures: Vec::new(),
+                items: Vec::new(),
+                events: Vec::new(),
+                last_update: SystemTime::UNIX_EPOCH,
+            });
+        }
+
+        let mut proof = manager.generate_world_state_merkle_proof(&world_state).await.unwrap();
+        assert!(!proof.proof_elements.is_empty());
+        assert!(manager.verify_world_state_merkle_proof(&world_state, &proof).await.unwrap());
+
+        proof.proof_elements[0] = "0".repeat(64);
+        assert!(!manager.verify_world_state_merkle_proof(&world_state, &proof).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_skill_evolution_votes_accumulate_across_blocks() {
+        let mut manager = started_manager().await;
+        let discoverer = Uuid::new_v4();
+        let evolution = |consensus_votes| WorldChange::SkillEvolution {
+            skill_name: "Stormcalling".to_string(),
+            evolution_type: "experimentation".to_string(),
+            timestamp: SystemTime::now(),
+            discoverer: Some(discoverer),
+            consensus_votes,
+        };
+
+        manager.apply_finalized_block(signed_block(&manager, 1, SystemTime::now(), vec![evolution(3)])).await.unwrap();
+        let discovery = manager.get_world_state().await.skill_discoveries["Stormcalling"].clone();
+        assert_eq!(discovery.vote_tally, 3);
+        assert!(!discovery.confirmed);
+
+        manager.apply_finalized_block(signed_block(&manager, 2, SystemTime::now(), vec![evolution(2)])).await.unwrap();
+        let discovery = manager.get_world_state().await.skill_discoveries["Stormcalling"].clone();
+        assert_eq!(discovery.vote_tally, 5);
+        assert!(discovery.confirmed);
+        assert_eq!(discovery.discoverer, discoverer);
+    }
+
+    #[tokio::test]
+    async fn test_npcs_picking_up_same_item_conflict() {
+        let manager = started_manager().await;
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(9_000);
+        let npc_pickup = |npc: u128, offset: u64, item: &str| WorldChange::NPCAction {
+            npc_id: Uuid::from_u128(npc),
+            action_type: "pickup".to_string(),
+            area_id: "market".to_string(),
+            timestamp: t0 + Duration::from_secs(offset),
+            data: serde_json::json!({ "item_id": item }),
+        };
+
+        assert!(manager.changes_conflict(&npc_pickup(1, 0, "ruby"), &npc_pickup(2, 3, "ruby")).await.unwrap());
+        assert!(!manager.changes_conflict(&npc_pickup(1, 0, "ruby"), &npc_pickup(2, 3, "opal")).await.unwrap());
+
+        // A player reaching for the same item collides with the NPC either way round
+        let player_pickup = WorldChange::PlayerAction {
+            player_id: Uuid::from_u128(3),
+            action_type: "pickup".to_string(),
+            area_id: "market".to_string(),
+            timestamp: t0 + Duration::from_secs(5),
+            data: serde_json::json!({ "item_id": "ruby" }),
+        };
+        assert!(manager.changes_conflict(&player_pickup, &npc_pickup(1, 0, "ruby")).await.unwrap());
+        assert!(manager.changes_conflict(&npc_pickup(1, 0, "ruby"), &player_pickup).await.unwrap());
+
+        let resolved = manager.apply_conflict_resolution_rules(vec![
+            npc_pickup(2, 3, "ruby"),
+            npc_pickup(1, 0, "ruby"),
+            npc_pickup(4, 1, "opal"),
+        ]).await.unwrap();
+        assert_eq!(resolved.kept.len(), 2);
+        assert!(matches!(&resolved.kept[0], WorldChange::NPCAction { npc_id, .. } if *npc_id == Uuid::from_u128(1)));
+        assert_eq!(resolved.dropped.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_preview_lists_the_losing_pickup() {
+        let mut manager = started_manager().await;
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(9_000);
+        let pickup = |player: u128, offset: u64| WorldChange::PlayerAction {
+            player_id: Uuid::from_u128(player),
+            action_type: "pickup".to_string(),
+            area_id: "market".to_string(),
+            timestamp: t0 + Duration::from_secs(offset),
+            data: serde_json::json!({ "item_id": "ruby" }),
+        };
+        let proposals = vec![
+            test_proposal(1, 1, t0, vec![pickup(10, 0)]),
+            test_proposal(2, 2, t0, vec![pickup(11, 2)]),
+        ];
+
+        let preview = manager.preview_conflict_resolution(&proposals).await.unwrap();
+        assert_eq!(preview.base_proposal, Uuid::from_u128(1));
+        assert_eq!(preview.kept.len(), 1);
+        assert!(matches!(&preview.kept[0], WorldChange::PlayerAction { player_id, .. } if *player_id == Uuid::from_u128(10)));
+        assert_eq!(preview.dropped.len(), 1);
+        let dropped = &preview.dropped[0];
+        assert!(matches!(&dropped.change, WorldChange::PlayerAction { player_id, .. } if *player_id == Uuid::from_u128(11)));
+        assert!(matches!(&dropped.kept_instead, WorldChange::PlayerAction { player_id, .. } if *player_id == Uuid::from_u128(10)));
+        assert!(dropped.reason.contains("higher-priority proposal"), "{}", dropped.reason);
+
+        // The preview is what resolving actually does
+        let resolved = manager.resolve_world_state_conflicts(proposals).await.unwrap();
+        assert_eq!(serde_json::to_vec(&resolved.world_changes).unwrap(), serde_json::to_vec(&preview.kept).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_transitive_conflicts_resolve_to_one_survivor() {
+        let manager = started_manager().await;
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(9_000);
+        let player = Uuid::from_u128(7);
+        let act = |offset_ms: u64| WorldChange::PlayerAction {
+            player_id: player,
+            action_type: "emote".to_string(),
+            area_id: "market".to_string(),
+            timestamp: t0 + Duration::from_millis(offset_ms),
+            data: serde_json::json!({}),
+        };
+
+        // A~B and B~C are under a second apart, but A and C are not
+        let (a, b, c) = (act(0), act(800), act(1_600));
+        assert!(manager.changes_conflict(&a, &b).await.unwrap());
+        assert!(manager.changes_conflict(&b, &c).await.unwrap());
+        assert!(!manager.changes_conflict(&a, &c).await.unwrap());
+
+        let resolved = manager.apply_conflict_resolution_rules(vec![c, a, b]).await.unwrap();
+        assert_eq!(resolved.kept.len(), 1);
+        assert!(matches!(&resolved.kept[0], WorldChange::PlayerAction { timestamp, .. } if *timestamp == t0));
+        assert_eq!(resolved.dropped.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_balances_reflect_mint_and_transfer() {
+        let mut manager = started_manager().await;
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        {
+            let token_economy = manager.token_economy.read().await;
+            token_economy.create_token(token_economy::TokenDefinition {
+                symbol: "GEM".to_string(),
+                name: "Gemstone".to_string(),
+                decimals: 0,
+                token_type: token_economy::TokenType::Utility,
+                max_supply: None,
+                current_supply: 0,
+                is_mintable: true,
+                is_burnable: true,
+                is_pausable: false,
+                is_upgradeable: false,
+                utility_functions: Vec::new(),
+                contract_addresses: HashMap::new(),
+                icon_url: String::new(),
+                website_url: String::new(),
+                created_at: chrono::Utc::now(),
+            }).await.unwrap();
+            token_economy.mint_tokens("GEM".to_string(), alice, 5_000).await.unwrap();
+        }
+        fund(&manager, alice, 70).await;
+        assert_eq!(manager.get_balance(alice, "GEM").await.unwrap(), 5_000);
+
+        // 2000 moved; alice also pays the 12 transfer fee (10 base + 0.1%)
+        manager.transfer_tokens(alice, bob, "GEM".to_string(), 2_000).await.unwrap();
+        assert_eq!(manager.get_balance(alice, "GEM").await.unwrap(), 2_988);
+        assert_eq!(manager.get_balance(bob, "GEM").await.unwrap(), 2_000);
+        assert_eq!(manager.get_balance(bob, "ORE").await.unwrap(), 0);
+
+        let alice_balances = manager.get_all_balances(alice).await.unwrap();
+        assert_eq!(alice_balances.len(), 2);
+        assert_eq!(alice_balances["GEM"], 2_988);
+        assert_eq!(manager.get_all_balances(bob).await.unwrap(), HashMap::from([("GEM".to_string(), 2_000)]));
+    }
+
+    #[tokio::test]
+    async fn test_remove_liquidity_returns_deposited_tokens() {
+        let mut manager = started_manager().await;
+        let provider = Uuid::new_v4();
+        let pool_id = Uuid::new_v4();
+        {
+            let token_economy = manager.token_economy.read().await;
+            token_economy.defi_protocols.write().await.liquidity_pools.insert(pool_id.to_string(), token_economy::LiquidityPool {
+                pool_id: pool_id.to_string(),
+                name: "GEM/ORE".to_string(),
+                token_a: "GEM".to_string(),
+                token_b: "ORE".to_string(),
+                reserve_a: 0,
+                reserve_b: 0,
+                total_liquidity: 0,
+                fee_percentage: 0.3,
+                liquidity_providers: HashMap::new(),
+                volume_24h: 0,
+                fees_collected: 0,
+                apy: 0.0,
+                is_active: true,
+            });
+            let mut token_manager = token_economy.token_manager.write().await;
+            token_manager.balances.insert((provider, "GEM".to_string()), 10_000);
+            token_manager.balances.insert((provider, "ORE".to_string()), 40_000);
+        }
+        fund(&manager, provider, 200).await; // recording fees
+
+        let lp_tokens: u64 = manager.process_defi_operation(provider, DeFiOperation::AddLiquidity {
+            pool_id,
+            token_a_type: "GEM".to_string(),
+            token_b_type: "ORE".to_string(),
+            token_a_amount: 4_000,
+            token_b_amount: 16_000,
+        }).await.unwrap().parse().unwrap();
+        assert_eq!(lp_tokens, 8_000);
+        assert_eq!(manager.get_balance(provider, "GEM").await.unwrap(), 6_000);
+
+        // Half the position first, then the rest
+        manager.process_defi_operation(provider, DeFiOperation::RemoveLiquidity { pool_id, liquidity_amount: 4_000 }).await.unwrap();
+        assert_eq!(manager.get_balance(provider, "GEM").await.unwrap(), 8_000);
+        assert_eq!(manager.get_balance(provider, "ORE").await.unwrap(), 32_000);
+        manager.process_defi_operation(provider, DeFiOperation::RemoveLiquidity { pool_id, liquidity_amount: 4_000 }).await.unwrap();
+
+        // No swaps ran, so there were no pool fees and the provider is made whole
+        assert_eq!(manager.get_balance(provider, "GEM").await.unwrap(), 10_000);
+        assert_eq!(manager.get_balance(provider, "ORE").await.unwrap(), 40_000);
+        {
+            let token_economy = manager.token_economy.read().await;
+            let defi = token_economy.defi_protocols.read().await;
+            let pool = &defi.liquidity_pools[&pool_id.to_string()];
+            assert_eq!((pool.reserve_a, pool.reserve_b, pool.total_liquidity), (0, 0, 0));
+            assert!(pool.liquidity_providers.is_empty());
+        }
+
+        // Nothing left to burn
+        assert!(manager.process_defi_operation(provider, DeFiOperation::RemoveLiquidity { pool_id, liquidity_amount: 1 }).await.is_err());
+        let storage = manager.blockchain_storage.read().await;
+        let recorded = storage.pending_transactions.iter()
+            .filter(|tx| tx.data.to_string().contains("\"amount_b\":8000"))
+            .count();
+        assert_eq!(recorded, 2);
+    }
+
+    #[tokio::test]
+    async fn test_unstake_is_rejected_until_lock_expires() {
+        let mut manager = started_manager().await;
+        let staker = Uuid::new_v4();
+        fund(&manager, staker, 10_000).await;
+
+        let stake_id: Uuid = manager.process_defi_operation(staker, DeFiOperation::Stake {
+            token_type: "ARCEON".to_string(),
+            amount: 7_300,
+            duration_days: 10,
+        }).await.unwrap().parse().unwrap();
+        // 7300 locked plus the 50 fee for recording the stake
+        assert_eq!(native_balance(&manager, staker).await, 2_650);
+
+        assert!(manager.process_defi_operation(staker, DeFiOperation::Unstake { stake_id }).await.is_err());
+        assert_eq!(native_balance(&manager, staker).await, 2_650);
+
+        // Wind the lock back so it started 10.5 days ago and has just expired
+        {
+            let token_economy = manager.token_economy.read().await;
+            let mut token_manager = token_economy.token_manager.write().await;
+            let lock = &mut token_manager.token_locks.get_mut(&(staker, "ARCEON".to_string())).unwrap()[0];
+            lock.locked_at = chrono::Utc::now() - chrono::Duration::hours(252);
+            lock.unlock_at = chrono::Utc::now() - chrono::Duration::minutes(1);
+        }
+
+        // 5% a year on 7300 is 1 per day; the half day does not count
+        let payout: u64 = manager.process_defi_operation(staker, DeFiOperation::Unstake { stake_id }).await.unwrap().parse().unwrap();
+        assert_eq!(payout, 7_310);
+        assert_eq!(native_balance(&manager, staker).await, 9_910);
+        assert!(manager.process_defi_operation(staker, DeFiOperation::Unstake { stake_id }).await.is_err());
+    }
+
+    async fn add_nft_collection(manager: &BlockchainManager, collection_id: &str) {
+        add_nft_collection_with_royalty(manager, collection_id, 5.0).await
+    }
+
+    async fn add_nft_collection_with_royalty(manager: &BlockchainManager, collection_id: &str, royalty_percentage: f64) {
+        let collection = nft_system::NFTCollection {
+            collection_id: collection_id.to_string(),
+            name: collection_id.to_string(),
+            symbol: "TEST".to_string(),
+            description: String::new(),
+            creator_id: Uuid::nil(),
+            creation_timestamp: chrono::Utc::now(),
+            collection_image: String::new(),
+            banner_image: String::new(),
+            website: None,
+            social_links: HashMap::new(),
+            total_supply: 0,
+            max_supply: None,
+            floor_price: None,
+            total_volume: 0,
+            royalty_info: nft_system::RoyaltyInfo {
+                royalty_percentage,
+                royalty_recipients: Vec::new(),
+                total_royalties_collected: 0,
+            },
+            collection_attributes: Vec::new(),
+            verified: false,
+            featured: false,
+            category: nft_system::NFTCategory::Gaming,
+            tags: Vec::new(),
+            transfer_cooldown_seconds: None,
+        };
+        let nft_system = manager.nft_system.read().await;
+        nft_system.nft_registry.write().await.collections.insert(collection_id.to_string(), collection);
+    }
+
+    fn recorded_events(storage: &BlockchainStorage, event_type: &str) -> usize {
+        storage.pending_transactions.iter()
+            .filter(|tx| tx.data["WorldEvent"]["event_type"] == event_type)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_nft_transfer_and_burn_are_recorded() {
+        let mut manager = started_manager().await;
+        add_nft_collection(&manager, "relics").await;
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        fund(&manager, alice, 100).await;
+        fund(&manager, bob, 100).await;
+        let token_id = manager.create_blockchain_nft("relics".to_string(), alice, Vec::new()).await.unwrap();
+
+        manager.transfer_blockchain_nft(token_id, alice, bob).await.unwrap();
+        assert_eq!(manager.nft_system.read().await.nft_registry.read().await.nfts[&token_id].owner_id, bob);
+
+        manager.burn_blockchain_nft(token_id, bob).await.unwrap();
+        {
+            let nft_system = manager.nft_system.read().await;
+            let registry = nft_system.nft_registry.read().await;
+            assert!(registry.nfts[&token_id].is_burned);
+            assert!(registry.burned_tokens.contains(&token_id));
+        }
+        assert!(manager.transfer_blockchain_nft(token_id, bob, alice).await.is_err());
+
+        let storage = manager.blockchain_storage.read().await;
+        assert_eq!(recorded_events(&storage, "NFT_TRANSFER"), 1);
+        assert_eq!(recorded_events(&storage, "NFT_BURN"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_nft_transfer_by_non_owner_is_rejected() {
+        let mut manager = started_manager().await;
+        add_nft_collection(&manager, "relics").await;
+        let (alice, mallory) = (Uuid::new_v4(), Uuid::new_v4());
+        fund(&manager, alice, 50).await;
+        fund(&manager, mallory, 100).await;
+        let token_id = manager.create_blockchain_nft("relics".to_string(), alice, Vec::new()).await.unwrap();
+
+        assert!(manager.transfer_blockchain_nft(token_id, mallory, mallory).await.is_err());
+        assert!(manager.burn_blockchain_nft(token_id, mallory).await.is_err());
+        assert_eq!(manager.nft_system.read().await.nft_registry.read().await.nfts[&token_id].owner_id, alice);
+
+        let storage = manager.blockchain_storage.read().await;
+        assert_eq!(recorded_events(&storage, "NFT_TRANSFER"), 0);
+        assert_eq!(recorded_events(&storage, "NFT_BURN"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_nft_sale_clamps_royalty_and_pays_out_full_price() {
+        let mut manager = started_manager().await;
+        add_nft_collection_with_royalty(&manager, "relics", 25.0).await;
+        let (creator, seller, buyer) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        fund(&manager, creator, 100).await;
+        let token_id = manager.create_blockchain_nft("relics".to_string(), creator, Vec::new()).await.unwrap();
+        manager.transfer_blockchain_nft(token_id, creator, seller).await.unwrap();
+        fund(&manager, buyer, 12_000).await;
+
+        let sale = manager.sell_nft(token_id, seller, buyer, 10_000).await.unwrap();
+
+        // The collection asks for 25% but the config caps royalties at 10%
+        assert_eq!(sale.royalty_percentage, 10.0);
+        assert_eq!(sale.royalty, 1_000);
+        assert_eq!(sale.marketplace_fee, 250);
+        assert_eq!(sale.seller_proceeds, 8_750);
+        assert_eq!(sale.royalty + sale.marketplace_fee + sale.seller_proceeds, 10_000);
+
+        assert_eq!(native_balance(&manager, buyer).await, 1_950); // less the 50 recording fee
+        assert_eq!(native_balance(&manager, creator).await, 1_000);
+        assert_eq!(native_balance(&manager, sale.marketplace_account).await, 250);
+        assert_eq!(native_balance(&manager, seller).await, 8_750);
+        assert_eq!(manager.nft_system.read().await.nft_registry.read().await.nfts[&token_id].owner_id, buyer);
+        assert_eq!(recorded_events(&*manager.blockchain_storage.read().await, "NFT_SALE"), 1);
+
+        // The old owner can no longer sell it
+        assert!(manager.sell_nft(token_id, seller, buyer, 10_000).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_nft_sale_that_cannot_transfer_refunds_the_buyer() {
+        let mut manager = started_manager().await;
+        add_nft_collection_with_royalty(&manager, "relics", 5.0).await;
+        let (creator, seller, buyer) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        fund(&manager, creator, 100).await;
+        let token_id = manager.create_blockchain_nft("relics".to_string(), creator, Vec::new()).await.unwrap();
+        manager.transfer_blockchain_nft(token_id, creator, seller).await.unwrap();
+        fund(&manager, buyer, 12_000).await;
+        let creator_balance = native_balance(&manager, creator).await;
+
+        // Locked after the sale is priced, so the transfer is what refuses
+        manager.nft_system.read().await.nft_registry.write().await
+            .nfts.get_mut(&token_id).unwrap().lock_status = crate::nft_system::LockStatus::Staked;
+        let err = manager.sell_nft(token_id, seller, buyer, 10_000).await.unwrap_err();
+        assert!(err.to_string().contains("locked"), "{}", err);
+
+        // Every payout went back to the buyer and the NFT never moved
+        assert_eq!(native_balance(&manager, buyer).await, 12_000);
+        assert_eq!(native_balance(&manager, seller).await, 0);
+        assert_eq!(native_balance(&manager, creator).await, creator_balance);
+        let marketplace_account = manager.nft_system.read().await.config.marketplace_account;
+        assert_eq!(native_balance(&manager, marketplace_account).await, 0);
+        assert_eq!(manager.nft_system.read().await.nft_registry.read().await.nfts[&token_id].owner_id, seller);
+        assert_eq!(recorded_events(&*manager.blockchain_storage.read().await, "NFT_SALE"), 0);
+    }
+
+    async fn auto_checkpoint_names(manager: &BlockchainManager) -> Vec<String> {
+        let storage = manager.blockchain_storage.read().await;
+        let mut names: Vec<String> = storage.blockchain_saves.iter()
+            .flat_map(|saves| saves.keys())
+            .filter(|name| name.starts_with("auto_checkpoint_"))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_retention_keeps_newest() {
+        let mut config = test_config();
+        config.checkpoint_retention = 5;
+        let mut manager = started_manager_with(config).await;
+
+        let mut created = Vec::new();
+        for _ in 0..15 {
+            created.push(manager.create_automatic_checkpoint().await.unwrap());
+        }
+        created.sort();
+        assert_eq!(auto_checkpoint_names(&manager).await, created[10..].to_vec());
+
+        // Retention 0 disables cleanup
+        let mut config = test_config();
+        config.checkpoint_retention = 0;
+        let mut keep_all = started_manager_with(config).await;
+        for _ in 0..15 {
+            keep_all.create_automatic_checkpoint().await.unwrap();
+        }
+        assert_eq!(auto_checkpoint_names(&keep_all).await.len(), 15);
+    }
+
+    #[tokio::test]
+    async fn test_delta_snapshot_matches_full_snapshot() {
+        let mut full_config = test_config();
+        full_config.snapshot_interval = 1;
+        let mut delta_config = test_config();
+        delta_config.snapshot_interval = 4;
+        let mut full = started_manager_with(full_config).await;
+        let mut delta = started_manager_with(delta_config).await;
+
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(2_000_000);
+        for epoch in 1..=7u64 {
+            let timestamp = t0 + Duration::from_secs(epoch * 10);
+            let changes = vec![
+                WorldChange::SkillEvolution {
+                    skill_name: format!("Skill{}", epoch % 3),
+                    evolution_type: "practice".to_string(),
+                    timestamp,
+                    discoverer: None,
+                    consensus_votes: 2,
+                },
+                WorldChange::WorldEvent {
+                    event_id: Uuid::from_u128(epoch as u128),
+                    event_type: "STORM".to_string(),
+                    timestamp,
+                    affected_areas: vec![GENESIS_AREAS[0].to_string()],
+                    data: serde_json::json!({ "epoch": epoch }),
+                },
+            ];
+            full.apply_finalized_block(signed_block(&full, epoch, timestamp, changes.clone())).await.unwrap();
+            delta.apply_finalized_block(signed_block(&delta, epoch, timestamp, changes)).await.unwrap();
+        }
+
+        {
+            let storage = delta.blockchain_storage.read().await;
+            let mut full_epochs: Vec<u64> = storage.world_snapshots.keys().copied().collect();
+            full_epochs.sort();
+            assert_eq!(full_epochs, vec![0, 4]);
+            assert_eq!(storage.world_deltas.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3, 5, 6, 7]);
+        }
+
+        for epoch in 1..=7 {
+            let expected = full.get_world_snapshot(epoch).await.unwrap();
+            let rebuilt = delta.get_world_snapshot(epoch).await.unwrap();
+            assert_eq!(rebuilt.epoch, epoch);
+            assert_eq!(rebuilt.block_hash, expected.block_hash);
+            assert_eq!(
+                serde_json::to_value(&rebuilt.world_state).unwrap(),
+                serde_json::to_value(&expected.world_state).unwrap(),
+                "epoch {}", epoch
+            );
+        }
+        assert!(delta.get_world_snapshot(8).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_initiator_is_the_acting_player() {
+        let mut manager = started_manager().await;
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        fund(&manager, alice, 1_000).await;
+
+        manager.transfer_tokens(alice, bob, "ARCEON".to_string(), 100).await.unwrap();
+
+        let storage = manager.blockchain_storage.read().await;
+        let recorded = storage.pending_transactions.back().unwrap();
+        assert_eq!(recorded.initiator, alice);
+        assert_eq!(recorded.data["WorldEvent"]["event_type"], "TOKEN_TRANSFER");
+        drop(storage);
+        // Alice, not the node, paid the 50 recording fee (on top of the 10 transfer fee)
+        assert_eq!(native_balance(&manager, alice).await, 840);
+
+        // Bob's 100 covers the fee but not the fee and 60 more, so he is turned away before any tokens move
+        assert!(manager.transfer_tokens(bob, alice, "ARCEON".to_string(), 60).await.is_err());
+        assert_eq!(native_balance(&manager, bob).await, 100);
+        assert_eq!(native_balance(&manager, alice).await, 840);
+    }
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    async fn signing_manager() -> BlockchainManager {
+        let mut config = test_config();
+        config.require_transaction_signatures = true;
+        started_manager_with(config).await
+    }
+
+    #[tokio::test]
+    async fn test_signed_transaction_is_accepted() {
+        let mut manager = signing_manager().await;
+        let player = Uuid::new_v4();
+        fund(&manager, player, 10).await;
+        manager.add_local_signer(player, signing_key(7));
+
+        manager.submit_world_change(player, player_action(player)).await.unwrap();
+
+        let transaction = manager.blockchain_storage.read().await.pending_transactions.back().unwrap().clone();
+        assert_eq!(transaction.initiator, player);
+        assert!(transaction.signature.is_some());
+        assert!(manager.verify_transaction(&transaction).unwrap());
+
+        // Node-initiated records are signed with the node's own key
+        manager.create_automatic_checkpoint().await.unwrap();
+        let transaction = manager.blockchain_storage.read().await.pending_transactions.back().unwrap().clone();
+        assert!(manager.verify_transaction(&transaction).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_or_forged_transactions_are_rejected() {
+        let mut manager = signing_manager().await;
+        let player = Uuid::new_v4();
+        fund(&manager, player, 10).await;
+
+        // No key held for the player, so the change goes out unsigned
+        assert!(manager.submit_world_change(player, player_action(player)).await.is_err());
+
+        manager.register_transaction_key(player, signing_key(7).verifying_key());
+        let unsigned = WorldTransaction {
+            transaction_id: Uuid::new_v4(),
+            transaction_type: TransactionType::PlayerAction,
+            initiator: player,
+            nonce: 1,
+            timestamp: SystemTime::now(),
+            data: serde_json::to_value(player_action(player)).unwrap(),
+            signature: None,
+        };
+        assert!(manager.submit_transaction(unsigned.clone()).await.is_err());
+
+        // Signed by someone else's key
+        let mut forged = unsigned.clone();
+        BlockchainManager::sign_transaction(&mut forged, &signing_key(8)).unwrap();
+        assert!(manager.submit_transaction(forged).await.is_err());
+
+        // Properly signed, then altered in flight
+        let mut tampered = unsigned.clone();
+        BlockchainManager::sign_transaction(&mut tampered, &signing_key(7)).unwrap();
+        tampered.data = serde_json::to_value(player_action(Uuid::new_v4())).unwrap();
+        assert!(manager.submit_transaction(tampered).await.is_err());
+
+        assert!(manager.blockchain_storage.read().await.pending_transactions.is_empty());
+        assert_eq!(native_balance(&manager, player).await, 10);
+
+        let mut signed = unsigned;
+        BlockchainManager::sign_transaction(&mut signed, &signing_key(7)).unwrap();
+        manager.submit_transaction(signed).await.unwrap();
+        assert_eq!(manager.blockchain_storage.read().await.pending_transactions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_transaction_with_stale_nonce_is_rejected() {
+        let mut manager = started_manager().await;
+        let player = Uuid::new_v4();
+        fund(&manager, player, 10).await;
+        manager.add_local_signer(player, signing_key(3));
+
+        let change = player_action(player);
+        manager.submit_world_change(player, change.clone()).await.unwrap();
+        let original = manager.blockchain_storage.read().await.pending_transactions.back().unwrap().clone();
+        assert_eq!(original.nonce, 1);
+
+        let block = signed_block_with_nonces(&manager, 1, SystemTime::now(), vec![change], vec![(player, original.nonce)]);
+        manager.apply_finalized_block(block).await.unwrap();
+        assert_eq!(manager.get_nonce(player).await, 1);
+        assert_eq!(manager.get_world_state().await.account_nonces.get(&player), Some(&1));
+
+        // The same signed transaction, replayed by a peer
+        assert!(manager.submit_transaction(original.clone()).await.is_err());
+
+        // Skipping ahead is rejected too; only the next nonce gets through
+        let mut skipped = original.clone();
+        skipped.transaction_id = Uuid::new_v4();
+        skipped.nonce = 3;
+        BlockchainManager::sign_transaction(&mut skipped, &signing_key(3)).unwrap();
+        assert!(manager.submit_transaction(skipped.clone()).await.is_err());
+
+        skipped.nonce = 2;
+        BlockchainManager::sign_transaction(&mut skipped, &signing_key(3)).unwrap();
+        manager.submit_transaction(skipped).await.unwrap();
+        assert_eq!(manager.get_nonce(player).await, 2);
+        assert_eq!(native_balance(&manager, player).await, 8);
+    }
+
+    #[tokio::test]
+    async fn test_nonces_advance_from_the_block_on_a_node_that_never_saw_the_transaction() {
+        let mut origin = started_manager().await;
+        let player = Uuid::new_v4();
+        fund(&origin, player, 10).await;
+        origin.add_local_signer(player, signing_key(3));
+        let change = player_action(player);
+        origin.submit_world_change(player, change.clone()).await.unwrap();
+        let original = origin.blockchain_storage.read().await.pending_transactions.back().unwrap().clone();
+
+        // A peer whose mempool is empty learns the nonce from the block alone
+        let mut peer = started_manager().await;
+        fund(&peer, player, 10).await;
+        peer.register_transaction_key(player, signing_key(3).verifying_key());
+        let block = signed_block_with_nonces(&peer, 1, SystemTime::now(), vec![change.clone()], vec![(player, 1)]);
+        peer.apply_finalized_block(block).await.unwrap();
+        assert_eq!(peer.get_nonce(player).await, 1);
+        assert!(peer.submit_transaction(original).await.is_err());
+
+        // A later block finalizing the same nonce again is a replay
+        let replayed = signed_block_with_nonces(&peer, 2, SystemTime::now(), vec![change], vec![(player, 1)]);
+        let error = peer.apply_finalized_block(replayed).await.unwrap_err();
+        assert!(error.to_string().contains("expected 2"), "{}", error);
+        assert!(peer.get_block(2).await.is_none());
+    }
+
+    fn to_consensus_block(block: &FinalizedBlock) -> arceon_consensus::FinalizedBlock {
+        serde_json::from_value(serde_json::to_value(block).unwrap()).unwrap()
+    }
+
+    /// A consensus node that has synced, and so confirmed, `blocks`
+    async fn consensus_with_confirmed(blocks: &[FinalizedBlock]) -> arceon_consensus::ConsensusManager {
+        let mut consensus = arceon_consensus::ConsensusManager::new(Uuid::new_v4(), false, 0, Default::default()).unwrap();
+        consensus.handle_consensus_message(arceon_consensus::ConsensusMessage::SyncResponse {
+            blocks: blocks.iter().map(to_consensus_block).collect(),
+            current_state: arceon_consensus::ConsensusState {
+                current_epoch: blocks.len() as u64,
+                current_round: 0,
+                current_step: arceon_consensus::ConsensusStep::Propose,
+                last_finalized_block: None,
+                active_proposal: None,
+                votes: HashMap::new(),
+                vote_history: HashMap::new(),
+                view_change_votes: HashMap::new(),
+                total_stake: 0,
+            },
+        }).await.unwrap();
+        consensus
+    }
+
+    #[tokio::test]
+    async fn test_follower_applies_the_blocks_consensus_confirms() {
+        let mut follower = BlockchainManager::new(&test_config()).await.unwrap();
+        follower.start(Uuid::new_v4(), false, 0).await.unwrap();
+
+        let mut blocks: Vec<FinalizedBlock> = Vec::new();
+        for epoch in 0..3 {
+            let mut block = test_block(epoch, SystemTime::now(), vec![world_event()]);
+            block.block_hash = [epoch as u8 + 1; 32];
+            block.merkle_root = follower.calculate_merkle_root(&block.world_changes).unwrap();
+            block.previous_hash = blocks.last().map(|previous| previous.block_hash);
+            blocks.push(block);
+        }
+        let consensus = consensus_with_confirmed(&blocks).await;
+
+        // Consensus already required a precommit quorum, so no block signatures are needed
+        assert_eq!(follower.apply_confirmed_blocks(&consensus).await.unwrap(), 3);
+        assert_eq!(follower.get_block(2).await.unwrap().block_hash, blocks[2].block_hash);
+        assert_eq!(follower.blockchain_storage.read().await.last_finalized_epoch, 2);
+
+        // Each confirmed block is taken once, and blocks already held are skipped
+        assert_eq!(follower.apply_confirmed_blocks(&consensus).await.unwrap(), 0);
+        let resynced = consensus_with_confirmed(&blocks).await;
+        assert_eq!(follower.apply_confirmed_blocks(&resynced).await.unwrap(), 0);
+
+        // A node holding its own genesis block at epoch 0 refuses a different one
+        let mut masternode = started_manager().await;
+        let consensus = consensus_with_confirmed(&blocks).await;
+        let err = masternode.apply_confirmed_blocks(&consensus).await.unwrap_err();
+        assert!(err.to_string().contains("conflicts"), "{}", err);
+        assert!(masternode.get_block(1).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_block_hash_lookup_survives_load() {
+        let mut manager = started_manager().await;
+        for epoch in 1..=3 {
+            manager.apply_finalized_block(signed_block(&manager, epoch, SystemTime::now(), Vec::new())).await.unwrap();
+        }
+        manager.save_world_state_to_blockchain("before_epoch_4".to_string()).await.unwrap();
+        manager.apply_finalized_block(signed_block(&manager, 4, SystemTime::now(), Vec::new())).await.unwrap();
+        assert_eq!(manager.get_block_by_hash([4; 32]).await.unwrap().epoch, 4);
+
+        manager.load_world_state_from_blockchain("before_epoch_4".to_string()).await.unwrap();
+
+        // The index now reflects the restored blocks only
+        assert_eq!(manager.get_block_by_hash([2; 32]).await.unwrap().epoch, 2);
+        assert!(manager.get_block_by_hash([4; 32]).await.is_none());
+        assert!(manager.get_block_by_hash([9; 32]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_block_range_is_inclusive() {
+        let mut manager = started_manager().await;
+        for epoch in 1..=5 {
+            manager.apply_finalized_block(signed_block(&manager, epoch, SystemTime::now(), Vec::new())).await.unwrap();
+        }
+
+        let epochs: Vec<u64> = manager.get_block_range(2, 4).await.iter().map(|block| block.epoch).collect();
+        assert_eq!(epochs, vec![2, 3, 4]);
+        assert_eq!(manager.get_block_range(4, 9).await.len(), 2);
+        assert!(manager.get_block_range(4, 2).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_block_with_tampered_changes_is_rejected() {
+        let mut manager = started_manager().await;
+        let mut block = signed_block(&manager, 1, SystemTime::now(), vec![world_event()]);
+        block.world_changes.push(world_event());
+
+        let error = manager.apply_finalized_block(block).await.unwrap_err();
+        assert!(error.to_string().contains("merkle root"), "{}", error);
+        assert!(manager.get_block(1).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_block_with_its_last_change_repeated_is_rejected() {
+        let mut manager = started_manager().await;
+        let changes: Vec<WorldChange> = (0..3).map(|_| player_action(Uuid::new_v4())).collect();
+        let mut block = signed_block(&manager, 1, SystemTime::now(), changes);
+        block.world_changes.push(block.world_changes[2].clone());
+
+        assert!(manager.apply_finalized_block(block).await.is_err());
+        assert!(manager.get_block(1).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_under_signed_block_is_rejected() {
+        let mut manager = started_manager().await;
+        let (second, third) = (Uuid::new_v4(), Uuid::new_v4());
+        for (validator, seed) in [(second, 2), (third, 3)] {
+            manager.register_validator(validator, 1_000).await;
+            manager.register_validator_key(validator, signing_key(seed).verifying_key()).await;
+        }
+
+        // One of three equally staked validators is not enough
+        let block = signed_block(&manager, 1, SystemTime::now(), Vec::new());
+        let error = manager.apply_finalized_block(block.clone()).await.unwrap_err();
+        assert!(error.to_string().contains("2/3"), "{}", error);
+
+        // A signature from an unregistered key doesn't count either
+        let mut forged = block.clone();
+        BlockchainManager::sign_block(&mut forged, second, &signing_key(9)).unwrap();
+        assert!(manager.apply_finalized_block(forged).await.is_err());
+        assert!(manager.get_block(1).await.is_none());
+
+        let mut block = block;
+        BlockchainManager::sign_block(&mut block, second, &signing_key(2)).unwrap();
+        manager.apply_finalized_block(block).await.unwrap();
+        assert_eq!(manager.get_block(1).await.unwrap().epoch, 1);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_lock_then_complete() {
+        let mut manager = started_manager().await;
+        let user = Uuid::new_v4();
+        fund(&manager, user, 1_000).await;
+
+        let bridge_id = manager.initiate_cross_chain_bridge(user, "arceon".to_string(), "ethereum".to_string(), "ARCEON".to_string(), 400).await.unwrap();
+        assert_eq!(manager.get_bridge_transfer(&bridge_id).await.unwrap().status, BridgeStatus::Locked);
+        assert_eq!(native_balance(&manager, user).await, 550);
+        assert_eq!(native_balance(&manager, BRIDGE_ESCROW_ACCOUNT).await, 400);
+
+        assert!(manager.complete_bridge_transfer(&bridge_id, String::new()).await.is_err());
+        let completed = manager.complete_bridge_transfer(&bridge_id, "0xmint".to_string()).await.unwrap();
+        assert_eq!(completed.status, BridgeStatus::Completed);
+        assert_eq!(completed.proof.as_deref(), Some("0xmint"));
+
+        // Completed transfers can be neither completed again nor refunded
+        assert!(manager.complete_bridge_transfer(&bridge_id, "0xmint".to_string()).await.is_err());
+        assert!(manager.refund_bridge_transfer(&bridge_id).await.is_err());
+
+        let storage = manager.blockchain_storage.read().await;
+        let statuses: Vec<_> = storage.pending_transactions.iter()
+            .filter(|tx| tx.data["WorldEvent"]["event_type"] == "CROSS_CHAIN_BRIDGE")
+            .map(|tx| tx.data["WorldEvent"]["data"]["status"].clone())
+            .collect();
+        assert_eq!(statuses, vec!["Locked", "Minted", "Completed"]);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_lock_times_out_and_refunds() {
+        let mut manager = started_manager().await;
+        let user = Uuid::new_v4();
+        fund(&manager, user, 1_000).await;
+
+        let bridge_id = manager.initiate_cross_chain_bridge(user, "arceon".to_string(), "ethereum".to_string(), "ARCEON".to_string(), 400).await.unwrap();
+        assert!(manager.refund_bridge_transfer(&bridge_id).await.is_err());
+
+        // Let the lock run past its timeout
+        {
+            let mut storage = manager.blockchain_storage.write().await;
+            let transfer = storage.bridge_transfers.get_mut(&bridge_id).unwrap();
+            transfer.locked_at -= Duration::from_secs(manager.config.bridge_refund_timeout_secs + 1);
+        }
+
+        let refunded = manager.refund_bridge_transfer(&bridge_id).await.unwrap();
+        assert_eq!(refunded.status, BridgeStatus::Refunded);
+        assert_eq!(native_balance(&manager, user).await, 950);
+        assert_eq!(native_balance(&manager, BRIDGE_ESCROW_ACCOUNT).await, 0);
+        assert!(manager.complete_bridge_transfer(&bridge_id, "0xlate".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_eligibility_uses_registered_validator_stake() {
+        let mut manager = started_manager().await;
+        let validator = Uuid::new_v4();
+        let required = manager.config.reward_amount * 100;
+
+        // Registered with too little stake: claiming more doesn't help
+        manager.register_validator(validator, required - 1).await;
+        let eligibility = manager.validate_masternode_eligibility(validator, required * 10).await.unwrap();
+        assert_eq!(eligibility.current_stake, required - 1);
+        assert!(!eligibility.is_eligible);
+
+        manager.register_validator(validator, required * 2).await;
+        let eligibility = manager.validate_masternode_eligibility(validator, 0).await.unwrap();
+        assert_eq!(eligibility.current_stake, required * 2);
+        assert_eq!(eligibility.reputation_score, 100.0);
+
+        // Once deregistered the node has no validator record to vouch for it
+        assert!(manager.deregister_validator(validator).await.is_some());
+        assert!(manager.get_validator_info(validator).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_uptime_reflects_gaps_in_activity_history() {
+        let manager = started_manager().await;
+        let validator = Uuid::new_v4();
+        let interval = Duration::from_secs(ACTIVITY_INTERVAL_SECS);
+        let now = SystemTime::now();
+        let start = now - interval * 99;
+
+        // Up for the first 40 intervals, down for 50, back for the last 10
+        for i in (0..40).chain(90..100) {
+            manager.record_validator_activity(validator, start + interval * i).await;
+        }
+
+        let storage = manager.blockchain_storage.read().await;
+        assert!((storage.uptime_percentage(validator, now) - 50.0).abs() < 1e-9);
+        assert_eq!(storage.uptime_percentage(Uuid::new_v4(), now), 0.0);
+        drop(storage);
+
+        // Samples are part of the stored chain data, so they survive a save and restore
+        let compressed = manager.compress_blockchain_data().await.unwrap();
+        let mut restored = BlockchainManager::new(&test_config()).await.unwrap();
+        restored.restore_blockchain_data(compressed).await.unwrap();
+        let storage = restored.blockchain_storage.read().await;
+        assert!((storage.uptime_percentage(validator, now) - 50.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_masternode_report_surfaces_insufficient_stake() {
+        let manager = started_manager().await;
+        let validator = Uuid::new_v4();
+        let required = manager.config.reward_amount * 100;
+        manager.register_validator(validator, required / 2).await;
+
+        let report = manager.masternode_report(validator).await.unwrap();
+        assert!(!report.is_eligible);
+        assert_eq!(report.current_stake, required / 2);
+        assert_eq!(report.validation_errors.len(), 1);
+        assert!(report.validation_errors[0].starts_with("Insufficient stake"));
+
+        let printed = report.to_string();
+        assert!(printed.contains("NOT eligible"));
+        assert!(printed.contains(&report.validation_errors[0]));
+    }
+
+    #[tokio::test]
+    async fn test_validator_join_and_leave_messages_update_registry() {
+        let manager = started_manager().await;
+        let validator = Uuid::new_v4();
+        let sender = manager.consensus_sender().unwrap();
+
+        let public_key = signing_key(4).verifying_key().to_bytes();
+        sender.send(ConsensusMessage::ValidatorJoin { node_id: validator, stake_amount: 3_000, public_key, timestamp: SystemTime::now() }).await.unwrap();
+        for _ in 0..100 {
+            if manager.get_validator_info(validator).await.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let info = manager.get_validator_info(validator).await.unwrap();
+        assert_eq!(info.stake_amount, 3_000);
+        assert!((info.voting_power - 0.75).abs() < 1e-9);
+
+        sender.send(ConsensusMessage::ValidatorLeave { node_id: validator, timestamp: SystemTime::now() }).await.unwrap();
+        for _ in 0..100 {
+            if manager.get_validator_info(validator).await.is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(manager.get_validator_info(validator).await.is_none());
+    }
+
+    /// Hand `manager` a join for `validator` and wait until its message loop has taken it
+    async fn join_validator(manager: &BlockchainManager, validator: Uuid, stake_amount: u64, key: &SigningKey) {
+        let join = ConsensusMessage::ValidatorJoin {
+            node_id: validator,
+            stake_amount,
+            public_key: key.verifying_key().to_bytes(),
+            timestamp: SystemTime::now(),
+        };
+        manager.consensus_sender().unwrap().send(join).await.unwrap();
+        for _ in 0..100 {
+            if manager.get_validator_info(validator).await.is_some() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("validator {} never joined", validator);
+    }
+
+    #[tokio::test]
+    async fn test_local_signature_alone_does_not_finalize_against_joined_validators() {
+        let mut manager = started_manager().await;
+        let remote = Uuid::new_v4();
+        join_validator(&manager, remote, 3_000, &signing_key(5)).await;
+
+        // The local masternode holds a quarter of the known stake
+        let block = signed_block(&manager, 1, SystemTime::now(), Vec::new());
+        let error = manager.apply_finalized_block(block.clone()).await.unwrap_err();
+        assert!(error.to_string().contains("1000 of 4000"), "{}", error);
+
+        // The key carried in the join is the one the remote signature is checked against
+        let mut block = block;
+        BlockchainManager::sign_block(&mut block, remote, &signing_key(5)).unwrap();
+        manager.apply_finalized_block(block).await.unwrap();
+        assert_eq!(manager.get_block(1).await.unwrap().epoch, 1);
+    }
+
+    #[tokio::test]
+    async fn test_validator_without_a_known_key_still_counts_toward_total_stake() {
+        let mut manager = started_manager().await;
+        manager.register_validator(Uuid::new_v4(), 1_000).await;
+
+        let block = signed_block(&manager, 1, SystemTime::now(), Vec::new());
+        assert!(manager.apply_finalized_block(block).await.is_err());
+        assert!(manager.get_block(1).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_follower_with_no_known_validators_checks_only_the_merkle_root() {
+        let mut manager = BlockchainManager::new(&test_config()).await.unwrap();
+        manager.start(Uuid::new_v4(), false, 0).await.unwrap();
+
+        let mut tampered = test_block(1, SystemTime::now(), vec![player_action(Uuid::new_v4())]);
+        tampered.merkle_root = "not the root".to_string();
+        assert!(manager.apply_finalized_block(tampered).await.is_err());
+
+        let mut unsigned = test_block(1, SystemTime::now(), vec![player_action(Uuid::new_v4())]);
+        unsigned.merkle_root = manager.calculate_merkle_root(&unsigned.world_changes).unwrap();
+        manager.apply_finalized_block(unsigned).await.unwrap();
+
+        // Once a validator is known, its signature is required
+        let validator = Uuid::new_v4();
+        join_validator(&manager, validator, 1_000, &signing_key(6)).await;
+        let mut block = test_block(2, SystemTime::now(), Vec::new());
+        block.merkle_root = manager.calculate_merkle_root(&block.world_changes).unwrap();
+        assert!(manager.apply_finalized_block(block.clone()).await.is_err());
+        BlockchainManager::sign_block(&mut block, validator, &signing_key(6)).unwrap();
+        manager.apply_finalized_block(block).await.unwrap();
+        assert_eq!(manager.get_block(2).await.unwrap().epoch, 2);
+    }
+
+    #[tokio::test]
+    async fn test_validator_rewards_follow_real_stake() {
+        let mut manager = started_manager().await;
+        let minimum = manager.config.reward_amount * 100;
+        manager.register_validator(manager.node_id, minimum).await;
+        let (small, large) = (Uuid::new_v4(), Uuid::new_v4());
+        for (validator, seed, stake) in [(small, 1, minimum), (large, 2, minimum * 4)] {
+            manager.register_validator(validator, stake).await;
+            manager.register_validator_key(validator, signing_key(seed).verifying_key()).await;
+        }
+
+        let mut block = signed_block(&manager, 1, SystemTime::now(), Vec::new());
+        block.proposer = manager.node_id;
+        BlockchainManager::sign_block(&mut block, small, &signing_key(1)).unwrap();
+        BlockchainManager::sign_block(&mut block, large, &signing_key(2)).unwrap();
+        manager.apply_finalized_block(block).await.unwrap();
+
+        let distribution = manager.distribute_masternode_rewards(1).await.unwrap();
+        let reward_of = |node_id| distribution.validator_rewards.iter().find(|reward| reward.node_id == node_id).unwrap().amount;
+        // Four times the stake earns twice the reward under the square-root stake multiplier
+        assert_eq!(reward_of(large), 2 * reward_of(small));
+
+        assert_eq!(manager.calculate_total_rewards_earned(large).await.unwrap(), reward_of(large));
+        let proposer_total = distribution.masternode_rewards[0].amount + reward_of(manager.node_id);
+        assert_eq!(manager.calculate_total_rewards_earned(manager.node_id).await.unwrap(), proposer_total);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_applied_blocks() {
+        let mut manager = started_manager().await;
+        let mut first = manager.subscribe();
+        let mut second = manager.subscribe();
+        let mut transfers = manager.subscribe_events("TOKEN_TRANSFER");
+
+        let transfer = WorldChange::WorldEvent {
+            event_id: Uuid::new_v4(),
+            event_type: "TOKEN_TRANSFER".to_string(),
+            timestamp: SystemTime::now(),
+            affected_areas: vec!["GLOBAL".to_string()],
+            data: serde_json::json!({ "amount": 5 }),
+        };
+        let block = signed_block(&manager, 1, SystemTime::now(), vec![world_event(), transfer.clone()]);
+        manager.apply_finalized_block(block).await.unwrap();
+
+        assert_eq!(first.recv().await.unwrap().epoch, 1);
+        assert_eq!(second.recv().await.unwrap().epoch, 1);
+
+        let received = tokio::time::timeout(Duration::from_secs(1), transfers.recv()).await.unwrap().unwrap();
+        assert_eq!(serde_json::to_value(&received).unwrap(), serde_json::to_value(&transfer).unwrap());
+        assert!(transfers.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pruning_keeps_genesis_and_recent_blocks() {
+        let mut config = test_config();
+        config.snapshot_interval = 5;
+        let mut manager = started_manager_with(config).await;
+        for epoch in 1..=12 {
+            manager.apply_finalized_block(signed_block(&manager, epoch, SystemTime::now(), Vec::new())).await.unwrap();
+        }
+
+        assert_eq!(manager.prune_before(8).await, 7);
+        for epoch in 1..8 {
+            assert!(manager.get_block(epoch).await.is_none(), "epoch {} should be pruned", epoch);
+        }
+        assert!(manager.get_block(0).await.is_some());
+        assert_eq!(manager.get_block_range(0, 12).await.len(), 6);
+
+        // The snapshot at epoch 5 anchors the deltas that later epochs are rebuilt from
+        assert!(manager.get_world_snapshot(9).await.is_some());
+        assert!(manager.get_world_snapshot(3).await.is_none());
+
+        // With a history window, old blocks go as new ones arrive
+        manager.config.max_history_epochs = 3;
+        manager.apply_finalized_block(signed_block(&manager, 13, SystemTime::now(), Vec::new())).await.unwrap();
+        let epochs: Vec<u64> = manager.get_block_range(0, 13).await.iter().map(|block| block.epoch).collect();
+        assert_eq!(epochs, vec![0, 11, 12, 13]);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_fee_is_split_between_burn_and_treasury() {
+        let mut manager = started_manager().await;
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        fund(&manager, alice, 1_000).await;
+
+        manager.transfer_tokens(alice, bob, "ARCEON".to_string(), 100).await.unwrap();
+
+        // Bob gets the full amount; alice pays it plus the 10 transfer fee and 50 recording fee
+        assert_eq!(native_balance(&manager, bob).await, 100);
+        assert_eq!(native_balance(&manager, alice).await, 840);
+
+        // Half of each fee is burned, the other half funds the treasury
+        let token_economy = manager.token_economy.read().await;
+        assert_eq!(token_economy.treasury_balance("ARCEON").await, 5 + 25);
+        assert_eq!(token_economy.token_manager.read().await.burned_amounts["ARCEON"], 5 + 25);
+
+        // 95 plus the 10 fee is more than bob holds, so nothing moves
+        assert!(token_economy.transfer_tokens(bob, alice, "ARCEON".to_string(), 95).await.is_err());
+        drop(token_economy);
+        assert_eq!(native_balance(&manager, bob).await, 100);
+        assert_eq!(native_balance(&manager, alice).await, 840);
+    }
+
+    /// Save the current world as `save_name` and export it to a fresh temp file
+    async fn exported_save(manager: &mut BlockchainManager, save_name: &str) -> std::path::PathBuf {
+        manager.save_world_state_to_blockchain(save_name.to_string()).await.unwrap();
+        let path = std::env::temp_dir().join(format!("arceon_export_{}.json", Uuid::new_v4()));
+        manager.export_world_state(save_name.to_string(), path.to_string_lossy().into_owned()).await.unwrap();
+        path
+    }
+
+    /// Rewrite the export at `path` through `edit`
+    fn edit_export(path: &std::path::Path, edit: impl FnOnce(&mut serde_json::Value)) {
+        let mut export: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        edit(&mut export);
+        std::fs::write(path, serde_json::to_string(&export).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_accepts_current_export_version() {
+        let mut manager = started_manager().await;
+        let path = exported_save(&mut manager, "backup").await;
+
+        manager.import_world_state(path.to_string_lossy().into_owned(), "restored".to_string()).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let saves = manager.list_blockchain_saves().await.unwrap();
+        assert!(saves.iter().any(|save| save.save_name == "restored"));
+    }
+
+    #[tokio::test]
+    async fn test_import_migrates_older_minor_export_version() {
+        let mut manager = started_manager().await;
+        let path = exported_save(&mut manager, "backup").await;
+        let epoch = manager.get_world_state().await.current_epoch;
+
+        // A 1.0 file predates the top-level epoch
+        edit_export(&path, |export| {
+            export["version"] = serde_json::json!("1.0");
+            export.as_object_mut().unwrap().remove("epoch");
+        });
+
+        let migrated = read_portable_export(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(migrated.version, CURRENT_EXPORT_VERSION);
+        assert_eq!(migrated.epoch, epoch);
+
+        manager.import_world_state(path.to_string_lossy().into_owned(), "restored".to_string()).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_incompatible_major_export_version() {
+        let mut manager = started_manager().await;
+        let path = exported_save(&mut manager, "backup").await;
+        edit_export(&path, |export| export["version"] = serde_json::json!("2.0"));
+
+        let err = manager.import_world_state(path.to_string_lossy().into_owned(), "restored".to_string()).await.unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(err.to_string().contains("2.0"), "{}", err);
+        let saves = manager.list_blockchain_saves().await.unwrap();
+        assert!(!saves.iter().any(|save| save.save_name == "restored"));
+    }
+
+    fn test_player(name: &str, swordsmanship: f64) -> PlayerState {
+        PlayerState {
+            player_id: Uuid::new_v4(),
+            name: name.to_string(),
+            race: "Human".to_string(),
+            current_area: "Central Plains".to_string(),
+            skills: HashMap::from([("swordsmanship".to_string(), swordsmanship)]),
+            inventory: Vec::new(),
+            last_seen: SystemTime::now(),
+            online: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_players_restores_only_the_named_player() {
+        let mut manager = started_manager().await;
+        let (alice, bob) = (test_player("alice", 10.0), test_player("bob", 20.0));
+        {
+            let mut world_state = manager.world_state.write().await;
+            world_state.players.insert(alice.player_id, alice.clone());
+            world_state.players.insert(bob.player_id, bob.clone());
+        }
+        let path = exported_save(&mut manager, "backup").await;
+
+        // Both players progress after the backup, then alice's record is corrupted
+        {
+            let mut world_state = manager.world_state.write().await;
+            world_state.players.get_mut(&alice.player_id).unwrap().skills.clear();
+            world_state.players.get_mut(&bob.player_id).unwrap().skills.insert("swordsmanship".to_string(), 25.0);
+        }
+
+        manager.import_players(path.to_string_lossy().into_owned(), &[alice.player_id]).await.unwrap();
+        let missing = manager.import_players(path.to_string_lossy().into_owned(), &[Uuid::new_v4()]).await;
+        std::fs::remove_file(&path).unwrap();
+        assert!(missing.is_err());
+
+        let world_state = manager.get_world_state().await;
+        assert_eq!(world_state.players[&alice.player_id].skills["swordsmanship"], 10.0);
+        assert_eq!(world_state.players[&bob.player_id].skills["swordsmanship"], 25.0);
+    }
+
+    #[tokio::test]
+    async fn test_integrity_hash_changes_when_a_player_skill_changes() {
+        let mut manager = started_manager().await;
+        let (alice, bob) = (test_player("alice", 10.0), test_player("bob", 20.0));
+        {
+            let mut world_state = manager.world_state.write().await;
+            world_state.players.insert(alice.player_id, alice.clone());
+            world_state.players.insert(bob.player_id, bob);
+        }
+        manager.save_world_state_to_blockchain("backup".to_string()).await.unwrap();
+        let storage = manager.blockchain_storage.read().await;
+        let mut save_data = storage.blockchain_saves.as_ref().unwrap()["backup"].save_data.clone();
+        drop(storage);
+        let original = manager.calculate_save_integrity_hash(&save_data).unwrap();
+        assert_eq!(original, save_data.integrity_hash);
+
+        save_data.world_state.players.get_mut(&alice.player_id).unwrap()
+            .skills.insert("swordsmanship".to_string(), 10.5);
+        assert_ne!(manager.calculate_save_integrity_hash(&save_data).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_replay_from_genesis_matches_the_live_snapshot() {
+        let mut config = test_config();
+        config.snapshot_interval = 3;
+        let mut manager = started_manager_with(config).await;
+
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(3_000_000);
+        for epoch in 1..=5u64 {
+            let timestamp = t0 + Duration::from_secs(epoch * 10);
+            let changes = vec![
+                WorldChange::SkillEvolution {
+                    skill_name: format!("Skill{}", epoch % 2),
+                    evolution_type: "practice".to_string(),
+                    timestamp,
+                    discoverer: None,
+                    consensus_votes: 3,
+                },
+                WorldChange::WorldEvent {
+                    event_id: Uuid::from_u128(epoch as u128),
+                    event_type: "STORM".to_string(),
+                    timestamp,
+                    affected_areas: vec![GENESIS_AREAS[0].to_string()],
+                    data: serde_json::json!({ "epoch": epoch }),
+                },
+            ];
+            manager.apply_finalized_block(signed_block(&manager, epoch, timestamp, changes)).await.unwrap();
+        }
+
+        let replayed = manager.replay_from_genesis().await.unwrap();
+        let live = manager.get_world_snapshot(5).await.unwrap().world_state;
+        assert_eq!(replayed.current_epoch, 5);
+        assert_eq!(serde_json::to_value(&replayed).unwrap(), serde_json::to_value(&live).unwrap());
+        assert_eq!(
+            serde_json::to_value(&replayed).unwrap(),
+            serde_json::to_value(manager.get_world_state().await).unwrap()
+        );
+
+        // A hole in the history can't be replayed over
+        manager.blockchain_storage.write().await.blocks.remove(&2);
+        assert!(manager.replay_from_genesis().await.is_err());
+    }
+
+
+    #[tokio::test]
+    async fn test_integrity_check_flags_a_tampered_snapshot() {
+        let mut config = test_config();
+        config.snapshot_interval = 1;
+        let mut manager = started_manager_with(config).await;
+
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(4_000_000);
+        let mut previous_hash = manager.get_block(0).await.unwrap().block_hash;
+        for epoch in 1..=4u64 {
+            let timestamp = t0 + Duration::from_secs(epoch * 10);
+            let mut block = test_block(epoch, timestamp, vec![WorldChange::WorldEvent {
+                event_id: Uuid::from_u128(epoch as u128),
+                event_type: "STORM".to_string(),
+                timestamp,
+                affected_areas: vec![GENESIS_AREAS[0].to_string()],
+                data: serde_json::json!({ "epoch": epoch }),
+            }]);
+            block.previous_hash = Some(previous_hash);
+            block.merkle_root = manager.calculate_merkle_root(&block.world_changes).unwrap();
+            BlockchainManager::sign_block(&mut block, manager.node_id, &manager.local_signers[&manager.node_id]).unwrap();
+            previous_hash = block.block_hash;
+            manager.apply_finalized_block(block).await.unwrap();
+        }
+
+        let report = manager.verify_chain_integrity().await;
+        assert!(report.is_intact(), "{:?}", report);
+        assert_eq!((report.epochs_replayed, report.snapshots_checked), (5, 5));
+
+        {
+            let mut storage = manager.blockchain_storage.write().await;
+            storage.world_snapshots.get_mut(&3).unwrap().world_state.world_time += 1;
+            storage.blocks.get_mut(&4).unwrap().previous_hash = Some([0xee; 32]);
+        }
+        let report = manager.verify_chain_integrity().await;
+        assert_eq!(report.divergent_snapshots.len(), 1);
+        assert_eq!(report.divergent_snapshots[0].epoch, 3);
+        assert_eq!(report.broken_links, vec![4]);
+    }
+
+}
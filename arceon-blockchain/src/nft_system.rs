@@ -44,6 +44,12 @@ pub struct NFTConfig {
     pub evolution_requirements_multiplier: f64,
     pub cross_chain_enabled: bool,
     pub supported_chains: Vec<String>,
+    #[serde(default = "default_marketplace_account")]
+    pub marketplace_account: Uuid, // Receives marketplace fees from sales
+}
+
+fn default_marketplace_account() -> Uuid {
+    Uuid::from_u128(0x4d4b54) // "MKT"
 }
 
 /// NFT registry for tracking all NFTs
@@ -252,6 +258,8 @@ pub struct NFTCollection {
     pub featured: bool,
     pub category: NFTCategory,
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub transfer_cooldown_seconds: Option<u64>, // Minimum time between transfers/sales of a token
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -805,7 +813,7 @@ impl NFTSystem {
         }
         
         // Create NFT token
-        let rarity_score = self.calculate_rarity_score(&mint_request.attributes, &mint_request.collection_id).await?;
+        let rarity_score = Self::calculate_rarity_score(&registry, &mint_request.attributes, &mint_request.collection_id);
         let nft_token = NFTToken {
             token_id,
             collection_id: mint_request.collection_id.clone(),
@@ -887,10 +895,9 @@ impl NFTSystem {
         Ok(token_id)
     }
 
-    /// Calculate rarity score for NFT attributes
-    async fn calculate_rarity_score(&self, attributes: &[NFTAttribute], collection_id: &str) -> Result<f64> {
-        let registry = self.nft_registry.read().await;
-        
+    /// Calculate rarity score for NFT attributes, reading the registry the caller
+    /// already holds locked (taking the lock again here would deadlock `mint_nft`)
+    fn calculate_rarity_score(registry: &NFTRegistry, attributes: &[NFTAttribute], collection_id: &str) -> f64 {
         if let Some(collection) = registry.collections.get(collection_id) {
             let mut rarity_score = 0.0;
             
@@ -913,9 +920,9 @@ impl NFTSystem {
                 }
             }
             
-            Ok(rarity_score)
+            rarity_score
         } else {
-            Ok(1.0) // Default score if collection not found
+            1.0 // Default score if collection not found
         }
     }
 
@@ -923,6 +930,9 @@ impl NFTSystem {
     pub async fn transfer_nft(&self, token_id: Uuid, from_owner: Uuid, to_owner: Uuid) -> Result<()> {
         let mut registry = self.nft_registry.write().await;
         
+        // Enforce the collection's transfer cooldown
+        registry.check_transfer_cooldown(&token_id, Utc::now())?;
+        
         let nft = registry.nfts.get_mut(&token_id)
             .ok_or_else(|| anyhow::anyhow!("NFT not found"))?;
         
@@ -931,6 +941,10 @@ impl NFTSystem {
             return Err(anyhow::anyhow!("Not the owner of this NFT"));
         }
         
+        if nft.is_burned {
+            return Err(anyhow::anyhow!("NFT has been burned"));
+        }
+        
         // Check lock status
         if !matches!(nft.lock_status, LockStatus::Unlocked) {
             return Err(anyhow::anyhow!("NFT is locked and cannot be transferred"));
@@ -968,6 +982,54 @@ impl NFTSystem {
         Ok(())
     }
 
+    /// Permanently destroy an NFT held by `owner`
+    pub async fn burn_nft(&self, token_id: Uuid, owner: Uuid) -> Result<()> {
+        let mut registry = self.nft_registry.write().await;
+        
+        let nft = registry.nfts.get_mut(&token_id)
+            .ok_or_else(|| anyhow::anyhow!("NFT not found"))?;
+        
+        // Verify ownership
+        if nft.owner_id != owner {
+            return Err(anyhow::anyhow!("Not the owner of this NFT"));
+        }
+        
+        if nft.is_burned {
+            return Err(anyhow::anyhow!("NFT has already been burned"));
+        }
+        
+        // Check lock status
+        if !matches!(nft.lock_status, LockStatus::Unlocked) {
+            return Err(anyhow::anyhow!("NFT is locked and cannot be burned"));
+        }
+        
+        nft.is_burned = true;
+        nft.transfer_history.push(NFTTransfer {
+            transfer_id: Uuid::new_v4(),
+            from_owner: owner,
+            to_owner: Uuid::nil(), // Burn to null address
+            timestamp: Utc::now(),
+            transaction_hash: format!("burn_{}", token_id),
+            transfer_type: TransferType::Burn,
+            price: None,
+            marketplace_listing_id: None,
+        });
+        let collection_id = nft.collection_id.clone();
+        
+        if let Some(owner_tokens) = registry.owner_tokens.get_mut(&owner) {
+            owner_tokens.retain(|&id| id != token_id);
+        }
+        if let Some(collection) = registry.collections.get_mut(&collection_id) {
+            collection.total_supply = collection.total_supply.saturating_sub(1);
+        }
+        registry.total_supply = registry.total_supply.saturating_sub(1);
+        registry.burned_tokens.push(token_id);
+        
+        tracing::info!("Burned NFT {} owned by {}", token_id, owner);
+        
+        Ok(())
+    }
+
     /// Create marketplace listing
     pub async fn create_listing(&self, listing_request: ListingRequest) -> Result<Uuid> {
         let mut marketplace = self.marketplace.write().await;
@@ -1026,6 +1088,9 @@ impl NFTSystem {
         let listing_price = listing.price;
         let listing_currency = listing.currency.clone();
         
+        // Enforce the collection's transfer cooldown
+        registry.check_transfer_cooldown(&listing_token_id, Utc::now())?;
+        
         // Calculate fees and royalties
         let marketplace_fee = (listing_price as f64 * self.config.marketplace_fee_percentage / 100.0) as u64;
         let royalty_amount = self.calculate_royalty_amount(&listing_token_id, listing_price).await?;
@@ -1114,13 +1179,51 @@ impl NFTSystem {
         Ok(sale_id)
     }
 
+    /// Split `price` for a sale of `token_id` by `seller` into creator royalty (capped at
+    /// `royalty_percentage_cap`), marketplace fee, and seller proceeds
+    pub async fn sale_breakdown(&self, token_id: Uuid, seller: Uuid, price: u64) -> Result<SaleBreakdown> {
+        let registry = self.nft_registry.read().await;
+        
+        let nft = registry.nfts.get(&token_id)
+            .ok_or_else(|| anyhow::anyhow!("NFT not found"))?;
+        
+        // Verify ownership
+        if nft.owner_id != seller {
+            return Err(anyhow::anyhow!("Not the owner of this NFT"));
+        }
+        
+        if nft.is_burned {
+            return Err(anyhow::anyhow!("NFT has been burned"));
+        }
+        
+        let royalty_percentage = registry.collections.get(&nft.collection_id)
+            .map(|collection| collection.royalty_info.royalty_percentage.min(self.config.royalty_percentage_cap))
+            .unwrap_or(0.0)
+            .max(0.0);
+        let royalty = (price as f64 * royalty_percentage / 100.0) as u64;
+        let marketplace_fee = ((price as f64 * self.config.marketplace_fee_percentage / 100.0) as u64)
+            .min(price - royalty);
+        
+        Ok(SaleBreakdown {
+            token_id,
+            price,
+            creator_id: nft.creator_id,
+            royalty_percentage,
+            royalty,
+            marketplace_account: self.config.marketplace_account,
+            marketplace_fee,
+            seller_proceeds: price - royalty - marketplace_fee,
+        })
+    }
+
     /// Calculate royalty amount for a sale
     async fn calculate_royalty_amount(&self, token_id: &Uuid, sale_price: u64) -> Result<u64> {
         let registry = self.nft_registry.read().await;
         
         if let Some(nft) = registry.nfts.get(token_id) {
             if let Some(collection) = registry.collections.get(&nft.collection_id) {
-                let royalty_amount = (sale_price as f64 * collection.royalty_info.royalty_percentage / 100.0) as u64;
+                let royalty_percentage = collection.royalty_info.royalty_percentage.min(self.config.royalty_percentage_cap);
+                let royalty_amount = (sale_price as f64 * royalty_percentage / 100.0) as u64;
                 return Ok(royalty_amount);
             }
         }
@@ -1357,6 +1460,35 @@ impl NFTRegistry {
             burned_tokens: Vec::new(),
         }
     }
+
+    /// Reject a transfer if the token last changed hands within its collection's cooldown window
+    fn check_transfer_cooldown(&self, token_id: &Uuid, now: DateTime<Utc>) -> Result<()> {
+        let nft = self.nfts.get(token_id)
+            .ok_or_else(|| anyhow::anyhow!("NFT not found"))?;
+        
+        let cooldown_seconds = match self.collections.get(&nft.collection_id)
+            .and_then(|collection| collection.transfer_cooldown_seconds) {
+            Some(seconds) if seconds > 0 => seconds as i64,
+            _ => return Ok(()),
+        };
+        
+        // Only ownership changes count towards the cooldown, not minting or staking
+        let last_transfer = nft.transfer_history.iter().rev()
+            .find(|transfer| matches!(transfer.transfer_type, TransferType::Transfer | TransferType::Sale))
+            .map(|transfer| transfer.timestamp);
+        
+        if let Some(last_transfer) = last_transfer {
+            let elapsed = now.signed_duration_since(last_transfer).num_seconds();
+            if elapsed < cooldown_seconds {
+                return Err(anyhow::anyhow!(
+                    "NFT is on transfer cooldown for another {} seconds",
+                    cooldown_seconds - elapsed
+                ));
+            }
+        }
+        
+        Ok(())
+    }
 }
 
 impl NFTMarketplace {
@@ -1471,10 +1603,24 @@ impl Default for NFTConfig {
             evolution_requirements_multiplier: 1.0,
             cross_chain_enabled: true,
             supported_chains: vec!["Ethereum".to_string(), "Polygon".to_string(), "BSC".to_string()],
+            marketplace_account: default_marketplace_account(),
         }
     }
 }
 
+/// How a sale price is divided between creator, marketplace, and seller
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaleBreakdown {
+    pub token_id: Uuid,
+    pub price: u64,
+    pub creator_id: Uuid,
+    pub royalty_percentage: f64, // After applying the configured cap
+    pub royalty: u64,
+    pub marketplace_account: Uuid,
+    pub marketplace_fee: u64,
+    pub seller_proceeds: u64,
+}
+
 // Request structures for API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MintRequest {
@@ -1498,4 +1644,78 @@ pub struct ListingRequest {
     pub price: u64,
     pub currency: Currency,
     pub expires_at: Option<DateTime<Utc>>,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_collection(collection_id: &str, transfer_cooldown_seconds: Option<u64>) -> NFTCollection {
+        NFTCollection {
+            collection_id: collection_id.to_string(),
+            name: "Test Collection".to_string(),
+            symbol: "TEST".to_string(),
+            description: "Collection used in tests".to_string(),
+            creator_id: Uuid::new_v4(),
+            creation_timestamp: Utc::now(),
+            collection_image: String::new(),
+            banner_image: String::new(),
+            website: None,
+            social_links: HashMap::new(),
+            total_supply: 0,
+            max_supply: None,
+            floor_price: None,
+            total_volume: 0,
+            royalty_info: RoyaltyInfo {
+                royalty_percentage: 5.0,
+                royalty_recipients: Vec::new(),
+                total_royalties_collected: 0,
+            },
+            collection_attributes: Vec::new(),
+            verified: false,
+            featured: false,
+            category: NFTCategory::Gaming,
+            tags: Vec::new(),
+            transfer_cooldown_seconds,
+        }
+    }
+
+    fn test_mint_request(collection_id: &str, owner: Uuid) -> MintRequest {
+        MintRequest {
+            collection_id: collection_id.to_string(),
+            recipient_id: owner,
+            creator_id: owner,
+            name: "Test NFT".to_string(),
+            description: "NFT used in tests".to_string(),
+            image_url: String::new(),
+            metadata_uri: String::new(),
+            external_url: None,
+            attributes: Vec::new(),
+            utility_features: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_cooldown_enforced() {
+        let system = NFTSystem::new(NFTConfig::default()).await.unwrap();
+        system.nft_registry.write().await.collections
+            .insert("swords".to_string(), test_collection("swords", Some(3600)));
+
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let token_id = system.mint_nft(test_mint_request("swords", alice)).await.unwrap();
+
+        // Minting does not start the cooldown
+        assert!(system.transfer_nft(token_id, alice, bob).await.is_ok());
+        // An immediate second transfer is rejected
+        assert!(system.transfer_nft(token_id, bob, carol).await.is_err());
+
+        // Simulate the cooldown elapsing
+        {
+            let mut registry = system.nft_registry.write().await;
+            let nft = registry.nfts.get_mut(&token_id).unwrap();
+            nft.transfer_history.last_mut().unwrap().timestamp = Utc::now() - chrono::Duration::seconds(3601);
+        }
+
+        assert!(system.transfer_nft(token_id, bob, carol).await.is_ok());
+        assert_eq!(system.nft_registry.read().await.nfts[&token_id].owner_id, carol);
+    }
+}
@@ -44,6 +44,32 @@ pub struct TokenEconomyConfig {
     pub defi_enabled: bool,
     pub governance_enabled: bool,
     pub vesting_enabled: bool,
+    /// Shortest stake accepted; rewards accrue per full period of this length
+    #[serde(default = "default_staking_min_duration_hours")]
+    pub staking_min_duration_hours: u64,
+    #[serde(default = "default_staking_reward_rate")]
+    pub staking_reward_rate: f64, // Annual percentage paid on staked principal
+    /// Flat fee on every transfer, in the transferred token, paid on top of the amount
+    #[serde(default = "default_transaction_fee")]
+    pub transaction_fee: u64,
+    #[serde(default = "default_transaction_fee_percentage")]
+    pub transaction_fee_percentage: f64, // Added to the flat fee, as a percentage of the amount
+}
+
+fn default_transaction_fee() -> u64 {
+    10
+}
+
+fn default_transaction_fee_percentage() -> f64 {
+    0.1
+}
+
+fn default_staking_min_duration_hours() -> u64 {
+    24
+}
+
+fn default_staking_reward_rate() -> f64 {
+    5.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -265,6 +291,26 @@ pub struct LiquidityPool {
     pub is_active: bool,
 }
 
+/// Where a collected fee went: the `TransactionBurning` share is burned, the rest
+/// goes to the governance treasury
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSplit {
+    pub fee: u64,
+    pub burn_amount: u64,
+    pub treasury_allocation: u64,
+}
+
+/// Underlying tokens paid out when LP tokens are burned
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityWithdrawal {
+    pub pool_id: String,
+    pub liquidity_burned: u64,
+    pub token_a: String,
+    pub amount_a: u64,
+    pub token_b: String,
+    pub amount_b: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LPPosition {
     pub provider_id: Uuid,
@@ -1128,25 +1174,23 @@ impl TokenEconomySystem {
         let from_balance_key = (from_user, token_symbol.clone());
         let to_balance_key = (to_user, token_symbol.clone());
         
+        // The sender pays the fee on top of the amount
+        let fee = self.calculate_transfer_fee(&token_symbol, amount).await?;
         let from_balance = *manager.balances.get(&from_balance_key).unwrap_or(&0);
-        if from_balance < amount {
-            return Err(anyhow::anyhow!("Insufficient balance"));
+        if from_balance < amount.saturating_add(fee) {
+            return Err(anyhow::anyhow!("Insufficient balance: {} {} plus a {} fee needed, {} available",
+                                       amount, token_symbol, fee, from_balance));
         }
-        
-        // Calculate transfer fee (could be based on token or transaction type)
-        let fee = self.calculate_transfer_fee(&token_symbol, amount).await?;
-        let transfer_amount = amount - fee;
+        let transfer_amount = amount;
         
         // Update balances
-        manager.balances.insert(from_balance_key, from_balance - amount);
+        manager.balances.insert(from_balance_key, from_balance - amount - fee);
         
         let to_balance = *manager.balances.get(&to_balance_key).unwrap_or(&0);
         manager.balances.insert(to_balance_key, to_balance + transfer_amount);
         
-        // Handle fee (burn or collect)
-        if fee > 0 {
-            self.handle_transfer_fee(&token_symbol, fee, &mut manager).await?;
-        }
+        // Split the fee between burning and the treasury
+        self.collect_fee(&token_symbol, fee, &mut manager).await;
         
         // Record transaction
         let transaction_id = Uuid::new_v4();
@@ -1175,33 +1219,161 @@ impl TokenEconomySystem {
 
     /// Calculate transfer fee
     async fn calculate_transfer_fee(&self, _token_symbol: &str, amount: u64) -> Result<u64> {
-        // Simple fee calculation - could be made more sophisticated
-        let base_fee = 10; // Base fee in smallest token unit
-        let percentage_fee = (amount as f64 * 0.001) as u64; // 0.1%
-        Ok(base_fee + percentage_fee)
+        let percentage_fee = (amount as f64 * self.config.transaction_fee_percentage / 100.0) as u64;
+        Ok(self.config.transaction_fee.saturating_add(percentage_fee))
     }
 
-    /// Handle transfer fee (burn or collect to treasury)
-    async fn handle_transfer_fee(&self, token_symbol: &str, fee: u64, manager: &mut TokenManager) -> Result<()> {
-        // Check deflation mechanisms in config
-        for mechanism in &self.config.deflation_mechanisms {
-            match mechanism {
-                DeflationMechanism::TransactionBurning { percentage } => {
-                    let burn_amount = (fee as f64 * percentage / 100.0) as u64;
-                    if burn_amount > 0 {
-                        // Burn tokens (remove from supply)
-                        let current_burned = manager.burned_amounts.get(token_symbol).unwrap_or(&0);
-                        manager.burned_amounts.insert(token_symbol.to_string(), current_burned + burn_amount);
-                        
-                        let current_supply = manager.total_supplies.get(token_symbol).unwrap_or(&0);
-                        manager.total_supplies.insert(token_symbol.to_string(), current_supply - burn_amount);
-                        
-                        tracing::debug!("Burned {} {} tokens from transfer fee", burn_amount, token_symbol);
-                    }
-                }
-                _ => {} // Other mechanisms handled elsewhere
+    /// Burn the `TransactionBurning` share of a fee that has already left the payer's
+    /// balance, and credit the rest to the governance treasury
+    async fn collect_fee(&self, token_symbol: &str, fee: u64, manager: &mut TokenManager) -> FeeSplit {
+        let burn_percentage: f64 = self.config.deflation_mechanisms.iter()
+            .filter_map(|mechanism| match mechanism {
+                DeflationMechanism::TransactionBurning { percentage } => Some(*percentage),
+                _ => None, // Other mechanisms handled elsewhere
+            })
+            .sum();
+        let burn_amount = ((fee as f64 * burn_percentage.clamp(0.0, 100.0) / 100.0) as u64).min(fee);
+        let split = FeeSplit { fee, burn_amount, treasury_allocation: fee - burn_amount };
+
+        if split.burn_amount > 0 {
+            // Burn tokens (remove from supply)
+            *manager.burned_amounts.entry(token_symbol.to_string()).or_insert(0) += split.burn_amount;
+            if let Some(supply) = manager.total_supplies.get_mut(token_symbol) {
+                *supply = supply.saturating_sub(split.burn_amount);
             }
+            tracing::debug!("Burned {} {} tokens from fee", split.burn_amount, token_symbol);
+        }
+        if split.treasury_allocation > 0 {
+            let mut governance = self.governance_system.write().await;
+            *governance.governance_treasury.total_balance.entry(token_symbol.to_string()).or_insert(0) += split.treasury_allocation;
         }
+
+        split
+    }
+
+    /// Tokens of `token_symbol` the governance treasury holds
+    pub async fn treasury_balance(&self, token_symbol: &str) -> u64 {
+        let governance = self.governance_system.read().await;
+        governance.governance_treasury.total_balance.get(token_symbol).copied().unwrap_or(0)
+    }
+
+    /// Charge a native-token fee to a user's wallet, split between burning and the treasury
+    pub async fn charge_fee(&self, payer: Uuid, amount: u64) -> Result<Uuid> {
+        let mut manager = self.token_manager.write().await;
+        let token_symbol = self.config.native_token_symbol.clone();
+
+        let balance_key = (payer, token_symbol.clone());
+        let balance = *manager.balances.get(&balance_key).unwrap_or(&0);
+        if balance < amount {
+            return Err(anyhow::anyhow!("Insufficient balance to pay fee of {} {}: {} available",
+                                       amount, token_symbol, balance));
+        }
+
+        manager.balances.insert(balance_key, balance - amount);
+        self.collect_fee(&token_symbol, amount, &mut manager).await;
+
+        let transaction_id = Uuid::new_v4();
+        manager.transaction_history.push(TokenTransaction {
+            transaction_id,
+            transaction_type: TransactionType::Fee,
+            from_user: Some(payer),
+            to_user: None,
+            token_symbol: token_symbol.clone(),
+            amount,
+            fee: 0,
+            timestamp: Utc::now(),
+            block_hash: None,
+            transaction_hash: format!("fee_{}_{}", token_symbol, transaction_id),
+            status: TransactionStatus::Confirmed,
+            metadata: HashMap::new(),
+        });
+
+        tracing::debug!("Charged {} {} fee to {}", amount, token_symbol, payer);
+
+        Ok(transaction_id)
+    }
+
+    /// Pay several recipients from one balance in a single step, without transfer fees.
+    /// Either every payout is made or none are.
+    pub async fn distribute_payment(&self, payer: Uuid, token_symbol: String, payouts: &[(Uuid, u64)]) -> Result<Vec<Uuid>> {
+        let mut manager = self.token_manager.write().await;
+        
+        let total: u64 = payouts.iter().map(|(_, amount)| *amount).sum();
+        let payer_key = (payer, token_symbol.clone());
+        let payer_balance = *manager.balances.get(&payer_key).unwrap_or(&0);
+        if payer_balance < total {
+            return Err(anyhow::anyhow!("Insufficient balance: {} {} needed, {} available",
+                                       total, token_symbol, payer_balance));
+        }
+        manager.balances.insert(payer_key, payer_balance - total);
+        
+        let mut transaction_ids = Vec::with_capacity(payouts.len());
+        for (recipient, amount) in payouts.iter().filter(|(_, amount)| *amount > 0) {
+            *manager.balances.entry((*recipient, token_symbol.clone())).or_insert(0) += amount;
+            
+            let transaction_id = Uuid::new_v4();
+            manager.transaction_history.push(TokenTransaction {
+                transaction_id,
+                transaction_type: TransactionType::Transfer,
+                from_user: Some(payer),
+                to_user: Some(*recipient),
+                token_symbol: token_symbol.clone(),
+                amount: *amount,
+                fee: 0,
+                timestamp: Utc::now(),
+                block_hash: None,
+                transaction_hash: format!("payment_{}_{}", token_symbol, transaction_id),
+                status: TransactionStatus::Confirmed,
+                metadata: HashMap::new(),
+            });
+            transaction_ids.push(transaction_id);
+        }
+        
+        tracing::info!("Distributed {} {} from {} to {} recipients", total, token_symbol, payer, transaction_ids.len());
+        
+        Ok(transaction_ids)
+    }
+
+    /// Undo a `distribute_payment`: take each payout back from its recipient and return the
+    /// total to the payer. Either every payout is returned or, if a recipient no longer
+    /// holds what it was paid, none are.
+    pub async fn refund_payment(&self, payer: Uuid, token_symbol: String, payouts: &[(Uuid, u64)]) -> Result<()> {
+        let mut manager = self.token_manager.write().await;
+        
+        let mut owed: HashMap<Uuid, u64> = HashMap::new();
+        for (recipient, amount) in payouts {
+            *owed.entry(*recipient).or_insert(0) += amount;
+        }
+        for (recipient, amount) in &owed {
+            let balance = *manager.balances.get(&(*recipient, token_symbol.clone())).unwrap_or(&0);
+            if balance < *amount {
+                return Err(anyhow::anyhow!("Cannot refund {} {} from {}: {} available",
+                                           amount, token_symbol, recipient, balance));
+            }
+        }
+        
+        for (recipient, amount) in owed.into_iter().filter(|(_, amount)| *amount > 0) {
+            *manager.balances.entry((recipient, token_symbol.clone())).or_insert(0) -= amount;
+            *manager.balances.entry((payer, token_symbol.clone())).or_insert(0) += amount;
+            
+            let transaction_id = Uuid::new_v4();
+            manager.transaction_history.push(TokenTransaction {
+                transaction_id,
+                transaction_type: TransactionType::Transfer,
+                from_user: Some(recipient),
+                to_user: Some(payer),
+                token_symbol: token_symbol.clone(),
+                amount,
+                fee: 0,
+                timestamp: Utc::now(),
+                block_hash: None,
+                transaction_hash: format!("refund_{}_{}", token_symbol, transaction_id),
+                status: TransactionStatus::Confirmed,
+                metadata: HashMap::new(),
+            });
+        }
+        
+        tracing::info!("Refunded a payment from {} in {}", payer, token_symbol);
         
         Ok(())
     }
@@ -1264,6 +1436,131 @@ impl TokenEconomySystem {
         Ok(liquidity_tokens)
     }
 
+    /// Burn a provider's LP tokens and return their proportional share of both reserves
+    pub async fn remove_liquidity(&self, user_id: Uuid, pool_id: String, liquidity_amount: u64) -> Result<LiquidityWithdrawal> {
+        let mut defi = self.defi_protocols.write().await;
+        let mut manager = self.token_manager.write().await;
+        
+        let pool = defi.liquidity_pools.get_mut(&pool_id)
+            .ok_or_else(|| anyhow::anyhow!("Liquidity pool not found: {}", pool_id))?;
+        
+        let position = pool.liquidity_providers.get_mut(&user_id)
+            .ok_or_else(|| anyhow::anyhow!("No liquidity position in pool {}", pool_id))?;
+        if liquidity_amount == 0 || position.liquidity_tokens < liquidity_amount {
+            return Err(anyhow::anyhow!("Insufficient LP tokens: {} held, {} requested",
+                                       position.liquidity_tokens, liquidity_amount));
+        }
+        
+        // Share of each reserve in proportion to the LP tokens burned
+        let amount_a = (pool.reserve_a as u128 * liquidity_amount as u128 / pool.total_liquidity as u128) as u64;
+        let amount_b = (pool.reserve_b as u128 * liquidity_amount as u128 / pool.total_liquidity as u128) as u64;
+        
+        // Burn the LP tokens
+        position.liquidity_tokens -= liquidity_amount;
+        if position.liquidity_tokens == 0 {
+            pool.liquidity_providers.remove(&user_id);
+        }
+        pool.total_liquidity -= liquidity_amount;
+        
+        // Update pool reserves
+        pool.reserve_a -= amount_a;
+        pool.reserve_b -= amount_b;
+        
+        // Update user balances
+        *manager.balances.entry((user_id, pool.token_a.clone())).or_insert(0) += amount_a;
+        *manager.balances.entry((user_id, pool.token_b.clone())).or_insert(0) += amount_b;
+        
+        tracing::info!("Removed liquidity from pool {}: {} LP tokens = {} {} + {} {}", 
+                      pool_id, liquidity_amount, amount_a, pool.token_a, amount_b, pool.token_b);
+        
+        Ok(LiquidityWithdrawal {
+            pool_id,
+            liquidity_burned: liquidity_amount,
+            token_a: pool.token_a.clone(),
+            amount_a,
+            token_b: pool.token_b.clone(),
+            amount_b,
+        })
+    }
+
+    /// Lock tokens for `duration_days`; returns the stake id used to unstake them
+    pub async fn stake(&self, user_id: Uuid, token_symbol: String, amount: u64, duration_days: u32) -> Result<Uuid> {
+        let mut manager = self.token_manager.write().await;
+        
+        let duration_hours = duration_days as u64 * 24;
+        if duration_hours < self.config.staking_min_duration_hours {
+            return Err(anyhow::anyhow!("Stake must last at least {} hours", self.config.staking_min_duration_hours));
+        }
+        
+        let balance_key = (user_id, token_symbol.clone());
+        let balance = *manager.balances.get(&balance_key).unwrap_or(&0);
+        if amount == 0 || balance < amount {
+            return Err(anyhow::anyhow!("Insufficient balance to stake {} {}", amount, token_symbol));
+        }
+        manager.balances.insert(balance_key.clone(), balance - amount);
+        
+        let lock_id = Uuid::new_v4();
+        let locked_at = Utc::now();
+        manager.token_locks.entry(balance_key).or_default().push(TokenLock {
+            lock_id,
+            amount,
+            locked_at,
+            unlock_at: locked_at + chrono::Duration::hours(duration_hours as i64),
+            lock_type: LockType::Staking,
+            is_revocable: false,
+            metadata: HashMap::new(),
+        });
+        
+        tracing::info!("Staked {} {} for {} days: {}", amount, token_symbol, duration_days, lock_id);
+        
+        Ok(lock_id)
+    }
+
+    /// Release a stake whose lock period has ended, paying back principal plus rewards.
+    /// Returns the total amount credited.
+    pub async fn unstake(&self, stake_id: Uuid) -> Result<u64> {
+        let mut manager = self.token_manager.write().await;
+        let now = Utc::now();
+        
+        let (balance_key, index) = manager.token_locks.iter()
+            .find_map(|(key, locks)| {
+                locks.iter()
+                    .position(|lock| lock.lock_id == stake_id && matches!(lock.lock_type, LockType::Staking))
+                    .map(|index| (key.clone(), index))
+            })
+            .ok_or_else(|| anyhow::anyhow!("Stake not found: {}", stake_id))?;
+        
+        let lock = &manager.token_locks[&balance_key][index];
+        if now < lock.unlock_at {
+            return Err(anyhow::anyhow!("Stake {} is locked until {}", stake_id, lock.unlock_at));
+        }
+        
+        let reward = self.staking_reward(lock.amount, (now - lock.locked_at).num_hours().max(0) as u64);
+        let payout = lock.amount + reward;
+        
+        let locks = manager.token_locks.get_mut(&balance_key).unwrap();
+        locks.remove(index);
+        if locks.is_empty() {
+            manager.token_locks.remove(&balance_key);
+        }
+        
+        *manager.balances.entry(balance_key.clone()).or_insert(0) += payout;
+        if reward > 0 {
+            *manager.total_supplies.entry(balance_key.1.clone()).or_insert(0) += reward;
+        }
+        
+        tracing::info!("Unstaked {}: {} {} returned with {} reward", stake_id, payout - reward, balance_key.1, reward);
+        
+        Ok(payout)
+    }
+
+    /// Reward for `amount` staked `staked_hours`; only full minimum-duration periods count
+    fn staking_reward(&self, amount: u64, staked_hours: u64) -> u64 {
+        let period_hours = self.config.staking_min_duration_hours.max(1);
+        let rewarded_hours = staked_hours / period_hours * period_hours;
+        (amount as f64 * self.config.staking_reward_rate / 100.0 * rewarded_hours as f64 / (365.0 * 24.0)) as u64
+    }
+
     /// Swap tokens in AMM pool
     pub async fn swap_tokens(&self, user_id: Uuid, pool_id: String, token_in: String, amount_in: u64, min_amount_out: u64) -> Result<u64> {
         let mut defi = self.defi_protocols.write().await;
@@ -1334,6 +1631,15 @@ impl TokenEconomySystem {
         *manager.balances.get(&balance_key).unwrap_or(&0)
     }
 
+    /// Get every non-zero balance a user holds, keyed by token symbol
+    pub async fn get_balances(&self, user_id: Uuid) -> HashMap<String, u64> {
+        let manager = self.token_manager.read().await;
+        manager.balances.iter()
+            .filter(|((owner, _), balance)| *owner == user_id && **balance > 0)
+            .map(|((_, symbol), balance)| (symbol.clone(), *balance))
+            .collect()
+    }
+
     /// Get system metrics
     pub async fn get_system_metrics(&self) -> TokenEconomyMetrics {
         self.metrics.read().await.clone()
@@ -1539,7 +1845,7 @@ impl Default for TokenEconomyConfig {
             total_supply_cap: 1_000_000_000, // 1 billion tokens
             inflation_rate: 3.0, // 3% annually
             deflation_mechanisms: vec![
-                DeflationMechanism::TransactionBurning { percentage: 0.1 },
+                DeflationMechanism::TransactionBurning { percentage: 50.0 }, // half of every fee
                 DeflationMechanism::NFTMintingBurning { percentage: 1.0 },
             ],
             supported_chains: vec![
@@ -1559,6 +1865,10 @@ impl Default for TokenEconomyConfig {
             defi_enabled: true,
             governance_enabled: true,
             vesting_enabled: true,
+            staking_min_duration_hours: default_staking_min_duration_hours(),
+            staking_reward_rate: default_staking_reward_rate(),
+            transaction_fee: default_transaction_fee(),
+            transaction_fee_percentage: default_transaction_fee_percentage(),
         }
     }
 }
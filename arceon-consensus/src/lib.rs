@@ -1,12 +1,17 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, BTreeMap, VecDeque};
-use std::time::{SystemTime, Duration, UNIX_EPOCH};
+use std::collections::{HashMap, HashSet, BTreeMap, VecDeque};
+use std::time::{SystemTime, Duration};
 use tokio::sync::{RwLock, mpsc};
-use tracing::{info, warn, error, debug};
+use tracing::{info, warn, debug};
 use uuid::Uuid;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use sha2::{Sha256, Digest};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+pub use arceon_crypto::merkle;
+pub mod simulation;
 
 /// Robust P2P consensus mechanism for Arceon's decentralized network
 /// Implements a hybrid consensus combining Proof of Stake with Byzantine Fault Tolerance
@@ -18,14 +23,21 @@ pub struct ConsensusManager {
     pub pending_proposals: Arc<RwLock<HashMap<Uuid, WorldStateProposal>>>,
     pub validators: Arc<RwLock<HashMap<Uuid, ValidatorInfo>>>,
     pub consensus_config: ConsensusConfig,
-    pub message_sender: Option<mpsc::UnboundedSender<ConsensusMessage>>,
+    pub message_sender: Option<mpsc::Sender<ConsensusMessage>>, // bounded; see `send_message`
     pub block_producer: Arc<RwLock<BlockProducer>>,
     pub finality_tracker: Arc<RwLock<FinalityTracker>>,
+    pub world_view: Arc<RwLock<WorldView>>,
+    signing_key: SigningKey,
+    timeout_watcher: Option<tokio::task::JoinHandle<()>>,
+    processed_evidence: HashSet<[u8; 32]>, // digests of slashing proofs already acted on
+    leaving_validators: HashMap<Uuid, u64>, // validator -> epoch it asked to leave in
+    completed_exits: VecDeque<ValidatorExit>, // removed validators, not yet taken for settling
+    saturated_sends: Arc<AtomicU64>, // messages dropped because the outbound queue stayed full
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusConfig {
-    pub consensus_threshold: f64, // Percentage of stake needed for consensus (e.g., 0.67 for 67%)
+    pub consensus_threshold: f64, // Share of stake needed for consensus, at least MIN_CONSENSUS_THRESHOLD (e.g. 0.67)
     pub block_time: Duration,     // Target time between blocks
     pub max_validators: usize,    // Maximum number of validators
     pub min_stake: u64,          // Minimum stake to become validator
@@ -34,9 +46,72 @@ pub struct ConsensusConfig {
     pub timeout_propose: Duration,
     pub timeout_prevote: Duration,
     pub timeout_precommit: Duration,
+    #[serde(default = "default_slashing_penalty")]
+    pub slashing_penalty: f64,   // Fraction of stake burned per proven offence
+    #[serde(default = "default_max_slashings")]
+    pub max_slashings: u32,      // Validators slashed more often than this are deactivated
+    #[serde(default = "default_message_queue_capacity")]
+    pub message_queue_capacity: usize, // Outbound messages queued before senders wait
+    #[serde(default = "default_send_timeout")]
+    pub send_timeout: Duration,  // How long a message waits for room in a full queue before it is dropped
 }
 
-#[derive(Debug, Clone)]
+/// Lowest safe `consensus_threshold`. Any two sets of validators that each hold the
+/// threshold share of stake overlap in at least `2 × threshold − 1` of it. BFT safety
+/// assumes up to a third of stake is Byzantine, so only at 2/3 and above must every
+/// overlap contain an honest validator, who never votes for two conflicting blocks.
+/// Below it, two conflicting blocks can both finalize and the chain forks.
+pub const MIN_CONSENSUS_THRESHOLD: f64 = 2.0 / 3.0;
+
+impl ConsensusConfig {
+    /// Reject a `consensus_threshold` outside `MIN_CONSENSUS_THRESHOLD..=1.0`
+    pub fn validate(&self) -> Result<()> {
+        if !(MIN_CONSENSUS_THRESHOLD..=1.0).contains(&self.consensus_threshold) {
+            return Err(anyhow::anyhow!(
+                "consensus_threshold {} is unsafe: it must be between 2/3 and 1 so conflicting blocks cannot both finalize",
+                self.consensus_threshold
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_slashing_penalty() -> f64 {
+    0.1
+}
+
+fn default_max_slashings() -> u32 {
+    2
+}
+
+fn default_message_queue_capacity() -> usize {
+    1024
+}
+
+fn default_send_timeout() -> Duration {
+    Duration::from_millis(500)
+}
+
+/// Queue `message` for peers. A full queue is waited on for up to `timeout`; a message
+/// that still finds no room is dropped and counted in `saturated_sends`, so a stalled
+/// consumer slows consensus down instead of growing memory without limit.
+async fn queue_message(sender: &Option<mpsc::Sender<ConsensusMessage>>, timeout: Duration, saturated_sends: &AtomicU64, message: ConsensusMessage) {
+    let Some(sender) = sender else {
+        return;
+    };
+    if let Err(mpsc::error::SendTimeoutError::Timeout(_)) = sender.send_timeout(message, timeout).await {
+        saturated_sends.fetch_add(1, Ordering::Relaxed);
+        warn!("Outbound consensus queue stayed full for {:?}; message dropped", timeout);
+    }
+}
+
+/// Reputation a validator earns for each finalized block it signed
+pub const REPUTATION_GAIN: f64 = 1.0;
+
+/// Reputation an active validator loses for each finalized block it did not sign
+pub const REPUTATION_DECAY: f64 = 2.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusState {
     pub current_epoch: u64,
     pub current_round: u32,
@@ -44,11 +119,12 @@ pub struct ConsensusState {
     pub last_finalized_block: Option<BlockHash>,
     pub active_proposal: Option<Uuid>,
     pub votes: HashMap<Uuid, Vote>,
+    pub vote_history: HashMap<Uuid, Vec<Vote>>, // every vote seen this round, per validator
     pub view_change_votes: HashMap<Uuid, ViewChangeVote>,
     pub total_stake: u64,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ConsensusStep {
     Propose,
     Prevote,
@@ -152,7 +228,10 @@ pub struct ValidatorInfo {
     pub last_activity: SystemTime,
     pub reputation_score: f64,
     pub blocks_produced: u64,
+    #[serde(default)]
+    pub blocks_signed: u64, // confirmed blocks carrying this validator's signature
     pub slashing_count: u32,
+    pub public_key: [u8; 32], // ed25519 key votes from this validator must be signed with
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -163,12 +242,17 @@ pub enum ConsensusMessage {
     ValidatorJoin {
         node_id: Uuid,
         stake_amount: u64,
+        public_key: [u8; 32],
         timestamp: SystemTime,
     },
     ValidatorLeave {
         node_id: Uuid,
         timestamp: SystemTime,
     },
+    ValidatorRotation {
+        epoch: u64,
+        active_validators: Vec<Uuid>,
+    },
     SlashingEvidence {
         accused_node: Uuid,
         evidence_type: SlashingType,
@@ -209,6 +293,16 @@ pub struct FinalizedBlock {
     pub previous_hash: Option<BlockHash>,
 }
 
+/// A validator that has finished leaving: its stake is out of `total_stake` and is due
+/// back to it, along with any rewards it earned up to `final_epoch`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorExit {
+    pub node_id: Uuid,
+    pub stake_amount: u64,
+    pub blocks_produced: u64,
+    pub final_epoch: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct BlockProducer {
     pub current_proposal: Option<WorldStateProposal>,
@@ -217,16 +311,102 @@ pub struct BlockProducer {
     pub is_proposer: bool,
 }
 
+/// Tracks blocks from the moment validators finalize them until they are safe to apply.
+///
+/// A finalized block stays unconfirmed, and can still be discarded by `reorg_to`, until
+/// `finality_depth` further blocks have been built on it. Confirmed blocks move into
+/// `finalized_blocks` and queue up for the game to apply.
 #[derive(Debug, Clone)]
 pub struct FinalityTracker {
-    pub finalized_blocks: BTreeMap<u64, FinalizedBlock>,
+    pub finalized_blocks: BTreeMap<u64, FinalizedBlock>, // confirmed chain
     pub pending_blocks: HashMap<BlockHash, FinalizedBlock>,
+    pub unconfirmed_blocks: BTreeMap<u64, FinalizedBlock>, // finalized, waiting for successors
+    pub confirmed_queue: VecDeque<FinalizedBlock>, // confirmed, not yet taken for applying
     pub finality_votes: HashMap<BlockHash, HashMap<Uuid, bool>>,
-    pub last_finalized_epoch: u64,
+    pub last_finalized_epoch: u64, // newest confirmed epoch
+}
+
+/// Player actions consensus accepts
+pub const PLAYER_ACTION_TYPES: &[&str] = &["move", "pickup", "drop", "craft", "emote", "say", "attack", "trade", "use_skill", "build"];
+
+/// NPC actions consensus accepts
+pub const NPC_ACTION_TYPES: &[&str] = &["move", "pickup", "drop", "craft", "emote", "say", "attack", "trade", "spawn"];
+
+/// Area updates consensus accepts
+pub const AREA_UPDATE_TYPES: &[&str] = &["general", "weather", "simulation_tick", "resource_respawn", "structure"];
+
+/// The game state world changes are checked against: which areas exist and where each
+/// player and NPC is. The game keeps it current through `update_world_view`.
+#[derive(Debug, Clone, Default)]
+pub struct WorldView {
+    pub areas: HashSet<String>,
+    pub player_areas: HashMap<Uuid, String>, // player -> area they are in
+    pub npc_areas: HashMap<Uuid, String>,    // NPC -> area it is in
+}
+
+impl FinalityTracker {
+    /// Record a newly finalized block and confirm every unconfirmed block that now has
+    /// `finality_depth` successors; returns the epochs confirmed.
+    ///
+    /// The block must extend the tip: the next epoch, linked to the tip's hash. Receiving
+    /// the unconfirmed block already held for an epoch again is a no-op; a different
+    /// block for that epoch is a conflict the caller resolves with `reorg_to` first.
+    pub fn add_block(&mut self, block: FinalizedBlock, finality_depth: u32) -> Result<Vec<u64>> {
+        if !self.finalized_blocks.is_empty() && block.epoch <= self.last_finalized_epoch {
+            return Err(anyhow::anyhow!("Block for epoch {} is behind confirmed epoch {}", block.epoch, self.last_finalized_epoch));
+        }
+        if let Some(held) = self.unconfirmed_blocks.get(&block.epoch) {
+            if held.block_hash == block.block_hash {
+                return Ok(Vec::new());
+            }
+            return Err(anyhow::anyhow!("Block for epoch {} conflicts with the unconfirmed block already held for it", block.epoch));
+        }
+        if let Some(parent) = self.tip() {
+            if block.epoch != parent.epoch + 1 {
+                return Err(anyhow::anyhow!("Block for epoch {} leaves a gap after tip epoch {}", block.epoch, parent.epoch));
+            }
+            if block.previous_hash != Some(parent.block_hash) {
+                return Err(anyhow::anyhow!("Block for epoch {} does not link to the tip block", block.epoch));
+            }
+        }
+        let tip = block.epoch;
+        self.unconfirmed_blocks.insert(block.epoch, block);
+
+        let mut confirmed = Vec::new();
+        while let Some(entry) = self.unconfirmed_blocks.first_entry() {
+            if entry.key() + finality_depth as u64 > tip {
+                break;
+            }
+            let block = entry.remove();
+            confirmed.push(block.epoch);
+            self.last_finalized_epoch = block.epoch;
+            self.finalized_blocks.insert(block.epoch, block.clone());
+            self.confirmed_queue.push_back(block);
+        }
+        Ok(confirmed)
+    }
+
+    /// Discard unconfirmed blocks after `epoch`; confirmed blocks cannot be rolled back.
+    /// Returns the discarded blocks, oldest first.
+    pub fn reorg_to(&mut self, epoch: u64) -> Result<Vec<FinalizedBlock>> {
+        if !self.finalized_blocks.is_empty() && epoch < self.last_finalized_epoch {
+            return Err(anyhow::anyhow!("Cannot reorg to epoch {}: epoch {} is already confirmed", epoch, self.last_finalized_epoch));
+        }
+        let discarded = self.unconfirmed_blocks.split_off(&(epoch + 1));
+        Ok(discarded.into_values().collect())
+    }
+
+    /// Newest block, confirmed or not
+    pub fn tip(&self) -> Option<&FinalizedBlock> {
+        self.unconfirmed_blocks.values().last().or_else(|| self.finalized_blocks.values().last())
+    }
 }
 
 impl ConsensusManager {
-    pub fn new(node_id: Uuid, is_masternode: bool, stake_amount: u64, config: ConsensusConfig) -> Self {
+    /// Fails if `config` is unsafe; see `ConsensusConfig::validate`
+    pub fn new(node_id: Uuid, is_masternode: bool, stake_amount: u64, config: ConsensusConfig) -> Result<Self> {
+        config.validate()?;
+
         let consensus_state = ConsensusState {
             current_epoch: 0,
             current_round: 0,
@@ -234,6 +414,7 @@ impl ConsensusManager {
             last_finalized_block: None,
             active_proposal: None,
             votes: HashMap::new(),
+            vote_history: HashMap::new(),
             view_change_votes: HashMap::new(),
             total_stake: 0,
         };
@@ -248,11 +429,13 @@ impl ConsensusManager {
         let finality_tracker = FinalityTracker {
             finalized_blocks: BTreeMap::new(),
             pending_blocks: HashMap::new(),
+            unconfirmed_blocks: BTreeMap::new(),
+            confirmed_queue: VecDeque::new(),
             finality_votes: HashMap::new(),
             last_finalized_epoch: 0,
         };
 
-        Self {
+        Ok(Self {
             node_id,
             is_masternode,
             stake_amount,
@@ -263,11 +446,41 @@ impl ConsensusManager {
             message_sender: None,
             block_producer: Arc::new(RwLock::new(block_producer)),
             finality_tracker: Arc::new(RwLock::new(finality_tracker)),
-        }
+            world_view: Arc::new(RwLock::new(WorldView::default())),
+            signing_key: Self::generate_signing_key(),
+            timeout_watcher: None,
+            processed_evidence: HashSet::new(),
+            leaving_validators: HashMap::new(),
+            completed_exits: VecDeque::new(),
+            saturated_sends: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    fn generate_signing_key() -> SigningKey {
+        use rand::RngCore;
+
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        SigningKey::from_bytes(&secret)
+    }
+
+    /// Queue `message` for peers; see `queue_message`
+    async fn send_message(&self, message: ConsensusMessage) {
+        queue_message(&self.message_sender, self.consensus_config.send_timeout, &self.saturated_sends, message).await;
+    }
+
+    /// Outbound messages dropped because the queue stayed full past `send_timeout`
+    pub fn saturated_sends(&self) -> u64 {
+        self.saturated_sends.load(Ordering::Relaxed)
+    }
+
+    /// Public half of this node's vote signing key
+    pub fn public_key(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
     }
 
     /// Initialize the consensus system
-    pub async fn initialize(&mut self, message_sender: mpsc::UnboundedSender<ConsensusMessage>) -> Result<()> {
+    pub async fn initialize(&mut self, message_sender: mpsc::Sender<ConsensusMessage>) -> Result<()> {
         info!("🔧 Initializing consensus system for node: {}", self.node_id);
         
         self.message_sender = Some(message_sender);
@@ -276,6 +489,9 @@ impl ConsensusManager {
         if self.stake_amount >= self.consensus_config.min_stake {
             self.join_validator_set().await?;
         }
+
+        self.begin_round().await;
+        self.start_timeout_watcher();
         
         info!("✅ Consensus system initialized successfully");
         Ok(())
@@ -293,11 +509,14 @@ impl ConsensusManager {
             last_activity: SystemTime::now(),
             reputation_score: 100.0, // Start with perfect reputation
             blocks_produced: 0,
+            blocks_signed: 0,
             slashing_count: 0,
+            public_key: self.public_key(),
         };
 
         let mut validators = self.validators.write().await;
         validators.insert(self.node_id, validator_info.clone());
+        Self::recalculate_voting_powers(&mut validators);
         
         // Update total stake
         let mut state = self.consensus_state.write().await;
@@ -306,18 +525,58 @@ impl ConsensusManager {
         drop(validators);
 
         // Broadcast join message
-        if let Some(sender) = &self.message_sender {
-            let _ = sender.send(ConsensusMessage::ValidatorJoin {
-                node_id: self.node_id,
-                stake_amount: self.stake_amount,
-                timestamp: SystemTime::now(),
-            });
-        }
+        self.send_message(ConsensusMessage::ValidatorJoin {
+            node_id: self.node_id,
+            stake_amount: self.stake_amount,
+            public_key: self.public_key(),
+            timestamp: SystemTime::now(),
+        }).await;
 
         info!("✅ Successfully joined validator set");
         Ok(())
     }
 
+    /// Leave the validator set gracefully; see `handle_validator_leave`
+    pub async fn leave_validator_set(&mut self) -> Result<()> {
+        info!("📝 Leaving validator set");
+        let timestamp = SystemTime::now();
+        self.handle_validator_leave(self.node_id, timestamp).await?;
+
+        self.send_message(ConsensusMessage::ValidatorLeave { node_id: self.node_id, timestamp }).await;
+        Ok(())
+    }
+
+    /// Take the validators that finished leaving since the last call, for the ledger to
+    /// return their stake and settle their rewards
+    pub fn take_completed_exits(&mut self) -> Vec<ValidatorExit> {
+        self.completed_exits.drain(..).collect()
+    }
+
+    /// Reset every validator's voting power to its share of reputation-weighted stake;
+    /// returns the total stake. With no stake at all every power is zero rather than NaN.
+    fn recalculate_voting_powers(validators: &mut HashMap<Uuid, ValidatorInfo>) -> u64 {
+        let total_stake: u64 = validators.values().map(|v| v.stake_amount).sum();
+        let total_weight: f64 = validators.values().map(Self::weighted_stake).sum();
+        for validator in validators.values_mut() {
+            validator.voting_power = if total_weight > 0.0 {
+                Self::weighted_stake(validator) / total_weight
+            } else {
+                0.0
+            };
+        }
+        total_stake
+    }
+
+    /// Scales stake by reputation: a validator at full reputation counts its whole stake,
+    /// one that has lost all of it counts half
+    fn reputation_multiplier(reputation_score: f64) -> f64 {
+        (reputation_score / 100.0).clamp(0.5, 1.5)
+    }
+
+    fn weighted_stake(validator: &ValidatorInfo) -> f64 {
+        validator.stake_amount as f64 * Self::reputation_multiplier(validator.reputation_score)
+    }
+
     /// Calculate voting power based on stake and reputation
     async fn calculate_voting_power(&self) -> f64 {
         let validators = self.validators.read().await;
@@ -331,8 +590,7 @@ impl ConsensusManager {
         
         // Apply reputation multiplier if we're already a validator
         if let Some(validator) = validators.get(&self.node_id) {
-            let reputation_multiplier = (validator.reputation_score / 100.0).clamp(0.5, 1.5);
-            base_power * reputation_multiplier
+            base_power * Self::reputation_multiplier(validator.reputation_score)
         } else {
             base_power
         }
@@ -352,12 +610,15 @@ impl ConsensusManager {
             ConsensusMessage::ViewChange(view_change) => {
                 self.handle_view_change(view_change).await?;
             }
-            ConsensusMessage::ValidatorJoin { node_id, stake_amount, timestamp } => {
-                self.handle_validator_join(node_id, stake_amount, timestamp).await?;
+            ConsensusMessage::ValidatorJoin { node_id, stake_amount, public_key, timestamp } => {
+                self.handle_validator_join(node_id, stake_amount, public_key, timestamp).await?;
             }
             ConsensusMessage::ValidatorLeave { node_id, timestamp } => {
                 self.handle_validator_leave(node_id, timestamp).await?;
             }
+            ConsensusMessage::ValidatorRotation { epoch, active_validators } => {
+                self.handle_validator_rotation(epoch, active_validators).await?;
+            }
             ConsensusMessage::SlashingEvidence { accused_node, evidence_type, proof, timestamp } => {
                 self.handle_slashing_evidence(accused_node, evidence_type, proof, timestamp).await?;
             }
@@ -404,9 +665,13 @@ impl ConsensusManager {
     async fn validate_proposal(&self, proposal: &WorldStateProposal) -> Result<bool> {
         // Check if proposer is valid validator
         let validators = self.validators.read().await;
-        if !validators.contains_key(&proposal.proposer) {
+        let Some(proposer) = validators.get(&proposal.proposer) else {
+            return Ok(false);
+        };
+        if !Self::verify_proposal_signature(proposal, &proposer.public_key) {
             return Ok(false);
         }
+        drop(validators);
 
         // Check epoch and round
         let state = self.consensus_state.read().await;
@@ -422,7 +687,7 @@ impl ConsensusManager {
         }
 
         // Verify merkle root
-        let calculated_root = self.calculate_merkle_root(&proposal.world_changes)?;
+        let calculated_root = Self::calculate_merkle_root(&proposal.world_changes)?;
         if calculated_root != proposal.merkle_root {
             return Ok(false);
         }
@@ -430,31 +695,50 @@ impl ConsensusManager {
         Ok(true)
     }
 
+    /// Replace the game state world changes are validated against
+    pub async fn update_world_view(&self, world_view: WorldView) {
+        *self.world_view.write().await = world_view;
+    }
+
     /// Validate individual world change
     async fn validate_world_change(&self, change: &WorldChange) -> Result<bool> {
         match change {
-            WorldChange::PlayerAction { player_id, action_type, area_id, timestamp, data: _ } => {
-                // Validate player exists and action is valid
+            WorldChange::PlayerAction { player_id, action_type, area_id, .. } => {
+                // The player must exist, be in the stated area and do something allowed
                 debug!("Validating player action: {} in {} by {}", action_type, area_id, player_id);
-                // TODO: Check against game rules
-                Ok(true)
+                let world_view = self.world_view.read().await;
+                let Some(player_area) = world_view.player_areas.get(player_id) else {
+                    debug!("Rejecting action by unknown player {}", player_id);
+                    return Ok(false);
+                };
+                if player_area != area_id {
+                    debug!("Rejecting action by {} in {}: they are in {}", player_id, area_id, player_area);
+                    return Ok(false);
+                }
+                Ok(PLAYER_ACTION_TYPES.contains(&action_type.as_str()))
             }
-            WorldChange::NPCAction { npc_id, action_type, area_id, timestamp, data: _ } => {
-                // Validate NPC exists and action is valid
+            WorldChange::NPCAction { npc_id, action_type, area_id, .. } => {
+                // Same rules as players, against where the NPC is
                 debug!("Validating NPC action: {} in {} by {}", action_type, area_id, npc_id);
-                Ok(true)
+                let world_view = self.world_view.read().await;
+                if world_view.npc_areas.get(npc_id) != Some(area_id) {
+                    debug!("Rejecting action by NPC {}: not in {}", npc_id, area_id);
+                    return Ok(false);
+                }
+                Ok(NPC_ACTION_TYPES.contains(&action_type.as_str()))
             }
-            WorldChange::AreaUpdate { area_id, update_type, timestamp, data: _ } => {
-                // Validate area update
+            WorldChange::AreaUpdate { area_id, update_type, .. } => {
+                // The area must exist and the update be of a known kind
                 debug!("Validating area update: {} in {}", update_type, area_id);
-                Ok(true)
+                let world_view = self.world_view.read().await;
+                Ok(world_view.areas.contains(area_id) && AREA_UPDATE_TYPES.contains(&update_type.as_str()))
             }
-            WorldChange::SkillEvolution { skill_name, evolution_type, consensus_votes, .. } => {
+            WorldChange::SkillEvolution { consensus_votes, .. } => {
                 // Validate skill evolution has sufficient consensus
                 let required_votes = (self.get_active_validator_count().await as f64 * 0.6) as u32;
                 Ok(*consensus_votes >= required_votes)
             }
-            WorldChange::WorldEvent { event_id, event_type, affected_areas, .. } => {
+            WorldChange::WorldEvent { event_type, affected_areas, .. } => {
                 // Validate world event
                 debug!("Validating world event: {} affecting {} areas", event_type, affected_areas.len());
                 Ok(true)
@@ -463,18 +747,8 @@ impl ConsensusManager {
     }
 
     /// Calculate merkle root for world changes
-    fn calculate_merkle_root(&self, changes: &[WorldChange]) -> Result<String> {
-        if changes.is_empty() {
-            return Ok("0".repeat(64));
-        }
-
-        let mut hasher = Sha256::new();
-        for change in changes {
-            let serialized = serde_json::to_string(change)?;
-            hasher.update(serialized.as_bytes());
-        }
-        
-        Ok(format!("{:x}", hasher.finalize()))
+    fn calculate_merkle_root(changes: &[WorldChange]) -> Result<String> {
+        Ok(merkle::to_hex(&merkle::MerkleTree::build(changes)?.root()))
     }
 
     /// Cast a prevote
@@ -487,14 +761,12 @@ impl ConsensusManager {
             epoch: state.current_epoch,
             round: state.current_round,
             timestamp: SystemTime::now(),
-            signature: self.sign_vote(&proposal_id, &VoteType::Prevote(support))?,
+            signature: self.sign_vote(&proposal_id, &VoteType::Prevote(support), state.current_epoch, state.current_round)?,
         };
         drop(state);
 
         // Broadcast vote
-        if let Some(sender) = &self.message_sender {
-            let _ = sender.send(ConsensusMessage::Vote(vote));
-        }
+        self.send_message(ConsensusMessage::Vote(vote)).await;
 
         info!("🗳️ Cast prevote for proposal {}: {}", proposal_id, support);
         Ok(())
@@ -510,14 +782,12 @@ impl ConsensusManager {
             epoch: state.current_epoch,
             round: state.current_round,
             timestamp: SystemTime::now(),
-            signature: self.sign_vote(&proposal_id, &VoteType::Precommit(commit))?,
+            signature: self.sign_vote(&proposal_id, &VoteType::Precommit(commit), state.current_epoch, state.current_round)?,
         };
         drop(state);
 
         // Broadcast vote
-        if let Some(sender) = &self.message_sender {
-            let _ = sender.send(ConsensusMessage::Vote(vote));
-        }
+        self.send_message(ConsensusMessage::Vote(vote)).await;
 
         info!("✅ Cast precommit for proposal {}: {}", proposal_id, commit);
         Ok(())
@@ -533,8 +803,15 @@ impl ConsensusManager {
             return Ok(());
         }
 
-        // Store vote
+        // Store vote, catching validators who contradict an earlier vote this round
         let mut state = self.consensus_state.write().await;
+        let history = state.vote_history.entry(vote.voter_id).or_default();
+        if let Some(earlier) = history.iter().find(|earlier| Self::votes_conflict(earlier, &vote)).cloned() {
+            drop(state);
+            warn!("⚠️ Equivocation by {} in epoch {} round {}", vote.voter_id, vote.epoch, vote.round);
+            return self.report_equivocation(earlier, vote).await;
+        }
+        history.push(vote.clone());
         state.votes.insert(vote.voter_id, vote.clone());
         
         // Check if we have enough votes to progress
@@ -560,16 +837,32 @@ impl ConsensusManager {
         Ok(())
     }
 
+    /// Broadcast slashing evidence for two conflicting votes and apply it locally
+    async fn report_equivocation(&mut self, earlier: Vote, later: Vote) -> Result<()> {
+        let accused_node = later.voter_id;
+        let proof = serde_json::to_vec(&(earlier, later))?;
+        let timestamp = SystemTime::now();
+
+        self.send_message(ConsensusMessage::SlashingEvidence {
+            accused_node,
+            evidence_type: SlashingType::Equivocation,
+            proof: proof.clone(),
+            timestamp,
+        }).await;
+
+        self.handle_slashing_evidence(accused_node, SlashingType::Equivocation, proof, timestamp).await
+    }
+
     /// Validate a vote
     async fn validate_vote(&self, vote: &Vote) -> Result<bool> {
         // Check if voter is valid validator
         let validators = self.validators.read().await;
-        if !validators.contains_key(&vote.voter_id) {
+        let Some(validator) = validators.get(&vote.voter_id) else {
             return Ok(false);
-        }
+        };
 
-        // Verify signature
-        if !self.verify_vote_signature(vote)? {
+        // Verify signature against the voter's registered key
+        if !Self::verify_vote_signature(vote, &validator.public_key)? {
             return Ok(false);
         }
 
@@ -591,6 +884,10 @@ impl ConsensusManager {
         let total_voting_power = self.calculate_total_voting_power().await;
         let prevote_power = self.calculate_vote_power(&prevotes).await;
 
+        // No active stake means no quorum, rather than comparing against NaN
+        if total_voting_power <= 0.0 {
+            return Ok(false);
+        }
         Ok(prevote_power / total_voting_power >= self.consensus_config.consensus_threshold)
     }
 
@@ -603,6 +900,9 @@ impl ConsensusManager {
         let total_voting_power = self.calculate_total_voting_power().await;
         let precommit_power = self.calculate_vote_power(&precommits).await;
 
+        if total_voting_power <= 0.0 {
+            return Ok(false);
+        }
         Ok(precommit_power / total_voting_power >= self.consensus_config.consensus_threshold)
     }
 
@@ -664,20 +964,74 @@ impl ConsensusManager {
         state.current_step = ConsensusStep::Propose;
         state.active_proposal = None;
         state.votes.clear();
+        state.vote_history.clear();
+        let next_epoch = state.current_epoch;
         drop(state);
-
-        // Store finalized block
-        let mut finality_tracker = self.finality_tracker.write().await;
-        finality_tracker.finalized_blocks.insert(proposal.epoch, finalized_block.clone());
-        finality_tracker.last_finalized_epoch = proposal.epoch;
-        drop(finality_tracker);
+        self.complete_validator_exits(proposal.epoch).await;
+        self.maybe_rotate_validators(next_epoch).await;
+        self.begin_round().await;
 
         info!("✅ Block finalized for epoch {} with {} changes", 
             proposal.epoch, finalized_block.world_changes.len());
 
+        // Hold the block until enough successors confirm it
+        let mut finality_tracker = self.finality_tracker.write().await;
+        let confirmed = finality_tracker.add_block(finalized_block, self.consensus_config.finality_depth)?;
+        let confirmed_blocks: Vec<FinalizedBlock> = confirmed.iter()
+            .filter_map(|epoch| finality_tracker.finalized_blocks.get(epoch).cloned())
+            .collect();
+        drop(finality_tracker);
+        for epoch in confirmed {
+            debug!("🔒 Block for epoch {} confirmed at depth {}", epoch, self.consensus_config.finality_depth);
+        }
+        self.credit_block_signers(&confirmed_blocks).await;
+
         Ok(())
     }
 
+    /// Update reputations for each newly confirmed block. Signers are read from the
+    /// block's own signature set, which every node holding the block agrees on, not from
+    /// the precommits this node happened to receive.
+    async fn credit_block_signers(&self, blocks: &[FinalizedBlock]) {
+        let mut validators = self.validators.write().await;
+        for block in blocks {
+            let signers: HashSet<Uuid> = block.validator_signatures.keys().copied().collect();
+            Self::update_reputations(&mut validators, &signers);
+        }
+    }
+
+    /// Take the blocks confirmed since the last call, in epoch order, for applying to
+    /// the live world state
+    pub async fn take_confirmed_blocks(&self) -> Vec<FinalizedBlock> {
+        self.finality_tracker.write().await.confirmed_queue.drain(..).collect()
+    }
+
+    /// Roll back to `epoch`, discarding the unconfirmed blocks after it. Consensus resumes
+    /// from the epoch after the new tip.
+    pub async fn reorg_to(&self, epoch: u64) -> Result<Vec<FinalizedBlock>> {
+        let mut finality_tracker = self.finality_tracker.write().await;
+        let discarded = finality_tracker.reorg_to(epoch)?;
+        if discarded.is_empty() {
+            return Ok(discarded);
+        }
+        let tip = finality_tracker.tip().map(|block| (block.block_hash, block.epoch));
+        drop(finality_tracker);
+
+        let mut state = self.consensus_state.write().await;
+        state.last_finalized_block = tip.map(|(hash, _)| hash);
+        state.current_epoch = tip.map_or(0, |(_, epoch)| epoch + 1);
+        state.current_round = 0;
+        state.current_step = ConsensusStep::Propose;
+        state.active_proposal = None;
+        state.votes.clear();
+        state.vote_history.clear();
+        drop(state);
+        self.begin_round().await;
+
+        warn!("↩️ Reorganised to epoch {}, discarding {} unconfirmed blocks", epoch, discarded.len());
+        Ok(discarded)
+    }
+
     /// Calculate block hash
     fn calculate_block_hash(&self, proposal: &WorldStateProposal) -> Result<BlockHash> {
         let mut hasher = Sha256::new();
@@ -700,91 +1054,276 @@ impl ConsensusManager {
         // Check if we should propose a new block
         if producer.is_proposer && producer.pending_changes.len() >= 10 {
             drop(producer);
-            self.propose_new_block().await?;
+            self.propose_new_block(SystemTime::now()).await?;
         }
         
         Ok(())
     }
 
-    /// Propose a new block
-    async fn propose_new_block(&mut self) -> Result<()> {
-        info!("📝 Proposing new block");
-        
-        let mut producer = self.block_producer.write().await;
-        let changes: Vec<_> = producer.pending_changes.drain(..).collect();
-        drop(producer);
+    /// Propose a block if this node is the proposer and `block_time` has passed since the
+    /// last one, however few changes are queued; returns whether a proposal went out.
+    /// The timeout watcher calls this on every poll once the manager is initialized.
+    pub async fn proposer_tick(&self, now: SystemTime) -> Result<bool> {
+        self.timeout_context().proposer_tick(now).await
+    }
 
-        if changes.is_empty() {
-            return Ok(());
-        }
+    /// Propose a new block, timestamped `now`
+    async fn propose_new_block(&self, now: SystemTime) -> Result<()> {
+        self.timeout_context().propose_new_block(now).await
+    }
 
-        let state = self.consensus_state.read().await;
-        let merkle_root = self.calculate_merkle_root(&changes)?;
-        
-        let proposal = WorldStateProposal {
-            proposal_id: Uuid::new_v4(),
-            proposer: self.node_id,
-            epoch: state.current_epoch,
-            round: state.current_round,
-            timestamp: SystemTime::now(),
-            world_changes: changes,
-            previous_block_hash: state.last_finalized_block,
-            merkle_root,
-            signature: None, // TODO: Add cryptographic signature
+    /// Bytes a proposal signature covers
+    fn proposal_signing_payload(proposal: &WorldStateProposal) -> Vec<u8> {
+        let mut payload = b"proposal".to_vec();
+        payload.extend_from_slice(proposal.proposal_id.as_bytes());
+        payload.extend_from_slice(&proposal.epoch.to_be_bytes());
+        payload.extend_from_slice(&proposal.round.to_be_bytes());
+        payload.extend_from_slice(proposal.merkle_root.as_bytes());
+        payload
+    }
+
+    fn sign_proposal(signing_key: &SigningKey, proposal: &WorldStateProposal) -> String {
+        use base64::Engine;
+
+        let signature = signing_key.sign(&Self::proposal_signing_payload(proposal));
+        base64::engine::general_purpose::STANDARD.encode(signature.to_bytes())
+    }
+
+    fn verify_proposal_signature(proposal: &WorldStateProposal, public_key: &[u8; 32]) -> bool {
+        use base64::Engine;
+
+        let Ok(verifying_key) = VerifyingKey::from_bytes(public_key) else {
+            return false;
         };
-        drop(state);
+        let Some(signature) = proposal.signature.as_ref()
+            .and_then(|signature| base64::engine::general_purpose::STANDARD.decode(signature).ok())
+            .and_then(|bytes| Signature::from_slice(&bytes).ok()) else {
+            return false;
+        };
+        verifying_key.verify(&Self::proposal_signing_payload(proposal), &signature).is_ok()
+    }
 
-        // Broadcast proposal
-        if let Some(sender) = &self.message_sender {
-            let _ = sender.send(ConsensusMessage::Proposal(proposal));
-        }
+    /// Bytes a vote signature covers
+    fn vote_signing_payload(proposal_id: &Uuid, vote_type: &VoteType, epoch: u64, round: u32) -> Vec<u8> {
+        let (kind, value) = match vote_type {
+            VoteType::Prevote(support) => (0u8, *support),
+            VoteType::Precommit(commit) => (1u8, *commit),
+        };
 
-        Ok(())
+        let mut payload = Vec::with_capacity(16 + 2 + 8 + 4);
+        payload.extend_from_slice(proposal_id.as_bytes());
+        payload.extend_from_slice(&[kind, value as u8]);
+        payload.extend_from_slice(&epoch.to_be_bytes());
+        payload.extend_from_slice(&round.to_be_bytes());
+        payload
     }
 
-    /// Sign a vote (placeholder implementation)
-    fn sign_vote(&self, proposal_id: &Uuid, vote_type: &VoteType) -> Result<String> {
-        // TODO: Implement proper cryptographic signature
-        let mut hasher = Sha256::new();
-        hasher.update(self.node_id.to_string().as_bytes());
-        hasher.update(proposal_id.to_string().as_bytes());
-        hasher.update(format!("{:?}", vote_type).as_bytes());
-        Ok(format!("{:x}", hasher.finalize()))
+    /// Sign a vote with this node's ed25519 key; returns the base64 signature
+    fn sign_vote(&self, proposal_id: &Uuid, vote_type: &VoteType, epoch: u64, round: u32) -> Result<String> {
+        use base64::Engine;
+
+        let payload = Self::vote_signing_payload(proposal_id, vote_type, epoch, round);
+        let signature = self.signing_key.sign(&payload);
+        Ok(base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()))
+    }
+
+    /// Verify a vote signature against the voter's public key
+    fn verify_vote_signature(vote: &Vote, public_key: &[u8; 32]) -> Result<bool> {
+        use base64::Engine;
+
+        let Ok(verifying_key) = VerifyingKey::from_bytes(public_key) else {
+            return Ok(false);
+        };
+        let Ok(signature_bytes) = base64::engine::general_purpose::STANDARD.decode(&vote.signature) else {
+            return Ok(false);
+        };
+        let Ok(signature) = Signature::from_slice(&signature_bytes) else {
+            return Ok(false);
+        };
+
+        let payload = Self::vote_signing_payload(&vote.proposal_id, &vote.vote_type, vote.epoch, vote.round);
+        Ok(verifying_key.verify(&payload, &signature).is_ok())
+    }
+
+    fn view_change_signing_payload(epoch: u64, new_round: u32) -> Vec<u8> {
+        let mut payload = b"view-change".to_vec();
+        payload.extend_from_slice(&epoch.to_be_bytes());
+        payload.extend_from_slice(&new_round.to_be_bytes());
+        payload
+    }
+
+    fn sign_view_change(signing_key: &SigningKey, epoch: u64, new_round: u32) -> String {
+        use base64::Engine;
+
+        let signature = signing_key.sign(&Self::view_change_signing_payload(epoch, new_round));
+        base64::engine::general_purpose::STANDARD.encode(signature.to_bytes())
+    }
+
+    fn verify_view_change_signature(view_change: &ViewChangeVote, public_key: &[u8; 32]) -> bool {
+        use base64::Engine;
+
+        let Ok(verifying_key) = VerifyingKey::from_bytes(public_key) else {
+            return false;
+        };
+        let Some(signature) = base64::engine::general_purpose::STANDARD.decode(&view_change.signature).ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok()) else {
+            return false;
+        };
+
+        let payload = Self::view_change_signing_payload(view_change.epoch, view_change.new_round);
+        verifying_key.verify(&payload, &signature).is_ok()
+    }
+
+    /// Voting power of every active validator
+    async fn active_voting_powers(validators: &RwLock<HashMap<Uuid, ValidatorInfo>>) -> HashMap<Uuid, f64> {
+        validators.read().await.values()
+            .filter(|v| v.is_active)
+            .map(|v| (v.node_id, v.voting_power))
+            .collect()
     }
 
-    /// Verify vote signature (placeholder implementation)
-    fn verify_vote_signature(&self, vote: &Vote) -> Result<bool> {
-        // TODO: Implement proper cryptographic signature verification
-        let expected = self.sign_vote(&vote.proposal_id, &vote.vote_type)?;
-        Ok(vote.signature == expected)
+    /// Move to the next round if enough voting power has asked for it
+    fn try_advance_round(state: &mut ConsensusState, voting_powers: &HashMap<Uuid, f64>, threshold: f64) -> bool {
+        let target_round = state.current_round + 1;
+        let total_power: f64 = voting_powers.values().sum();
+        let agreeing_power: f64 = state.view_change_votes.values()
+            .filter(|v| v.epoch == state.current_epoch && v.new_round == target_round)
+            .filter_map(|v| voting_powers.get(&v.voter_id))
+            .sum();
+
+        if total_power <= 0.0 || agreeing_power / total_power < threshold {
+            return false;
+        }
+
+        state.current_round = target_round;
+        state.current_step = ConsensusStep::Propose;
+        state.votes.clear();
+        state.vote_history.clear();
+        state.view_change_votes.clear();
+        info!("🔄 View change triggered, new round: {}", state.current_round);
+        true
     }
 
     /// Handle view change
     async fn handle_view_change(&mut self, view_change: ViewChangeVote) -> Result<()> {
         info!("🔄 Processing view change from {} for round {}", 
             view_change.voter_id, view_change.new_round);
-        
+
+        let public_key = self.validators.read().await.get(&view_change.voter_id).map(|v| v.public_key);
+        if !public_key.is_some_and(|key| Self::verify_view_change_signature(&view_change, &key)) {
+            warn!("❌ Invalid view change rejected from {}", view_change.voter_id);
+            return Ok(());
+        }
+
+        let voting_powers = Self::active_voting_powers(&self.validators).await;
         let mut state = self.consensus_state.write().await;
         state.view_change_votes.insert(view_change.voter_id, view_change);
-        
-        // Check if we have enough view change votes
-        let view_change_count = state.view_change_votes.len();
-        let validator_count = self.get_active_validator_count().await;
-        
-        if view_change_count > validator_count * 2 / 3 {
-            // Trigger view change
-            state.current_round += 1;
-            state.current_step = ConsensusStep::Propose;
-            state.votes.clear();
-            state.view_change_votes.clear();
-            info!("🔄 View change triggered, new round: {}", state.current_round);
+        let advanced = Self::try_advance_round(&mut state, &voting_powers, self.consensus_config.consensus_threshold);
+        drop(state);
+
+        if advanced {
+            self.begin_round().await;
         }
         
         Ok(())
     }
 
+    /// Pick the proposer for `(epoch, round)`, weighted by voting power.
+    ///
+    /// The choice is seeded from the epoch and round only, so every node with the same
+    /// validator set makes the same pick. Returns nil when there are no active validators.
+    fn select_proposer(voting_powers: &HashMap<Uuid, f64>, epoch: u64, round: u32) -> Uuid {
+        let mut candidates: Vec<_> = voting_powers.iter()
+            .filter(|(_, power)| **power > 0.0)
+            .collect();
+        if candidates.is_empty() {
+            return Uuid::nil();
+        }
+        candidates.sort_by_key(|(node_id, _)| **node_id);
+        let total_power: f64 = candidates.iter().map(|(_, power)| **power).sum();
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"proposer");
+        hasher.update(epoch.to_be_bytes());
+        hasher.update(round.to_be_bytes());
+        let digest = hasher.finalize();
+        let seed = u64::from_be_bytes(digest[..8].try_into().unwrap());
+
+        let mut target = (seed as f64 / u64::MAX as f64) * total_power;
+        for (node_id, power) in &candidates {
+            if target < **power {
+                return **node_id;
+            }
+            target -= **power;
+        }
+        *candidates.last().unwrap().0
+    }
+
+    /// The validator scheduled to propose in `(epoch, round)`
+    pub async fn current_proposer(&self, epoch: u64, round: u32) -> Uuid {
+        let voting_powers = Self::active_voting_powers(&self.validators).await;
+        Self::select_proposer(&voting_powers, epoch, round)
+    }
+
+    /// Work out whether this node proposes in the round that is starting
+    async fn begin_round(&self) {
+        let (epoch, round) = {
+            let state = self.consensus_state.read().await;
+            (state.current_epoch, state.current_round)
+        };
+        let proposer = self.current_proposer(epoch, round).await;
+        self.block_producer.write().await.is_proposer = proposer == self.node_id;
+        debug!("🎲 Proposer for epoch {} round {}: {}", epoch, round, proposer);
+    }
+
+    /// Watch for rounds that stop making progress, and drive block production.
+    ///
+    /// If the current step has not changed within its configured timeout, this node
+    /// broadcasts a signed view change for the next round and counts its own vote. On
+    /// the same poll a proposer whose `block_time` has passed proposes its queued changes.
+    fn start_timeout_watcher(&mut self) {
+        if let Some(watcher) = self.timeout_watcher.take() {
+            watcher.abort();
+        }
+
+        let context = self.timeout_context();
+        let poll_interval = context.config.timeout_propose
+            .min(context.config.timeout_prevote)
+            .min(context.config.timeout_precommit)
+            .min(context.config.block_time)
+            .max(Duration::from_millis(40)) / 4;
+
+        self.timeout_watcher = Some(tokio::spawn(async move {
+            let origin = tokio::time::Instant::now();
+            let mut timer = StepTimer::default();
+
+            loop {
+                tokio::time::sleep(poll_interval).await;
+                context.tick(&mut timer, origin.elapsed()).await;
+                if let Err(e) = context.proposer_tick(SystemTime::now()).await {
+                    warn!("Failed to propose block: {}", e);
+                }
+            }
+        }));
+    }
+
+    /// Handles the watcher's timeout checks and proposals need, for the watcher task or a
+    /// caller with its own clock
+    fn timeout_context(&self) -> TimeoutContext {
+        TimeoutContext {
+            node_id: self.node_id,
+            config: self.consensus_config.clone(),
+            signing_key: self.signing_key.clone(),
+            consensus_state: self.consensus_state.clone(),
+            validators: self.validators.clone(),
+            block_producer: self.block_producer.clone(),
+            message_sender: self.message_sender.clone(),
+            saturated_sends: self.saturated_sends.clone(),
+        }
+    }
+
     /// Handle validator join
-    async fn handle_validator_join(&mut self, node_id: Uuid, stake_amount: u64, timestamp: SystemTime) -> Result<()> {
+    async fn handle_validator_join(&mut self, node_id: Uuid, stake_amount: u64, public_key: [u8; 32], timestamp: SystemTime) -> Result<()> {
         info!("➕ Processing validator join: {} with stake {}", node_id, stake_amount);
         
         if stake_amount < self.consensus_config.min_stake {
@@ -792,25 +1331,24 @@ impl ConsensusManager {
             return Ok(());
         }
 
+        // A full set takes new validators in at the next rotation
+        let active_count = self.validators.read().await.values().filter(|v| v.is_active && v.node_id != node_id).count();
         let validator_info = ValidatorInfo {
             node_id,
             stake_amount,
             voting_power: 0.0, // Will be calculated
-            is_active: true,
+            is_active: active_count < self.consensus_config.max_validators,
             last_activity: timestamp,
             reputation_score: 100.0,
             blocks_produced: 0,
+            blocks_signed: 0,
             slashing_count: 0,
+            public_key,
         };
 
         let mut validators = self.validators.write().await;
         validators.insert(node_id, validator_info);
-        
-        // Recalculate voting powers
-        let total_stake: u64 = validators.values().map(|v| v.stake_amount).sum();
-        for validator in validators.values_mut() {
-            validator.voting_power = (validator.stake_amount as f64) / (total_stake as f64);
-        }
+        let total_stake = Self::recalculate_voting_powers(&mut validators);
         
         let mut state = self.consensus_state.write().await;
         state.total_stake = total_stake;
@@ -821,90 +1359,319 @@ impl ConsensusManager {
         Ok(())
     }
 
-    /// Handle validator leave
-    async fn handle_validator_leave(&mut self, node_id: Uuid, _timestamp: SystemTime) -> Result<()> {
-        info!("➖ Processing validator leave: {}", node_id);
-        
-        let mut validators = self.validators.write().await;
-        if let Some(validator) = validators.remove(&node_id) {
-            let mut state = self.consensus_state.write().await;
-            state.total_stake -= validator.stake_amount;
-            
-            // Recalculate voting powers
-            let total_stake = state.total_stake;
-            for validator in validators.values_mut() {
-                validator.voting_power = (validator.stake_amount as f64) / (total_stake as f64);
+    /// Once per confirmed block: reward the active validators that signed it and decay
+    /// the ones that missed it, keeping reputation within 0..=100, then reweigh voting
+    /// power to match
+    fn update_reputations(validators: &mut HashMap<Uuid, ValidatorInfo>, signers: &HashSet<Uuid>) {
+        for validator in validators.values_mut().filter(|v| v.is_active) {
+            if signers.contains(&validator.node_id) {
+                validator.reputation_score = (validator.reputation_score + REPUTATION_GAIN).min(100.0);
+                validator.blocks_signed += 1;
+                validator.last_activity = SystemTime::now();
+            } else {
+                validator.reputation_score = (validator.reputation_score - REPUTATION_DECAY).max(0.0);
             }
-            
-            info!("✅ Validator {} removed from set", node_id);
         }
-        
-        Ok(())
+        Self::recalculate_voting_powers(validators);
     }
 
-    /// Handle slashing evidence
-    async fn handle_slashing_evidence(&mut self, accused_node: Uuid, evidence_type: SlashingType, _proof: Vec<u8>, _timestamp: SystemTime) -> Result<()> {
-        warn!("⚖️ Processing slashing evidence against {} for {:?}", accused_node, evidence_type);
-        
-        let mut validators = self.validators.write().await;
-        if let Some(validator) = validators.get_mut(&accused_node) {
-            validator.slashing_count += 1;
+    /// Rank validators for a place in the active set using only inputs every node agrees
+    /// on: most stake, then fewest slashings, then most confirmed blocks signed, with
+    /// node id as the final tie-break
+    fn rotation_order(validators: &HashMap<Uuid, ValidatorInfo>, max_slashings: u32) -> Vec<Uuid> {
+        let mut eligible: Vec<&ValidatorInfo> = validators.values()
+            .filter(|v| v.slashing_count <= max_slashings)
+            .collect();
+        eligible.sort_by(|a, b| b.stake_amount.cmp(&a.stake_amount)
+            .then_with(|| a.slashing_count.cmp(&b.slashing_count))
+            .then_with(|| b.blocks_signed.cmp(&a.blocks_signed))
+            .then_with(|| a.node_id.cmp(&b.node_id)));
+        eligible.into_iter().map(|v| v.node_id).collect()
+    }
+
+    /// The sorted active set a rotation should produce from the given validators
+    fn expected_active_set(&self, validators: &HashMap<Uuid, ValidatorInfo>) -> Vec<Uuid> {
+        let mut active_validators: Vec<Uuid> = Self::rotation_order(validators, self.consensus_config.max_slashings)
+            .into_iter()
+            .filter(|node_id| !self.leaving_validators.contains_key(node_id))
+            .take(self.consensus_config.max_validators)
+            .collect();
+        active_validators.sort();
+        active_validators
+    }
+
+    fn is_rotation_boundary(&self, epoch: u64) -> bool {
+        let rotation_blocks = self.consensus_config.validator_rotation_blocks;
+        rotation_blocks != 0 && epoch != 0 && epoch.is_multiple_of(rotation_blocks)
+    }
+
+    /// At every `validator_rotation_blocks` boundary, activate the top `max_validators`
+    /// validators and deactivate the rest, then announce the new active set
+    async fn maybe_rotate_validators(&self, epoch: u64) {
+        if !self.is_rotation_boundary(epoch) {
+            return;
+        }
+
+        let mut validators = self.validators.write().await;
+        let active_validators = self.expected_active_set(&validators);
+        for (node_id, validator) in validators.iter_mut() {
+            validator.is_active = active_validators.binary_search(node_id).is_ok();
+        }
+        drop(validators);
+
+        info!("🔁 Validator rotation at epoch {}: {} active", epoch, active_validators.len());
+        self.send_message(ConsensusMessage::ValidatorRotation { epoch, active_validators }).await;
+    }
+
+    /// Check a peer's announced rotation against the set we derive ourselves. A matching
+    /// announcement is adopted, which catches up a node that has not rotated yet; any
+    /// other set is rejected and leaves ours untouched.
+    async fn handle_validator_rotation(&self, epoch: u64, mut active_validators: Vec<Uuid>) -> Result<()> {
+        if !self.is_rotation_boundary(epoch) {
+            return Err(anyhow::anyhow!("Rejected validator rotation at epoch {}: not a rotation boundary", epoch));
+        }
+        active_validators.sort();
+        let mut validators = self.validators.write().await;
+        let expected = self.expected_active_set(&validators);
+        if expected != active_validators {
+            return Err(anyhow::anyhow!("Rejected validator set for epoch {}: {} announced validators do not match the {} expected",
+                epoch, active_validators.len(), expected.len()));
+        }
+        for (node_id, validator) in validators.iter_mut() {
+            validator.is_active = expected.binary_search(node_id).is_ok();
+        }
+        Ok(())
+    }
+
+    /// First phase of a validator leave: deactivate it so it is no longer picked as
+    /// proposer or counted in votes. It stays registered, with its stake, until a block
+    /// for the current epoch finalizes; `complete_validator_exits` then removes it.
+    /// Replayed leave messages are ignored.
+    async fn handle_validator_leave(&mut self, node_id: Uuid, _timestamp: SystemTime) -> Result<()> {
+        info!("➖ Processing validator leave: {}", node_id);
+
+        if self.leaving_validators.contains_key(&node_id) {
+            debug!("Validator {} is already leaving", node_id);
+            return Ok(());
+        }
+        let mut validators = self.validators.write().await;
+        let Some(validator) = validators.get_mut(&node_id) else {
+            debug!("Ignoring leave from unknown validator {}", node_id);
+            return Ok(());
+        };
+        validator.is_active = false;
+        drop(validators);
+
+        let epoch = self.consensus_state.read().await.current_epoch;
+        self.leaving_validators.insert(node_id, epoch);
+        info!("⏳ Validator {} deactivated; leaves once epoch {} is finalized", node_id, epoch);
+        Ok(())
+    }
+
+    /// Second phase of a validator leave: once every epoch it could still be working on
+    /// has finalized, remove it and take its stake out of the total
+    async fn complete_validator_exits(&mut self, finalized_epoch: u64) {
+        let exiting: Vec<(Uuid, u64)> = self.leaving_validators.iter()
+            .filter(|(_, epoch)| **epoch <= finalized_epoch)
+            .map(|(node_id, epoch)| (*node_id, *epoch))
+            .collect();
+        if exiting.is_empty() {
+            return;
+        }
+
+        let mut validators = self.validators.write().await;
+        for (node_id, _) in &exiting {
+            self.leaving_validators.remove(node_id);
+            if let Some(validator) = validators.remove(node_id) {
+                info!("✅ Validator {} removed from set", node_id);
+                self.completed_exits.push_back(ValidatorExit {
+                    node_id: *node_id,
+                    stake_amount: validator.stake_amount,
+                    blocks_produced: validator.blocks_produced,
+                    final_epoch: finalized_epoch,
+                });
+            }
+        }
+        // Summed from what is left, so a validator's stake can only ever leave the total once
+        let total_stake = Self::recalculate_voting_powers(&mut validators);
+        self.consensus_state.write().await.total_stake = total_stake;
+    }
+
+    /// Handle slashing evidence
+    async fn handle_slashing_evidence(&mut self, accused_node: Uuid, evidence_type: SlashingType, proof: Vec<u8>, _timestamp: SystemTime) -> Result<()> {
+        warn!("⚖️ Processing slashing evidence against {} for {:?}", accused_node, evidence_type);
+
+        let evidence_digest: [u8; 32] = Sha256::digest(&proof).into();
+        if self.processed_evidence.contains(&evidence_digest) {
+            debug!("Ignoring slashing evidence already acted on");
+            return Ok(());
+        }
+        if !self.verify_slashing_evidence(accused_node, &evidence_type, &proof).await {
+            warn!("❌ Rejected unverifiable slashing evidence against {}", accused_node);
+            return Ok(());
+        }
+        self.processed_evidence.insert(evidence_digest);
+        
+        let mut validators = self.validators.write().await;
+        if let Some(validator) = validators.get_mut(&accused_node) {
+            let penalty = (validator.stake_amount as f64 * self.consensus_config.slashing_penalty) as u64;
+            validator.stake_amount -= penalty.min(validator.stake_amount);
+            validator.slashing_count += 1;
             validator.reputation_score = (validator.reputation_score * 0.8).max(0.0);
+            warn!("🔪 Slashed {} stake from validator {}", penalty, accused_node);
             
             // Severe slashing removes validator
-            if validator.slashing_count >= 3 {
+            if validator.slashing_count > self.consensus_config.max_slashings {
                 validator.is_active = false;
                 warn!("🚫 Validator {} deactivated due to repeated slashing", accused_node);
             }
+
+            let total_stake = Self::recalculate_voting_powers(&mut validators);
+            self.consensus_state.write().await.total_stake = total_stake;
         }
         
         Ok(())
     }
 
-    /// Handle sync request
+    /// Check a slashing proof for the given offence.
+    ///
+    /// Double voting and equivocation carry the two conflicting signed votes. Invalid
+    /// proposals and inactivity cannot be proven by a third party, so they are only
+    /// accepted when this node observed the offence itself.
+    async fn verify_slashing_evidence(&self, accused_node: Uuid, evidence_type: &SlashingType, proof: &[u8]) -> bool {
+        let validators = self.validators.read().await;
+        let Some(accused) = validators.get(&accused_node) else {
+            return false;
+        };
+
+        match evidence_type {
+            SlashingType::DoubleVoting | SlashingType::Equivocation => {
+                let Ok((first, second)) = serde_json::from_slice::<(Vote, Vote)>(proof) else {
+                    return false;
+                };
+                first.voter_id == accused_node
+                    && second.voter_id == accused_node
+                    && Self::votes_conflict(&first, &second)
+                    && Self::verify_vote_signature(&first, &accused.public_key).unwrap_or(false)
+                    && Self::verify_vote_signature(&second, &accused.public_key).unwrap_or(false)
+            }
+            SlashingType::InvalidProposal => {
+                let Ok(claimed) = serde_json::from_slice::<WorldStateProposal>(proof) else {
+                    return false;
+                };
+                let proposals = self.pending_proposals.read().await;
+                let Some(observed) = proposals.get(&claimed.proposal_id) else {
+                    return false;
+                };
+                observed.proposer == accused_node
+                    && Self::calculate_merkle_root(&observed.world_changes).ok().as_ref() != Some(&observed.merkle_root)
+            }
+            SlashingType::Inactivity => {
+                let rotation_period = self.consensus_config.block_time * self.consensus_config.validator_rotation_blocks as u32;
+                accused.last_activity.elapsed().is_ok_and(|idle| idle > rotation_period)
+            }
+        }
+    }
+
+    /// Two votes from the same validator in the same step that disagree
+    fn votes_conflict(first: &Vote, second: &Vote) -> bool {
+        let same_step = matches!(
+            (&first.vote_type, &second.vote_type),
+            (VoteType::Prevote(_), VoteType::Prevote(_)) | (VoteType::Precommit(_), VoteType::Precommit(_))
+        );
+        let same_value = matches!(
+            (&first.vote_type, &second.vote_type),
+            (VoteType::Prevote(a), VoteType::Prevote(b)) | (VoteType::Precommit(a), VoteType::Precommit(b)) if a == b
+        );
+
+        first.voter_id == second.voter_id
+            && first.epoch == second.epoch
+            && first.round == second.round
+            && same_step
+            && (first.proposal_id != second.proposal_id || !same_value)
+    }
+
+    /// Handle sync request: answer with the confirmed blocks in the requested range
     async fn handle_sync_request(&mut self, requester: Uuid, from_epoch: u64, to_epoch: Option<u64>) -> Result<()> {
         info!("🔄 Processing sync request from {} for epochs {}+", requester, from_epoch);
         
         let finality_tracker = self.finality_tracker.read().await;
         let end_epoch = to_epoch.unwrap_or(finality_tracker.last_finalized_epoch);
         
-        let blocks: Vec<_> = finality_tracker.finalized_blocks
-            .range(from_epoch..=end_epoch)
-            .map(|(_, block)| block.clone())
-            .collect();
+        let blocks: Vec<_> = if from_epoch <= end_epoch {
+            finality_tracker.finalized_blocks
+                .range(from_epoch..=end_epoch)
+                .map(|(_, block)| block.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
         
         let current_state = self.consensus_state.read().await.clone();
         drop(finality_tracker);
         
-        if let Some(sender) = &self.message_sender {
-            let _ = sender.send(ConsensusMessage::SyncResponse {
-                blocks,
-                current_state,
-            });
-        }
+        self.send_message(ConsensusMessage::SyncResponse {
+            blocks,
+            current_state,
+        }).await;
         
         Ok(())
     }
 
-    /// Handle sync response
-    async fn handle_sync_response(&mut self, blocks: Vec<FinalizedBlock>, current_state: ConsensusState) -> Result<()> {
-        info!("📥 Processing sync response with {} blocks", blocks.len());
+    /// Handle sync response: take blocks that extend our confirmed chain without a gap,
+    /// each one checked against its merkle root and its predecessor's hash. The first
+    /// block that fails stops the sync; everything after it is rejected.
+    async fn handle_sync_response(&mut self, mut blocks: Vec<FinalizedBlock>, current_state: ConsensusState) -> Result<()> {
+        info!("📥 Processing sync response with {} blocks (peer at epoch {})", blocks.len(), current_state.current_epoch);
+        blocks.sort_by_key(|block| block.epoch);
         
         let mut finality_tracker = self.finality_tracker.write().await;
+        let mut accepted = Vec::new();
         for block in blocks {
-            finality_tracker.finalized_blocks.insert(block.epoch, block);
-        }
-        
-        if let Some(latest_block) = finality_tracker.finalized_blocks.values().last() {
-            finality_tracker.last_finalized_epoch = latest_block.epoch;
+            let tip = finality_tracker.finalized_blocks.values().last();
+            let expected_epoch = tip.map_or(0, |tip| tip.epoch + 1);
+            if block.epoch < expected_epoch {
+                continue; // already have it
+            }
+            if block.epoch > expected_epoch {
+                warn!("❌ Sync response skips from epoch {} to {}; rejecting the rest", expected_epoch, block.epoch);
+                break;
+            }
+            if block.previous_hash != tip.map(|tip| tip.block_hash) {
+                warn!("❌ Synced block for epoch {} does not follow our chain", block.epoch);
+                break;
+            }
+            if Self::calculate_merkle_root(&block.world_changes)? != block.merkle_root {
+                warn!("❌ Synced block for epoch {} has a bad merkle root", block.epoch);
+                break;
+            }
+
+            finality_tracker.unconfirmed_blocks.remove(&block.epoch);
+            finality_tracker.last_finalized_epoch = block.epoch;
+            finality_tracker.finalized_blocks.insert(block.epoch, block.clone());
+            finality_tracker.confirmed_queue.push_back(block.clone());
+            accepted.push(block);
         }
+        let tip = finality_tracker.finalized_blocks.values().last().map(|block| (block.block_hash, block.epoch));
         drop(finality_tracker);
+        self.credit_block_signers(&accepted).await;
         
-        // Update our consensus state
-        let mut state = self.consensus_state.write().await;
-        *state = current_state;
+        // Resume consensus after the synced chain
+        if let Some((hash, epoch)) = tip.filter(|_| !accepted.is_empty()) {
+            let mut state = self.consensus_state.write().await;
+            if state.current_epoch <= epoch {
+                state.current_epoch = epoch + 1;
+                state.current_round = 0;
+                state.current_step = ConsensusStep::Propose;
+                state.last_finalized_block = Some(hash);
+                state.votes.clear();
+                state.vote_history.clear();
+            }
+            drop(state);
+            self.begin_round().await;
+        }
         
-        info!("✅ Sync completed, updated to epoch {}", state.current_epoch);
+        info!("✅ Sync accepted {} blocks, finalized up to epoch {:?}", accepted.len(), tip.map(|(_, epoch)| epoch));
         Ok(())
     }
 
@@ -927,6 +1694,7 @@ impl ConsensusManager {
             active_validators: validators.values().filter(|v| v.is_active).count(),
             total_stake: state.total_stake,
             finalized_blocks: finality_tracker.finalized_blocks.len(),
+            unconfirmed_blocks: finality_tracker.unconfirmed_blocks.len(),
             last_finalized_epoch: finality_tracker.last_finalized_epoch,
             pending_proposals: self.pending_proposals.read().await.len(),
         }
@@ -941,10 +1709,149 @@ pub struct ConsensusStats {
     pub active_validators: usize,
     pub total_stake: u64,
     pub finalized_blocks: usize,
+    pub unconfirmed_blocks: usize,
     pub last_finalized_epoch: u64,
     pub pending_proposals: usize,
 }
 
+/// When the step a node is in began, on whatever clock drives its timeouts
+#[derive(Debug, Default)]
+struct StepTimer {
+    observed: Option<(u64, u32, ConsensusStep)>,
+    step_started: Duration,
+    voted_for: Option<(u64, u32)>,
+}
+
+/// Everything a timeout check or block proposal touches, detached from the manager
+#[derive(Clone)]
+struct TimeoutContext {
+    node_id: Uuid,
+    config: ConsensusConfig,
+    signing_key: SigningKey,
+    consensus_state: Arc<RwLock<ConsensusState>>,
+    validators: Arc<RwLock<HashMap<Uuid, ValidatorInfo>>>,
+    block_producer: Arc<RwLock<BlockProducer>>,
+    message_sender: Option<mpsc::Sender<ConsensusMessage>>,
+    saturated_sends: Arc<AtomicU64>,
+}
+
+impl TimeoutContext {
+    /// Note which step the node is in at `now`; if it has been stuck there past its
+    /// timeout, vote for a view change to the next round
+    async fn tick(&self, timer: &mut StepTimer, now: Duration) {
+        let voting_powers = ConsensusManager::active_voting_powers(&self.validators).await;
+        let mut state = self.consensus_state.write().await;
+        let current = (state.current_epoch, state.current_round, state.current_step.clone());
+        if timer.observed.as_ref() != Some(&current) {
+            timer.observed = Some(current);
+            timer.step_started = now;
+            return;
+        }
+
+        let Some((timeout, reason)) = self.config.step_timeout(&state.current_step) else {
+            return;
+        };
+        let new_round = state.current_round + 1;
+        if now.saturating_sub(timer.step_started) < timeout
+            || timer.voted_for == Some((state.current_epoch, new_round))
+            || !voting_powers.contains_key(&self.node_id)
+        {
+            return;
+        }
+
+        warn!("⏰ Step {:?} timed out in round {}, requesting view change", state.current_step, state.current_round);
+        let view_change = ViewChangeVote {
+            voter_id: self.node_id,
+            new_round,
+            epoch: state.current_epoch,
+            timestamp: SystemTime::now(),
+            reason,
+            signature: ConsensusManager::sign_view_change(&self.signing_key, state.current_epoch, new_round),
+        };
+        timer.voted_for = Some((state.current_epoch, new_round));
+        state.view_change_votes.insert(self.node_id, view_change.clone());
+        if ConsensusManager::try_advance_round(&mut state, &voting_powers, self.config.consensus_threshold) {
+            let proposer = ConsensusManager::select_proposer(&voting_powers, state.current_epoch, state.current_round);
+            self.block_producer.write().await.is_proposer = proposer == self.node_id;
+        }
+        drop(state);
+
+        queue_message(&self.message_sender, self.config.send_timeout, &self.saturated_sends, ConsensusMessage::ViewChange(view_change)).await;
+    }
+
+    /// See `ConsensusManager::proposer_tick`
+    async fn proposer_tick(&self, now: SystemTime) -> Result<bool> {
+        let producer = self.block_producer.read().await;
+        let due = now.duration_since(producer.last_block_time).is_ok_and(|elapsed| elapsed >= self.config.block_time);
+        if !producer.is_proposer || !due || producer.pending_changes.is_empty() {
+            return Ok(false);
+        }
+        drop(producer);
+
+        self.propose_new_block(now).await?;
+        Ok(true)
+    }
+
+    /// Propose a new block from the queued changes, timestamped `now`
+    async fn propose_new_block(&self, now: SystemTime) -> Result<()> {
+        info!("📝 Proposing new block");
+        
+        let mut producer = self.block_producer.write().await;
+        let changes: Vec<_> = producer.pending_changes.drain(..).collect();
+        if !changes.is_empty() {
+            producer.last_block_time = now;
+        }
+        drop(producer);
+
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let state = self.consensus_state.read().await;
+        let merkle_root = ConsensusManager::calculate_merkle_root(&changes)?;
+        
+        let mut proposal = WorldStateProposal {
+            proposal_id: Uuid::new_v4(),
+            proposer: self.node_id,
+            epoch: state.current_epoch,
+            round: state.current_round,
+            timestamp: now,
+            world_changes: changes,
+            previous_block_hash: state.last_finalized_block,
+            merkle_root,
+            signature: None,
+        };
+        drop(state);
+        proposal.signature = Some(ConsensusManager::sign_proposal(&self.signing_key, &proposal));
+        self.block_producer.write().await.current_proposal = Some(proposal.clone());
+
+        // Broadcast proposal
+        queue_message(&self.message_sender, self.config.send_timeout, &self.saturated_sends, ConsensusMessage::Proposal(proposal)).await;
+
+        Ok(())
+    }
+}
+
+impl ConsensusConfig {
+    /// How long a step may stall before a view change, and the reason reported for it
+    fn step_timeout(&self, step: &ConsensusStep) -> Option<(Duration, ViewChangeReason)> {
+        match step {
+            ConsensusStep::Propose => Some((self.timeout_propose, ViewChangeReason::TimeoutPropose)),
+            ConsensusStep::Prevote => Some((self.timeout_prevote, ViewChangeReason::TimeoutPrevote)),
+            ConsensusStep::Precommit => Some((self.timeout_precommit, ViewChangeReason::TimeoutPrecommit)),
+            ConsensusStep::Commit => None,
+        }
+    }
+}
+
+impl Drop for ConsensusManager {
+    fn drop(&mut self) {
+        if let Some(watcher) = self.timeout_watcher.take() {
+            watcher.abort();
+        }
+    }
+}
+
 impl Default for ConsensusConfig {
     fn default() -> Self {
         Self {
@@ -957,6 +1864,10 @@ impl Default for ConsensusConfig {
             timeout_propose: Duration::from_secs(30),
             timeout_prevote: Duration::from_secs(10),
             timeout_precommit: Duration::from_secs(10),
+            slashing_penalty: default_slashing_penalty(),
+            max_slashings: default_max_slashings(),
+            message_queue_capacity: default_message_queue_capacity(),
+            send_timeout: default_send_timeout(),
         }
     }
 }
@@ -964,6 +1875,689 @@ impl Default for ConsensusConfig {
 /// Thread-safe consensus manager
 pub type SharedConsensusManager = Arc<RwLock<ConsensusManager>>;
 
-pub fn create_shared_consensus_manager(node_id: Uuid, is_masternode: bool, stake_amount: u64, config: ConsensusConfig) -> SharedConsensusManager {
-    Arc::new(RwLock::new(ConsensusManager::new(node_id, is_masternode, stake_amount, config)))
-}
\ No newline at end of file
+pub fn create_shared_consensus_manager(node_id: Uuid, is_masternode: bool, stake_amount: u64, config: ConsensusConfig) -> Result<SharedConsensusManager> {
+    Ok(Arc::new(RwLock::new(ConsensusManager::new(node_id, is_masternode, stake_amount, config)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> ConsensusConfig {
+        ConsensusConfig::default()
+    }
+
+    fn signed_prevote(manager: &ConsensusManager, proposal_id: Uuid) -> Vote {
+        Vote {
+            voter_id: manager.node_id,
+            proposal_id,
+            vote_type: VoteType::Prevote(true),
+            epoch: 0,
+            round: 0,
+            timestamp: SystemTime::now(),
+            signature: manager.sign_vote(&proposal_id, &VoteType::Prevote(true), 0, 0).unwrap(),
+        }
+    }
+
+    fn test_block(epoch: u64) -> FinalizedBlock {
+        FinalizedBlock {
+            block_hash: [epoch as u8 + 1; 32],
+            epoch,
+            round: 0,
+            proposer: Uuid::nil(),
+            timestamp: SystemTime::UNIX_EPOCH,
+            world_changes: Vec::new(),
+            validator_signatures: HashMap::new(),
+            merkle_root: merkle::to_hex(&merkle::MerkleTree::from_leaves(Vec::new()).root()),
+            previous_hash: epoch.checked_sub(1).map(|previous| [previous as u8 + 1; 32]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blocks_apply_only_once_finality_depth_is_reached() {
+        let manager = ConsensusManager::new(Uuid::new_v4(), true, 1000, ConsensusConfig { finality_depth: 2, ..test_config() }).unwrap();
+        let add = |epoch| manager.finality_tracker.try_write().unwrap().add_block(test_block(epoch), 2).unwrap();
+
+        // The first block has only one successor, so nothing is applied yet
+        assert!(add(0).is_empty());
+        assert!(add(1).is_empty());
+        assert!(manager.take_confirmed_blocks().await.is_empty());
+        assert_eq!(manager.get_consensus_stats().await.unconfirmed_blocks, 2);
+
+        // A second successor confirms it
+        assert_eq!(add(2), vec![0]);
+        let applied: Vec<u64> = manager.take_confirmed_blocks().await.iter().map(|b| b.epoch).collect();
+        assert_eq!(applied, vec![0]);
+
+        // The unconfirmed tail can be discarded; the confirmed block cannot
+        let discarded: Vec<u64> = manager.reorg_to(0).await.unwrap().iter().map(|b| b.epoch).collect();
+        assert_eq!(discarded, vec![1, 2]);
+        assert_eq!(manager.consensus_state.read().await.current_epoch, 1);
+        assert_eq!(manager.consensus_state.read().await.last_finalized_block, Some(test_block(0).block_hash));
+        assert!(manager.finality_tracker.write().await.add_block(test_block(0), 2).is_err());
+        assert!(add(1).is_empty());
+    }
+
+    #[test]
+    fn test_finality_tracker_only_accepts_blocks_that_extend_the_tip() {
+        let mut tracker = ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap()
+            .finality_tracker.try_read().unwrap().clone();
+        tracker.add_block(test_block(0), 2).unwrap();
+        tracker.add_block(test_block(1), 2).unwrap();
+
+        // The same block again changes nothing
+        assert!(tracker.add_block(test_block(1), 2).unwrap().is_empty());
+        assert_eq!(tracker.unconfirmed_blocks.len(), 2);
+
+        // A different block for a held epoch conflicts until the tail is reorged away
+        let rival = FinalizedBlock { block_hash: [0xAA; 32], ..test_block(1) };
+        let err = tracker.add_block(rival.clone(), 2).unwrap_err();
+        assert!(err.to_string().contains("conflicts"), "{}", err);
+
+        // Skipping an epoch, or not linking to the tip, is rejected
+        let err = tracker.add_block(test_block(3), 2).unwrap_err();
+        assert!(err.to_string().contains("gap"), "{}", err);
+        let unlinked = FinalizedBlock { previous_hash: Some([0xBB; 32]), ..test_block(2) };
+        let err = tracker.add_block(unlinked, 2).unwrap_err();
+        assert!(err.to_string().contains("does not link"), "{}", err);
+        assert_eq!(tracker.tip().map(|block| block.epoch), Some(1));
+
+        tracker.reorg_to(0).unwrap();
+        tracker.add_block(rival, 2).unwrap();
+        assert_eq!(tracker.tip().map(|block| block.block_hash), Some([0xAA; 32]));
+    }
+
+    #[tokio::test]
+    async fn test_player_acting_outside_their_area_is_rejected() {
+        let manager = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        let (aria, goblin) = (Uuid::new_v4(), Uuid::new_v4());
+        manager.update_world_view(WorldView {
+            areas: HashSet::from(["Central Plains".to_string(), "Dark Forest".to_string()]),
+            player_areas: HashMap::from([(aria, "Central Plains".to_string())]),
+            npc_areas: HashMap::from([(goblin, "Dark Forest".to_string())]),
+        }).await;
+        let player_action = |player_id, action_type: &str, area_id: &str| WorldChange::PlayerAction {
+            player_id,
+            action_type: action_type.to_string(),
+            area_id: area_id.to_string(),
+            timestamp: SystemTime::now(),
+            data: serde_json::Value::Null,
+        };
+
+        assert!(manager.validate_world_change(&player_action(aria, "pickup", "Central Plains")).await.unwrap());
+        assert!(!manager.validate_world_change(&player_action(aria, "pickup", "Dark Forest")).await.unwrap());
+        assert!(!manager.validate_world_change(&player_action(aria, "teleport", "Central Plains")).await.unwrap());
+        assert!(!manager.validate_world_change(&player_action(Uuid::new_v4(), "pickup", "Central Plains")).await.unwrap());
+
+        let npc_action = |area_id: &str| WorldChange::NPCAction {
+            npc_id: goblin,
+            action_type: "attack".to_string(),
+            area_id: area_id.to_string(),
+            timestamp: SystemTime::now(),
+            data: serde_json::Value::Null,
+        };
+        assert!(manager.validate_world_change(&npc_action("Dark Forest")).await.unwrap());
+        assert!(!manager.validate_world_change(&npc_action("Central Plains")).await.unwrap());
+
+        let area_update = |area_id: &str| WorldChange::AreaUpdate {
+            area_id: area_id.to_string(),
+            update_type: "weather".to_string(),
+            timestamp: SystemTime::now(),
+            data: serde_json::Value::Null,
+        };
+        assert!(manager.validate_world_change(&area_update("Dark Forest")).await.unwrap());
+        assert!(!manager.validate_world_change(&area_update("Sunken City")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rotation_keeps_only_the_top_staked_validators_active() {
+        let config = ConsensusConfig { max_validators: 2, validator_rotation_blocks: 10, ..test_config() };
+        let (sender, mut receiver) = mpsc::channel(test_config().message_queue_capacity);
+        let mut manager = ConsensusManager::new(Uuid::new_v4(), false, 0, config.clone()).unwrap();
+        manager.message_sender = Some(sender);
+
+        let stakes = [1000u64, 4000, 2000, 3000];
+        let ids: Vec<Uuid> = stakes.iter().map(|_| Uuid::new_v4()).collect();
+        for (node_id, stake) in ids.iter().zip(stakes) {
+            manager.handle_validator_join(*node_id, stake, [0u8; 32], SystemTime::now()).await.unwrap();
+        }
+        // Only the first two to join fit before the cap
+        assert_eq!(manager.get_active_validator_count().await, 2);
+
+        // Not a boundary: nothing changes
+        manager.maybe_rotate_validators(9).await;
+        assert!(manager.validators.read().await[&ids[0]].is_active);
+
+        manager.maybe_rotate_validators(10).await;
+        let validators = manager.validators.read().await;
+        let active: HashSet<Uuid> = validators.values().filter(|v| v.is_active).map(|v| v.node_id).collect();
+        assert_eq!(active, HashSet::from([ids[1], ids[3]]));
+        drop(validators);
+
+        let total_power = manager.calculate_total_voting_power().await;
+        assert!((total_power - 0.7).abs() < 1e-9, "{}", total_power);
+
+        let announced = std::iter::from_fn(|| receiver.try_recv().ok())
+            .find_map(|message| match message {
+                ConsensusMessage::ValidatorRotation { epoch, active_validators } => Some((epoch, active_validators)),
+                _ => None,
+            })
+            .expect("rotation was not broadcast");
+        assert_eq!(announced.0, 10);
+        assert_eq!(announced.1.iter().copied().collect::<HashSet<_>>(), active);
+
+        // A node that joined the same validators but has not rotated adopts the announcement
+        let mut follower = ConsensusManager::new(Uuid::new_v4(), false, 0, config).unwrap();
+        for (node_id, stake) in ids.iter().zip(stakes) {
+            follower.handle_validator_join(*node_id, stake, [0u8; 32], SystemTime::now()).await.unwrap();
+        }
+        let before: Vec<bool> = ids.iter().map(|id| follower.validators.try_read().unwrap()[id].is_active).collect();
+
+        // Any other set is rejected and leaves the local set alone
+        let err = follower.handle_validator_rotation(10, vec![ids[0], ids[1]]).await.unwrap_err();
+        assert!(err.to_string().contains("do not match"), "{}", err);
+        assert!(follower.handle_validator_rotation(9, announced.1.clone()).await.is_err());
+        let after: Vec<bool> = ids.iter().map(|id| follower.validators.try_read().unwrap()[id].is_active).collect();
+        assert_eq!(before, after);
+
+        follower.handle_validator_rotation(10, announced.1).await.unwrap();
+        let validators = follower.validators.read().await;
+        let adopted: HashSet<Uuid> = validators.values().filter(|v| v.is_active).map(|v| v.node_id).collect();
+        assert_eq!(adopted, active);
+    }
+
+    #[test]
+    fn test_rotation_breaks_stake_ties_on_slashings_then_blocks_signed_then_id() {
+        let validator = |stake_amount, slashing_count, blocks_signed| {
+            let node_id = Uuid::new_v4();
+            (node_id, ValidatorInfo {
+                node_id,
+                stake_amount,
+                voting_power: 0.0,
+                is_active: true,
+                last_activity: SystemTime::now(),
+                // Reputation is local bookkeeping and must not sway the order
+                reputation_score: if blocks_signed > 0 { 0.0 } else { 100.0 },
+                blocks_produced: 0,
+                blocks_signed,
+                slashing_count,
+                public_key: [0u8; 32],
+            })
+        };
+        let top = validator(2000, 1, 0);
+        let clean = validator(1000, 0, 0);
+        let slashed = validator(1000, 1, 9);
+        let signed = validator(1000, 0, 5);
+        let (tie_a, tie_b) = (validator(500, 0, 0), validator(500, 0, 0));
+        let validators: HashMap<Uuid, ValidatorInfo> = [top.clone(), clean.clone(), slashed.clone(), signed.clone(), tie_a.clone(), tie_b.clone()]
+            .into_iter()
+            .collect();
+
+        let (first_tie, second_tie) = if tie_a.0 < tie_b.0 { (tie_a.0, tie_b.0) } else { (tie_b.0, tie_a.0) };
+        assert_eq!(ConsensusManager::rotation_order(&validators, 3),
+            vec![top.0, signed.0, clean.0, slashed.0, first_tie, second_tie]);
+        // Over the slashing limit drops out entirely
+        assert_eq!(ConsensusManager::rotation_order(&validators, 0), vec![signed.0, clean.0, first_tie, second_tie]);
+    }
+
+    #[tokio::test]
+    async fn test_started_manager_proposes_on_block_time_without_a_manual_tick() {
+        let config = ConsensusConfig { block_time: Duration::from_millis(100), ..test_config() };
+        let (sender, mut receiver) = mpsc::channel(config.message_queue_capacity);
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, config).unwrap();
+        alice.initialize(sender).await.unwrap();
+        while receiver.try_recv().is_ok() {}
+
+        // One change is well under the count that proposes immediately
+        alice.add_world_change(WorldChange::AreaUpdate {
+            area_id: "Central Plains".to_string(),
+            update_type: "weather".to_string(),
+            timestamp: SystemTime::UNIX_EPOCH,
+            data: serde_json::Value::Null,
+        }).await.unwrap();
+
+        let proposal = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(ConsensusMessage::Proposal(proposal)) = receiver.recv().await {
+                    return proposal;
+                }
+            }
+        }).await.expect("watcher never proposed");
+        assert_eq!(proposal.proposer, alice.node_id);
+        assert_eq!(proposal.world_changes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_proposer_proposes_queued_changes_once_block_time_elapses() {
+        let (sender, mut receiver) = mpsc::channel(test_config().message_queue_capacity);
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        alice.initialize(sender).await.unwrap();
+        while receiver.try_recv().is_ok() {}
+
+        for i in 0..3 {
+            alice.add_world_change(WorldChange::AreaUpdate {
+                area_id: "Central Plains".to_string(),
+                update_type: "weather".to_string(),
+                timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(i),
+                data: serde_json::Value::Null,
+            }).await.unwrap();
+        }
+        let last_block_time = alice.block_producer.read().await.last_block_time;
+
+        // Too few changes to propose on count, and the block time has not passed
+        assert!(!alice.proposer_tick(last_block_time + Duration::from_secs(9)).await.unwrap());
+        assert!(receiver.try_recv().is_err());
+
+        assert!(alice.proposer_tick(last_block_time + test_config().block_time).await.unwrap());
+        let Ok(ConsensusMessage::Proposal(proposal)) = receiver.try_recv() else {
+            panic!("no proposal broadcast");
+        };
+        assert_eq!(proposal.proposer, alice.node_id);
+        assert_eq!(proposal.world_changes.len(), 3);
+        assert!(ConsensusManager::verify_proposal_signature(&proposal, &alice.public_key()));
+        assert!(alice.block_producer.read().await.pending_changes.is_empty());
+
+        // Nothing left to propose
+        assert!(!alice.proposer_tick(SystemTime::now() + Duration::from_secs(60)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_lagging_node_syncs_the_finalized_chain() {
+        let (source_sender, mut source_outbox) = mpsc::channel(test_config().message_queue_capacity);
+        let mut source = ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap();
+        source.message_sender = Some(source_sender);
+        let mut lagging = ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap();
+        for epoch in 0..6 {
+            source.finality_tracker.write().await.add_block(test_block(epoch), 0).unwrap();
+        }
+        for epoch in 0..2 {
+            lagging.finality_tracker.write().await.add_block(test_block(epoch), 0).unwrap();
+        }
+
+        source.handle_consensus_message(ConsensusMessage::SyncRequest {
+            requester: lagging.node_id,
+            from_epoch: 2,
+            to_epoch: None,
+        }).await.unwrap();
+        let response = source_outbox.try_recv().unwrap();
+        let ConsensusMessage::SyncResponse { blocks, current_state } = response else {
+            panic!("expected a sync response");
+        };
+        assert_eq!(blocks.len(), 4);
+
+        // A response with a hole is only taken up to the hole
+        let with_gap: Vec<_> = blocks.iter().filter(|block| block.epoch != 3).cloned().collect();
+        lagging.handle_sync_response(with_gap, current_state.clone()).await.unwrap();
+        assert_eq!(lagging.finality_tracker.read().await.last_finalized_epoch, 2);
+
+        lagging.handle_sync_response(blocks, current_state).await.unwrap();
+        let synced = lagging.finality_tracker.read().await.finalized_blocks.clone();
+        let original = source.finality_tracker.read().await.finalized_blocks.clone();
+        assert_eq!(
+            synced.values().map(|block| (block.epoch, block.block_hash)).collect::<Vec<_>>(),
+            original.values().map(|block| (block.epoch, block.block_hash)).collect::<Vec<_>>(),
+        );
+        assert_eq!(lagging.finality_tracker.read().await.last_finalized_epoch, 5);
+        assert_eq!(lagging.consensus_state.read().await.current_epoch, 6);
+    }
+
+    #[tokio::test]
+    async fn test_reputation_rises_for_signers_and_falls_for_absentees() {
+        let mut manager = ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap();
+        let (present, absent) = (Uuid::new_v4(), Uuid::new_v4());
+        for node_id in [present, absent] {
+            manager.handle_validator_join(node_id, 1000, [0u8; 32], SystemTime::now()).await.unwrap();
+        }
+        manager.validators.write().await.get_mut(&present).unwrap().reputation_score = 95.0;
+
+        for _ in 0..3 {
+            ConsensusManager::update_reputations(&mut *manager.validators.write().await, &HashSet::from([present]));
+        }
+        let validators = manager.validators.read().await;
+        assert_eq!(validators[&present].reputation_score, 98.0);
+        assert_eq!(validators[&absent].reputation_score, 94.0);
+        drop(validators);
+
+        // Clamped at the top of the range
+        for _ in 0..10 {
+            ConsensusManager::update_reputations(&mut *manager.validators.write().await, &HashSet::from([present]));
+        }
+        assert_eq!(manager.validators.read().await[&present].reputation_score, 100.0);
+        assert_eq!(manager.validators.read().await[&absent].reputation_score, 74.0);
+    }
+
+    #[tokio::test]
+    async fn test_reputation_comes_from_confirmed_blocks_and_weighs_voting_power() {
+        let (signer, absentee) = (Uuid::new_v4(), Uuid::new_v4());
+        let blocks: Vec<FinalizedBlock> = (0..3).map(|epoch| FinalizedBlock {
+            validator_signatures: HashMap::from([(signer, "signed".to_string())]),
+            ..test_block(epoch)
+        }).collect();
+
+        let mut observers = Vec::new();
+        for _ in 0..2 {
+            let mut observer = ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap();
+            for node_id in [signer, absentee] {
+                observer.handle_validator_join(node_id, 1000, [0u8; 32], SystemTime::now()).await.unwrap();
+            }
+            let state = observer.consensus_state.read().await.clone();
+            observer.handle_sync_response(blocks.clone(), state).await.unwrap();
+            observers.push(observer);
+        }
+
+        // Every node holding the same blocks lands on the same reputations
+        for observer in &observers {
+            let validators = observer.validators.read().await;
+            assert_eq!(validators[&signer].reputation_score, 100.0);
+            assert_eq!(validators[&absentee].reputation_score, 94.0);
+            // Equal stake, but the absentee's lower reputation costs it voting power
+            assert!(validators[&signer].voting_power > validators[&absentee].voting_power);
+            let total: f64 = validators.values().map(|v| v.voting_power).sum();
+            assert!((total - 1.0).abs() < 1e-9, "{}", total);
+        }
+    }
+
+    #[test]
+    fn test_voting_power_without_any_stake_is_zero_not_nan() {
+        let node_id = Uuid::new_v4();
+        let mut validators = HashMap::from([(node_id, ValidatorInfo {
+            node_id,
+            stake_amount: 0,
+            voting_power: 1.0,
+            is_active: true,
+            last_activity: SystemTime::now(),
+            reputation_score: 100.0,
+            blocks_produced: 0,
+            blocks_signed: 0,
+            slashing_count: 0,
+            public_key: [0u8; 32],
+        })]);
+        assert_eq!(ConsensusManager::recalculate_voting_powers(&mut validators), 0);
+        assert_eq!(validators[&node_id].voting_power, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_leaving_validator_stake_leaves_total_exactly_once() {
+        let mut manager = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        let (staying, leaving) = (Uuid::new_v4(), Uuid::new_v4());
+        manager.handle_validator_join(staying, 1000, [0u8; 32], SystemTime::now()).await.unwrap();
+        manager.handle_validator_join(leaving, 1500, [0u8; 32], SystemTime::now()).await.unwrap();
+        assert_eq!(manager.consensus_state.read().await.total_stake, 2500);
+
+        // Phase one: out of the active set, but its stake stays until the epoch finalizes
+        for _ in 0..2 {
+            manager.handle_validator_leave(leaving, SystemTime::now()).await.unwrap();
+        }
+        assert!(!manager.validators.read().await[&leaving].is_active);
+        assert_eq!(manager.consensus_state.read().await.total_stake, 2500);
+
+        let proposal = WorldStateProposal {
+            proposal_id: Uuid::new_v4(),
+            proposer: staying,
+            epoch: 0,
+            round: 0,
+            timestamp: SystemTime::now(),
+            world_changes: Vec::new(),
+            previous_block_hash: None,
+            merkle_root: String::new(),
+            signature: None,
+        };
+        manager.pending_proposals.write().await.insert(proposal.proposal_id, proposal.clone());
+        manager.finalize_proposal(proposal.proposal_id).await.unwrap();
+
+        // Phase two: removed, with its stake out of the total once, however often the leave replays
+        manager.handle_validator_leave(leaving, SystemTime::now()).await.unwrap();
+        assert!(!manager.validators.read().await.contains_key(&leaving));
+        assert_eq!(manager.consensus_state.read().await.total_stake, 1000);
+        let exits = manager.take_completed_exits();
+        assert_eq!(exits.len(), 1);
+        assert_eq!((exits[0].node_id, exits[0].stake_amount, exits[0].final_epoch), (leaving, 1500, 0));
+        assert!(manager.take_completed_exits().is_empty());
+    }
+
+    #[test]
+    fn test_vote_signature_fails_under_another_validators_key() {
+        let alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        let mallory = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        let proposal_id = Uuid::new_v4();
+
+        let vote = signed_prevote(&alice, proposal_id);
+        assert!(ConsensusManager::verify_vote_signature(&vote, &alice.public_key()).unwrap());
+        assert!(!ConsensusManager::verify_vote_signature(&vote, &mallory.public_key()).unwrap());
+
+        // A vote forged in alice's name is rejected under her key
+        let mut forged = signed_prevote(&mallory, proposal_id);
+        forged.voter_id = alice.node_id;
+        assert!(!ConsensusManager::verify_vote_signature(&forged, &alice.public_key()).unwrap());
+
+        // The signature is bound to the round it was cast in
+        let mut replayed = vote.clone();
+        replayed.round = 1;
+        assert!(!ConsensusManager::verify_vote_signature(&replayed, &alice.public_key()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_forged_vote_is_not_counted() {
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        let mallory = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        let victim = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        for validator in [&mallory, &victim] {
+            alice.handle_validator_join(validator.node_id, 1000, validator.public_key(), SystemTime::now()).await.unwrap();
+        }
+
+        let mut forged = signed_prevote(&mallory, Uuid::new_v4());
+        forged.voter_id = victim.node_id;
+        assert!(!alice.validate_vote(&forged).await.unwrap());
+
+        let honest = signed_prevote(&mallory, Uuid::new_v4());
+        assert!(alice.validate_vote(&honest).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_stalled_propose_step_advances_round() {
+        let config = ConsensusConfig {
+            timeout_propose: Duration::from_millis(50),
+            ..test_config()
+        };
+        let (sender, mut receiver) = mpsc::channel(test_config().message_queue_capacity);
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, config.clone()).unwrap();
+        alice.initialize(sender).await.unwrap();
+        let bob = ConsensusManager::new(Uuid::new_v4(), true, 1000, config).unwrap();
+        alice.handle_validator_join(bob.node_id, 1000, bob.public_key(), SystemTime::now()).await.unwrap();
+
+        // Nobody proposes; alice times out and asks for round 1
+        let view_change = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                if let Some(ConsensusMessage::ViewChange(vote)) = receiver.recv().await {
+                    break vote;
+                }
+            }
+        }).await.unwrap();
+        assert!(matches!(view_change.reason, ViewChangeReason::TimeoutPropose));
+        assert_eq!(view_change.new_round, 1);
+
+        // Half the voting power is not enough
+        assert_eq!(alice.consensus_state.read().await.current_round, 0);
+
+        let bob_vote = ViewChangeVote {
+            voter_id: bob.node_id,
+            new_round: 1,
+            epoch: 0,
+            timestamp: SystemTime::now(),
+            reason: ViewChangeReason::TimeoutPropose,
+            signature: ConsensusManager::sign_view_change(&bob.signing_key, 0, 1),
+        };
+        alice.handle_consensus_message(ConsensusMessage::ViewChange(bob_vote)).await.unwrap();
+
+        let state = alice.consensus_state.read().await;
+        assert_eq!(state.current_round, 1);
+        assert_eq!(state.current_step, ConsensusStep::Propose);
+    }
+
+    #[tokio::test]
+    async fn test_proposer_frequency_matches_stake() {
+        let stakes = [1000u64, 2000, 7000];
+        let observers = [
+            ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap(),
+            ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap(),
+        ];
+        let validator_ids: Vec<Uuid> = stakes.iter().map(|_| Uuid::new_v4()).collect();
+        for observer in &observers {
+            let mut validators = observer.validators.write().await;
+            for (node_id, stake) in validator_ids.iter().zip(stakes) {
+                validators.insert(*node_id, ValidatorInfo {
+                    node_id: *node_id,
+                    stake_amount: stake,
+                    voting_power: 0.0,
+                    is_active: true,
+                    last_activity: SystemTime::now(),
+                    reputation_score: 100.0,
+                    blocks_produced: 0,
+                    blocks_signed: 0,
+                    slashing_count: 0,
+                    public_key: [0u8; 32],
+                });
+            }
+            ConsensusManager::recalculate_voting_powers(&mut validators);
+        }
+
+        let epochs = 5000;
+        let mut selections: HashMap<Uuid, u32> = HashMap::new();
+        for epoch in 0..epochs {
+            let proposer = observers[0].current_proposer(epoch, 0).await;
+            assert_eq!(proposer, observers[1].current_proposer(epoch, 0).await);
+            *selections.entry(proposer).or_default() += 1;
+        }
+
+        for (node_id, stake) in validator_ids.iter().zip(stakes) {
+            let frequency = selections.get(node_id).copied().unwrap_or(0) as f64 / epochs as f64;
+            let share = stake as f64 / 10_000.0;
+            assert!((frequency - share).abs() < 0.03, "{} vs {}", frequency, share);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sole_validator_is_proposer() {
+        let (sender, _receiver) = mpsc::channel(test_config().message_queue_capacity);
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        alice.initialize(sender).await.unwrap();
+
+        assert_eq!(alice.current_proposer(0, 0).await, alice.node_id);
+        assert!(alice.block_producer.read().await.is_proposer);
+    }
+
+    #[tokio::test]
+    async fn test_double_voting_evidence_reduces_stake() {
+        let (sender, _receiver) = mpsc::channel(test_config().message_queue_capacity);
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        alice.initialize(sender).await.unwrap();
+        let mallory = ConsensusManager::new(Uuid::new_v4(), true, 2000, test_config()).unwrap();
+        alice.handle_validator_join(mallory.node_id, 2000, mallory.public_key(), SystemTime::now()).await.unwrap();
+
+        let first = signed_prevote(&mallory, Uuid::new_v4());
+        let second = signed_prevote(&mallory, Uuid::new_v4());
+        let proof = serde_json::to_vec(&(first, second)).unwrap();
+        let evidence = ConsensusMessage::SlashingEvidence {
+            accused_node: mallory.node_id,
+            evidence_type: SlashingType::DoubleVoting,
+            proof,
+            timestamp: SystemTime::now(),
+        };
+        alice.handle_consensus_message(evidence.clone()).await.unwrap();
+
+        let slashed = alice.validators.read().await.get(&mallory.node_id).unwrap().clone();
+        assert_eq!(slashed.stake_amount, 1800);
+        assert_eq!(slashed.slashing_count, 1);
+        assert!(slashed.reputation_score < 100.0);
+        assert_eq!(alice.consensus_state.read().await.total_stake, 2800);
+
+        // The same proof cannot be used twice
+        alice.handle_consensus_message(evidence).await.unwrap();
+        assert_eq!(alice.validators.read().await.get(&mallory.node_id).unwrap().stake_amount, 1800);
+    }
+
+    #[tokio::test]
+    async fn test_forged_slashing_evidence_is_ignored() {
+        let (sender, _receiver) = mpsc::channel(test_config().message_queue_capacity);
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        alice.initialize(sender).await.unwrap();
+        let victim = ConsensusManager::new(Uuid::new_v4(), true, 2000, test_config()).unwrap();
+        let mallory = ConsensusManager::new(Uuid::new_v4(), true, 2000, test_config()).unwrap();
+        alice.handle_validator_join(victim.node_id, 2000, victim.public_key(), SystemTime::now()).await.unwrap();
+
+        // Votes signed by mallory but attributed to the victim
+        let mut first = signed_prevote(&mallory, Uuid::new_v4());
+        let mut second = signed_prevote(&mallory, Uuid::new_v4());
+        first.voter_id = victim.node_id;
+        second.voter_id = victim.node_id;
+        alice.handle_slashing_evidence(
+            victim.node_id,
+            SlashingType::DoubleVoting,
+            serde_json::to_vec(&(first, second)).unwrap(),
+            SystemTime::now(),
+        ).await.unwrap();
+
+        let validator = alice.validators.read().await.get(&victim.node_id).unwrap().clone();
+        assert_eq!(validator.stake_amount, 2000);
+        assert_eq!(validator.slashing_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_prevotes_produce_slashing_evidence() {
+        let (sender, mut receiver) = mpsc::channel(test_config().message_queue_capacity);
+        let mut alice = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        alice.initialize(sender).await.unwrap();
+        let mallory = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        let observer = ConsensusManager::new(Uuid::new_v4(), true, 1000, test_config()).unwrap();
+        for validator in [&mallory, &observer] {
+            alice.handle_validator_join(validator.node_id, 1000, validator.public_key(), SystemTime::now()).await.unwrap();
+        }
+
+        let proposal_id = Uuid::new_v4();
+        let support = signed_prevote(&mallory, proposal_id);
+        let against = Vote {
+            vote_type: VoteType::Prevote(false),
+            signature: mallory.sign_vote(&proposal_id, &VoteType::Prevote(false), 0, 0).unwrap(),
+            ..support.clone()
+        };
+        alice.handle_consensus_message(ConsensusMessage::Vote(support.clone())).await.unwrap();
+        // Resending the same vote is not an offence
+        alice.handle_consensus_message(ConsensusMessage::Vote(support)).await.unwrap();
+        alice.handle_consensus_message(ConsensusMessage::Vote(against)).await.unwrap();
+
+        let mut evidence = None;
+        while let Ok(message) = receiver.try_recv() {
+            if let ConsensusMessage::SlashingEvidence { accused_node, evidence_type, proof, .. } = message {
+                evidence = Some((accused_node, evidence_type, proof));
+            }
+        }
+        let (accused_node, evidence_type, proof) = evidence.expect("no slashing evidence emitted");
+        assert_eq!(accused_node, mallory.node_id);
+        assert!(matches!(evidence_type, SlashingType::Equivocation));
+        let (first, second): (Vote, Vote) = serde_json::from_slice(&proof).unwrap();
+        assert!(matches!(first.vote_type, VoteType::Prevote(true)));
+        assert!(matches!(second.vote_type, VoteType::Prevote(false)));
+
+        assert_eq!(alice.validators.read().await.get(&mallory.node_id).unwrap().slashing_count, 1);
+    }
+
+    #[test]
+    fn test_thresholds_below_two_thirds_are_rejected() {
+        for unsafe_threshold in [0.5, 0.66, 1.01, f64::NAN] {
+            let config = ConsensusConfig { consensus_threshold: unsafe_threshold, ..test_config() };
+            assert!(ConsensusManager::new(Uuid::new_v4(), true, 1000, config).is_err(), "{} accepted", unsafe_threshold);
+        }
+        let config = ConsensusConfig { consensus_threshold: MIN_CONSENSUS_THRESHOLD, ..test_config() };
+        assert!(ConsensusManager::new(Uuid::new_v4(), true, 1000, config).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_no_voting_power_never_reaches_a_threshold() {
+        let manager = ConsensusManager::new(Uuid::new_v4(), false, 0, test_config()).unwrap();
+        assert_eq!(manager.calculate_total_voting_power().await, 0.0);
+        let state = manager.consensus_state.read().await.clone();
+        assert!(!manager.check_prevote_threshold(&state).await.unwrap());
+        assert!(!manager.check_precommit_threshold(&state).await.unwrap());
+    }
+
+}
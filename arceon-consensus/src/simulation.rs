@@ -0,0 +1,220 @@
+//! Deterministic in-process network of consensus nodes, for exercising proposals, votes,
+//! view changes and finality without sockets or wall-clock timers.
+//!
+//! Messages a node sends are queued rather than delivered, and the test decides when
+//! they arrive: `step` delivers the oldest one to every node, the sender included, as a
+//! gossip broadcast would. Time only moves when `advance_clock` is called, which is also
+//! when proposers get their block-time tick and stalled steps time out.
+
+use crate::{BlockHash, ConsensusConfig, ConsensusManager, ConsensusMessage, StepTimer, WorldChange};
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Decides whether a message from the first node to the second is lost
+type DropRule = Box<dyn FnMut(Uuid, Uuid, &ConsensusMessage) -> bool + Send>;
+
+struct SimulatedNode {
+    manager: ConsensusManager,
+    outbox: mpsc::Receiver<ConsensusMessage>,
+    timer: StepTimer,
+}
+
+pub struct ConsensusSimulator {
+    nodes: Vec<SimulatedNode>,
+    in_flight: VecDeque<(Uuid, ConsensusMessage)>, // sender, message; oldest first
+    start: SystemTime,
+    elapsed: Duration,
+    drop_rule: Option<DropRule>,
+    dropped: usize,
+}
+
+impl ConsensusSimulator {
+    /// One validator per entry of `stakes`, every one registered with every other.
+    /// Node ids are fixed (1, 2, ...) so proposer selection is the same on every run.
+    pub async fn new(stakes: &[u64], config: ConsensusConfig) -> Result<Self> {
+        let mut nodes = Vec::with_capacity(stakes.len());
+        for (i, stake) in stakes.iter().enumerate() {
+            let node_id = Uuid::from_u128(i as u128 + 1);
+            let mut manager = ConsensusManager::new(node_id, true, *stake, config.clone())?;
+            let (sender, outbox) = mpsc::channel(config.message_queue_capacity);
+            manager.message_sender = Some(sender);
+            nodes.push(SimulatedNode { manager, outbox, timer: StepTimer::default() });
+        }
+
+        let start = SystemTime::now();
+        let validators: Vec<(Uuid, u64, [u8; 32])> = nodes.iter()
+            .map(|node| (node.manager.node_id, node.manager.stake_amount, node.manager.public_key()))
+            .collect();
+        for node in &mut nodes {
+            for (node_id, stake, public_key) in &validators {
+                node.manager.handle_validator_join(*node_id, *stake, *public_key, start).await?;
+            }
+            node.manager.begin_round().await;
+            node.manager.block_producer.write().await.last_block_time = start;
+        }
+
+        Ok(Self {
+            nodes,
+            in_flight: VecDeque::new(),
+            start,
+            elapsed: Duration::ZERO,
+            drop_rule: None,
+            dropped: 0,
+        })
+    }
+
+    pub fn node(&self, index: usize) -> &ConsensusManager {
+        &self.nodes[index].manager
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Current simulated time
+    pub fn now(&self) -> SystemTime {
+        self.start + self.elapsed
+    }
+
+    /// Lose every message for which `rule(from, to, message)` is true, from now on
+    pub fn set_drop_rule(&mut self, rule: impl FnMut(Uuid, Uuid, &ConsensusMessage) -> bool + Send + 'static) {
+        self.drop_rule = Some(Box::new(rule));
+    }
+
+    pub fn clear_drop_rule(&mut self) {
+        self.drop_rule = None;
+    }
+
+    /// Deliveries lost to the drop rule so far
+    pub fn dropped_messages(&self) -> usize {
+        self.dropped
+    }
+
+    /// Messages sent but not yet delivered
+    pub fn pending_messages(&mut self) -> usize {
+        self.collect_outboxes();
+        self.in_flight.len()
+    }
+
+    /// Queue `change` with the node due to propose the current round
+    pub async fn submit_world_change(&mut self, change: WorldChange) -> Result<()> {
+        let (epoch, round) = {
+            let state = self.nodes[0].manager.consensus_state.read().await;
+            (state.current_epoch, state.current_round)
+        };
+        let proposer = self.nodes[0].manager.current_proposer(epoch, round).await;
+        match self.nodes.iter_mut().find(|node| node.manager.node_id == proposer) {
+            Some(node) => node.manager.add_world_change(change).await,
+            None => Err(anyhow::anyhow!("No node is scheduled to propose epoch {} round {}", epoch, round)),
+        }
+    }
+
+    /// Deliver the oldest message in flight; false once there is nothing left to deliver
+    pub async fn step(&mut self) -> Result<bool> {
+        self.collect_outboxes();
+        let Some((from, message)) = self.in_flight.pop_front() else {
+            return Ok(false);
+        };
+
+        for node in &mut self.nodes {
+            let to = node.manager.node_id;
+            if self.drop_rule.as_mut().is_some_and(|rule| rule(from, to, &message)) {
+                self.dropped += 1;
+                continue;
+            }
+            // A node rejecting a message is part of what is being simulated, not a failure
+            if let Err(e) = node.manager.handle_consensus_message(message.clone()).await {
+                warn!("Simulated node {} failed to handle a message from {}: {}", to, from, e);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Deliver messages until none are left or `max_steps` have been delivered;
+    /// returns how many were
+    pub async fn run_until_idle(&mut self, max_steps: usize) -> Result<usize> {
+        let mut steps = 0;
+        while steps < max_steps && self.step().await? {
+            steps += 1;
+        }
+        Ok(steps)
+    }
+
+    /// Move the clock forward, giving every proposer its block-time tick and letting
+    /// stalled steps time out
+    pub async fn advance_clock(&mut self, by: Duration) -> Result<()> {
+        self.elapsed += by;
+        let now = self.now();
+        for node in &mut self.nodes {
+            node.manager.proposer_tick(now).await?;
+            node.manager.timeout_context().tick(&mut node.timer, self.elapsed).await;
+        }
+        Ok(())
+    }
+
+    /// Hashes of the blocks `index` has finalized, confirmed or not, in epoch order
+    pub async fn block_hashes(&self, index: usize) -> Vec<BlockHash> {
+        let finality_tracker = self.nodes[index].manager.finality_tracker.read().await;
+        finality_tracker.finalized_blocks.values()
+            .chain(finality_tracker.unconfirmed_blocks.values())
+            .map(|block| block.block_hash)
+            .collect()
+    }
+
+    fn collect_outboxes(&mut self) {
+        for node in &mut self.nodes {
+            while let Ok(message) = node.outbox.try_recv() {
+                self.in_flight.push_back((node.manager.node_id, message));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world_event(n: u64) -> WorldChange {
+        WorldChange::WorldEvent {
+            event_id: Uuid::from_u128(n as u128),
+            event_type: "FESTIVAL".to_string(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(n),
+            affected_areas: vec!["GLOBAL".to_string()],
+            data: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_four_nodes_agree_on_finalized_blocks() {
+        let config = ConsensusConfig::default();
+        let mut simulator = ConsensusSimulator::new(&[1000; 4], config.clone()).await.unwrap();
+
+        for epoch in 0..3 {
+            simulator.submit_world_change(world_event(epoch)).await.unwrap();
+            simulator.advance_clock(config.block_time).await.unwrap();
+            simulator.run_until_idle(1_000).await.unwrap();
+        }
+
+        let reference = simulator.block_hashes(0).await;
+        assert_eq!(reference.len(), 3);
+        for node in 1..simulator.node_count() {
+            assert_eq!(simulator.block_hashes(node).await, reference);
+        }
+        for node in 0..simulator.node_count() {
+            assert_eq!(simulator.node(node).consensus_state.read().await.current_epoch, 3);
+        }
+
+        // Cut one node off: the other three still hold enough stake to finalize
+        let isolated = simulator.node(3).node_id;
+        simulator.set_drop_rule(move |from, to, _| from == isolated || to == isolated);
+        simulator.submit_world_change(world_event(3)).await.ok();
+        simulator.advance_clock(config.block_time).await.unwrap();
+        simulator.run_until_idle(1_000).await.unwrap();
+        assert!(simulator.dropped_messages() > 0);
+        assert_eq!(simulator.block_hashes(3).await.len(), 3);
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::{SendTimeoutError, TryRecvError, TrySendError}};
+use tokio::sync::Notify;
+
+/// Queue length used when no capacity is configured
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 1024;
+
+/// How long a consensus message waits for room when no timeout is configured
+pub const DEFAULT_SEND_TIMEOUT: Duration = Duration::from_millis(500);
+
+struct GossipQueue<T> {
+    capacity: usize,
+    messages: Mutex<VecDeque<T>>,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+    ready: Notify,
+    dropped: AtomicU64,
+}
+
+/// A bounded queue for gossip, where the newest news matters most: when it is full the
+/// oldest queued message is dropped to make room, and the drop is counted.
+pub fn gossip_channel<T>(capacity: usize) -> (GossipSender<T>, GossipReceiver<T>) {
+    let queue = Arc::new(GossipQueue {
+        capacity: capacity.max(1),
+        messages: Mutex::new(VecDeque::new()),
+        senders: AtomicUsize::new(1),
+        receiver_alive: AtomicBool::new(true),
+        ready: Notify::new(),
+        dropped: AtomicU64::new(0),
+    });
+    (GossipSender { queue: queue.clone() }, GossipReceiver { queue })
+}
+
+/// Sending half of a `gossip_channel`
+pub struct GossipSender<T> {
+    queue: Arc<GossipQueue<T>>,
+}
+
+impl<T> Clone for GossipSender<T> {
+    fn clone(&self) -> Self {
+        self.queue.senders.fetch_add(1, Ordering::AcqRel);
+        Self { queue: self.queue.clone() }
+    }
+}
+
+impl<T> Drop for GossipSender<T> {
+    fn drop(&mut self) {
+        if self.queue.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.queue.ready.notify_one();
+        }
+    }
+}
+
+impl<T> GossipSender<T> {
+    /// Queue `message`, dropping the oldest queued one if the queue is full.
+    /// Fails, handing the message back, only once the receiver is gone.
+    pub fn send(&self, message: T) -> Result<(), T> {
+        if !self.queue.receiver_alive.load(Ordering::Acquire) {
+            return Err(message);
+        }
+        {
+            let mut messages = self.queue.messages.lock().unwrap();
+            if messages.len() >= self.queue.capacity {
+                messages.pop_front();
+                self.queue.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            messages.push_back(message);
+        }
+        self.queue.ready.notify_one();
+        Ok(())
+    }
+
+    /// Messages dropped because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Receiving half of a `gossip_channel`
+pub struct GossipReceiver<T> {
+    queue: Arc<GossipQueue<T>>,
+}
+
+impl<T> Drop for GossipReceiver<T> {
+    fn drop(&mut self) {
+        self.queue.receiver_alive.store(false, Ordering::Release);
+    }
+}
+
+impl<T> GossipReceiver<T> {
+    /// Next message, oldest first; None once the queue is empty and every sender is gone
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.try_recv() {
+                Ok(message) => return Some(message),
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => self.queue.ready.notified().await,
+            }
+        }
+    }
+
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        if let Some(message) = self.queue.messages.lock().unwrap().pop_front() {
+            return Ok(message);
+        }
+        if self.queue.senders.load(Ordering::Acquire) == 0 {
+            return Err(TryRecvError::Disconnected);
+        }
+        Err(TryRecvError::Empty)
+    }
+
+    /// Messages waiting to be received
+    pub fn len(&self) -> usize {
+        self.queue.messages.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Messages dropped because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// A bounded queue for consensus traffic, where every message matters: a full queue makes
+/// the sender wait up to `timeout` for room, and only then is the message given up on and
+/// counted as saturation.
+pub fn consensus_channel<T>(capacity: usize, timeout: Duration) -> (ConsensusSender<T>, mpsc::Receiver<T>) {
+    let (inner, receiver) = mpsc::channel(capacity.max(1));
+    (ConsensusSender { inner, timeout, saturated: Arc::new(AtomicU64::new(0)) }, receiver)
+}
+
+/// Sending half of a `consensus_channel`
+#[derive(Debug)]
+pub struct ConsensusSender<T> {
+    inner: mpsc::Sender<T>,
+    timeout: Duration,
+    saturated: Arc<AtomicU64>,
+}
+
+impl<T> Clone for ConsensusSender<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), timeout: self.timeout, saturated: self.saturated.clone() }
+    }
+}
+
+impl<T> ConsensusSender<T> {
+    /// Queue `message`, waiting up to the channel's timeout for room
+    pub async fn send(&self, message: T) -> Result<()> {
+        match self.inner.send_timeout(message, self.timeout).await {
+            Ok(()) => Ok(()),
+            Err(SendTimeoutError::Timeout(_)) => {
+                self.saturated.fetch_add(1, Ordering::Relaxed);
+                Err(anyhow::anyhow!("Consensus queue stayed full for {:?}; message dropped", self.timeout))
+            }
+            Err(SendTimeoutError::Closed(_)) => Err(anyhow::anyhow!("Consensus queue receiver has stopped")),
+        }
+    }
+
+    /// Queue `message` if there is room right now, for callers that cannot wait
+    pub fn try_send(&self, message: T) -> Result<()> {
+        match self.inner.try_send(message) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.saturated.fetch_add(1, Ordering::Relaxed);
+                Err(anyhow::anyhow!("Consensus queue is full; message dropped"))
+            }
+            Err(TrySendError::Closed(_)) => Err(anyhow::anyhow!("Consensus queue receiver has stopped")),
+        }
+    }
+
+    /// Messages dropped because the queue stayed full, across every clone of this sender
+    pub fn saturated(&self) -> u64 {
+        self.saturated.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::entities::being::{Being, ExperienceSource};
+use crate::state::GameState;
+
+/// Damage a hit does before the attacker's skills and the defender's Defense count
+pub const BASE_DAMAGE: f64 = 5.0;
+
+/// Strength experience for landing the killing blow
+pub const KILL_EXPERIENCE: f64 = 50.0;
+
+/// World time (milliseconds) between two swings while autoattacking
+pub const AUTOATTACK_INTERVAL: u64 = 2_000;
+
+/// Who a player is fighting
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CombatTarget {
+    Npc(Uuid),
+    Player(String), // player_id
+}
+
+/// What one swing did
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttackOutcome {
+    pub attacker: String, // player_id
+    pub target: CombatTarget,
+    pub target_name: String,
+    pub area_id: String,
+    pub location: String, // where an NPC target was standing; empty for players
+    pub damage: f64,
+    pub target_health: f64,
+    pub killed: bool,
+    pub loot: Vec<Uuid>, // item ids taken from a slain NPC
+}
+
+/// Damage of one hit from `attacker` on `defender`. Strength (and half of Dexterity)
+/// adds to the base; Defense takes off a share that grows with diminishing returns.
+pub fn damage(attacker: &Being, defender: &Being) -> f64 {
+    let level = |being: &Being, skill: &str| being.skills.skills.get(skill).map_or(0.0, |skill| skill.level);
+    let offense = BASE_DAMAGE + level(attacker, "Strength") + level(attacker, "Dexterity") * 0.5;
+    let defense = level(defender, "Defense");
+    (offense * 50.0 / (50.0 + defense)).max(1.0)
+}
+
+impl GameState {
+    /// NPC or other player called `name` in the same area as `player_id`, ignoring case
+    pub fn find_combat_target(&self, player_id: &str, name: &str) -> Option<CombatTarget> {
+        let area_id = &self.online_players.get(player_id)?.current_area_id;
+
+        let mut npcs: Vec<_> = self.npcs.iter()
+            .filter(|(_, npc)| npc.current_area == *area_id && npc.being.name.eq_ignore_ascii_case(name))
+            .map(|(npc_id, _)| *npc_id)
+            .collect();
+        npcs.sort();
+        if let Some(npc_id) = npcs.first() {
+            return Some(CombatTarget::Npc(*npc_id));
+        }
+
+        self.find_online_player(name)
+            .filter(|target_id| *target_id != player_id && self.online_players[*target_id].current_area_id == *area_id)
+            .map(|target_id| CombatTarget::Player(target_id.to_string()))
+    }
+
+    /// Swing at `target`. A slain NPC is removed from the world and its inventory goes to
+    /// the attacker; a slain player is reincarnated on the spot with full vitals and their
+    /// skills intact. Either way, everyone fighting the target stops.
+    pub fn attack(&mut self, player_id: &str, target: &CombatTarget) -> Result<AttackOutcome, String> {
+        let player_data = self.online_players.get(player_id)
+            .ok_or_else(|| format!("Player {} not found", player_id))?;
+        let (attacker_being_id, area_id) = (player_data.being_id.clone(), player_data.current_area_id.clone());
+        if *target == CombatTarget::Player(player_id.to_string()) {
+            return Err("You cannot attack yourself.".to_string());
+        }
+
+        let attacker = self.beings.get(&attacker_being_id).ok_or_else(|| "Character not found.".to_string())?;
+        let attacker_name = attacker.name.clone();
+        let (defender, location) = match target {
+            CombatTarget::Npc(npc_id) => {
+                let npc = self.npcs.get(npc_id)
+                    .filter(|npc| npc.current_area == area_id)
+                    .ok_or_else(|| "Your target is no longer here.".to_string())?;
+                (&npc.being, npc.current_location.clone())
+            }
+            CombatTarget::Player(target_id) => {
+                let being = self.online_players.get(target_id)
+                    .filter(|data| data.current_area_id == area_id)
+                    .and_then(|data| self.beings.get(&data.being_id))
+                    .ok_or_else(|| "Your target is no longer here.".to_string())?;
+                (being, String::new())
+            }
+        };
+        let damage = damage(attacker, defender);
+        let target_name = defender.name.clone();
+
+        let defender = match target {
+            CombatTarget::Npc(npc_id) => &mut self.npcs.get_mut(npc_id).expect("checked above").being,
+            CombatTarget::Player(target_id) => {
+                let being_id = &self.online_players[target_id].being_id;
+                self.beings.get_mut(being_id).expect("checked above")
+            }
+        };
+        defender.vitals.health.current = (defender.vitals.health.current - damage).max(0.0);
+        defender.gain_skill_experience("Defense", damage * 0.5, ExperienceSource::TakingDamage);
+        let target_health = defender.vitals.health.current;
+        let killed = target_health <= 0.0;
+
+        let mut loot = Vec::new();
+        if killed {
+            match target {
+                CombatTarget::Npc(npc_id) => {
+                    let npc = self.npcs.remove(npc_id).expect("checked above");
+                    if let Some(location) = self.areas.get_mut(&npc.current_area).and_then(|area| area.locations.get_mut(&npc.current_location)) {
+                        location.npcs_present.retain(|id| id != npc_id);
+                    }
+                    if let (Some(dropped), Some(inventory)) = (
+                        npc.being.inventory,
+                        self.beings.get_mut(&attacker_being_id).and_then(|being| being.inventory.as_mut()),
+                    ) {
+                        loot = dropped.items.keys().copied().collect();
+                        loot.sort();
+                        inventory.items.extend(dropped.items);
+                    }
+                }
+                CombatTarget::Player(_) => {
+                    defender.die(format!("Slain by {}", attacker_name), true);
+                }
+            }
+            self.end_combat_with(target);
+        }
+
+        if let Some(attacker) = self.beings.get_mut(&attacker_being_id) {
+            attacker.gain_skill_experience("Strength", damage, ExperienceSource::DealingDamage);
+            if killed {
+                attacker.gain_skill_experience("Strength", KILL_EXPERIENCE, ExperienceSource::CombatVictory);
+            }
+        }
+
+        Ok(AttackOutcome {
+            attacker: player_id.to_string(),
+            target: target.clone(),
+            target_name,
+            area_id,
+            location,
+            damage,
+            target_health,
+            killed,
+            loot,
+        })
+    }
+
+    /// Stop everyone who is fighting `target`, and `target` itself if it is a player
+    pub fn end_combat_with(&mut self, target: &CombatTarget) {
+        let attackers: Vec<String> = self.combat_targets.iter()
+            .filter(|(_, fighting)| *fighting == target)
+            .map(|(player_id, _)| player_id.clone())
+            .collect();
+        for player_id in attackers {
+            self.combat_targets.remove(&player_id);
+            self.autoattacking.remove(&player_id);
+        }
+        if let CombatTarget::Player(player_id) = target {
+            self.combat_targets.remove(player_id);
+            self.autoattacking.remove(player_id);
+        }
+    }
+}
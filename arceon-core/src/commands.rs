@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A player command parsed from text input.
+///
+/// Clients parse what the player typed into this type and send it to the server,
+/// which executes it. Keeping the parsing in one place means the GUI, the HTTP server
+/// and the core engine all agree on what a command means.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum GameCommand {
+    Look { target: Option<String> },
+    Move { destination: String },
+    Enter { structure: String },
+    Exit,
+    Who,
+    Stats,
+    Skills,
+    Profile,
+    Inventory,
+    Pickup { item: String },
+    Drop { item: String },
+    Say { message: String },
+    Tell { target: String, message: String },
+    TradeOffer { player: String, goods: String },
+    TradeAccept,
+    TradeCancel,
+    Talk { npc: String },
+    Attack { target: String },
+    AutoAttack { enabled: Option<bool> },
+    MacroCreate { name: String, steps: String },
+    MacroList,
+    MacroDelete { name: String },
+    MacroRun { name: String },
+    Build { structure_type: String, name: String },
+    Admin { action: AdminCommand },
+    Npcs,
+    Quests,
+    Reputation,
+    Save,
+    Help,
+    Unknown { input: String },
+}
+
+/// Operator commands. Only players flagged as admins may run them; anyone else is told
+/// the command doesn't exist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminCommand {
+    Teleport { area: String },
+    SpawnItem { item_type: String },
+    Kick { player: String },
+    Checkpoint,
+}
+
+impl AdminCommand {
+    pub fn verb(&self) -> &'static str {
+        match self {
+            AdminCommand::Teleport { .. } => "teleport",
+            AdminCommand::SpawnItem { .. } => "spawn_item",
+            AdminCommand::Kick { .. } => "kick",
+            AdminCommand::Checkpoint => "checkpoint",
+        }
+    }
+}
+
+impl GameCommand {
+    /// Parse raw player input. Returns `None` for blank input.
+    pub fn parse(input: &str) -> Option<Self> {
+        let parts: Vec<&str> = input.split_whitespace().collect();
+        let (verb, args) = parts.split_first()?;
+        let rest = args.join(" ");
+
+        let command = match verb.to_lowercase().as_str() {
+            "look" | "l" => GameCommand::Look {
+                target: if rest.is_empty() { None } else { Some(rest) },
+            },
+            "move" | "go" => GameCommand::Move { destination: rest.to_lowercase() },
+            "enter" => GameCommand::Enter { structure: rest },
+            "exit" => GameCommand::Exit,
+            "who" => GameCommand::Who,
+            "stats" => GameCommand::Stats,
+            "skills" => GameCommand::Skills,
+            "profile" => GameCommand::Profile,
+            "inventory" | "inv" | "i" => GameCommand::Inventory,
+            "pickup" | "get" | "take" => GameCommand::Pickup { item: rest },
+            "drop" => GameCommand::Drop { item: rest },
+            "say" => GameCommand::Say { message: rest },
+            "tell" | "whisper" => GameCommand::Tell {
+                target: args.first().map(|target| target.to_string()).unwrap_or_default(),
+                message: args.iter().skip(1).copied().collect::<Vec<_>>().join(" "),
+            },
+            "trade" => match args.first().map(|arg| arg.to_lowercase()).as_deref() {
+                Some("accept") => GameCommand::TradeAccept,
+                Some("cancel") => GameCommand::TradeCancel,
+                _ => GameCommand::TradeOffer {
+                    player: args.first().map(|player| player.to_string()).unwrap_or_default(),
+                    goods: match args.get(1) {
+                        Some(word) if word.eq_ignore_ascii_case("offer") => args[2..].join(" "),
+                        _ => String::new(),
+                    },
+                },
+            },
+            "talk" | "speak" => GameCommand::Talk { npc: rest },
+            "attack" | "kill" => GameCommand::Attack { target: rest },
+            "autoattack" => GameCommand::AutoAttack {
+                enabled: match rest.to_lowercase().as_str() {
+                    "on" => Some(true),
+                    "off" => Some(false),
+                    _ => None,
+                },
+            },
+            "macro" => {
+                let name = args.get(1).map(|name| name.to_string()).unwrap_or_default();
+                match args.first().map(|arg| arg.to_lowercase()).as_deref() {
+                    Some("create") => GameCommand::MacroCreate {
+                        name,
+                        steps: args.get(2..).map(|steps| steps.join(" ")).unwrap_or_default(),
+                    },
+                    Some("delete") => GameCommand::MacroDelete { name },
+                    Some("run") => GameCommand::MacroRun { name },
+                    _ => GameCommand::MacroList,
+                }
+            },
+            "build" | "construct" => GameCommand::Build {
+                structure_type: args.first().map(|structure_type| structure_type.to_lowercase()).unwrap_or_default(),
+                name: args.iter().skip(1).copied().collect::<Vec<_>>().join(" "),
+            },
+            "teleport" => GameCommand::Admin { action: AdminCommand::Teleport { area: rest } },
+            "spawn_item" => GameCommand::Admin { action: AdminCommand::SpawnItem { item_type: rest } },
+            "kick" => GameCommand::Admin { action: AdminCommand::Kick { player: rest } },
+            "checkpoint" => GameCommand::Admin { action: AdminCommand::Checkpoint },
+            "npcs" => GameCommand::Npcs,
+            "quests" => GameCommand::Quests,
+            "reputation" | "rep" => GameCommand::Reputation,
+            "save" => GameCommand::Save,
+            "help" => GameCommand::Help,
+            _ => GameCommand::Unknown { input: parts.join(" ") },
+        };
+
+        Some(command)
+    }
+}
+
+/// Canonical text form; parsing it yields the same command again
+impl fmt::Display for GameCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (verb, arg) = match self {
+            GameCommand::Look { target } => ("look", target.as_deref().unwrap_or("")),
+            GameCommand::Move { destination } => ("move", destination.as_str()),
+            GameCommand::Enter { structure } => ("enter", structure.as_str()),
+            GameCommand::Exit => ("exit", ""),
+            GameCommand::Who => ("who", ""),
+            GameCommand::Stats => ("stats", ""),
+            GameCommand::Skills => ("skills", ""),
+            GameCommand::Profile => ("profile", ""),
+            GameCommand::Inventory => ("inventory", ""),
+            GameCommand::Pickup { item } => ("pickup", item.as_str()),
+            GameCommand::Drop { item } => ("drop", item.as_str()),
+            GameCommand::Say { message } => ("say", message.as_str()),
+            GameCommand::Tell { target, message } => {
+                return write!(f, "{}", format!("tell {} {}", target, message).trim_end());
+            }
+            GameCommand::TradeOffer { player, goods } => {
+                return match (player.is_empty(), goods.is_empty()) {
+                    (true, _) => write!(f, "trade"),
+                    (false, true) => write!(f, "trade {}", player),
+                    (false, false) => write!(f, "trade {} offer {}", player, goods),
+                };
+            }
+            GameCommand::TradeAccept => ("trade", "accept"),
+            GameCommand::TradeCancel => ("trade", "cancel"),
+            GameCommand::Talk { npc } => ("talk", npc.as_str()),
+            GameCommand::Attack { target } => ("attack", target.as_str()),
+            GameCommand::AutoAttack { enabled } => ("autoattack", match enabled {
+                Some(true) => "on",
+                Some(false) => "off",
+                None => "",
+            }),
+            GameCommand::MacroCreate { name, steps } => {
+                return write!(f, "{}", format!("macro create {} {}", name, steps).trim_end());
+            }
+            GameCommand::MacroList => ("macro", "list"),
+            GameCommand::MacroDelete { name } => return write!(f, "{}", format!("macro delete {}", name).trim_end()),
+            GameCommand::MacroRun { name } => return write!(f, "{}", format!("macro run {}", name).trim_end()),
+            GameCommand::Build { structure_type, name } => {
+                return write!(f, "{}", format!("build {} {}", structure_type, name).trim_end());
+            }
+            GameCommand::Admin { action } => (action.verb(), match action {
+                AdminCommand::Teleport { area } => area.as_str(),
+                AdminCommand::SpawnItem { item_type } => item_type.as_str(),
+                AdminCommand::Kick { player } => player.as_str(),
+                AdminCommand::Checkpoint => "",
+            }),
+            GameCommand::Npcs => ("npcs", ""),
+            GameCommand::Quests => ("quests", ""),
+            GameCommand::Reputation => ("reputation", ""),
+            GameCommand::Save => ("save", ""),
+            GameCommand::Help => ("help", ""),
+            GameCommand::Unknown { input } => return write!(f, "{}", input),
+        };
+
+        if arg.is_empty() {
+            write!(f, "{}", verb)
+        } else {
+            write!(f, "{} {}", verb, arg)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_and_server_parse_identically() {
+        let inputs = [
+            ("look", GameCommand::Look { target: None }),
+            ("l  old  man", GameCommand::Look { target: Some("old man".to_string()) }),
+            ("go North", GameCommand::Move { destination: "north".to_string() }),
+            ("move 2", GameCommand::Move { destination: "2".to_string() }),
+            ("enter The Inn", GameCommand::Enter { structure: "The Inn".to_string() }),
+            ("take Rusty Sword", GameCommand::Pickup { item: "Rusty Sword".to_string() }),
+            ("inv", GameCommand::Inventory),
+            ("say hello  there", GameCommand::Say { message: "hello there".to_string() }),
+            ("tell Aria  meet me  at the gate", GameCommand::Tell { target: "Aria".to_string(), message: "meet me at the gate".to_string() }),
+            ("trade Bram offer 30 tokens", GameCommand::TradeOffer { player: "Bram".to_string(), goods: "30 tokens".to_string() }),
+            ("trade ACCEPT", GameCommand::TradeAccept),
+            ("speak Elder Maren", GameCommand::Talk { npc: "Elder Maren".to_string() }),
+            ("kill Dire Wolf", GameCommand::Attack { target: "Dire Wolf".to_string() }),
+            ("autoattack OFF", GameCommand::AutoAttack { enabled: Some(false) }),
+            ("autoattack", GameCommand::AutoAttack { enabled: None }),
+            ("macro create opener Taunt, wait 2, Fire Magic", GameCommand::MacroCreate {
+                name: "opener".to_string(),
+                steps: "Taunt, wait 2, Fire Magic".to_string(),
+            }),
+            ("macro", GameCommand::MacroList),
+            ("macro RUN opener", GameCommand::MacroRun { name: "opener".to_string() }),
+            ("build House  Oak Hall", GameCommand::Build { structure_type: "house".to_string(), name: "Oak Hall".to_string() }),
+            ("teleport  Misty Marshlands", GameCommand::Admin { action: AdminCommand::Teleport { area: "Misty Marshlands".to_string() } }),
+            ("checkpoint", GameCommand::Admin { action: AdminCommand::Checkpoint }),
+            ("REP", GameCommand::Reputation),
+            ("save", GameCommand::Save),
+            ("dance wildly", GameCommand::Unknown { input: "dance wildly".to_string() }),
+        ];
+
+        for (input, expected) in inputs {
+            // What the GUI builds from the player's text
+            let from_client = GameCommand::parse(input).unwrap();
+            assert_eq!(from_client, expected, "input: {}", input);
+
+            // What the server receives over the wire
+            let wire = serde_json::to_string(&from_client).unwrap();
+            let from_server: GameCommand = serde_json::from_str(&wire).unwrap();
+            assert_eq!(from_server, expected);
+
+            // What core sees when handed the canonical text form
+            assert_eq!(GameCommand::parse(&from_client.to_string()).unwrap(), expected);
+        }
+
+        assert_eq!(GameCommand::parse("   "), None);
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::entities::being::SkillCurve;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
@@ -16,6 +17,69 @@ pub struct NetworkConfig {
     pub bootstrap_nodes: Vec<String>,
     pub max_peers: usize,
     pub discovery_interval: u64,
+    /// Longest wait (seconds) between redials of an unreachable bootstrap node
+    #[serde(default = "default_bootstrap_backoff_max")]
+    pub bootstrap_backoff_max: u64,
+    /// Most epochs served in one sync response
+    #[serde(default = "default_sync_batch_size")]
+    pub sync_batch_size: u64,
+    /// Byte cap for one encoded sync response; must stay under gossipsub's max transmit size
+    #[serde(default = "default_max_sync_message_bytes")]
+    pub max_sync_message_bytes: usize,
+    /// Seconds a sync may wait on a request before asking again for the epochs still missing
+    #[serde(default = "default_sync_timeout")]
+    pub sync_timeout: u64,
+    /// Gossip messages per second accepted from one peer once its burst is spent
+    #[serde(default = "default_peer_message_rate")]
+    pub peer_message_rate: f64,
+    /// Messages a peer may send back to back before the rate limit applies
+    #[serde(default = "default_peer_message_burst")]
+    pub peer_message_burst: u32,
+    /// Gossip area-local traffic (chat, NPCs, area events) on per-area topics, so a node
+    /// only hears about the areas it subscribes to
+    #[serde(default)]
+    pub area_topics: bool,
+    /// Group areas onto this many shard topics instead of one topic each; 0 for one per area
+    #[serde(default)]
+    pub area_topic_shards: u32,
+    /// Recently handled messages remembered so a payload arriving twice is handled once
+    #[serde(default = "default_dedup_cache_size")]
+    pub dedup_cache_size: usize,
+    /// Outbound gossip messages queued for the event loop; when full the oldest is dropped
+    #[serde(default = "default_gossip_queue_capacity")]
+    pub gossip_queue_capacity: usize,
+}
+
+fn default_bootstrap_backoff_max() -> u64 {
+    300
+}
+
+fn default_sync_batch_size() -> u64 {
+    50
+}
+
+fn default_max_sync_message_bytes() -> usize {
+    60 * 1024
+}
+
+fn default_sync_timeout() -> u64 {
+    30
+}
+
+fn default_peer_message_rate() -> f64 {
+    20.0
+}
+
+fn default_peer_message_burst() -> u32 {
+    50
+}
+
+fn default_dedup_cache_size() -> usize {
+    4096
+}
+
+fn default_gossip_queue_capacity() -> usize {
+    crate::channel::DEFAULT_CHANNEL_CAPACITY
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -26,6 +90,104 @@ pub struct BlockchainConfig {
     pub block_time: u64,
     pub reward_amount: u64,
     pub network_name: String,
+    #[serde(default)]
+    pub fee_schedule: FeeSchedule,
+    /// How far above its stake-weighted share a proposer may go before it is flagged (0.25 = 25%)
+    #[serde(default = "default_fairness_max_deviation")]
+    pub fairness_max_deviation: f64,
+    /// Fixed genesis time (unix seconds); every node on a network must agree on it
+    #[serde(default = "default_genesis_timestamp")]
+    pub genesis_timestamp: u64,
+    /// Automatic checkpoints to keep, newest first; 0 keeps all of them
+    #[serde(default = "default_checkpoint_retention")]
+    pub checkpoint_retention: usize,
+    /// Every Nth epoch keeps a full world snapshot; epochs between keep only their changes
+    /// and are rebuilt on demand. 1 keeps a full snapshot every epoch.
+    #[serde(default = "default_snapshot_interval")]
+    pub snapshot_interval: u64,
+    /// Reject world transactions not signed by their initiator's registered key.
+    /// Off by default so a single dev node works without key setup.
+    #[serde(default)]
+    pub require_transaction_signatures: bool,
+    /// Seconds a locked bridge transfer waits for completion before it can be refunded
+    #[serde(default = "default_bridge_refund_timeout_secs")]
+    pub bridge_refund_timeout_secs: u64,
+    /// Finalized epochs to keep blocks for; older ones are pruned as new blocks arrive.
+    /// 0 keeps the whole history.
+    #[serde(default = "default_max_history_epochs")]
+    pub max_history_epochs: u64,
+    /// Where the read-only HTTP query API listens when built with the `http-api` feature
+    #[serde(default = "default_http_bind_address")]
+    pub http_bind_address: String,
+    /// Consensus messages queued for the message loop before senders have to wait
+    #[serde(default = "default_consensus_queue_capacity")]
+    pub consensus_queue_capacity: usize,
+    /// How long (milliseconds) a consensus message waits for room in a full queue before it is dropped
+    #[serde(default = "default_consensus_send_timeout_ms")]
+    pub consensus_send_timeout_ms: u64,
+}
+
+fn default_consensus_queue_capacity() -> usize {
+    crate::channel::DEFAULT_CHANNEL_CAPACITY
+}
+
+fn default_consensus_send_timeout_ms() -> u64 {
+    crate::channel::DEFAULT_SEND_TIMEOUT.as_millis() as u64
+}
+
+fn default_http_bind_address() -> String {
+    "127.0.0.1:8645".to_string()
+}
+
+fn default_max_history_epochs() -> u64 {
+    10_000
+}
+
+fn default_bridge_refund_timeout_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_snapshot_interval() -> u64 {
+    10
+}
+
+fn default_checkpoint_retention() -> usize {
+    10
+}
+
+fn default_genesis_timestamp() -> u64 {
+    1_735_689_600 // 2025-01-01T00:00:00Z
+}
+
+fn default_fairness_max_deviation() -> f64 {
+    0.25
+}
+
+/// Base fee, in native token units, charged per world-change transaction type
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FeeSchedule {
+    pub player_action: u64,
+    pub npc_action: u64,
+    pub area_update: u64,
+    pub skill_discovery: u64,
+    pub global_event: u64,
+    pub structure_construction: u64,
+    pub item_transfer: u64,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self {
+            player_action: 1,
+            npc_action: 0,
+            area_update: 5,
+            skill_discovery: 10,
+            global_event: 50,
+            structure_construction: 25,
+            item_transfer: 2,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -35,6 +197,26 @@ pub struct WorldConfig {
     pub city_count: usize,
     pub npc_population: usize,
     pub respawn_timers: HashMap<String, u64>,
+    /// Areas kept in memory before idle ones are paged to disk
+    #[serde(default = "default_max_resident_areas")]
+    pub max_resident_areas: usize,
+    /// World time an unpopulated area stays resident after players leave its vicinity
+    #[serde(default = "default_area_eviction_grace")]
+    pub area_eviction_grace: u64,
+    /// Player ids allowed to run admin commands (teleport, spawn_item, kick, checkpoint)
+    #[serde(default)]
+    pub admin_players: Vec<String>,
+    /// Experience curve by skill name; skills not listed use `SkillCurve::default()`
+    #[serde(default)]
+    pub skill_curves: HashMap<String, SkillCurve>,
+}
+
+fn default_max_resident_areas() -> usize {
+    256
+}
+
+fn default_area_eviction_grace() -> u64 {
+    600
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -69,6 +251,16 @@ impl Config {
                 bootstrap_nodes: vec![],
                 max_peers: 50,
                 discovery_interval: 30,
+                bootstrap_backoff_max: default_bootstrap_backoff_max(),
+                sync_batch_size: default_sync_batch_size(),
+                max_sync_message_bytes: default_max_sync_message_bytes(),
+                sync_timeout: default_sync_timeout(),
+                peer_message_rate: default_peer_message_rate(),
+                peer_message_burst: default_peer_message_burst(),
+                area_topics: false,
+                area_topic_shards: 0,
+                dedup_cache_size: default_dedup_cache_size(),
+                gossip_queue_capacity: default_gossip_queue_capacity(),
             },
             blockchain: BlockchainConfig {
                 data_dir: "./data/blockchain".to_string(),
@@ -77,6 +269,17 @@ impl Config {
                 block_time: 10,
                 reward_amount: 100,
                 network_name: "arceon_mainnet".to_string(),
+                fee_schedule: FeeSchedule::default(),
+                fairness_max_deviation: default_fairness_max_deviation(),
+                genesis_timestamp: default_genesis_timestamp(),
+                checkpoint_retention: default_checkpoint_retention(),
+                snapshot_interval: default_snapshot_interval(),
+                require_transaction_signatures: false,
+                bridge_refund_timeout_secs: default_bridge_refund_timeout_secs(),
+                max_history_epochs: default_max_history_epochs(),
+                http_bind_address: default_http_bind_address(),
+                consensus_queue_capacity: default_consensus_queue_capacity(),
+                consensus_send_timeout_ms: default_consensus_send_timeout_ms(),
             },
             world: WorldConfig {
                 seed: 12345,
@@ -88,8 +291,13 @@ impl Config {
                     timers.insert("npc".to_string(), 300);
                     timers.insert("player".to_string(), 120);
                     timers.insert("beast".to_string(), 180);
+                    timers.insert("resource".to_string(), crate::environment::DEFAULT_RESOURCE_RESPAWN_SECS);
                     timers
                 },
+                max_resident_areas: default_max_resident_areas(),
+                area_eviction_grace: default_area_eviction_grace(),
+                admin_players: Vec::new(),
+                skill_curves: HashMap::new(),
             },
             ai: AiConfig {
                 npc_think_interval: 5,
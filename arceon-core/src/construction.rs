@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+use crate::state::GameState;
+
+/// Kinds of structure a player can put up with `build`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StructureType {
+    House,
+    Shop,
+    Tavern,
+    Blacksmith,
+    Library,
+    Temple,
+    Tower,
+    Wall,
+}
+
+impl StructureType {
+    pub const ALL: [StructureType; 8] = [
+        StructureType::House,
+        StructureType::Shop,
+        StructureType::Tavern,
+        StructureType::Blacksmith,
+        StructureType::Library,
+        StructureType::Temple,
+        StructureType::Tower,
+        StructureType::Wall,
+    ];
+
+    /// Structure type named `name`, ignoring case
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|structure_type| structure_type.to_string().eq_ignore_ascii_case(name))
+    }
+
+    /// Items (by name) and how many of each go into building one
+    pub fn materials(&self) -> &'static [(&'static str, u32)] {
+        match self {
+            StructureType::House => &[("Wood", 10), ("Stone", 5)],
+            StructureType::Shop => &[("Wood", 12), ("Stone", 6)],
+            StructureType::Tavern => &[("Wood", 20), ("Stone", 10)],
+            StructureType::Blacksmith => &[("Stone", 15), ("Iron Ore", 5)],
+            StructureType::Library => &[("Wood", 15), ("Stone", 10)],
+            StructureType::Temple => &[("Stone", 25)],
+            StructureType::Tower => &[("Stone", 30), ("Wood", 5)],
+            StructureType::Wall => &[("Stone", 8)],
+        }
+    }
+}
+
+impl fmt::Display for StructureType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A structure standing in an area, with who built it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Structure {
+    pub id: Uuid,
+    pub name: String,
+    pub structure_type: StructureType,
+    pub builder_id: String, // being_id
+    pub builder_name: String,
+    pub built_at: u64, // world_time
+}
+
+impl GameState {
+    /// Structures standing in `area_id`, oldest first
+    pub fn structures_in(&self, area_id: &str) -> &[Structure] {
+        self.structures.get(area_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Build a `structure_type` called `name` in the player's area, using up the materials
+    /// it needs from their inventory. Nothing is taken unless every material is there.
+    /// Returns the structure and the area it stands in.
+    pub fn build_structure(&mut self, player_id: &str, structure_type: &str, name: &str) -> Result<(Structure, String), String> {
+        let structure_type = StructureType::parse(structure_type).ok_or_else(|| {
+            let known: Vec<String> = StructureType::ALL.iter().map(|t| t.to_string().to_lowercase()).collect();
+            format!("You don't know how to build a {}. Try one of: {}", structure_type, known.join(", "))
+        })?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("What will you call it? Use: build <type> <name>".to_string());
+        }
+
+        let player_data = self.online_players.get(player_id)
+            .ok_or_else(|| format!("Player {} not found", player_id))?;
+        let (being_id, area_id) = (player_data.being_id.clone(), player_data.current_area_id.clone());
+        if !self.areas.contains_key(&area_id) {
+            return Err("You are in an unknown location.".to_string());
+        }
+        if self.structures_in(&area_id).iter().any(|structure| structure.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("There is already a structure called {} here.", name));
+        }
+
+        let items = &self.items;
+        let being = self.beings.get_mut(&being_id).ok_or_else(|| "Character not found.".to_string())?;
+        let builder_name = being.name.clone();
+        let inventory = being.inventory.as_mut().ok_or_else(|| "You cannot carry items.".to_string())?;
+
+        // Held stacks of each material, in a fixed order so the same stacks are used up everywhere
+        let mut stacks = Vec::new();
+        for (material, needed) in structure_type.materials() {
+            let mut held: Vec<Uuid> = inventory.items.keys()
+                .filter(|id| items.get(id).is_some_and(|item| item.name.eq_ignore_ascii_case(material)))
+                .copied()
+                .collect();
+            held.sort();
+            let total: u32 = held.iter().map(|id| inventory.items[id].stack_size).sum();
+            if total < *needed {
+                return Err(format!("You need {} {} to build a {} but have {}.",
+                    needed, material, structure_type.to_string().to_lowercase(), total));
+            }
+            stacks.push((held, *needed));
+        }
+
+        for (held, mut needed) in stacks {
+            for item_id in held {
+                let stack = inventory.items.get_mut(&item_id).expect("stack counted above");
+                let used = stack.stack_size.min(needed);
+                stack.stack_size -= used;
+                needed -= used;
+                if stack.stack_size == 0 {
+                    inventory.items.remove(&item_id);
+                }
+                if needed == 0 {
+                    break;
+                }
+            }
+        }
+
+        let structure = Structure {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            structure_type,
+            builder_id: being_id,
+            builder_name,
+            built_at: self.world_time,
+        };
+        self.structures.entry(area_id.clone()).or_default().push(structure.clone());
+        Ok((structure, area_id))
+    }
+}
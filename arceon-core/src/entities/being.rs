@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use bevy_ecs::prelude::Component;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
@@ -6,7 +7,7 @@ use super::archetypes::ArchetypeSystem;
 
 /// Universal Being system - represents any living entity (NPC, Player, Beast, etc.)
 /// All beings can have skills, vitals, and capabilities regardless of type
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Being {
     pub id: Uuid,
     pub name: String,
@@ -145,6 +146,43 @@ pub struct Skill {
     pub prerequisite_skills: Vec<String>, // Skills needed to unlock this
 }
 
+/// How experience turns into level for a skill: `level = log2(experience / scale + 1) ×
+/// levels_per_doubling`, so every `levels_per_doubling` levels take twice the experience
+/// of the last lot. Levels are continuous; a level-up is crossing a whole number.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SkillCurve {
+    pub scale: f64,
+    pub levels_per_doubling: f64,
+}
+
+impl Default for SkillCurve {
+    /// Level 10 at 100 experience, level 20 at 300
+    fn default() -> Self {
+        Self { scale: 100.0, levels_per_doubling: 10.0 }
+    }
+}
+
+impl SkillCurve {
+    /// Level reached with `experience`; never below 1
+    pub fn level_at(&self, experience: f64) -> f64 {
+        ((experience / self.scale + 1.0).log2() * self.levels_per_doubling).max(1.0)
+    }
+
+    /// Experience at which `level` is reached
+    pub fn experience_for_level(&self, level: f64) -> f64 {
+        self.scale * ((level / self.levels_per_doubling).exp2() - 1.0)
+    }
+}
+
+/// Whole levels a skill crossed in one grant of experience
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillLevelUp {
+    pub skill_name: String,
+    pub old_level: u32,
+    pub new_level: u32,
+    pub experience: f64, // total after the grant
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Copy)]
 pub enum SkillType {
     // Core skills that exist in the system
@@ -579,7 +617,8 @@ impl Being {
         // Give player characters the core attribute skills
         let skill_registry = super::skills::SkillRegistry::new();
         
-        // Add core attributes with starting levels
+        // Add core attributes with starting levels, and the experience those levels take
+        let curve = SkillCurve::default();
         let core_skills = vec![
             ("Dexterity", 5.0), ("Courage", 5.0), ("Wisdom", 5.0),
             ("Strength", 5.0), ("Vitality", 5.0), ("Charisma", 5.0), ("Intelligence", 5.0)
@@ -588,7 +627,7 @@ impl Being {
         for (skill_name, starting_level) in core_skills {
             if let Some(mut skill) = skill_registry.create_skill(skill_name) {
                 skill.level = starting_level;
-                skill.experience = (starting_level * starting_level * 10.0).max(25.0); // Scaled experience
+                skill.experience = curve.experience_for_level(starting_level);
                 skill.discovered_by.push(being.id);
                 being.skills.skills.insert(skill_name.to_string(), skill);
             }
@@ -618,7 +657,7 @@ impl Being {
         being.skills.skills.insert("Defense".to_string(), Skill {
             name: "Defense".to_string(),
             level: 5.0,
-            experience: 50.0,
+            experience: curve.experience_for_level(5.0),
             category: SkillCategory::Defense,
             passive_trait: PassiveTrait {
                 name: "Basic Defense".to_string(),
@@ -641,25 +680,40 @@ impl Being {
         being
     }
 
-    /// Gain experience in a skill from an action
+    /// Gain experience in a skill from an action, on the default curve
     pub fn gain_skill_experience(&mut self, skill_name: &str, amount: f64, source: ExperienceSource) {
+        self.grant_xp(skill_name, amount, source, &SkillCurve::default());
+    }
+
+    /// Gain experience in a skill on `curve`, discovering the skill first if need be.
+    /// Returns the whole levels crossed, however many, if the grant crossed any.
+    pub fn grant_xp(&mut self, skill_name: &str, amount: f64, source: ExperienceSource, curve: &SkillCurve) -> Option<SkillLevelUp> {
         // Create skill if it doesn't exist (discovery)
         if !self.skills.skills.contains_key(skill_name) {
             if let Some(skill) = self.discover_skill(skill_name, DiscoveryMethod::Experimentation) {
                 self.skills.skills.insert(skill_name.to_string(), skill);
             } else {
-                return; // Skill couldn't be discovered
+                return None; // Skill couldn't be discovered
             }
         }
 
         // Add experience to skill
+        let mut level_up = None;
         if let Some(skill) = self.skills.skills.get_mut(skill_name) {
             skill.experience += amount;
             
             // Calculate new level (continuous progression)
-            let new_level = Self::calculate_skill_level(skill.experience);
+            let new_level = curve.level_at(skill.experience);
             let old_level = skill.level;
             skill.level = new_level;
+            if new_level.floor() > old_level.floor() {
+                level_up = Some(SkillLevelUp {
+                    skill_name: skill_name.to_string(),
+                    old_level: old_level.floor() as u32,
+                    new_level: new_level.floor() as u32,
+                    experience: skill.experience,
+                });
+            }
 
             // Log experience gain
             self.skills.experience_log.push(ExperienceGain {
@@ -677,13 +731,14 @@ impl Being {
                 self.update_archetypes();
             }
         }
+        level_up
     }
 
-    /// Calculate skill level from experience (no level caps)
-    fn calculate_skill_level(experience: f64) -> f64 {
-        // Logarithmic progression: level = log2(exp/100 + 1) * 10
-        // This gives smooth, continuous progression without caps
-        ((experience / 100.0 + 1.0).log2() * 10.0).max(1.0)
+    /// Experience still needed for a skill's next whole level on `curve`
+    pub fn xp_to_next_level(&self, skill_name: &str, curve: &SkillCurve) -> Option<f64> {
+        let skill = self.skills.skills.get(skill_name)?;
+        let next_level = curve.level_at(skill.experience).floor() + 1.0;
+        Some((curve.experience_for_level(next_level) - skill.experience).max(0.0))
     }
 
     /// Attempt to discover a new skill
@@ -22,7 +22,7 @@ pub use item::*;
 pub use skill::{Skill as SkillEnumType, SkillLevel, Skills, CombatSkills, CraftingSkills, MagicSkills, SurvivalSkills, SocialSkills};
 pub use being::{Being, BeingType, Race, BeingCapability, ExperienceSource, DiscoveryMethod, 
     EvolutionRequest, RequestStatus, EvolutionEvidence, Skill, SkillType, SkillCategory, 
-    PassiveTrait, ActiveTrait, SkillRequirement, Vital, ExperienceGain};
+    PassiveTrait, ActiveTrait, SkillRequirement, Vital, ExperienceGain, SkillCurve, SkillLevelUp};
 
 /// Base component for all entities
 #[derive(Component, Debug, Clone, Serialize, Deserialize)]
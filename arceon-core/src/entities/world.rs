@@ -146,7 +146,7 @@ pub struct Weather {
     pub visibility: f32,  // 0.0 to 1.0
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WeatherCondition {
     Clear,
     Cloudy,
@@ -0,0 +1,179 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::entities::{item::Item, world::{Climate, Weather, WeatherCondition}};
+use crate::state::{GameState, WORLD_TIME_PER_HOUR};
+
+/// World time between weather rolls in an area
+pub const WEATHER_CHANGE_INTERVAL: u64 = 2 * WORLD_TIME_PER_HOUR;
+
+/// Seconds a picked-clean resource node waits before restocking, when the
+/// `resource` respawn timer is not configured
+pub const DEFAULT_RESOURCE_RESPAWN_SECS: u64 = 600;
+
+/// Something that happened to an area on its own, to be broadcast as an `AreaEvent`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentEvent {
+    pub area_id: String,
+    pub event_type: &'static str,
+    pub description: String,
+}
+
+/// A spot that yields a harvestable item and restocks a while after it is taken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceNode {
+    pub area_id: String,
+    pub location: String,
+    pub item: Item, // copied in each time the node restocks
+    pub item_id: Option<Uuid>, // the copy lying there now, until someone takes it
+    pub respawn_delay: u64, // world time from being picked clean to restocking
+    pub depleted_at: Option<u64>, // world_time the copy was found gone
+}
+
+impl WeatherCondition {
+    /// Conditions this one can turn into at the next roll, with relative weights.
+    /// Snow only follows in areas cold enough for it.
+    fn next_conditions(&self, climate: &Climate) -> Vec<(WeatherCondition, u32)> {
+        use WeatherCondition::*;
+        let wet = if climate.base_temperature <= 2 { Snowy } else { Rainy };
+        let humid = (climate.humidity * 4.0).round() as u32;
+        match self {
+            Clear => vec![(Clear, 4), (Cloudy, 3), (Windy, 1), (Foggy, humid)],
+            Cloudy => vec![(Cloudy, 2), (Clear, 2), (wet, 1 + humid), (Windy, 1)],
+            Rainy | Snowy => vec![(*self, 2), (Cloudy, 3), (Stormy, 1)],
+            Stormy => vec![(wet, 3), (Cloudy, 2), (Windy, 1)],
+            Foggy => vec![(Foggy, 1), (Clear, 2), (Cloudy, 2)],
+            Windy => vec![(Windy, 1), (Clear, 2), (Cloudy, 2), (Stormy, 1)],
+        }
+    }
+
+    /// Weather bad enough that wandering NPCs stay where they are
+    pub fn keeps_travellers_in(&self) -> bool {
+        matches!(self, WeatherCondition::Stormy | WeatherCondition::Snowy)
+    }
+
+    /// How the weather reads in an area description
+    pub fn describe(&self) -> &'static str {
+        match self {
+            WeatherCondition::Clear => "The sky is clear.",
+            WeatherCondition::Cloudy => "Clouds hang overhead.",
+            WeatherCondition::Rainy => "Rain is falling.",
+            WeatherCondition::Stormy => "A storm rages overhead.",
+            WeatherCondition::Snowy => "Snow is falling.",
+            WeatherCondition::Foggy => "A thick fog has settled.",
+            WeatherCondition::Windy => "A strong wind is blowing.",
+        }
+    }
+}
+
+impl Weather {
+    /// Weather of `condition` in an area with `climate`
+    fn for_condition(condition: WeatherCondition, climate: &Climate) -> Self {
+        let (temperature_shift, wind_speed, visibility) = match condition {
+            WeatherCondition::Clear => (2, 5, 1.0),
+            WeatherCondition::Cloudy => (0, 10, 0.9),
+            WeatherCondition::Rainy => (-3, 15, 0.7),
+            WeatherCondition::Stormy => (-5, 60, 0.4),
+            WeatherCondition::Snowy => (-8, 20, 0.5),
+            WeatherCondition::Foggy => (-1, 2, 0.2),
+            WeatherCondition::Windy => (-2, 40, 0.9),
+        };
+        Weather { condition, temperature: climate.base_temperature + temperature_shift, wind_speed, visibility }
+    }
+}
+
+impl GameState {
+    /// Roll new weather in every loaded area whose last roll was `WEATHER_CHANGE_INTERVAL`
+    /// or more ago. An area's first roll comes one interval after it is first seen.
+    pub fn update_weather(&mut self) -> Vec<EnvironmentEvent> {
+        let mut rng = rand::thread_rng();
+        let mut area_ids: Vec<String> = self.areas.keys().cloned().collect();
+        area_ids.sort();
+
+        let mut events = Vec::new();
+        for area_id in area_ids {
+            let rolled_at = *self.weather_rolled_at.entry(area_id.clone()).or_insert(self.world_time);
+            if self.world_time < rolled_at + WEATHER_CHANGE_INTERVAL {
+                continue;
+            }
+            self.weather_rolled_at.insert(area_id.clone(), self.world_time);
+
+            let Some(area) = self.areas.get_mut(&area_id) else { continue };
+            let current = area.climate.current_weather.condition;
+            let options = current.next_conditions(&area.climate);
+            let mut roll = rng.gen_range(0..options.iter().map(|(_, weight)| weight).sum::<u32>());
+            let next = options.iter()
+                .find(|(_, weight)| {
+                    let hit = roll < *weight;
+                    roll = roll.saturating_sub(*weight);
+                    hit
+                })
+                .map_or(current, |(condition, _)| *condition);
+            if next == current {
+                continue;
+            }
+
+            area.climate.current_weather = Weather::for_condition(next, &area.climate);
+            events.push(EnvironmentEvent {
+                area_id,
+                event_type: "WeatherChange",
+                description: next.describe().to_string(),
+            });
+        }
+        events
+    }
+
+    /// Put `item` at a location as a resource node that restocks `respawn_delay` world time
+    /// after it is picked clean. Returns the node id.
+    pub fn add_resource_node(&mut self, area_id: &str, location: &str, item: Item, respawn_delay: u64) -> Result<Uuid, String> {
+        let item_id = self.place_item(area_id, location, item.clone())?;
+        let node_id = Uuid::new_v4();
+        self.resource_nodes.insert(node_id, ResourceNode {
+            area_id: area_id.to_string(),
+            location: location.to_string(),
+            item,
+            item_id: Some(item_id),
+            respawn_delay,
+            depleted_at: None,
+        });
+        Ok(node_id)
+    }
+
+    /// Notice nodes whose item has been taken and restock those whose delay is up.
+    /// Nodes in areas that are paged out wait until the area is loaded again.
+    pub fn respawn_resources(&mut self) -> Vec<EnvironmentEvent> {
+        let mut node_ids: Vec<Uuid> = self.resource_nodes.keys().copied().collect();
+        node_ids.sort();
+
+        let mut events = Vec::new();
+        for node_id in node_ids {
+            let node = &self.resource_nodes[&node_id];
+            let Some(location) = self.areas.get(&node.area_id).and_then(|area| area.locations.get(&node.location)) else {
+                continue;
+            };
+
+            match (node.item_id, node.depleted_at) {
+                (Some(item_id), _) if !location.items_present.contains(&item_id) => {
+                    let world_time = self.world_time;
+                    let node = self.resource_nodes.get_mut(&node_id).expect("node listed above");
+                    node.item_id = None;
+                    node.depleted_at = Some(world_time);
+                }
+                (None, Some(depleted_at)) if self.world_time >= depleted_at + node.respawn_delay => {
+                    let (area_id, location, item) = (node.area_id.clone(), node.location.clone(), node.item.clone());
+                    let Ok(item_id) = self.place_item(&area_id, &location, item.clone()) else { continue };
+                    let node = self.resource_nodes.get_mut(&node_id).expect("node listed above");
+                    node.item_id = Some(item_id);
+                    node.depleted_at = None;
+                    events.push(EnvironmentEvent {
+                        area_id,
+                        event_type: "ResourceRespawn",
+                        description: format!("Fresh {} can be gathered at {} again.", item.name, location),
+                    });
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+}
@@ -5,16 +5,25 @@ pub mod events;
 pub mod state;
 pub mod error;
 pub mod persistence;
+pub mod commands;
+pub mod player_sync;
+pub mod combat;
+pub mod skill_macros;
+pub mod construction;
+pub mod environment;
+pub mod channel;
 
 use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use bevy_ecs::prelude::*;
 use std::time::Duration;
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 
 /// Types of NPCs that can be spawned in areas
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NpcType {
     // Urban NPCs
     Merchant,
@@ -44,12 +53,108 @@ pub enum NpcType {
     Mage,
 }
 
+/// Chance per world update that a wandering NPC heads to a neighbouring area
+const NPC_MIGRATION_CHANCE: f64 = 0.05;
+
+/// Reputation at or below which an NPC's faction treats a player as an enemy
+const HOSTILE_REPUTATION: i32 = -100;
+
+/// Reputation at or above which an NPC's faction greets a player as a friend
+const WARM_REPUTATION: i32 = 100;
+
+/// Reputation a faction's NPCs want before offering special services
+const SERVICE_REPUTATION: i32 = 250;
+
+/// Reputation at which a faction's NPCs refuse to deal with a player at all
+const REFUSAL_REPUTATION: i32 = -250;
+
+/// How an NPC feels about the player it is talking to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NpcDisposition {
+    Hostile,
+    Neutral,
+    Warm,
+}
+
+impl NpcDisposition {
+    pub fn from_reputation(reputation: i32) -> Self {
+        if reputation <= HOSTILE_REPUTATION {
+            NpcDisposition::Hostile
+        } else if reputation >= WARM_REPUTATION {
+            NpcDisposition::Warm
+        } else {
+            NpcDisposition::Neutral
+        }
+    }
+}
+
+impl NpcType {
+    /// What an NPC of this type is doing at the given in-game hour
+    pub fn activity(&self, hour: u32) -> &'static str {
+        let daytime = (6..18).contains(&hour);
+        match (self, daytime) {
+            (NpcType::Merchant, true) => "opens their shop and calls out to passers-by",
+            (NpcType::Merchant, false) => "has shuttered their shop for the night",
+            (NpcType::Guard, true) => "stands watch at their post",
+            (NpcType::Guard, false) => "patrols the dark streets with a lantern",
+            (NpcType::Scholar, true) => "pores over old tomes",
+            (NpcType::Scholar, false) => "studies late by candlelight",
+            (NpcType::Artisan, true) => "is working diligently at their trade",
+            (NpcType::Miner, _) => "chips away at a seam of ore",
+            (NpcType::Sailor | NpcType::Dockworker, true) => "is hauling cargo along the docks",
+            (NpcType::Mage, false) => "is studying the stars",
+            (NpcType::Ranger | NpcType::Nomad, false) => "tends a small campfire",
+            (_, true) => "can be seen going about their business",
+            (_, false) => "has turned in for the night",
+        }
+    }
+    
+    /// Whether NPCs of this type travel between areas rather than staying put
+    pub fn wanders(&self) -> bool {
+        matches!(self, NpcType::Ranger | NpcType::Nomad | NpcType::Sailor)
+    }
+    
+    /// Faction an NPC of this type answers to; players' standing with it sets the NPC's tone
+    pub fn faction(&self) -> &'static str {
+        match self {
+            NpcType::Merchant | NpcType::Artisan => "Merchants Guild",
+            NpcType::Guard | NpcType::Citizen | NpcType::Noble => "City Watch",
+            NpcType::Scholar | NpcType::Mage => "Arcane Conclave",
+            NpcType::Ranger | NpcType::Druid | NpcType::Hermit => "Circle of the Wild",
+            NpcType::Miner | NpcType::MountainGuide | NpcType::CaveDweller => "Mountain Clans",
+            NpcType::Nomad | NpcType::MarshFolk => "Free Wanderers",
+            NpcType::Sailor | NpcType::Dockworker => "Harbor Guild",
+        }
+    }
+    
+    /// Skills an NPC of this type starts with, and the level it has in them
+    pub fn starting_skills(&self) -> &'static [(&'static str, f64)] {
+        match self {
+            NpcType::Merchant => &[("Charisma", 15.0), ("Intelligence", 10.0)],
+            NpcType::Guard => &[("Sword", 15.0), ("Shield", 12.0), ("Courage", 10.0)],
+            NpcType::Citizen | NpcType::Noble => &[("Charisma", 8.0), ("Wisdom", 5.0)],
+            NpcType::Scholar => &[("Intelligence", 15.0), ("Wisdom", 12.0)],
+            NpcType::Artisan => &[("Blacksmithing", 15.0), ("Carpentry", 10.0)],
+            NpcType::Ranger => &[("Taming", 12.0), ("Gathering", 10.0), ("Dexterity", 10.0)],
+            NpcType::Druid => &[("Nature Magic", 15.0), ("Healing Magic", 8.0)],
+            NpcType::Hermit => &[("Wisdom", 15.0), ("Meditation", 10.0)],
+            NpcType::Miner | NpcType::CaveDweller => &[("Mining", 15.0), ("Strength", 10.0)],
+            NpcType::MountainGuide | NpcType::Nomad | NpcType::MarshFolk => &[("Gathering", 12.0), ("Vitality", 10.0)],
+            NpcType::Sailor | NpcType::Dockworker => &[("Strength", 12.0), ("Dexterity", 8.0)],
+            NpcType::Mage => &[("Abjuration", 15.0), ("Illusion Magic", 12.0)],
+        }
+    }
+}
+
 pub use config::Config;
+pub use commands::{AdminCommand, GameCommand};
+pub use player_sync::{PlayerDelta, PlayerSnapshot};
 pub use error::ArceonError;
 pub use entities::*;
 pub use systems::*;
 
-use crate::state::GameState;
+use crate::state::{GameState, NameReservation, SKILL_CONFIRMATION_VOTES};
+use crate::skill_macros::SkillMacro;
 
 /// Simple wrapper to make Arc<RwLock<GameState>> a Resource
 #[derive(Resource)]
@@ -61,6 +166,29 @@ pub trait NetworkBridge: Send + Sync {
     fn get_connected_peers(&self) -> Vec<String>;
 }
 
+/// How far a network event needs to travel from the node that produced it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventScope {
+    Local,  // Only relevant to the originating node (failed commands, stat queries)
+    Area,   // Relevant to peers with players in the affected area
+    Global, // Relevant to every peer on the network
+}
+
+impl EventScope {
+    /// Whether events of this scope leave the originating node
+    pub fn is_broadcast(&self) -> bool {
+        !matches!(self, EventScope::Local)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventScope::Local => "local",
+            EventScope::Area => "area",
+            EventScope::Global => "global",
+        }
+    }
+}
+
 /// Placeholder for external managers until we can import them properly
 #[derive(Resource)]
 pub struct NetworkManager;
@@ -72,35 +200,43 @@ pub struct BlockchainManager;
 pub struct ArceonCore {
     world: World,
     schedule: Schedule,
-    _config: Config,
+    config: Config,
     state: Arc<RwLock<GameState>>,
     network_bridge: Option<Box<dyn NetworkBridge>>,
     is_server_mode: bool,
+    area_pager: Option<persistence::AreaPager>,
+    player_store: Option<persistence::PlayerStore>,
+    world_persistence: Option<persistence::PersistenceManager>,
+    shutdown: watch::Sender<bool>, // true once `shutdown` has begun
 }
 
 impl ArceonCore {
     /// Create a new Arceon core instance
     pub async fn new(config: Config) -> Result<Self> {
-        let world = World::new();
-        let schedule = Schedule::default();
-        
-        // Initialize ECS systems (placeholder for now)
-        // schedule.add_systems((
-        //     systems::player_movement_system,
-        //     systems::npc_ai_system,
-        //     systems::world_update_system,
-        //     systems::skill_progression_system,
-        // ));
+        let mut world = World::new();
+        world.insert_resource(systems::WorldClock::default());
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            systems::player_movement_system,
+            systems::npc_ai_system,
+            systems::world_update_system,
+            systems::skill_progression_system,
+        ));
         
         let state = Arc::new(RwLock::new(GameState::new()));
         
         Ok(Self {
             world,
             schedule,
-            _config: config,
+            config,
             state,
             network_bridge: None,
             is_server_mode: false,
+            area_pager: None,
+            player_store: None,
+            world_persistence: None,
+            shutdown: watch::channel(false).0,
         })
     }
     
@@ -167,6 +303,9 @@ impl ArceonCore {
         
         loop {
             interval.tick().await;
+            if *self.shutdown.borrow() {
+                return Ok(());
+            }
             
             // Update world time
             {
@@ -193,14 +332,126 @@ impl ArceonCore {
         // Process NPC interactions and actions
         self.process_npc_actions().await?;
         
+        // Let wandering NPCs travel to neighbouring areas
+        self.migrate_npcs().await?;
+        
+        // Swing for players fighting on autoattack
+        self.run_autoattacks().await?;
+        
+        // Fire queued macro skills that are off cooldown
+        self.run_macros().await?;
+        
+        // Roll the weather and restock picked-clean resource nodes
+        self.update_environment().await?;
+        
         // Other world updates
-        // - Resource respawning
-        // - Weather changes  
         // - Dynamic events
         
         Ok(())
     }
     
+    /// Advance per-area weather and resource respawns, announcing each change as an `AreaEvent`
+    async fn update_environment(&mut self) -> Result<()> {
+        let events = {
+            let mut state = self.state.write().await;
+            let mut events = state.update_weather();
+            events.extend(state.respawn_resources());
+            events
+        };
+        
+        for event in events {
+            let event_data = serde_json::json!({
+                "area_id": event.area_id,
+                "event_type": event.event_type,
+                "description": event.description
+            });
+            self.broadcast_network_event("AreaEvent", EventScope::Area, event_data).await?;
+        }
+        Ok(())
+    }
+    
+    /// Make `item` a resource node at a location, restocking after the configured
+    /// `resource` respawn timer once it is picked clean. Returns the node id.
+    pub async fn add_resource_node(&self, area_id: &str, location: &str, item: Item) -> Result<uuid::Uuid> {
+        let respawn_secs = self.config.world.respawn_timers.get("resource")
+            .copied()
+            .unwrap_or(environment::DEFAULT_RESOURCE_RESPAWN_SECS);
+        self.state.write().await
+            .add_resource_node(area_id, location, item, respawn_secs * 1000)
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+    
+    /// Experience curve configured for `skill_name`
+    fn skill_curve(&self, skill_name: &str) -> SkillCurve {
+        self.config.world.skill_curves.get(skill_name).copied().unwrap_or_default()
+    }
+    
+    /// Give a player experience in a skill. Crossing one or more whole levels broadcasts
+    /// a `SkillGain` with the level reached, and returns the level-up.
+    pub async fn grant_xp(&mut self, player_id: &str, skill_name: &str, amount: f64, source: being::ExperienceSource) -> Result<Option<SkillLevelUp>> {
+        let curve = self.skill_curve(skill_name);
+        let level_up = {
+            let mut state = self.state.write().await;
+            let being_id = state.online_players.get(player_id)
+                .map(|player_data| player_data.being_id.clone())
+                .ok_or_else(|| anyhow::anyhow!("Player {} not found", player_id))?;
+            let being = state.beings.get_mut(&being_id)
+                .ok_or_else(|| anyhow::anyhow!("Character for {} not found", player_id))?;
+            being.grant_xp(skill_name, amount, source, &curve)
+        };
+        
+        if let Some(level_up) = &level_up {
+            let gain_data = serde_json::json!({
+                "player_id": player_id,
+                "skill_name": level_up.skill_name,
+                "new_level": level_up.new_level,
+                "experience": level_up.experience
+            });
+            self.broadcast_network_event("SkillGain", EventScope::Global, gain_data).await?;
+        }
+        Ok(level_up)
+    }
+    
+    /// Vote for or against an evolved skill. Every vote is broadcast as a `SkillEvolution`;
+    /// the one that brings support up to `SKILL_CONFIRMATION_VOTES` also submits the
+    /// `SkillEvolutionConfirmed` world change. A voter who has already voted on the skill
+    /// is ignored and false returned.
+    pub async fn vote_skill_evolution(&mut self, skill_name: &str, voter: &str, support: bool) -> Result<bool> {
+        let (supporting, newly_confirmed) = {
+            let mut state = self.state.write().await;
+            let Some(supporting) = state.record_skill_vote(skill_name, voter, support) else {
+                return Ok(false);
+            };
+            let newly_confirmed = supporting >= SKILL_CONFIRMATION_VOTES
+                && state.confirmed_skills.insert(skill_name.to_string());
+            (supporting, newly_confirmed)
+        };
+        
+        let vote_data = serde_json::json!({
+            "skill_name": skill_name,
+            "vote": if support { "support" } else { "oppose" },
+            "voter_id": voter
+        });
+        self.broadcast_network_event("SkillEvolution", EventScope::Global, vote_data).await?;
+        
+        if newly_confirmed {
+            let confirmation = serde_json::json!({
+                "skill_name": skill_name,
+                "evolution_type": "community_vote",
+                "consensus_votes": supporting
+            });
+            self.broadcast_network_event("SkillEvolutionConfirmed", EventScope::Global, confirmation).await?;
+        }
+        Ok(true)
+    }
+    
+    /// Experience a player still needs for the next whole level of a skill they have
+    pub async fn xp_to_next_level(&self, player_id: &str, skill_name: &str) -> Option<f64> {
+        let state = self.state.read().await;
+        let being = state.beings.get(&state.online_players.get(player_id)?.being_id)?;
+        being.xp_to_next_level(skill_name, &self.skill_curve(skill_name))
+    }
+    
     /// Update NPC AI behaviors and decision making
     async fn update_npc_behaviors(&mut self) -> Result<()> {
         let area_data = {
@@ -236,7 +487,8 @@ impl ArceonCore {
                         
                         // Get the area again mutably
                         if let Some(area_mut) = state.areas.get_mut(&area_id) {
-                            Self::spawn_npcs_in_area_static(area_mut, npcs_to_spawn)?;
+                            let spawned = Self::spawn_npcs_in_area_static(area_mut, npcs_to_spawn)?;
+                            state.npcs.extend(spawned);
                             events.push((area_id, npcs_to_spawn, desired_population));
                         }
                     }
@@ -252,7 +504,7 @@ impl ArceonCore {
                 "npcs_spawned": npcs_spawned,
                 "total_population": total_population
             });
-            self.broadcast_network_event("NPCSpawn", spawn_data).await?;
+            self.broadcast_network_event("NPCSpawn", EventScope::Area, spawn_data).await?;
         }
         
         Ok(())
@@ -288,27 +540,29 @@ impl ArceonCore {
         (base_population as f32 * size_multiplier).round() as usize
     }
     
-    /// Spawn specific NPCs in an area (static version)
-    fn spawn_npcs_in_area_static(area: &mut world::Area, count: usize) -> Result<()> {
-        use uuid::Uuid;
+    /// Spawn specific NPCs in an area (static version). Returns the new NPCs keyed by the
+    /// id placed in their location's `npcs_present`, for the caller to add to the game state.
+    fn spawn_npcs_in_area_static(area: &mut world::Area, count: usize) -> Result<Vec<(uuid::Uuid, state::Npc)>> {
+        let mut spawned = Vec::new();
         
         for _ in 0..count {
             // Choose appropriate NPC type based on area and locations
             let npc_type = Self::choose_npc_type_for_area_static(area);
-            let npc_id = Uuid::new_v4();
             
             // Find appropriate location for this NPC
             let location_name = Self::choose_npc_location_static(area, &npc_type);
             
             if let Some(location) = area.locations.get_mut(&location_name) {
+                let npc = state::Npc::spawn(npc_type, area.id.to_string(), location_name.clone());
+                let npc_id = npc.being.id;
                 location.npcs_present.push(npc_id);
                 
-                // TODO: Create actual NPC object in AI system
-                println!("📍 Spawned {:?} NPC in {} at {}", npc_type, area.name, location_name);
+                println!("📍 Spawned {:?} NPC in {} at {}", npc.npc_type, area.name, location_name);
+                spawned.push((npc_id, npc));
             }
         }
         
-        Ok(())
+        Ok(spawned)
     }
     
     /// Choose appropriate NPC type for an area (static version)
@@ -391,17 +645,8 @@ impl ArceonCore {
         }
     }
     
-    /// Simulate NPC behaviors in an area
+    /// Simulate NPC behaviors in an area, following the time of day
     async fn simulate_area_npcs(&mut self, area_id: &str, area: &world::Area) -> Result<()> {
-        // Simulate NPC activities based on time of day, area type, etc.
-        
-        // Example behaviors:
-        // - Merchants opening shops in the morning
-        // - Guards patrolling at regular intervals  
-        // - Scholars studying in libraries
-        // - Farmers working in fields
-        
-        // For now, just log some activity
         if !area.locations.is_empty() {
             let total_npcs: usize = area.locations.values()
                 .map(|loc| loc.npcs_present.len())
@@ -424,26 +669,99 @@ impl ArceonCore {
         
         // Occasionally generate NPC actions for atmosphere
         if rng.gen_bool(0.1) { // 10% chance per update
-            let actions = vec![
-                "is tending to their daily tasks",
-                "can be seen going about their business", 
-                "is having a conversation with another resident",
-                "is working diligently at their trade",
-                "pauses to observe the surroundings",
-                "is organizing their wares",
-                "is practicing their skills",
-                "is sharing news with fellow residents",
-            ];
+            let present: Vec<uuid::Uuid> = area.locations.values()
+                .flat_map(|location| location.npcs_present.iter().copied())
+                .collect();
+            let Some(npc_id) = present.choose(&mut rng).copied() else {
+                return Ok(());
+            };
+            
+            let (hour, npc_type) = {
+                let state = self.state.read().await;
+                (state.current_hour(), state.get_npc(&npc_id).map(|npc| npc.npc_type.clone()))
+            };
+            // NPCs without a record (spawned before records existed) act as citizens
+            let npc_type = npc_type.unwrap_or(NpcType::Citizen);
             
-            let action = actions.choose(&mut rng).unwrap();
             let event_data = serde_json::json!({
                 "area_id": area_id,
                 "area_name": area.name,
-                "action": action,
+                "npc_id": npc_id.to_string(),
+                "npc_type": format!("{:?}", npc_type),
+                "action": npc_type.activity(hour),
+                "hour": hour,
                 "npc_count": npc_count
             });
             
-            self.broadcast_network_event("NPCAction", event_data).await?;
+            self.broadcast_network_event("NPCAction", EventScope::Area, event_data).await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Move some wandering NPCs along an area connection to a neighbouring area
+    async fn migrate_npcs(&mut self) -> Result<()> {
+        use rand::Rng;
+        
+        let moves = {
+            let mut state = self.state.write().await;
+            let mut rng = rand::thread_rng();
+            let mut moves = Vec::new();
+            
+            let mut wanderers: Vec<uuid::Uuid> = state.npcs.iter()
+                .filter(|(_, npc)| npc.npc_type.wanders())
+                .map(|(npc_id, _)| *npc_id)
+                .collect();
+            wanderers.sort();
+            
+            for npc_id in wanderers {
+                if !rng.gen_bool(NPC_MIGRATION_CHANCE) {
+                    continue;
+                }
+                let npc = &state.npcs[&npc_id];
+                
+                // Nobody sets out into a storm or a snowfall
+                if state.areas.get(&npc.current_area).is_some_and(|area| area.climate.current_weather.condition.keeps_travellers_in()) {
+                    continue;
+                }
+                
+                // Only areas that are loaded and have somewhere to stand
+                let destinations: Vec<String> = state.areas.get(&npc.current_area)
+                    .map(|area| area.connected_areas.iter()
+                        .map(|conn| conn.target_area_id.to_string())
+                        .filter(|target| state.areas.get(target).is_some_and(|area| !area.locations.is_empty()))
+                        .collect())
+                    .unwrap_or_default();
+                let Some(to_area) = destinations.choose(&mut rng).cloned() else {
+                    continue;
+                };
+                let to_location = Self::choose_npc_location_static(&state.areas[&to_area], &npc.npc_type);
+                let (from_area, from_location) = (npc.current_area.clone(), npc.current_location.clone());
+                
+                if let Some(location) = state.areas.get_mut(&from_area).and_then(|area| area.locations.get_mut(&from_location)) {
+                    location.npcs_present.retain(|id| *id != npc_id);
+                }
+                if let Some(location) = state.areas.get_mut(&to_area).and_then(|area| area.locations.get_mut(&to_location)) {
+                    location.npcs_present.push(npc_id);
+                }
+                if let Some(npc) = state.npcs.get_mut(&npc_id) {
+                    npc.current_area = to_area.clone();
+                    npc.current_location = to_location;
+                }
+                moves.push((npc_id, from_area, to_area));
+            }
+            moves
+        };
+        
+        for (npc_id, from_area, to_area) in moves {
+            let move_data = serde_json::json!({
+                "npc_id": npc_id.to_string(),
+                "area_id": from_area,
+                "action": "move",
+                "from_area": from_area,
+                "to_area": to_area
+            });
+            self.broadcast_network_event("NPCAction", EventScope::Area, move_data).await?;
         }
         
         Ok(())
@@ -478,8 +796,8 @@ impl ArceonCore {
                         return Ok("There are no NPCs to talk to in this area.".to_string());
                     }
                     
-                    // Generate contextual NPC response based on area and NPC type
-                    let response = self.generate_npc_interaction(area, npc_name, player_id).await?;
+                    // Generate contextual NPC response based on area, NPC type and standing
+                    let response = self.generate_npc_interaction(&state, area, npc_name, player_id).await?;
                     
                     let interaction_data = serde_json::json!({
                         "player_id": player_id,
@@ -499,17 +817,47 @@ impl ArceonCore {
         
         // Broadcast the interaction after releasing state lock
         if let Some(data) = interaction_data {
-            self.broadcast_network_event("NPCInteraction", data).await?;
+            self.broadcast_network_event("NPCInteraction", EventScope::Area, data).await?;
         }
         
         Ok(response)
     }
     
-    /// Generate NPC interaction responses
-    async fn generate_npc_interaction(&self, area: &world::Area, _npc_name: &str, _player_id: &str) -> Result<String> {
+    /// Generate NPC interaction responses. The player's reputation with the NPC's faction
+    /// sets the tone: hostile NPCs won't share news or trade, warm ones greet the player as
+    /// a friend, and at high standing they offer services.
+    async fn generate_npc_interaction(&self, state: &GameState, area: &world::Area, npc_name: &str, _player_id: &str) -> Result<String> {
         use rand::seq::SliceRandom;
         use world::AreaType;
         
+        let area_id = area.id.to_string();
+        let mut npcs: Vec<_> = state.npcs.iter()
+            .filter(|(_, npc)| npc.current_area == area_id && npc.being.name.eq_ignore_ascii_case(npc_name))
+            .collect();
+        npcs.sort_by_key(|(npc_id, _)| **npc_id);
+        let npc_type = npcs.first().map(|(_, npc)| npc.npc_type.clone());
+        let faction = npc_type.as_ref().map(NpcType::faction);
+        let reputation = faction
+            .and_then(|faction| state.quest_system.faction_reputations.get(faction))
+            .map_or(0, |standing| standing.reputation_value);
+        let disposition = NpcDisposition::from_reputation(reputation);
+        let mut rng = rand::thread_rng();
+        
+        if disposition == NpcDisposition::Hostile {
+            let retorts = [
+                "\"Say what you came to say and be gone.\"",
+                "\"I've nothing for the likes of you.\"",
+                "\"Keep your distance. I know what you are.\"",
+            ];
+            let gestures = [" *glares at you*", " *spits on the ground*", " *rests a hand on their weapon*"];
+            let mut response = format!("\"You're not welcome here. The {} hasn't forgotten you.\" {}{}",
+                faction.unwrap_or("town"), retorts.choose(&mut rng).unwrap(), gestures.choose(&mut rng).unwrap());
+            if reputation <= REFUSAL_REPUTATION {
+                response.push_str("\n\"And don't think I'll trade with you.\"");
+            }
+            return Ok(response);
+        }
+        
         // Generate contextual responses based on area type
         let responses = match area.area_type {
             AreaType::Capital | AreaType::City => {
@@ -577,26 +925,75 @@ impl ArceonCore {
         };
         
         // Add some personality-based variations
-        let personality_additions = vec![
-            " *nods thoughtfully*",
-            " *glances around cautiously*", 
-            " *smiles warmly*",
-            " *strokes beard contemplatively*",
-            " *adjusts their equipment*",
-            " *looks you up and down appraisingly*",
-        ];
+        let personality_additions = match disposition {
+            NpcDisposition::Warm => vec![
+                " *smiles warmly*",
+                " *clasps your hand*",
+                " *beams at you*",
+            ],
+            _ => vec![
+                " *nods thoughtfully*",
+                " *glances around cautiously*", 
+                " *smiles warmly*",
+                " *strokes beard contemplatively*",
+                " *adjusts their equipment*",
+                " *looks you up and down appraisingly*",
+            ],
+        };
         
-        let mut rng = rand::thread_rng();
         let base_response = responses.choose(&mut rng).unwrap();
         let personality = personality_additions.choose(&mut rng).unwrap();
+        let mut response = match (disposition, faction) {
+            (NpcDisposition::Warm, Some(faction)) => {
+                format!("\"Good to see you again, friend of the {}!\" {}{}", faction, base_response, personality)
+            }
+            _ => format!("{}{}", base_response, personality),
+        };
         
-        Ok(format!("{}{}", base_response, personality))
+        if reputation >= SERVICE_REPUTATION {
+            let service = match npc_type {
+                Some(NpcType::Merchant | NpcType::Artisan) => "\"For you, my best wares at a friend's price.\"",
+                Some(NpcType::Guard | NpcType::Noble | NpcType::Citizen) => "\"The Watch will vouch for you at any gate.\"",
+                Some(NpcType::Scholar | NpcType::Mage) => "\"The restricted archives are open to you.\"",
+                Some(NpcType::Sailor | NpcType::Dockworker) => "\"There's always a berth for you on our ships.\"",
+                Some(_) => "\"I'll show you the hidden trails the others never see.\"",
+                None => "",
+            };
+            if !service.is_empty() {
+                response.push('\n');
+                response.push_str(service);
+            }
+        }
+        
+        Ok(response)
     }
     
     /// Create a new player character
     pub async fn create_player(&mut self, player_id: String, player_name: String, race: being::Race) -> Result<String> {
         let mut state = self.state.write().await;
         
+        // Player names are unique network-wide; refuse names already claimed on this node
+        if let Some(holder) = state.name_holder(&player_name) {
+            if holder != player_id {
+                return Err(anyhow::anyhow!("The name '{}' is already taken", player_name));
+            }
+        }
+        
+        // Returning players pick up their saved character where they left it
+        let saved = match &self.player_store {
+            Some(store) => store.load_player(&player_id).await?,
+            None => None,
+        };
+        if let Some(mut saved) = saved {
+            let area_known = state.areas.contains_key(&saved.current_area_id)
+                || state.paged_out_areas.contains(&saved.current_area_id);
+            if !area_known {
+                saved.current_area_id = state.areas.keys().next().cloned().unwrap_or_default();
+            }
+            drop(state);
+            return self.resume_player(player_id, saved).await;
+        }
+        
         // Find starting area for the race
         let starting_area_id = state.areas
             .values()
@@ -608,10 +1005,9 @@ impl ArceonCore {
                 }
             })
             .map(|area| area.id.to_string())
-            .unwrap_or_else(|| {
-                // Default to first area if no race-specific area found
-                state.areas.keys().next().unwrap().clone()
-            });
+            // Default to first area if no race-specific area found
+            .or_else(|| state.areas.keys().next().cloned())
+            .ok_or_else(|| anyhow::anyhow!("No areas are loaded to start {} in", player_name))?;
         
         // Create a new being for the player
         let race_string = format!("{:?}", race);
@@ -627,10 +1023,20 @@ impl ArceonCore {
             being_id: being_id.clone(),
             current_area_id: starting_area_id.clone(),
             last_activity: world_time,
+            is_admin: self.config.world.admin_players.contains(&player_id),
         });
         
+        let reservation = NameReservation {
+            player_id: player_id.clone(),
+            player_name: player_name.clone(),
+            reserved_at: chrono::Utc::now().timestamp_millis(),
+        };
+        state.reserve_player_name(reservation.clone());
+        
         drop(state); // Release the lock before broadcasting
         
+        self.broadcast_network_event("NameReservation", EventScope::Global, serde_json::to_value(&reservation)?).await?;
+        
         // Broadcast player join event
         let join_data = serde_json::json!({
             "player_id": player_id,
@@ -638,22 +1044,314 @@ impl ArceonCore {
             "race": race_string,
             "area_id": starting_area_id
         });
-        self.broadcast_network_event("PlayerJoin", join_data).await?;
+        self.broadcast_network_event("PlayerJoin", EventScope::Global, join_data).await?;
+        self.broadcast_player_update(&player_id).await?;
         
         Ok(being_id)
     }
     
+    /// Put a saved character back into the world
+    async fn resume_player(&mut self, player_id: String, saved: persistence::SavedPlayer) -> Result<String> {
+        let being_id = saved.being.id.to_string();
+        let player_name = saved.being.name.clone();
+        let race_string = format!("{:?}", saved.being.race);
+        let area_id = saved.current_area_id.clone();
+        
+        let reservation = {
+            let mut state = self.state.write().await;
+            let world_time = state.world_time;
+            state.add_being(saved.being);
+            state.online_players.insert(player_id.clone(), crate::state::PlayerData {
+                being_id: being_id.clone(),
+                current_area_id: area_id.clone(),
+                last_activity: world_time,
+                is_admin: self.config.world.admin_players.contains(&player_id),
+            });
+            
+            let reservation = NameReservation {
+                player_id: player_id.clone(),
+                player_name: player_name.clone(),
+                reserved_at: chrono::Utc::now().timestamp_millis(),
+            };
+            state.reserve_player_name(reservation.clone());
+            reservation
+        };
+        
+        self.broadcast_network_event("NameReservation", EventScope::Global, serde_json::to_value(&reservation)?).await?;
+        let join_data = serde_json::json!({
+            "player_id": player_id,
+            "player_name": player_name,
+            "race": race_string,
+            "area_id": area_id
+        });
+        self.broadcast_network_event("PlayerJoin", EventScope::Global, join_data).await?;
+        self.broadcast_player_update(&player_id).await?;
+        
+        Ok(being_id)
+    }
+    
+    /// Announce an item changing hands. The payload matches what the blockchain's pickup
+    /// conflict check reads, so two nodes grabbing the same item resolve to one winner.
+    async fn broadcast_item_transfer(&mut self, player_id: &str, action_type: &str, item_id: uuid::Uuid, area_id: &str, location_name: &str) -> Result<()> {
+        let transfer_data = serde_json::json!({
+            "player_id": player_id,
+            "action_type": action_type,
+            "area_id": area_id,
+            "item_id": item_id.to_string(),
+            "location": location_name
+        });
+        self.broadcast_network_event("ItemTransfer", EventScope::Area, transfer_data).await
+    }
+    
+    /// Announce a new structure as a `StructureConstruction` area update, carrying what the
+    /// blockchain records on the area's structure list
+    async fn broadcast_structure_construction(&mut self, structure: &construction::Structure, area_id: &str) -> Result<()> {
+        let materials: Vec<serde_json::Value> = structure.structure_type.materials().iter()
+            .map(|(material, count)| serde_json::json!({ "name": material, "count": count }))
+            .collect();
+        let construction_data = serde_json::json!({
+            "area_id": area_id,
+            "update_type": "StructureConstruction",
+            "structure_id": structure.id.to_string(),
+            "name": structure.name,
+            "structure_type": structure.structure_type.to_string(),
+            "builder": structure.builder_id,
+            "materials": materials
+        });
+        self.broadcast_network_event("StructureConstruction", EventScope::Area, construction_data).await
+    }
+    
+    /// Record each leg of a settled trade: an `ItemTransfer` per item and a `TOKEN_TRANSFER`
+    /// per token payment, in the same shape the blockchain records them
+    async fn broadcast_trade_settlement(&mut self, trade: &state::Trade, area_id: &str, being_ids: &HashMap<String, String>) -> Result<()> {
+        let mut owners: Vec<&String> = trade.offers.keys().collect();
+        owners.sort();
+        for owner in owners {
+            let recipient = trade.partner_of(owner);
+            for goods in &trade.offers[owner] {
+                match goods {
+                    state::TradeGoods::Item { item_id, stack_size } => {
+                        let transfer_data = serde_json::json!({
+                            "player_id": owner,
+                            "to": recipient,
+                            "action_type": "trade",
+                            "area_id": area_id,
+                            "item_id": item_id.to_string(),
+                            "stack_size": stack_size
+                        });
+                        self.broadcast_network_event("ItemTransfer", EventScope::Area, transfer_data).await?;
+                    }
+                    state::TradeGoods::Tokens(amount) => {
+                        let transfer_data = serde_json::json!({
+                            "from": being_ids.get(owner.as_str()),
+                            "to": being_ids.get(recipient),
+                            "token_type": state::NATIVE_TOKEN,
+                            "amount": amount
+                        });
+                        self.broadcast_network_event("TOKEN_TRANSFER", EventScope::Global, transfer_data).await?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// Swing for every autoattacking player whose next attack is due. Players whose
+    /// target has died or left stop autoattacking.
+    async fn run_autoattacks(&mut self) -> Result<()> {
+        let outcomes = {
+            let mut state = self.state.write().await;
+            let now = state.world_time;
+            let mut due: Vec<String> = state.autoattacking.iter()
+                .filter(|(_, last_swing)| now >= **last_swing + combat::AUTOATTACK_INTERVAL)
+                .map(|(player_id, _)| player_id.clone())
+                .collect();
+            due.sort();
+            
+            let mut outcomes = Vec::new();
+            for player_id in due {
+                let result = match state.combat_targets.get(&player_id).cloned() {
+                    Some(target) => state.attack(&player_id, &target),
+                    None => Err("No target".to_string()),
+                };
+                match result {
+                    Ok(outcome) => {
+                        if let Some(last_swing) = state.autoattacking.get_mut(&player_id) {
+                            *last_swing = now;
+                        }
+                        outcomes.push(outcome);
+                    }
+                    Err(_) => {
+                        state.combat_targets.remove(&player_id);
+                        state.autoattacking.remove(&player_id);
+                    }
+                }
+            }
+            outcomes
+        };
+        
+        for outcome in &outcomes {
+            self.broadcast_attack(outcome).await?;
+        }
+        Ok(())
+    }
+    
+    /// Fire due macro steps and tell the area about each skill used
+    async fn run_macros(&mut self) -> Result<()> {
+        let (fired, areas) = {
+            let mut state = self.state.write().await;
+            let fired = state.advance_macros();
+            let areas: Vec<String> = fired.iter()
+                .map(|skill_use| state.online_players.get(&skill_use.player_id).map(|data| data.current_area_id.clone()).unwrap_or_default())
+                .collect();
+            (fired, areas)
+        };
+        
+        for (skill_use, area_id) in fired.iter().zip(areas) {
+            let use_data = serde_json::json!({
+                "player_id": skill_use.player_id,
+                "area_id": area_id,
+                "skill": skill_use.skill,
+                "macro": skill_use.macro_name
+            });
+            self.broadcast_network_event("SkillUse", EventScope::Area, use_data).await?;
+        }
+        Ok(())
+    }
+    
+    /// Announce a swing to the area, plus an `ItemTransfer` for anything looted
+    async fn broadcast_attack(&mut self, outcome: &combat::AttackOutcome) -> Result<()> {
+        self.broadcast_network_event("Combat", EventScope::Area, serde_json::to_value(outcome)?).await?;
+        for item_id in &outcome.loot {
+            self.broadcast_item_transfer(&outcome.attacker, "loot", *item_id, &outcome.area_id, &outcome.location).await?;
+        }
+        Ok(())
+    }
+    
+    fn describe_attack_static(state: &GameState, outcome: &combat::AttackOutcome) -> String {
+        if !outcome.killed {
+            return format!("You hit {} for {:.1} damage ({:.1} health left).", outcome.target_name, outcome.damage, outcome.target_health);
+        }
+        
+        let mut response = format!("You hit {} for {:.1} damage and slay them!", outcome.target_name, outcome.damage);
+        let loot: Vec<&str> = outcome.loot.iter()
+            .filter_map(|item_id| state.items.get(item_id).map(|item| item.name.as_str()))
+            .collect();
+        if !loot.is_empty() {
+            response.push_str(&format!("\nYou take: {}", loot.join(", ")));
+        }
+        response
+    }
+    
+    /// Character name of an online player, falling back to their id
+    fn player_name_static(state: &GameState, player_id: &str) -> String {
+        state.online_players.get(player_id)
+            .and_then(|data| state.beings.get(&data.being_id))
+            .map_or_else(|| player_id.to_string(), |being| being.name.clone())
+    }
+    
+    fn describe_goods_static(state: &GameState, goods: &state::TradeGoods) -> String {
+        match goods {
+            state::TradeGoods::Tokens(amount) => format!("{} tokens", amount),
+            state::TradeGoods::Item { item_id, .. } => state.items.get(item_id)
+                .map_or_else(|| "an unknown item".to_string(), |item| format!("the {}", item.name)),
+        }
+    }
+    
+    /// Take a player out of the world, saving their character first if persistence is enabled
+    pub async fn remove_player(&mut self, player_id: &str) -> Result<()> {
+        {
+            let mut state = self.state.write().await;
+            if !state.online_players.contains_key(player_id) {
+                return Err(anyhow::anyhow!("Player {} not found", player_id));
+            }
+            // A trade can't outlive either side; everyone gets their offers back first
+            if let Some(trade) = state.cancel_trade(player_id) {
+                tracing::info!("Trade between {} and {} cancelled: {} left", trade.initiator, trade.counterparty, player_id);
+            }
+            state.end_combat_with(&combat::CombatTarget::Player(player_id.to_string()));
+            if let Some(store) = &self.player_store {
+                store.save_player(&state, player_id).await?;
+            }
+            
+            if let Some(player_data) = state.online_players.remove(player_id) {
+                state.beings.remove(&player_data.being_id);
+            }
+            state.broadcast_snapshots.remove(player_id);
+        }
+        
+        let leave_data = serde_json::json!({ "player_id": player_id });
+        self.broadcast_network_event("PlayerLeave", EventScope::Global, leave_data).await?;
+        Ok(())
+    }
+    
+    /// Apply a name reservation received from another node. If it displaces one of our
+    /// players, the suffixed name is announced so peers that missed the clash converge too.
+    pub async fn apply_name_reservation(&mut self, reservation: NameReservation) -> Result<()> {
+        let renamed = {
+            let mut state = self.state.write().await;
+            state.reserve_player_name(reservation)
+                .filter(|renamed| state.online_players.contains_key(&renamed.player_id))
+        };
+        
+        if let Some(renamed) = renamed {
+            tracing::info!("Player {} renamed to '{}' after a name clash", renamed.player_id, renamed.player_name);
+            self.broadcast_network_event("NameReservation", EventScope::Global, serde_json::to_value(&renamed)?).await?;
+        }
+        
+        Ok(())
+    }
+    
     /// Process a player command
     pub async fn process_command(&mut self, player_id: &str, command: &str) -> Result<String> {
-        let mut state = self.state.write().await;
-        
-        let parts: Vec<&str> = command.trim().split_whitespace().collect();
-        if parts.is_empty() {
-            return Ok("Please enter a command.".to_string());
+        match GameCommand::parse(command) {
+            Some(command) => self.execute_command(player_id, command).await,
+            None => Ok("Please enter a command.".to_string()),
         }
+    }
+    
+    /// Execute an already-parsed player command
+    pub async fn execute_command(&mut self, player_id: &str, command: GameCommand) -> Result<String> {
+        let response = self.run_command(player_id, command).await?;
+        self.broadcast_player_update(player_id).await?;
+        Ok(response)
+    }
+    
+    /// Send peers whatever changed in a player's skills or inventory since the last update.
+    /// Returns whether an update was sent.
+    pub async fn broadcast_player_update(&mut self, player_id: &str) -> Result<bool> {
+        let delta = {
+            let mut state = self.state.write().await;
+            let Some(being) = state.online_players.get(player_id)
+                .and_then(|player_data| state.beings.get(&player_data.being_id))
+            else {
+                return Ok(false);
+            };
+            
+            let current = PlayerSnapshot::of(being);
+            let previous = state.broadcast_snapshots.get(player_id).cloned().unwrap_or_default();
+            let delta = previous.diff(&current);
+            state.broadcast_snapshots.insert(player_id.to_string(), current);
+            delta
+        };
+        
+        let Some(delta) = delta else {
+            return Ok(false);
+        };
+        
+        let update_data = serde_json::json!({
+            "player_id": player_id,
+            "being_data": delta.encode()?
+        });
+        self.broadcast_network_event("PlayerUpdate", EventScope::Global, update_data).await?;
+        Ok(true)
+    }
+    
+    async fn run_command(&mut self, player_id: &str, command: GameCommand) -> Result<String> {
+        let mut state = self.state.write().await;
         
-        match parts[0].to_lowercase().as_str() {
-            "look" | "l" => {
+        match command {
+            GameCommand::Look { .. } => {
                 if let Some(player_data) = state.online_players.get(player_id) {
                     if let Some(area) = state.areas.get(&player_data.current_area_id) {
                         let players_here = state.get_players_in_area(&player_data.current_area_id);
@@ -661,12 +1359,20 @@ impl ArceonCore {
                             .filter(|&p| p != player_id)
                             .collect();
                         
-                        let mut response = format!("You are in {}.\n{}\n", area.name, area.description);
+                        let mut response = format!("You are in {}.\n{}\n{}\n", area.name, area.description,
+                            area.climate.current_weather.condition.describe());
                         
                         if !other_players.is_empty() {
                             response.push_str(&format!("Other players here: {}\n", other_players.join(", ")));
                         }
                         
+                        let structures: Vec<String> = state.structures_in(&player_data.current_area_id).iter()
+                            .map(|structure| format!("{} ({}, built by {})", structure.name, structure.structure_type, structure.builder_name))
+                            .collect();
+                        if !structures.is_empty() {
+                            response.push_str(&format!("Structures here: {}\n", structures.join(", ")));
+                        }
+                        
                         if !area.connected_areas.is_empty() {
                             let exit_names: Vec<String> = area.connected_areas.iter()
                                 .enumerate()
@@ -689,20 +1395,29 @@ impl ArceonCore {
                     Ok("Player not found.".to_string())
                 }
             },
-            "move" | "go" => {
-                if parts.len() < 2 {
+            GameCommand::Move { destination } => {
+                if destination.is_empty() {
                     return Ok("Where do you want to go? Use: move <number>".to_string());
                 }
                 
-                let exit_number = parts[1];
-                Self::move_player_static(player_id, exit_number, &mut state).await
+                let response = Self::move_player_static(player_id, &destination, &mut state).await?;
+                if let Some(pager) = &self.area_pager {
+                    pager.update_residency(&mut state).await?;
+                }
+                Ok(response)
             },
-            "who" => {
+            GameCommand::Enter { .. } => {
+                Ok("There is nothing here you can enter.".to_string())
+            },
+            GameCommand::Exit => {
+                Ok("You are not inside a structure that can be exited.".to_string())
+            },
+            GameCommand::Who => {
                 let player_count = state.online_players.len();
                 let players: Vec<String> = state.online_players.keys().cloned().collect();
                 Ok(format!("Players online ({}): {}", player_count, players.join(", ")))
             },
-            "stats" => {
+            GameCommand::Stats => {
                 if let Some(player_data) = state.online_players.get(player_id) {
                     if let Some(being) = state.beings.get(&player_data.being_id) {
                         let health = &being.vitals.health;
@@ -738,12 +1453,82 @@ impl ArceonCore {
                     Ok("Player not found.".to_string())
                 }
             },
-            "say" => {
-                if parts.len() < 2 {
+            GameCommand::Skills => {
+                if let Some(being) = state.online_players.get(player_id)
+                    .and_then(|player_data| state.beings.get(&player_data.being_id))
+                {
+                    let mut response = format!("=== Skills: {} ===\n", being.name);
+                    for (skill_name, level, experience) in being.get_formatted_skills() {
+                        response.push_str(&format!("  {}: {} (XP: {})\n", skill_name, level, experience));
+                    }
+                    Ok(response)
+                } else {
+                    Ok("Player not found.".to_string())
+                }
+            },
+            GameCommand::Inventory => {
+                let Some(being) = state.online_players.get(player_id)
+                    .and_then(|player_data| state.beings.get(&player_data.being_id))
+                else {
+                    return Ok("Player not found.".to_string());
+                };
+                let Some(inventory) = &being.inventory else {
+                    return Ok("You cannot carry items.".to_string());
+                };
+                
+                let mut held: Vec<String> = inventory.items.values()
+                    .map(|held| {
+                        let name = state.items.get(&held.item_id).map_or("Unknown item", |item| item.name.as_str());
+                        if held.stack_size > 1 {
+                            format!("  {} x{}", name, held.stack_size)
+                        } else {
+                            format!("  {}", name)
+                        }
+                    })
+                    .collect();
+                held.sort();
+                
+                if held.is_empty() {
+                    Ok("You are not carrying anything.".to_string())
+                } else {
+                    Ok(format!("You are carrying:\n{}", held.join("\n")))
+                }
+            },
+            GameCommand::Pickup { item } | GameCommand::Drop { item } if item.is_empty() => {
+                Ok("Which item? Use: pickup <item> or drop <item>".to_string())
+            },
+            GameCommand::Pickup { item } => {
+                let (item_id, location_name) = match state.pickup_item(player_id, &item) {
+                    Ok(taken) => taken,
+                    Err(reason) => return Ok(reason),
+                };
+                let item_name = state.items[&item_id].name.clone();
+                let area_id = state.online_players[player_id].current_area_id.clone();
+                drop(state); // Release lock before broadcasting
+                
+                self.broadcast_item_transfer(player_id, "pickup", item_id, &area_id, &location_name).await?;
+                Ok(format!("You pick up the {}.", item_name))
+            },
+            GameCommand::Drop { item } => {
+                let (item_id, location_name) = match state.drop_item(player_id, &item) {
+                    Ok(dropped) => dropped,
+                    Err(reason) => return Ok(reason),
+                };
+                let item_name = state.items[&item_id].name.clone();
+                let area_id = state.online_players[player_id].current_area_id.clone();
+                drop(state); // Release lock before broadcasting
+                
+                self.broadcast_item_transfer(player_id, "drop", item_id, &area_id, &location_name).await?;
+                Ok(format!("You drop the {}.", item_name))
+            },
+            GameCommand::Profile => {
+                Ok("Account profiles are only available through an authenticated server session.".to_string())
+            },
+            GameCommand::Say { message } => {
+                if message.is_empty() {
                     return Ok("What do you want to say? Use: say <message>".to_string());
                 }
                 
-                let message = parts[1..].join(" ");
                 if let Some(player_data) = state.online_players.get(player_id) {
                     let area_id = player_data.current_area_id.clone();
                     drop(state); // Release lock before broadcasting
@@ -754,23 +1539,178 @@ impl ArceonCore {
                         "area_id": area_id,
                         "message": message
                     });
-                    self.broadcast_network_event("Say", say_data).await?;
+                    self.broadcast_network_event("Say", EventScope::Area, say_data).await?;
                     
                     Ok(format!("You say: '{}'", message))
                 } else {
                     Ok("Player not found.".to_string())
                 }
             },
-            "talk" | "speak" => {
-                if parts.len() < 2 {
+            GameCommand::Tell { target, message } => {
+                if target.is_empty() || message.is_empty() {
+                    return Ok("Tell whom what? Use: tell <player> <message>".to_string());
+                }
+                if !state.online_players.contains_key(player_id) {
+                    return Ok("Player not found.".to_string());
+                }
+                
+                // Accept either the player id or the character's name
+                let Some(recipient_id) = state.find_online_player(&target).map(str::to_string) else {
+                    return Ok(format!("{} is not online.", target));
+                };
+                let recipient_name = Self::player_name_static(&state, &recipient_id);
+                drop(state); // Release lock before broadcasting
+                
+                let tell_data = serde_json::json!({
+                    "from": player_id,
+                    "to": recipient_id,
+                    "message": message
+                });
+                self.broadcast_network_event("Tell", EventScope::Global, tell_data).await?;
+                
+                Ok(format!("You tell {}: '{}'", recipient_name, message))
+            },
+            GameCommand::TradeOffer { player, goods } => {
+                if player.is_empty() || goods.is_empty() {
+                    return Ok("Use: trade <player> offer <item or amount tokens>, trade accept, or trade cancel".to_string());
+                }
+                let Some(partner_id) = state.find_online_player(&player).map(str::to_string) else {
+                    return Ok(format!("{} is not online.", player));
+                };
+                
+                match state.offer_trade(player_id, &partner_id, &goods) {
+                    Ok(offered) => {
+                        let description = Self::describe_goods_static(&state, &offered);
+                        let partner_name = Self::player_name_static(&state, &partner_id);
+                        Ok(format!("You offer {} to {}. Both of you must 'trade accept' to complete the trade.", description, partner_name))
+                    }
+                    Err(reason) => Ok(reason),
+                }
+            },
+            GameCommand::TradeAccept => {
+                let trade = match state.accept_trade(player_id) {
+                    Ok(Some(trade)) => trade,
+                    Ok(None) => return Ok("You accept the trade. Waiting for the other side to accept.".to_string()),
+                    Err(reason) => return Ok(reason),
+                };
+                let area_id = state.online_players.get(player_id).map(|data| data.current_area_id.clone()).unwrap_or_default();
+                let being_ids: HashMap<String, String> = [&trade.initiator, &trade.counterparty].into_iter()
+                    .filter_map(|id| state.online_players.get(id).map(|data| (id.clone(), data.being_id.clone())))
+                    .collect();
+                drop(state); // Release lock before broadcasting
+                
+                self.broadcast_trade_settlement(&trade, &area_id, &being_ids).await?;
+                Ok("The trade is complete.".to_string())
+            },
+            GameCommand::TradeCancel => {
+                match state.cancel_trade(player_id) {
+                    Some(_) => Ok("You call off the trade. All offers have been returned.".to_string()),
+                    None => Ok("You are not trading with anyone.".to_string()),
+                }
+            },
+            GameCommand::Attack { target } => {
+                let combat_target = if target.is_empty() {
+                    match state.combat_targets.get(player_id) {
+                        Some(current) => current.clone(),
+                        None => return Ok("Attack whom? Use: attack <name>".to_string()),
+                    }
+                } else {
+                    match state.find_combat_target(player_id, &target) {
+                        Some(found) => found,
+                        None => return Ok(format!("There is no {} here to attack.", target)),
+                    }
+                };
+                
+                state.combat_targets.insert(player_id.to_string(), combat_target.clone());
+                let outcome = match state.attack(player_id, &combat_target) {
+                    Ok(outcome) => outcome,
+                    Err(reason) => {
+                        state.combat_targets.remove(player_id);
+                        return Ok(reason);
+                    }
+                };
+                let response = Self::describe_attack_static(&state, &outcome);
+                drop(state); // Release lock before broadcasting
+                
+                self.broadcast_attack(&outcome).await?;
+                Ok(response)
+            },
+            GameCommand::AutoAttack { enabled } => {
+                let enabled = enabled.unwrap_or(!state.autoattacking.contains_key(player_id));
+                if !enabled {
+                    state.autoattacking.remove(player_id);
+                    return Ok("Autoattack off.".to_string());
+                }
+                if !state.combat_targets.contains_key(player_id) {
+                    return Ok("You have no target. Use: attack <name>".to_string());
+                }
+                let now = state.world_time;
+                state.autoattacking.insert(player_id.to_string(), now);
+                Ok("Autoattack on.".to_string())
+            },
+            GameCommand::MacroCreate { name, steps } => {
+                if name.is_empty() {
+                    return Ok("Use: macro create <name> <skill>, wait <seconds>, <skill>...".to_string());
+                }
+                let defined = SkillMacro::parse(&name, &steps)
+                    .and_then(|skill_macro| state.define_macro(player_id, skill_macro));
+                match defined {
+                    Ok(()) => Ok(format!("Macro {} saved.", name)),
+                    Err(reason) => Ok(reason),
+                }
+            },
+            GameCommand::MacroList => {
+                let macros = state.player_macros(player_id);
+                if macros.is_empty() {
+                    return Ok("You have no macros. Use: macro create <name> <skill>, wait <seconds>, <skill>...".to_string());
+                }
+                let mut response = "Your macros:".to_string();
+                for skill_macro in macros {
+                    let steps: Vec<String> = skill_macro.steps.iter()
+                        .map(|step| match step.delay_ms {
+                            0 => step.skill.clone(),
+                            delay_ms => format!("wait {}, {}", delay_ms as f64 / 1000.0, step.skill),
+                        })
+                        .collect();
+                    response.push_str(&format!("\n  {}: {}", skill_macro.name, steps.join(", ")));
+                }
+                Ok(response)
+            },
+            GameCommand::MacroDelete { name } => {
+                if state.delete_macro(player_id, &name) {
+                    Ok(format!("Macro {} deleted.", name))
+                } else {
+                    Ok(format!("You have no macro called {}.", name))
+                }
+            },
+            GameCommand::MacroRun { name } => {
+                match state.start_macro(player_id, &name) {
+                    Ok(skill_macro) => Ok(format!("Running macro {} ({} skills).", skill_macro.name, skill_macro.steps.len())),
+                    Err(reason) => Ok(reason),
+                }
+            },
+            GameCommand::Build { structure_type, name } => {
+                if structure_type.is_empty() {
+                    return Ok("What do you want to build? Use: build <type> <name>".to_string());
+                }
+                let (structure, area_id) = match state.build_structure(player_id, &structure_type, &name) {
+                    Ok(built) => built,
+                    Err(reason) => return Ok(reason),
+                };
+                drop(state); // Release lock before broadcasting
+                
+                self.broadcast_structure_construction(&structure, &area_id).await?;
+                Ok(format!("You build {}, a new {}.", structure.name, structure.structure_type.to_string().to_lowercase()))
+            },
+            GameCommand::Talk { npc: npc_name } => {
+                if npc_name.is_empty() {
                     return Ok("Who do you want to talk to? Use: talk <npc_name>".to_string());
                 }
                 
-                let npc_name = parts[1..].join(" ");
                 drop(state); // Release lock before calling interact_with_npc
                 self.interact_with_npc(player_id, &npc_name).await
             },
-            "npcs" => {
+            GameCommand::Npcs => {
                 if let Some(player_data) = state.online_players.get(player_id) {
                     if let Some(area) = state.areas.get(&player_data.current_area_id) {
                         let mut npc_info = Vec::new();
@@ -793,7 +1733,7 @@ impl ArceonCore {
                     Ok("Player not found.".to_string())
                 }
             },
-            "quests" => {
+            GameCommand::Quests => {
                 if let Some(player_data) = state.online_players.get(player_id) {
                     if let Some(being) = state.beings.get(&player_data.being_id) {
                         let available_quests = state.quest_system.get_available_quests_for_player(&being.skills.skills);
@@ -818,7 +1758,7 @@ impl ArceonCore {
                     Ok("Player not found.".to_string())
                 }
             },
-            "reputation" | "rep" => {
+            GameCommand::Reputation => {
                 let mut response = "Faction Reputation:\n".to_string();
                 for (faction_name, reputation) in &state.quest_system.faction_reputations {
                     response.push_str(&format!("  {}: {} ({:?})\n", 
@@ -832,15 +1772,112 @@ impl ArceonCore {
                 }
                 Ok(response)
             },
-            "help" => {
-                Ok("Available commands:\n  look/l - Look around\n  move/go <number> - Move to exit number\n  say <message> - Say something to nearby players\n  talk <npc> - Talk to an NPC\n  npcs - List NPCs in current area\n  quests - Show available quests\n  reputation/rep - Show faction reputation\n  who - List online players\n  stats - Show your character stats\n  help - Show this help".to_string())
+            GameCommand::Save => match &self.player_store {
+                Some(store) => {
+                    store.save_player(&state, player_id).await?;
+                    Ok("Your character has been saved.".to_string())
+                }
+                None => Ok("Character saving is not enabled on this server.".to_string()),
+            },
+            GameCommand::Help => {
+                Ok("Available commands:\n  look/l - Look around\n  move/go <number> - Move to exit number\n  say <message> - Say something to nearby players\n  tell <player> <message> - Send a private message\n  inventory/i - List what you are carrying\n  pickup <item> / drop <item> - Pick up or put down an item\n  trade <player> offer <item or amount tokens> - Offer a trade (then trade accept / trade cancel)\n  talk <npc> - Talk to an NPC\n  attack <name> - Attack an NPC or player here\n  autoattack [on|off] - Keep attacking your target\n  macro create <name> <skill>, wait <seconds>, ... / macro list / macro delete <name> / macro run <name> - Skill macros\n  build <type> <name> - Build a structure here from materials you carry\n  npcs - List NPCs in current area\n  quests - Show available quests\n  reputation/rep - Show faction reputation\n  who - List online players\n  stats - Show your character stats\n  save - Save your character\n  help - Show this help".to_string())
             },
-            _ => {
-                Ok(format!("Unknown command: {}. Type 'help' for available commands.", parts[0]))
+            // Non-admins get the same answer as for a command that doesn't exist
+            GameCommand::Admin { action } if !state.is_admin(player_id) => Ok(Self::unknown_command(action.verb())),
+            GameCommand::Admin { action } => {
+                drop(state); // Admin commands take the lock as they need it
+                self.run_admin_command(player_id, action).await
+            },
+            GameCommand::Unknown { input } => {
+                Ok(Self::unknown_command(input.split_whitespace().next().unwrap_or_default()))
             }
         }
     }
     
+    fn unknown_command(verb: &str) -> String {
+        format!("Unknown command: {}. Type 'help' for available commands.", verb)
+    }
+    
+    /// Carry out an operator command for an admin. Each one is broadcast as an `AdminAction`
+    /// naming the admin, so it is recorded alongside the world changes it caused.
+    async fn run_admin_command(&mut self, player_id: &str, action: AdminCommand) -> Result<String> {
+        let (reply, mut audit) = match action {
+            AdminCommand::Teleport { area } => {
+                if area.is_empty() {
+                    return Ok("Teleport where? Use: teleport <area>".to_string());
+                }
+                let mut state = self.state.write().await;
+                let area_id = state.areas.iter()
+                    .find(|(area_id, candidate)| candidate.name.eq_ignore_ascii_case(&area) || area_id.eq_ignore_ascii_case(&area))
+                    .map(|(area_id, _)| area_id.clone());
+                let Some(area_id) = area_id else {
+                    return Ok(format!("There is no area called {}.", area));
+                };
+                let area_name = state.areas[&area_id].name.clone();
+                if let Err(reason) = state.move_player_to_area(player_id, &area_id) {
+                    return Ok(reason);
+                }
+                if let Some(pager) = &self.area_pager {
+                    pager.update_residency(&mut state).await?;
+                }
+                (format!("You teleport to {}.", area_name), serde_json::json!({ "action": "teleport", "area_id": area_id }))
+            }
+            AdminCommand::SpawnItem { item_type } => {
+                if item_type.is_empty() {
+                    return Ok("Spawn what? Use: spawn_item <type>".to_string());
+                }
+                let mut state = self.state.write().await;
+                let Some(being_id) = state.online_players.get(player_id).map(|data| data.being_id.clone()) else {
+                    return Ok("Player not found.".to_string());
+                };
+                let item_id = uuid::Uuid::new_v4();
+                let Some(inventory) = state.beings.get_mut(&being_id).and_then(|being| being.inventory.as_mut()) else {
+                    return Ok("You cannot carry items.".to_string());
+                };
+                inventory.items.insert(item_id, being::InventoryItem { item_id, stack_size: 1, max_stack: 999 });
+                state.items.insert(item_id, Item {
+                    name: item_type.clone(),
+                    description: "Conjured by an administrator".to_string(),
+                    item_type: ItemType::Misc,
+                    rarity: Rarity::Common,
+                    value: 0,
+                    stack_size: 1,
+                    properties: HashMap::new(),
+                });
+                (format!("A {} appears in your pack.", item_type),
+                    serde_json::json!({ "action": "spawn_item", "item_id": item_id.to_string(), "item_type": item_type }))
+            }
+            AdminCommand::Kick { player } => {
+                let target = self.state.read().await.find_online_player(&player).map(str::to_string);
+                let Some(target) = target else {
+                    return Ok(format!("{} is not online.", player));
+                };
+                if target == player_id {
+                    return Ok("You cannot kick yourself.".to_string());
+                }
+                self.remove_player(&target).await?;
+                (format!("{} has been removed from the world.", player), serde_json::json!({ "action": "kick", "player_id": target }))
+            }
+            AdminCommand::Checkpoint => {
+                let mut saved = 0;
+                if let Some(store) = &self.player_store {
+                    let state = self.state.read().await;
+                    let mut player_ids: Vec<&String> = state.online_players.keys().collect();
+                    player_ids.sort();
+                    for online in player_ids {
+                        store.save_player(&state, online).await?;
+                        saved += 1;
+                    }
+                }
+                (format!("Checkpoint requested; {} character(s) saved.", saved), serde_json::json!({ "action": "checkpoint" }))
+            }
+        };
+        
+        audit["admin"] = serde_json::json!(player_id);
+        self.broadcast_network_event("AdminAction", EventScope::Global, audit).await?;
+        Ok(reply)
+    }
+    
     /// Move a player to a connected area
     async fn move_player_static(player_id: &str, exit_number: &str, state: &mut crate::state::GameState) -> Result<String> {
         // Get player data first
@@ -895,11 +1932,59 @@ impl ArceonCore {
         self.network_bridge = Some(bridge);
     }
     
-    /// Broadcast a network event to connected peers
-    async fn broadcast_network_event(&mut self, event_type: &str, data: serde_json::Value) -> Result<()> {
+    /// Keep only areas near active players in memory, paging the rest to disk
+    pub async fn enable_lazy_area_loading(&mut self, pager: persistence::AreaPager) -> Result<()> {
+        let change = pager.update_residency(&mut *self.state.write().await).await?;
+        tracing::info!("Lazy area loading enabled: {} area(s) paged out", change.evicted.len());
+        self.area_pager = Some(pager);
+        Ok(())
+    }
+    
+    /// Save characters to disk when players leave and restore them when they come back
+    pub fn enable_player_persistence(&mut self, store: persistence::PlayerStore) {
+        self.player_store = Some(store);
+    }
+    
+    /// Write a world snapshot through `manager` when the core shuts down
+    pub fn enable_world_snapshots(&mut self, manager: persistence::PersistenceManager) {
+        self.world_persistence = Some(manager);
+    }
+    
+    /// Turns true when `shutdown` begins; background loops started alongside the core
+    /// (auto-save, servers) should watch it and stop
+    pub fn shutdown_signal(&self) -> watch::Receiver<bool> {
+        self.shutdown.subscribe()
+    }
+    
+    /// Stop the core cleanly: signal background loops, take every player out of the world
+    /// (peers get a `PlayerLeave` for each, and characters are saved if player persistence
+    /// is on), then write a `SafeShutdown` world snapshot. Returns once the snapshot is on disk.
+    pub async fn shutdown(&mut self) -> Result<()> {
+        self.shutdown.send_replace(true);
+        
+        let mut player_ids: Vec<String> = self.state.read().await.online_players.keys().cloned().collect();
+        player_ids.sort();
+        for player_id in player_ids {
+            self.remove_player(&player_id).await?;
+        }
+        
+        if let Some(manager) = &self.world_persistence {
+            let state = self.state.read().await;
+            manager.safe_shutdown_save(&state, &state.quest_system).await?;
+        }
+        Ok(())
+    }
+    
+    /// Broadcast a network event to connected peers (local-scope events never leave this node)
+    async fn broadcast_network_event(&mut self, event_type: &str, scope: EventScope, data: serde_json::Value) -> Result<()> {
+        if !scope.is_broadcast() {
+            return Ok(());
+        }
+        
         if let Some(bridge) = &mut self.network_bridge {
             let message = serde_json::json!({
                 "type": event_type,
+                "scope": scope.as_str(),
                 "timestamp": chrono::Utc::now().timestamp(),
                 "data": data
             });
@@ -913,3 +1998,807 @@ impl ArceonCore {
         self.state.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Network bridge that records every message instead of sending it
+    #[derive(Clone, Default)]
+    struct RecordingBridge {
+        messages: Arc<std::sync::Mutex<Vec<serde_json::Value>>>,
+    }
+
+    impl NetworkBridge for RecordingBridge {
+        fn broadcast_message(&mut self, message: serde_json::Value) -> Result<()> {
+            self.messages.lock().unwrap().push(message);
+            Ok(())
+        }
+
+        fn get_connected_peers(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    async fn core_with_bridge() -> (ArceonCore, RecordingBridge) {
+        let mut core = ArceonCore::new(Config::default()).await.unwrap();
+        let bridge = RecordingBridge::default();
+        core.set_network_bridge(Box::new(bridge.clone()));
+        (core, bridge)
+    }
+
+    #[tokio::test]
+    async fn test_event_scope_controls_broadcast() {
+        let (mut core, bridge) = core_with_bridge().await;
+
+        core.broadcast_network_event("StatsQuery", EventScope::Local, serde_json::json!({})).await.unwrap();
+        assert!(bridge.messages.lock().unwrap().is_empty());
+
+        core.broadcast_network_event("Say", EventScope::Area, serde_json::json!({"message": "hi"})).await.unwrap();
+        let messages = bridge.messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["scope"], "area");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_names_resolve_identically_on_both_nodes() {
+        let (mut node_a, bridge_a) = core_with_bridge().await;
+        let (mut node_b, bridge_b) = core_with_bridge().await;
+        for node in [&node_a, &node_b] {
+            node.state.write().await.add_area(Area::new("Town".to_string(), AreaType::Village, None));
+        }
+
+        node_a.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        node_b.create_player("player-b".to_string(), "aria".to_string(), being::Race::Elf).await.unwrap();
+        assert!(node_a.create_player("player-c".to_string(), "ARIA".to_string(), being::Race::Human).await.is_err());
+
+        // Force a known ordering regardless of wall-clock timing
+        let claim_a = NameReservation { player_id: "player-a".to_string(), player_name: "Aria".to_string(), reserved_at: 1_000 };
+        let claim_b = NameReservation { player_id: "player-b".to_string(), player_name: "aria".to_string(), reserved_at: 1_000 };
+        node_a.state.write().await.name_reservations.insert("aria".to_string(), claim_a.clone());
+        node_b.state.write().await.name_reservations.insert("aria".to_string(), claim_b.clone());
+
+        node_a.apply_name_reservation(claim_b).await.unwrap();
+        node_b.apply_name_reservation(claim_a).await.unwrap();
+
+        // The loser's suffix is the first six alphanumerics of its player id
+        for node in [&node_a, &node_b] {
+            let state = node.state.read().await;
+            assert_eq!(state.name_holder("Aria"), Some("player-a"));
+            assert_eq!(state.name_holder("aria-player"), Some("player-b"));
+        }
+        let state_b = node_b.state.read().await;
+        let being_id = &state_b.online_players["player-b"].being_id;
+        assert_eq!(state_b.beings[being_id].name, "aria-player");
+
+        // Only the node that owns the loser re-announces the new name
+        let renames = |bridge: &RecordingBridge| bridge.messages.lock().unwrap().iter()
+            .filter(|m| m["type"] == "NameReservation" && m["data"]["player_name"] == "aria-player")
+            .count();
+        assert_eq!(renames(&bridge_a), 0);
+        assert_eq!(renames(&bridge_b), 1);
+    }
+
+    /// Builds a straight road of areas, each connected to its neighbours
+    fn area_chain(names: &[&str]) -> Vec<Area> {
+        let mut areas: Vec<Area> = names.iter()
+            .map(|name| Area::new(name.to_string(), AreaType::Plains, None))
+            .collect();
+        for i in 0..areas.len() {
+            let neighbours: Vec<uuid::Uuid> = [i.checked_sub(1), Some(i + 1)].into_iter()
+                .flatten()
+                .filter_map(|j| areas.get(j).map(|area| area.id))
+                .collect();
+            areas[i].connected_areas = neighbours.into_iter()
+                .map(|target_area_id| AreaConnection {
+                    target_area_id,
+                    connection_type: ConnectionType::Road,
+                    travel_time: 10,
+                    description: String::new(),
+                    requirements: Vec::new(),
+                })
+                .collect();
+        }
+        areas
+    }
+
+    /// An empty location with no connections
+    fn location(name: &str, location_type: world::LocationType) -> world::Location {
+        world::Location {
+            name: name.to_string(),
+            description: String::new(),
+            location_type,
+            npcs_present: Vec::new(),
+            items_present: Vec::new(),
+            connections: Vec::new(),
+            special_features: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_distant_areas_are_paged_out_and_reloaded_on_approach() {
+        let (mut core, _bridge) = core_with_bridge().await;
+        let areas = area_chain(&["Ashford", "Brackwater", "Coldmere", "Dunmoor"]);
+        let ids: Vec<String> = areas.iter().map(|area| area.id.to_string()).collect();
+        {
+            let mut state = core.state.write().await;
+            for area in areas {
+                state.add_area(area);
+            }
+            let being = being::Being::new_player("Wanderer".to_string(), being::Race::Human);
+            let being_id = being.id.to_string();
+            state.add_being(being);
+            state.online_players.insert("wanderer".to_string(), crate::state::PlayerData {
+                being_id,
+                current_area_id: ids[0].clone(),
+                last_activity: 0,
+                is_admin: false,
+            });
+        }
+
+        let area_dir = std::env::temp_dir().join(format!("arceon-areas-{}", uuid::Uuid::new_v4()));
+        let pager = persistence::AreaPager::new(area_dir.clone(), 16, 0).unwrap();
+        core.enable_lazy_area_loading(pager).await.unwrap();
+
+        let resident = |state: &GameState| -> Vec<bool> {
+            ids.iter().map(|id| state.areas.contains_key(id)).collect()
+        };
+        assert_eq!(resident(&*core.state.read().await), vec![true, true, false, false]);
+
+        // Ashford -> Brackwater brings Coldmere into range
+        core.process_command("wanderer", "move 1").await.unwrap();
+        assert_eq!(resident(&*core.state.read().await), vec![true, true, true, false]);
+
+        // Brackwater -> Coldmere: Ashford drops out, Dunmoor is read back from disk
+        core.process_command("wanderer", "move 2").await.unwrap();
+        let state = core.state.read().await;
+        assert_eq!(resident(&state), vec![false, true, true, true]);
+        assert_eq!(state.areas[&ids[3]].name, "Dunmoor");
+        assert!(state.paged_out_areas.contains(&ids[0]));
+        drop(state);
+
+        std::fs::remove_dir_all(area_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_schedule_ticks_advance_skill_experience() {
+        let mut core = ArceonCore::new(Config::default()).await.unwrap();
+        let being = core.world.spawn(Being::new_player("Smith".to_string(), being::Race::Human)).id();
+        let experience = |core: &ArceonCore| {
+            core.world.get::<Being>(being).unwrap().skills.skills.get("Defense").map_or(0.0, |skill| skill.experience)
+        };
+        let before = experience(&core);
+
+        core.world.entity_mut(being).insert(Acting { skill: "Defense".to_string(), experience: 25.0 });
+        core.update();
+        assert_eq!(experience(&core), before + 25.0);
+
+        // The flag is consumed, so idle ticks leave the skill alone
+        core.update();
+        core.update();
+        assert_eq!(experience(&core), before + 25.0);
+        assert_eq!(core.world.resource::<WorldClock>().ticks, 3);
+
+        core.world.entity_mut(being).insert(Acting { skill: "Defense".to_string(), experience: 25.0 });
+        core.update();
+        assert_eq!(experience(&core), before + 50.0);
+    }
+
+    #[tokio::test]
+    async fn test_spawned_npcs_resolve_to_entities_of_the_area_type() {
+        let (mut core, _bridge) = core_with_bridge().await;
+        let clearing = location("Clearing", world::LocationType::Clearing);
+        let mut forest = Area::new("Whisperwood".to_string(), AreaType::Forest, None);
+        let mut desert = Area::new("Red Dunes".to_string(), AreaType::Desert, None);
+        forest.locations.insert(clearing.name.clone(), clearing.clone());
+        desert.locations.insert(clearing.name.clone(), clearing);
+        let (forest_id, desert_id) = (forest.id.to_string(), desert.id.to_string());
+        {
+            let mut state = core.state.write().await;
+            state.add_area(forest);
+            state.add_area(desert);
+        }
+
+        core.spawn_missing_npcs().await.unwrap();
+
+        let state = core.state.read().await;
+        let npcs_in = |area_id: &str| -> Vec<&state::Npc> {
+            state.areas[area_id].locations.values()
+                .flat_map(|location| location.npcs_present.iter())
+                .map(|npc_id| state.get_npc(npc_id).expect("spawned npc has no entity"))
+                .collect()
+        };
+
+        let forest_npcs = npcs_in(&forest_id);
+        assert_eq!(forest_npcs.len(), 2);
+        for npc in forest_npcs {
+            assert!(matches!(npc.npc_type, NpcType::Ranger | NpcType::Druid | NpcType::Hermit));
+            assert_eq!(npc.home_area, forest_id);
+            assert!(!npc.being.skills.skills.is_empty());
+        }
+
+        let desert_npcs = npcs_in(&desert_id);
+        assert_eq!(desert_npcs.len(), 1);
+        assert_eq!(desert_npcs[0].npc_type, NpcType::Nomad);
+        assert_eq!(state.npcs.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_saved_player_resumes_with_identical_skills() {
+        let player_dir = std::env::temp_dir().join(format!("arceon-players-{}", uuid::Uuid::new_v4()));
+        let store = || persistence::PlayerStore::new(player_dir.clone()).unwrap();
+        let areas = area_chain(&["Ashford", "Brackwater"]);
+        let second_area = areas[1].id.to_string();
+
+        let (mut core, _bridge) = core_with_bridge().await;
+        core.enable_player_persistence(store());
+        core.add_areas(areas.clone()).await.unwrap();
+        let being_id = core.create_player("smith".to_string(), "Smith".to_string(), being::Race::Dwarf).await.unwrap();
+        let skills = {
+            let mut state = core.state.write().await;
+            let being = state.beings.get_mut(&being_id).unwrap();
+            being.gain_skill_experience("Defense", 400.0, being::ExperienceSource::BlockingAttack);
+            state.online_players.get_mut("smith").unwrap().current_area_id = second_area.clone();
+            state.beings[&being_id].skills.skills.iter()
+                .map(|(name, skill)| (name.clone(), (skill.level, skill.experience)))
+                .collect::<std::collections::HashMap<_, _>>()
+        };
+        core.remove_player("smith").await.unwrap();
+        assert!(core.state.read().await.online_players.is_empty());
+
+        // A fresh node reading the same directory picks the character back up
+        let (mut restarted, _bridge) = core_with_bridge().await;
+        restarted.enable_player_persistence(store());
+        restarted.add_areas(areas).await.unwrap();
+        let resumed_id = restarted.create_player("smith".to_string(), "Smith".to_string(), being::Race::Dwarf).await.unwrap();
+        assert_eq!(resumed_id, being_id);
+
+        let state = restarted.state.read().await;
+        assert_eq!(state.online_players["smith"].current_area_id, second_area);
+        let resumed_skills: std::collections::HashMap<_, _> = state.beings[&being_id].skills.skills.iter()
+            .map(|(name, skill)| (name.clone(), (skill.level, skill.experience)))
+            .collect();
+        assert_eq!(resumed_skills, skills);
+        drop(state);
+
+        std::fs::remove_dir_all(player_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_tell_reaches_online_players_only() {
+        let (mut core, bridge) = core_with_bridge().await;
+        core.add_areas(vec![Area::new("Town".to_string(), AreaType::Village, None)]).await.unwrap();
+        core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        core.create_player("player-b".to_string(), "Bram".to_string(), being::Race::Dwarf).await.unwrap();
+
+        let response = core.process_command("player-a", "tell Cato are you there").await.unwrap();
+        assert_eq!(response, "Cato is not online.");
+
+        let response = core.process_command("player-a", "tell bram meet me at the gate").await.unwrap();
+        assert_eq!(response, "You tell Bram: 'meet me at the gate'");
+        let messages = bridge.messages.lock().unwrap();
+        let tells: Vec<_> = messages.iter().filter(|m| m["type"] == "Tell").collect();
+        assert_eq!(tells.len(), 1);
+        assert_eq!(tells[0]["data"]["to"], "player-b");
+        assert_eq!(tells[0]["data"]["from"], "player-a");
+    }
+
+    #[tokio::test]
+    async fn test_items_are_picked_up_listed_and_dropped() {
+        let (mut core, bridge) = core_with_bridge().await;
+        let mut area = Area::new("Town".to_string(), AreaType::Village, None);
+        area.locations.insert("Market".to_string(), location("Market", world::LocationType::Market));
+        let area_id = area.id.to_string();
+        core.add_areas(vec![area]).await.unwrap();
+        core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        let item_id = core.state.write().await.place_item(&area_id, "Market", Item {
+            name: "Rusty Sword".to_string(),
+            description: String::new(),
+            item_type: ItemType::Weapon(WeaponType::Sword),
+            rarity: Rarity::Common,
+            value: 5,
+            stack_size: 1,
+            properties: std::collections::HashMap::new(),
+        }).unwrap();
+        let market_items = |state: &GameState| state.areas[&area_id].locations["Market"].items_present.clone();
+
+        assert_eq!(core.process_command("player-a", "inventory").await.unwrap(), "You are not carrying anything.");
+        assert_eq!(core.process_command("player-a", "pickup rusty sword").await.unwrap(), "You pick up the Rusty Sword.");
+        assert!(market_items(&*core.state.read().await).is_empty());
+        assert_eq!(core.process_command("player-a", "get rusty sword").await.unwrap(), "There is no rusty sword here.");
+        assert_eq!(core.process_command("player-a", "inventory").await.unwrap(), "You are carrying:\n  Rusty Sword");
+
+        assert_eq!(core.process_command("player-a", "drop Rusty Sword").await.unwrap(), "You drop the Rusty Sword.");
+        assert_eq!(market_items(&*core.state.read().await), vec![item_id]);
+        assert_eq!(core.process_command("player-a", "inventory").await.unwrap(), "You are not carrying anything.");
+
+        let messages = bridge.messages.lock().unwrap();
+        let transfers: Vec<_> = messages.iter()
+            .filter(|m| m["type"] == "ItemTransfer")
+            .map(|m| m["data"]["action_type"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(transfers, vec!["pickup", "drop"]);
+        assert!(messages.iter().any(|m| m["type"] == "ItemTransfer" && m["data"]["item_id"] == item_id.to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_building_a_structure_uses_materials_and_shows_in_the_area() {
+        let (mut core, bridge) = core_with_bridge().await;
+        let mut area = Area::new("Town".to_string(), AreaType::Village, None);
+        area.locations.insert("Market".to_string(), location("Market", world::LocationType::Market));
+        let area_id = area.id.to_string();
+        core.add_areas(vec![area]).await.unwrap();
+        core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        for (name, stack_size) in [("Wood", 12), ("Stone", 5)] {
+            core.state.write().await.place_item(&area_id, "Market", Item {
+                name: name.to_string(),
+                description: String::new(),
+                item_type: ItemType::Material,
+                rarity: Rarity::Common,
+                value: 1,
+                stack_size,
+                properties: std::collections::HashMap::new(),
+            }).unwrap();
+            core.process_command("player-a", &format!("pickup {}", name)).await.unwrap();
+        }
+
+        assert_eq!(core.process_command("player-a", "build tower Watchtower").await.unwrap(),
+            "You need 30 Stone to build a tower but have 5.");
+        assert_eq!(core.process_command("player-a", "build house Oak Hall").await.unwrap(), "You build Oak Hall, a new house.");
+        assert_eq!(core.process_command("player-a", "inventory").await.unwrap(), "You are carrying:\n  Wood x2");
+
+        let look = core.process_command("player-a", "look").await.unwrap();
+        assert!(look.contains("Structures here: Oak Hall (House, built by Aria)"), "{}", look);
+        assert_eq!(core.state.read().await.structures_in(&area_id).len(), 1);
+
+        let messages = bridge.messages.lock().unwrap();
+        let construction = messages.iter().find(|m| m["type"] == "StructureConstruction").unwrap();
+        assert_eq!(construction["data"]["name"], "Oak Hall");
+        assert_eq!(construction["data"]["area_id"], area_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_npc_tone_follows_faction_reputation() {
+        let (mut core, _bridge) = core_with_bridge().await;
+        let mut area = Area::new("Harborview".to_string(), AreaType::City, None);
+        area.locations.insert("Market".to_string(), location("Market", world::LocationType::Market));
+        let area_id = area.id.to_string();
+        core.add_areas(vec![area]).await.unwrap();
+        core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        {
+            let mut state = core.state.write().await;
+            let npc = state::Npc::spawn(NpcType::Merchant, area_id.clone(), "Market".to_string());
+            let npc_id = npc.being.id;
+            state.areas.get_mut(&area_id).unwrap().locations.get_mut("Market").unwrap().npcs_present.push(npc_id);
+            state.npcs.insert(npc_id, npc);
+            state.quest_system.update_faction_reputation("Merchants Guild", -300, "Robbed a caravan", None);
+        }
+
+        let hostile = core.process_command("player-a", "talk merchant").await.unwrap();
+        assert!(hostile.contains("You're not welcome here. The Merchants Guild"), "{}", hostile);
+        assert!(hostile.contains("don't think I'll trade with you"), "{}", hostile);
+
+        core.state.write().await.quest_system.update_faction_reputation("Merchants Guild", 900, "Saved the guildhall", None);
+        let warm = core.process_command("player-a", "talk merchant").await.unwrap();
+        assert!(warm.contains("Good to see you again, friend of the Merchants Guild!"), "{}", warm);
+        assert!(warm.contains("my best wares at a friend's price"), "{}", warm);
+        assert!(!warm.contains("not welcome"));
+    }
+
+    #[tokio::test]
+    async fn test_only_admins_can_teleport() {
+        let mut config = Config::default();
+        config.world.admin_players = vec!["admin".to_string()];
+        let mut core = ArceonCore::new(config).await.unwrap();
+        let bridge = RecordingBridge::default();
+        core.set_network_bridge(Box::new(bridge.clone()));
+        let areas = area_chain(&["Gate", "Keep"]);
+        let names: HashMap<String, String> = areas.iter().map(|area| (area.id.to_string(), area.name.clone())).collect();
+        core.add_areas(areas).await.unwrap();
+        core.create_player("admin".to_string(), "Warden".to_string(), being::Race::Human).await.unwrap();
+        core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        let area_of = |state: &GameState, player_id: &str| state.online_players[player_id].current_area_id.clone();
+        // Wherever each player started, aim them at the other area
+        let elsewhere = |area_id: &str| names.iter().find(|(id, _)| id.as_str() != area_id).map(|(id, name)| (id.clone(), name.clone())).unwrap();
+
+        let player_start = area_of(&*core.state.read().await, "player-a");
+        let (_, player_target) = elsewhere(&player_start);
+        assert_eq!(core.process_command("player-a", &format!("teleport {}", player_target)).await.unwrap(),
+            "Unknown command: teleport. Type 'help' for available commands.");
+        assert_eq!(area_of(&*core.state.read().await, "player-a"), player_start);
+
+        let admin_start = area_of(&*core.state.read().await, "admin");
+        let (target_id, target_name) = elsewhere(&admin_start);
+        assert_eq!(core.process_command("admin", &format!("teleport {}", target_name.to_lowercase())).await.unwrap(),
+            format!("You teleport to {}.", target_name));
+        assert_eq!(area_of(&*core.state.read().await, "admin"), target_id);
+
+        let messages = bridge.messages.lock().unwrap();
+        let audits: Vec<_> = messages.iter().filter(|m| m["type"] == "AdminAction").collect();
+        assert_eq!(audits.len(), 1);
+        assert_eq!(audits[0]["data"]["admin"], "admin");
+        assert_eq!(audits[0]["data"]["action"], "teleport");
+        assert_eq!(audits[0]["data"]["area_id"], target_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_wandering_npcs_migrate_and_stationary_npcs_stay() {
+        let (mut core, _bridge) = core_with_bridge().await;
+        let mut areas = area_chain(&["Oasis", "Dune Sea"]);
+        for area in &mut areas {
+            area.locations.insert("Crossroads".to_string(), location("Crossroads", world::LocationType::Camp));
+        }
+        let home = areas[0].id.to_string();
+        core.add_areas(areas).await.unwrap();
+
+        let (nomad, guard) = {
+            let mut state = core.state.write().await;
+            let mut ids = Vec::new();
+            for npc_type in [NpcType::Nomad, NpcType::Guard] {
+                let npc = state::Npc::spawn(npc_type, home.clone(), "Crossroads".to_string());
+                let npc_id = npc.being.id;
+                state.areas.get_mut(&home).unwrap().locations.get_mut("Crossroads").unwrap().npcs_present.push(npc_id);
+                state.npcs.insert(npc_id, npc);
+                ids.push(npc_id);
+            }
+            (ids[0], ids[1])
+        };
+
+        let mut nomad_left_home = false;
+        for _ in 0..400 {
+            core.migrate_npcs().await.unwrap();
+            let state = core.state.read().await;
+            let nomad_area = &state.npcs[&nomad].current_area;
+            nomad_left_home |= *nomad_area != home;
+            assert!(state.areas[nomad_area].locations["Crossroads"].npcs_present.contains(&nomad));
+            assert_eq!(state.npcs[&guard].current_area, home);
+        }
+        assert!(nomad_left_home);
+        assert!(core.state.read().await.areas[&home].locations["Crossroads"].npcs_present.contains(&guard));
+    }
+
+    #[test]
+    fn test_merchant_activity_follows_time_of_day() {
+        let mut state = GameState::new();
+        state.world_time = 12 * state::WORLD_TIME_PER_HOUR;
+        assert_eq!(state.current_hour(), 12);
+        let noon = NpcType::Merchant.activity(state.current_hour());
+
+        // A full day and twelve hours on from noon wraps round to midnight
+        state.world_time = 48 * state::WORLD_TIME_PER_HOUR;
+        assert_eq!(state.current_hour(), 0);
+        assert!(!state.is_daytime());
+        let midnight = NpcType::Merchant.activity(state.current_hour());
+
+        assert_ne!(noon, midnight);
+        assert!(noon.contains("opens their shop"));
+        assert_ne!(NpcType::Guard.activity(3), NpcType::Guard.activity(15));
+    }
+
+    /// Two players in one village: Aria holding a sword and Bram holding 30 tokens
+    async fn traders() -> (ArceonCore, RecordingBridge, uuid::Uuid) {
+        let (mut core, bridge) = core_with_bridge().await;
+        core.add_areas(vec![Area::new("Town".to_string(), AreaType::Village, None)]).await.unwrap();
+        let aria = core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        let bram = core.create_player("player-b".to_string(), "Bram".to_string(), being::Race::Dwarf).await.unwrap();
+
+        let sword = uuid::Uuid::new_v4();
+        let mut state = core.state.write().await;
+        state.items.insert(sword, Item {
+            name: "Rusty Sword".to_string(),
+            description: String::new(),
+            item_type: ItemType::Weapon(WeaponType::Sword),
+            rarity: Rarity::Common,
+            value: 5,
+            stack_size: 1,
+            properties: HashMap::new(),
+        });
+        state.beings.get_mut(&aria).unwrap().inventory.as_mut().unwrap().items
+            .insert(sword, being::InventoryItem { item_id: sword, stack_size: 1, max_stack: 999 });
+        state.token_balances.insert(bram, 30);
+        drop(state);
+        (core, bridge, sword)
+    }
+
+    fn holds(state: &GameState, player_id: &str, item_id: &uuid::Uuid) -> bool {
+        let being_id = &state.online_players[player_id].being_id;
+        state.beings[being_id].inventory.as_ref().unwrap().items.contains_key(item_id)
+    }
+
+    fn tokens(state: &GameState, player_id: &str) -> u64 {
+        state.token_balances.get(&state.online_players[player_id].being_id).copied().unwrap_or(0)
+    }
+
+    #[tokio::test]
+    async fn test_trade_swaps_escrowed_goods_on_mutual_accept() {
+        let (mut core, bridge, sword) = traders().await;
+
+        assert_eq!(core.process_command("player-a", "trade bram offer 100 tokens").await.unwrap(), "You do not have 100 tokens to offer.");
+        core.process_command("player-a", "trade bram offer rusty sword").await.unwrap();
+        assert!(!holds(&*core.state.read().await, "player-a", &sword));
+        core.process_command("player-b", "trade aria offer 30 tokens").await.unwrap();
+
+        assert_eq!(
+            core.process_command("player-a", "trade accept").await.unwrap(),
+            "You accept the trade. Waiting for the other side to accept."
+        );
+        assert_eq!(core.process_command("player-b", "trade accept").await.unwrap(), "The trade is complete.");
+
+        let state = core.state.read().await;
+        assert!(holds(&state, "player-b", &sword));
+        assert!(!holds(&state, "player-a", &sword));
+        assert_eq!(tokens(&state, "player-a"), 30);
+        assert_eq!(tokens(&state, "player-b"), 0);
+        assert!(state.trades.is_empty());
+        drop(state);
+
+        let messages = bridge.messages.lock().unwrap();
+        assert!(messages.iter().any(|m| m["type"] == "ItemTransfer" && m["data"]["to"] == "player-b"));
+        assert!(messages.iter().any(|m| m["type"] == "TOKEN_TRANSFER" && m["data"]["amount"] == 30));
+    }
+
+    #[tokio::test]
+    async fn test_trade_is_cancelled_when_a_player_leaves() {
+        let (mut core, bridge, sword) = traders().await;
+
+        core.process_command("player-a", "trade bram offer rusty sword").await.unwrap();
+        core.process_command("player-b", "trade aria offer 30 tokens").await.unwrap();
+        core.process_command("player-a", "trade accept").await.unwrap();
+        let bram_being = core.state.read().await.online_players["player-b"].being_id.clone();
+        core.remove_player("player-b").await.unwrap();
+
+        let state = core.state.read().await;
+        assert!(holds(&state, "player-a", &sword));
+        assert_eq!(state.token_balances[&bram_being], 30);
+        assert!(state.trades.is_empty());
+        drop(state);
+
+        assert_eq!(core.process_command("player-a", "trade accept").await.unwrap(), "You are not trading with anyone.");
+        assert!(!bridge.messages.lock().unwrap().iter().any(|m| m["type"] == "TOKEN_TRANSFER"));
+    }
+
+    #[tokio::test]
+    async fn test_slaying_an_npc_grants_loot_and_experience() {
+        let (mut core, bridge) = core_with_bridge().await;
+        let mut area = Area::new("Town".to_string(), AreaType::Village, None);
+        area.locations.insert("Market".to_string(), location("Market", world::LocationType::Market));
+        let area_id = area.id.to_string();
+        core.add_areas(vec![area]).await.unwrap();
+        let being_id = core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+
+        let (npc_id, coin_purse) = {
+            let mut state = core.state.write().await;
+            let mut npc = state::Npc::spawn(NpcType::Merchant, area_id.clone(), "Market".to_string());
+            let coin_purse = uuid::Uuid::new_v4();
+            state.items.insert(coin_purse, Item {
+                name: "Coin Purse".to_string(),
+                description: String::new(),
+                item_type: ItemType::Currency,
+                rarity: Rarity::Common,
+                value: 20,
+                stack_size: 1,
+                properties: HashMap::new(),
+            });
+            npc.being.inventory.as_mut().unwrap().items
+                .insert(coin_purse, being::InventoryItem { item_id: coin_purse, stack_size: 1, max_stack: 999 });
+            let npc_id = npc.being.id;
+            state.areas.get_mut(&area_id).unwrap().locations.get_mut("Market").unwrap().npcs_present.push(npc_id);
+            state.npcs.insert(npc_id, npc);
+            (npc_id, coin_purse)
+        };
+        let strength = |state: &GameState| state.beings[&being_id].skills.skills["Strength"].experience;
+        let starting_strength = strength(&*core.state.read().await);
+
+        assert_eq!(core.process_command("player-a", "attack nobody").await.unwrap(), "There is no nobody here to attack.");
+        let first_hit = core.process_command("player-a", "attack merchant").await.unwrap();
+        assert!(first_hit.starts_with("You hit Merchant for"), "{}", first_hit);
+        let health = core.state.read().await.npcs[&npc_id].being.vitals.health.current;
+        assert!(health < 100.0);
+
+        // Autoattack finishes the fight on its own as world time passes
+        assert_eq!(core.process_command("player-a", "autoattack on").await.unwrap(), "Autoattack on.");
+        for _ in 0..50 {
+            core.state.write().await.world_time += combat::AUTOATTACK_INTERVAL;
+            core.run_autoattacks().await.unwrap();
+            if !core.state.read().await.npcs.contains_key(&npc_id) {
+                break;
+            }
+        }
+
+        let state = core.state.read().await;
+        assert!(!state.npcs.contains_key(&npc_id));
+        assert!(state.areas[&area_id].locations["Market"].npcs_present.is_empty());
+        assert!(holds(&state, "player-a", &coin_purse));
+        assert!(strength(&state) >= starting_strength + combat::KILL_EXPERIENCE);
+        assert!(state.autoattacking.is_empty() && state.combat_targets.is_empty());
+        drop(state);
+
+        let messages = bridge.messages.lock().unwrap();
+        let combat: Vec<_> = messages.iter().filter(|m| m["type"] == "Combat").collect();
+        let killing_blow = combat.last().unwrap();
+        assert_eq!(killing_blow["data"]["killed"], true);
+        assert_eq!(killing_blow["data"]["target_health"], 0.0);
+        assert!(combat[..combat.len() - 1].iter().all(|m| m["data"]["killed"] == false));
+        assert!(messages.iter().any(|m| m["type"] == "ItemTransfer" && m["data"]["action_type"] == "loot"));
+    }
+
+    #[tokio::test]
+    async fn test_macro_skills_fire_in_order_after_cooldowns() {
+        let (mut core, bridge) = core_with_bridge().await;
+        core.add_areas(vec![Area::new("Town".to_string(), AreaType::Village, None)]).await.unwrap();
+        let being_id = core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+
+        assert_eq!(core.process_command("player-a", "macro create opener strength, wait 1, Dexterity, Charisma").await.unwrap(), "Macro opener saved.");
+        assert_eq!(core.process_command("player-a", "macro create bad Juggling").await.unwrap(), "You don't know the skill Juggling.");
+        assert_eq!(core.process_command("player-a", "macro list").await.unwrap(), "Your macros:\n  opener: Strength, wait 1, Dexterity, Charisma");
+
+        // Dexterity is still cooling down from earlier use
+        let start = core.state.read().await.world_time;
+        core.state.write().await.skill_ready_at
+            .entry(being_id.clone()).or_default()
+            .insert("Dexterity".to_string(), start + 3_000);
+        assert_eq!(core.process_command("player-a", "macro run opener").await.unwrap(), "Running macro opener (3 skills).");
+
+        let mut fired_at = Vec::new();
+        for _ in 0..50 {
+            core.run_macros().await.unwrap();
+            let state = core.state.read().await;
+            let count = bridge.messages.lock().unwrap().iter().filter(|m| m["type"] == "SkillUse").count();
+            fired_at.resize(count, state.world_time);
+            let done = state.running_macros.is_empty();
+            drop(state);
+            if done {
+                break;
+            }
+            core.state.write().await.world_time += 500;
+        }
+
+        let fired: Vec<String> = bridge.messages.lock().unwrap().iter()
+            .filter(|m| m["type"] == "SkillUse")
+            .map(|m| m["data"]["skill"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(fired, vec!["Strength", "Dexterity", "Charisma"]);
+        // Strength straight away, Dexterity held past its one-second wait by the cooldown
+        assert_eq!(fired_at, vec![start, start + 3_000, start + 3_000]);
+
+        assert_eq!(core.process_command("player-a", "macro delete opener").await.unwrap(), "Macro opener deleted.");
+        assert_eq!(core.process_command("player-a", "macro run opener").await.unwrap(), "You have no macro called opener.");
+    }
+
+    #[tokio::test]
+    async fn test_depleted_resource_node_respawns_after_its_timer() {
+        let mut config = Config::default();
+        config.world.respawn_timers.insert("resource".to_string(), 30);
+        let mut core = ArceonCore::new(config).await.unwrap();
+        let bridge = RecordingBridge::default();
+        core.set_network_bridge(Box::new(bridge.clone()));
+        let mut area = Area::new("Woods".to_string(), AreaType::Forest, None);
+        area.locations.insert("Grove".to_string(), location("Grove", world::LocationType::Wilderness));
+        let area_id = area.id.to_string();
+        core.add_areas(vec![area]).await.unwrap();
+        core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        core.add_resource_node(&area_id, "Grove", Item {
+            name: "Wood".to_string(),
+            description: String::new(),
+            item_type: ItemType::Material,
+            rarity: Rarity::Common,
+            value: 1,
+            stack_size: 3,
+            properties: std::collections::HashMap::new(),
+        }).await.unwrap();
+
+        core.process_command("player-a", "pickup wood").await.unwrap();
+        core.update_environment().await.unwrap();
+        let grove_items = |state: &GameState| state.areas[&area_id].locations["Grove"].items_present.len();
+
+        core.state.write().await.world_time += 29_000;
+        core.update_environment().await.unwrap();
+        assert_eq!(grove_items(&*core.state.read().await), 0);
+
+        core.state.write().await.world_time += 1_000;
+        core.update_environment().await.unwrap();
+        assert_eq!(grove_items(&*core.state.read().await), 1);
+        assert_eq!(core.process_command("player-a", "pickup wood").await.unwrap(), "You pick up the Wood.");
+
+        let messages = bridge.messages.lock().unwrap();
+        let respawn = messages.iter()
+            .find(|m| m["type"] == "AreaEvent" && m["data"]["event_type"] == "ResourceRespawn")
+            .unwrap();
+        assert_eq!(respawn["data"]["area_id"], area_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_weather_transitions_over_ticks() {
+        let (mut core, bridge) = core_with_bridge().await;
+        let area = Area::new("Plains".to_string(), AreaType::Plains, None);
+        let area_id = area.id.to_string();
+        core.add_areas(vec![area]).await.unwrap();
+        let condition = |state: &GameState| state.areas[&area_id].climate.current_weather.condition;
+
+        core.update_environment().await.unwrap();
+        core.state.write().await.world_time += environment::WEATHER_CHANGE_INTERVAL - 1;
+        core.update_environment().await.unwrap();
+        assert!(bridge.messages.lock().unwrap().is_empty());
+
+        let mut seen = vec![condition(&*core.state.read().await)];
+        core.state.write().await.world_time += 1;
+        for _ in 0..40 {
+            core.update_environment().await.unwrap();
+            seen.push(condition(&*core.state.read().await));
+            core.state.write().await.world_time += environment::WEATHER_CHANGE_INTERVAL;
+        }
+
+        let changes = seen.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        assert!(changes > 0, "weather stayed {:?} for 40 rolls", seen[0]);
+        let messages = bridge.messages.lock().unwrap();
+        let announced = messages.iter()
+            .filter(|m| m["type"] == "AreaEvent" && m["data"]["event_type"] == "WeatherChange")
+            .count();
+        assert_eq!(announced, changes);
+    }
+
+    #[tokio::test]
+    async fn test_grant_crossing_two_levels_reports_the_final_level() {
+        let (mut core, bridge) = core_with_bridge().await;
+        core.add_areas(area_chain(&["Town"])).await.unwrap();
+        core.create_player("player-a".to_string(), "Aria".to_string(), being::Race::Human).await.unwrap();
+        let curve = SkillCurve::default();
+        assert_eq!(curve.level_at(curve.experience_for_level(10.0)).round(), 10.0);
+
+        // Health starts at level 10; level 12 takes about 129.7 experience
+        let level_up = core.grant_xp("player-a", "Health", 30.0, being::ExperienceSource::SurvivingDanger).await.unwrap().unwrap();
+        assert_eq!((level_up.old_level, level_up.new_level), (10, 12));
+        let to_next = core.xp_to_next_level("player-a", "Health").await.unwrap();
+        assert!((to_next - (curve.experience_for_level(13.0) - 130.0)).abs() < 1e-9, "{}", to_next);
+
+        // A grant inside the same level is no level-up
+        assert!(core.grant_xp("player-a", "Health", 1.0, being::ExperienceSource::SurvivingDanger).await.unwrap().is_none());
+
+        let messages = bridge.messages.lock().unwrap();
+        let gains: Vec<_> = messages.iter().filter(|m| m["type"] == "SkillGain").collect();
+        assert_eq!(gains.len(), 1);
+        assert_eq!(gains[0]["data"]["new_level"], 12);
+        assert_eq!(gains[0]["data"]["skill_name"], "Health");
+    }
+
+    #[tokio::test]
+    async fn test_five_votes_confirm_a_skill_and_a_repeat_voter_is_ignored() {
+        let (mut core, bridge) = core_with_bridge().await;
+        for voter in ["v1", "v2", "v3", "v4", "v5"] {
+            assert!(core.vote_skill_evolution("Shadowstep", voter, true).await.unwrap());
+        }
+        assert!(!core.vote_skill_evolution("Shadowstep", "v3", true).await.unwrap());
+        assert!(core.state.read().await.confirmed_skills.contains("Shadowstep"));
+
+        let messages = bridge.messages.lock().unwrap();
+        let votes = messages.iter().filter(|m| m["type"] == "SkillEvolution").count();
+        assert_eq!(votes, 5);
+        let confirmations: Vec<_> = messages.iter().filter(|m| m["type"] == "SkillEvolutionConfirmed").collect();
+        assert_eq!(confirmations.len(), 1);
+        assert_eq!(confirmations[0]["data"]["consensus_votes"], 5);
+        assert_eq!(confirmations[0]["data"]["skill_name"], "Shadowstep");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_writes_a_checkpoint_and_signals_the_loops() {
+        let snapshot_dir = std::env::temp_dir().join(format!("arceon-snapshots-{}", uuid::Uuid::new_v4()));
+        let (mut core, bridge) = core_with_bridge().await;
+        core.enable_world_snapshots(persistence::PersistenceManager::new(snapshot_dir.clone()).unwrap());
+        core.add_areas(area_chain(&["Ashford"])).await.unwrap();
+        core.create_player("smith".to_string(), "Smith".to_string(), being::Race::Dwarf).await.unwrap();
+        let mut stop = core.shutdown_signal();
+        assert!(!*stop.borrow());
+
+        core.shutdown().await.unwrap();
+
+        assert!(stop.has_changed().unwrap() && *stop.borrow_and_update());
+        let manager = persistence::PersistenceManager::new(snapshot_dir.clone()).unwrap();
+        let snapshot = manager.load_latest_snapshot().await.unwrap();
+        assert!(matches!(snapshot.metadata.save_reason, persistence::SaveReason::SafeShutdown));
+        assert!(snapshot.game_state.online_players.is_empty());
+        let messages = bridge.messages.lock().unwrap();
+        assert!(messages.iter().any(|m| m["type"] == "PlayerLeave" && m["data"]["player_id"] == "smith"));
+        std::fs::remove_dir_all(&snapshot_dir).ok();
+    }
+
+}
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
 use chrono::{DateTime, Utc};
 use anyhow::{Result, Context};
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use std::sync::Arc;
 
 use crate::state::GameState;
@@ -90,8 +91,8 @@ impl PersistenceManager {
         })
     }
 
-    /// Start the auto-save system
-    pub async fn start_auto_save(&self, game_state: Arc<RwLock<GameState>>, quest_system: Arc<RwLock<QuestSystem>>) -> Result<()> {
+    /// Start the auto-save system; it stops once `stop` turns true
+    pub async fn start_auto_save(&self, game_state: Arc<RwLock<GameState>>, quest_system: Arc<RwLock<QuestSystem>>, mut stop: watch::Receiver<bool>) -> Result<()> {
         let mut interval = tokio::time::interval(self.auto_save_interval);
         let data_dir = self.data_directory.clone();
         let max_snapshots = self.max_snapshots;
@@ -99,7 +100,10 @@ impl PersistenceManager {
 
         tokio::spawn(async move {
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = stop.wait_for(|stopped| *stopped) => break,
+                }
 
                 // Create auto-save snapshot
                 let snapshot_result = {
@@ -508,4 +512,201 @@ impl GenesisLauncher {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Pages areas to disk so only those near active players stay in `GameState.areas`
+#[derive(Debug, Clone)]
+pub struct AreaPager {
+    pub area_directory: PathBuf,
+    pub max_resident_areas: usize,
+    pub eviction_grace: u64, // world time an idle area stays resident
+}
+
+/// Areas moved in and out of memory by one residency pass
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResidencyChange {
+    pub loaded: Vec<String>,
+    pub evicted: Vec<String>,
+}
+
+impl AreaPager {
+    pub fn new(area_directory: PathBuf, max_resident_areas: usize, eviction_grace: u64) -> Result<Self> {
+        fs::create_dir_all(&area_directory)
+            .with_context(|| format!("Failed to create area directory: {:?}", area_directory))?;
+
+        Ok(Self {
+            area_directory,
+            max_resident_areas,
+            eviction_grace,
+        })
+    }
+
+    pub fn from_config(data_directory: &Path, config: &crate::config::WorldConfig) -> Result<Self> {
+        Self::new(data_directory.join("areas"), config.max_resident_areas, config.area_eviction_grace)
+    }
+
+    fn area_path(&self, area_id: &str) -> PathBuf {
+        self.area_directory.join(format!("{}.json", area_id))
+    }
+
+    /// Write an area to disk and drop it from memory
+    pub async fn page_out(&self, game_state: &mut GameState, area_id: &str) -> Result<()> {
+        let Some(area) = game_state.areas.get(area_id) else {
+            return Ok(());
+        };
+
+        let json_data = serde_json::to_string(area)
+            .context("Failed to serialize area")?;
+        tokio::fs::write(self.area_path(area_id), json_data)
+            .await
+            .with_context(|| format!("Failed to page out area {}", area_id))?;
+
+        game_state.areas.remove(area_id);
+        game_state.paged_out_areas.insert(area_id.to_string());
+        Ok(())
+    }
+
+    /// Bring a paged-out area back into memory. Returns false if it was already resident.
+    pub async fn page_in(&self, game_state: &mut GameState, area_id: &str) -> Result<bool> {
+        if !game_state.paged_out_areas.contains(area_id) {
+            return Ok(false);
+        }
+
+        let json_data = tokio::fs::read_to_string(self.area_path(area_id))
+            .await
+            .with_context(|| format!("Failed to page in area {}", area_id))?;
+        let area: crate::entities::world::Area = serde_json::from_str(&json_data)
+            .with_context(|| format!("Failed to deserialize paged area {}", area_id))?;
+
+        game_state.paged_out_areas.remove(area_id);
+        game_state.areas.insert(area_id.to_string(), area);
+        Ok(true)
+    }
+
+    /// Keep populated areas and their neighbours resident, evicting the rest once idle
+    pub async fn update_residency(&self, game_state: &mut GameState) -> Result<ResidencyChange> {
+        let mut change = ResidencyChange::default();
+
+        let populated: HashSet<String> = game_state.online_players
+            .values()
+            .map(|player| player.current_area_id.clone())
+            .collect();
+        for area_id in &populated {
+            if self.page_in(game_state, area_id).await? {
+                change.loaded.push(area_id.clone());
+            }
+        }
+
+        let mut wanted = populated.clone();
+        for area_id in &populated {
+            if let Some(area) = game_state.areas.get(area_id) {
+                wanted.extend(area.connected_areas.iter().map(|conn| conn.target_area_id.to_string()));
+            }
+        }
+        for area_id in &wanted {
+            if self.page_in(game_state, area_id).await? {
+                change.loaded.push(area_id.clone());
+            }
+        }
+
+        let now = game_state.world_time;
+        for area_id in wanted.iter().filter(|id| game_state.areas.contains_key(*id)) {
+            game_state.area_last_active.insert(area_id.clone(), now);
+        }
+
+        // Oldest first, so the over-capacity pass evicts the longest-idle areas
+        let mut candidates: Vec<(u64, String)> = game_state.areas
+            .keys()
+            .filter(|id| !wanted.contains(*id))
+            .map(|id| (game_state.area_last_active.get(id).copied().unwrap_or(0), id.clone()))
+            .collect();
+        candidates.sort();
+
+        let mut resident = game_state.areas.len();
+        for (last_active, area_id) in candidates {
+            let idle = now.saturating_sub(last_active) >= self.eviction_grace;
+            if idle || resident > self.max_resident_areas {
+                self.page_out(game_state, &area_id).await?;
+                change.evicted.push(area_id);
+                resident -= 1;
+            }
+        }
+
+        change.loaded.sort();
+        change.evicted.sort();
+        Ok(change)
+    }
+}
+
+/// A character as written to its save file when the player leaves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub player_id: String,
+    pub being: crate::entities::being::Being, // includes skills and inventory
+    pub current_area_id: String,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// One save file per player, so characters survive restarts
+#[derive(Debug, Clone)]
+pub struct PlayerStore {
+    pub player_directory: PathBuf,
+}
+
+impl PlayerStore {
+    pub fn new(player_directory: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&player_directory)
+            .with_context(|| format!("Failed to create player directory: {:?}", player_directory))?;
+
+        Ok(Self { player_directory })
+    }
+
+    pub fn from_data_directory(data_directory: &Path) -> Result<Self> {
+        Self::new(data_directory.join("players"))
+    }
+
+    /// Player ids come from clients, so anything that isn't safe in a file name is replaced
+    fn player_path(&self, player_id: &str) -> PathBuf {
+        let file_name: String = player_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.player_directory.join(format!("{}.json", file_name))
+    }
+
+    /// Write an online player's being and current area to their save file
+    pub async fn save_player(&self, game_state: &GameState, player_id: &str) -> Result<()> {
+        let player_data = game_state.online_players.get(player_id)
+            .ok_or_else(|| anyhow::anyhow!("Player {} is not online", player_id))?;
+        let being = game_state.beings.get(&player_data.being_id)
+            .ok_or_else(|| anyhow::anyhow!("Being {} not found for player {}", player_data.being_id, player_id))?;
+
+        let saved = SavedPlayer {
+            player_id: player_id.to_string(),
+            being: being.clone(),
+            current_area_id: player_data.current_area_id.clone(),
+            saved_at: Utc::now(),
+        };
+        let json_data = serde_json::to_string_pretty(&saved)
+            .context("Failed to serialize player")?;
+        tokio::fs::write(self.player_path(player_id), json_data)
+            .await
+            .with_context(|| format!("Failed to save player {}", player_id))?;
+        Ok(())
+    }
+
+    /// Read a player's save file, or `None` if they have never been saved
+    pub async fn load_player(&self, player_id: &str) -> Result<Option<SavedPlayer>> {
+        let path = self.player_path(player_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let json_data = tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to load player {}", player_id))?;
+        let saved = serde_json::from_str(&json_data)
+            .with_context(|| format!("Failed to deserialize player {}", player_id))?;
+        Ok(Some(saved))
+    }
+}
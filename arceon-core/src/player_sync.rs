@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use crate::entities::being::Being;
+
+/// The parts of a player other nodes mirror between block finalizations
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub skills: BTreeMap<String, SkillProgress>,
+    pub inventory: BTreeMap<Uuid, u32>, // item_id -> stack size
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SkillProgress {
+    pub level: f64,
+    pub experience: f64,
+}
+
+/// Changes between two snapshots, carried as `being_data` in a `PlayerUpdate` message
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerDelta {
+    pub changed_skills: BTreeMap<String, SkillProgress>,
+    pub inventory_changes: BTreeMap<Uuid, u32>, // new stack size, 0 = item removed
+}
+
+impl PlayerSnapshot {
+    pub fn of(being: &Being) -> Self {
+        let skills = being.skills.skills
+            .iter()
+            .map(|(name, skill)| (name.clone(), SkillProgress { level: skill.level, experience: skill.experience }))
+            .collect();
+        let inventory = being.inventory
+            .as_ref()
+            .map(|inventory| inventory.items.values().map(|item| (item.item_id, item.stack_size)).collect())
+            .unwrap_or_default();
+
+        Self { skills, inventory }
+    }
+
+    /// What changed going from `self` to `newer`, or `None` if nothing did
+    pub fn diff(&self, newer: &PlayerSnapshot) -> Option<PlayerDelta> {
+        let changed_skills: BTreeMap<_, _> = newer.skills
+            .iter()
+            .filter(|(name, progress)| self.skills.get(*name) != Some(progress))
+            .map(|(name, progress)| (name.clone(), *progress))
+            .collect();
+
+        let mut inventory_changes: BTreeMap<_, _> = newer.inventory
+            .iter()
+            .filter(|(item_id, stack)| self.inventory.get(*item_id) != Some(stack))
+            .map(|(item_id, stack)| (*item_id, *stack))
+            .collect();
+        for item_id in self.inventory.keys().filter(|item_id| !newer.inventory.contains_key(*item_id)) {
+            inventory_changes.insert(*item_id, 0);
+        }
+
+        if changed_skills.is_empty() && inventory_changes.is_empty() {
+            None
+        } else {
+            Some(PlayerDelta { changed_skills, inventory_changes })
+        }
+    }
+
+    pub fn apply(&mut self, delta: &PlayerDelta) {
+        self.skills.extend(delta.changed_skills.iter().map(|(name, progress)| (name.clone(), *progress)));
+        for (item_id, stack) in &delta.inventory_changes {
+            if *stack == 0 {
+                self.inventory.remove(item_id);
+            } else {
+                self.inventory.insert(*item_id, *stack);
+            }
+        }
+    }
+}
+
+impl PlayerDelta {
+    pub fn encode(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use crate::entities::being::ExperienceSource;
+use crate::state::GameState;
+
+/// Experience a skill earns each time it is used
+pub const SKILL_USE_EXPERIENCE: f64 = 5.0;
+
+/// One skill in a macro, used `delay_ms` of world time after the step before it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroStep {
+    pub skill: String,
+    pub delay_ms: u64,
+}
+
+/// A named, ordered list of skills a player can fire with one command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillMacro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+impl SkillMacro {
+    /// Parse comma-separated steps, where "wait <seconds>" delays the skill after it:
+    /// `Taunt, wait 2, Fire Magic`
+    pub fn parse(name: &str, spec: &str) -> Result<Self, String> {
+        let mut steps = Vec::new();
+        let mut delay_ms = 0;
+        for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            let words: Vec<&str> = part.split_whitespace().collect();
+            match words.as_slice() {
+                [wait, seconds] if wait.eq_ignore_ascii_case("wait") => {
+                    let seconds: f64 = seconds.parse()
+                        .ok()
+                        .filter(|seconds: &f64| seconds.is_finite() && *seconds >= 0.0)
+                        .ok_or_else(|| format!("'{}' is not a number of seconds.", seconds))?;
+                    delay_ms += (seconds * 1000.0) as u64;
+                }
+                _ => {
+                    steps.push(MacroStep { skill: words.join(" "), delay_ms });
+                    delay_ms = 0;
+                }
+            }
+        }
+        if steps.is_empty() {
+            return Err("A macro needs at least one skill.".to_string());
+        }
+        Ok(Self { name: name.to_string(), steps })
+    }
+}
+
+/// A macro part-way through running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningMacro {
+    pub name: String,
+    pub remaining: VecDeque<MacroStep>,
+    pub next_at: u64, // world_time the next step may fire, cooldowns permitting
+}
+
+/// A skill fired by a macro
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillUse {
+    pub player_id: String,
+    pub skill: String,
+    pub macro_name: String,
+    pub world_time: u64,
+}
+
+impl GameState {
+    /// Save a macro for the player's character, replacing one of the same name. Skill
+    /// names are matched ignoring case and stored as the character knows them.
+    pub fn define_macro(&mut self, player_id: &str, mut skill_macro: SkillMacro) -> Result<(), String> {
+        let being = self.online_players.get(player_id)
+            .and_then(|data| self.beings.get(&data.being_id))
+            .ok_or_else(|| format!("Player {} not found", player_id))?;
+        for step in &mut skill_macro.steps {
+            step.skill = being.skills.skills.keys()
+                .find(|known| known.eq_ignore_ascii_case(&step.skill))
+                .cloned()
+                .ok_or_else(|| format!("You don't know the skill {}.", step.skill))?;
+        }
+
+        let macros = self.skill_macros.entry(being.id.to_string()).or_default();
+        macros.retain(|existing| !existing.name.eq_ignore_ascii_case(&skill_macro.name));
+        macros.push(skill_macro);
+        Ok(())
+    }
+
+    pub fn player_macros(&self, player_id: &str) -> &[SkillMacro] {
+        self.online_players.get(player_id)
+            .and_then(|data| self.skill_macros.get(&data.being_id))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Remove a macro; returns whether it existed
+    pub fn delete_macro(&mut self, player_id: &str, name: &str) -> bool {
+        let Some(macros) = self.online_players.get(player_id).and_then(|data| self.skill_macros.get_mut(&data.being_id)) else {
+            return false;
+        };
+        let before = macros.len();
+        macros.retain(|existing| !existing.name.eq_ignore_ascii_case(name));
+        macros.len() != before
+    }
+
+    /// Queue a macro's skills; any macro the player was already running is replaced
+    pub fn start_macro(&mut self, player_id: &str, name: &str) -> Result<SkillMacro, String> {
+        let skill_macro = self.player_macros(player_id)
+            .iter()
+            .find(|existing| existing.name.eq_ignore_ascii_case(name))
+            .cloned()
+            .ok_or_else(|| format!("You have no macro called {}.", name))?;
+
+        let first_delay = skill_macro.steps[0].delay_ms;
+        self.running_macros.insert(player_id.to_string(), RunningMacro {
+            name: skill_macro.name.clone(),
+            remaining: skill_macro.steps.iter().cloned().collect(),
+            next_at: self.world_time + first_delay,
+        });
+        Ok(skill_macro)
+    }
+
+    /// Fire every queued macro step that is due, in order. A step whose skill is still
+    /// cooling down holds up the rest of its macro until the skill is ready.
+    pub fn advance_macros(&mut self) -> Vec<SkillUse> {
+        let now = self.world_time;
+        let mut players: Vec<String> = self.running_macros.keys().cloned().collect();
+        players.sort();
+
+        let mut fired = Vec::new();
+        for player_id in players {
+            let Some(being_id) = self.online_players.get(&player_id).map(|data| data.being_id.clone()) else {
+                self.running_macros.remove(&player_id);
+                continue;
+            };
+
+            while let Some(running) = self.running_macros.get(&player_id) {
+                let Some(step) = running.remaining.front().cloned() else {
+                    self.running_macros.remove(&player_id);
+                    break;
+                };
+                let ready_at = self.skill_ready_at.get(&being_id)
+                    .and_then(|cooldowns| cooldowns.get(&step.skill))
+                    .copied()
+                    .unwrap_or(0)
+                    .max(running.next_at);
+                if ready_at > now {
+                    break;
+                }
+
+                let cooldown_ms = self.beings.get_mut(&being_id)
+                    .and_then(|being| {
+                        being.gain_skill_experience(&step.skill, SKILL_USE_EXPERIENCE, ExperienceSource::ActiveSkillUse(step.skill.clone()));
+                        being.skills.skills.get(&step.skill)
+                    })
+                    .and_then(|skill| skill.active_trait.as_ref())
+                    .map_or(0, |active| (active.cooldown.base_duration * 1000.0) as u64);
+                self.skill_ready_at.entry(being_id.clone()).or_default().insert(step.skill.clone(), now + cooldown_ms);
+
+                let running = self.running_macros.get_mut(&player_id).expect("checked above");
+                running.remaining.pop_front();
+                if let Some(next) = running.remaining.front() {
+                    running.next_at = now + next.delay_ms;
+                }
+                fired.push(SkillUse {
+                    player_id: player_id.clone(),
+                    skill: step.skill,
+                    macro_name: running.name.clone(),
+                    world_time: now,
+                });
+            }
+        }
+        fired
+    }
+}
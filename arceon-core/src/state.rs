@@ -1,6 +1,22 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use crate::entities::{world::Area, being::Being, quests::QuestSystem};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use crate::entities::{world::Area, being::{Being, BeingType, InventoryItem, Race, SkillCurve}, item::Item, quests::QuestSystem, skills::SkillRegistry};
+use crate::player_sync::PlayerSnapshot;
+use crate::combat::CombatTarget;
+use crate::skill_macros::{RunningMacro, SkillMacro};
+use crate::construction::Structure;
+use crate::environment::ResourceNode;
+use crate::NpcType;
+
+/// World time (milliseconds) that makes up one in-game hour, so a day lasts 24 real minutes
+pub const WORLD_TIME_PER_HOUR: u64 = 60_000;
+
+/// Supporting votes that confirm an evolved skill, matching what the blockchain requires
+pub const SKILL_CONFIRMATION_VOTES: u32 = 5;
+
+/// Token that player balances and trades are denominated in
+pub const NATIVE_TOKEN: &str = "ARCEON";
 
 /// Global game state
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +28,99 @@ pub struct GameState {
     pub areas: HashMap<String, Area>, // area_id -> area
     pub beings: HashMap<String, Being>, // being_id -> being
     pub quest_system: QuestSystem, // Quest and reputation system
+    #[serde(default)]
+    pub name_reservations: HashMap<String, NameReservation>, // lowercased name -> holder
+    #[serde(skip)]
+    pub broadcast_snapshots: HashMap<String, PlayerSnapshot>, // player_id -> last state sent to peers
+    #[serde(default)]
+    pub paged_out_areas: HashSet<String>, // area ids currently held on disk instead of in `areas`
+    #[serde(default)]
+    pub area_last_active: HashMap<String, u64>, // area_id -> world_time it was last near a player
+    #[serde(default)]
+    pub npcs: HashMap<Uuid, Npc>, // npc id (as listed in `Location::npcs_present`) -> npc
+    #[serde(default)]
+    pub items: HashMap<Uuid, Item>, // item id (as listed in `Location::items_present` or an inventory) -> item
+    #[serde(default)]
+    pub token_balances: HashMap<String, u64>, // being_id -> native tokens, mirrored from the chain
+    #[serde(default)]
+    pub trades: Vec<Trade>, // open trades; a player is in at most one
+    #[serde(default)]
+    pub combat_targets: HashMap<String, CombatTarget>, // player_id -> who they are fighting
+    #[serde(default)]
+    pub autoattacking: HashMap<String, u64>, // player_id -> world_time of their last automatic swing
+    #[serde(default)]
+    pub skill_macros: HashMap<String, Vec<SkillMacro>>, // being_id -> macros it has defined
+    #[serde(default)]
+    pub running_macros: HashMap<String, RunningMacro>, // player_id -> macro being worked through
+    #[serde(default)]
+    pub skill_ready_at: HashMap<String, HashMap<String, u64>>, // being_id -> skill -> world_time its cooldown ends
+    #[serde(default)]
+    pub structures: HashMap<String, Vec<Structure>>, // area_id -> structures built there
+    #[serde(default)]
+    pub weather_rolled_at: HashMap<String, u64>, // area_id -> world_time its weather was last rolled
+    #[serde(default)]
+    pub resource_nodes: HashMap<Uuid, ResourceNode>, // node id -> node
+    #[serde(default)]
+    pub skill_votes: HashMap<String, HashMap<String, bool>>, // skill -> voter -> whether they support it
+    #[serde(default)]
+    pub confirmed_skills: HashSet<String>, // skills whose evolution the community has confirmed
+}
+
+/// Something put up in a trade
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TradeGoods {
+    Item { item_id: Uuid, stack_size: u32 },
+    Tokens(u64),
+}
+
+/// A trade between two players. Offers are taken out of the offering player's inventory
+/// or balance straight away and held here until both accept or the trade is called off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trade {
+    pub initiator: String, // player_id
+    pub counterparty: String, // player_id
+    pub offers: HashMap<String, Vec<TradeGoods>>, // player_id -> escrowed goods
+    pub accepted: HashSet<String>, // player ids; cleared whenever an offer changes
+}
+
+impl Trade {
+    pub fn involves(&self, player_id: &str) -> bool {
+        self.initiator == player_id || self.counterparty == player_id
+    }
+
+    /// The other side of the trade from `player_id`
+    pub fn partner_of(&self, player_id: &str) -> &str {
+        if self.initiator == player_id { &self.counterparty } else { &self.initiator }
+    }
+}
+
+/// A spawned NPC: the being itself plus what it is and where it lives
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Npc {
+    pub npc_type: NpcType,
+    pub home_area: String, // area_id
+    pub current_area: String, // area_id, differs from `home_area` once it has wandered
+    pub current_location: String, // location name within `current_area`
+    pub being: Being,
+}
+
+impl Npc {
+    /// Create an NPC of `npc_type` with its type's starting skills; its id is `being.id`
+    pub fn spawn(npc_type: NpcType, home_area: String, location: String) -> Self {
+        let mut being = Being::new(format!("{:?}", npc_type), BeingType::Npc, Race::Human, false);
+        let skill_registry = SkillRegistry::new();
+        let curve = SkillCurve::default();
+        
+        for (skill_name, level) in npc_type.starting_skills() {
+            if let Some(mut skill) = skill_registry.create_skill(skill_name) {
+                skill.level = *level;
+                skill.experience = curve.experience_for_level(*level);
+                being.skills.skills.insert(skill_name.to_string(), skill);
+            }
+        }
+        
+        Self { npc_type, current_area: home_area.clone(), home_area, current_location: location, being }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +128,33 @@ pub struct PlayerData {
     pub being_id: String,
     pub current_area_id: String,
     pub last_activity: u64,
+    #[serde(default)]
+    pub is_admin: bool, // may run `AdminCommand`s
+}
+
+/// Claim on a player name, broadcast to every node so names stay unique network-wide
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NameReservation {
+    pub player_id: String,
+    pub player_name: String,
+    pub reserved_at: i64, // unix millis on the reserving node
+}
+
+impl NameReservation {
+    /// Earliest reservation wins; ties are broken by the lower player id so every node agrees
+    pub fn outranks(&self, other: &NameReservation) -> bool {
+        (self.reserved_at, &self.player_id) < (other.reserved_at, &other.player_id)
+    }
+
+    /// Name handed to the losing side of a clash, derived only from data every node has
+    pub fn suffixed_name(&self) -> String {
+        let suffix: String = self.player_id
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .take(6)
+            .collect();
+        format!("{}-{}", self.player_name, suffix)
+    }
 }
 
 impl GameState {
@@ -31,9 +167,37 @@ impl GameState {
             areas: HashMap::new(),
             beings: HashMap::new(),
             quest_system: QuestSystem::new(),
+            name_reservations: HashMap::new(),
+            broadcast_snapshots: HashMap::new(),
+            paged_out_areas: HashSet::new(),
+            area_last_active: HashMap::new(),
+            npcs: HashMap::new(),
+            items: HashMap::new(),
+            token_balances: HashMap::new(),
+            trades: Vec::new(),
+            combat_targets: HashMap::new(),
+            autoattacking: HashMap::new(),
+            skill_macros: HashMap::new(),
+            running_macros: HashMap::new(),
+            skill_ready_at: HashMap::new(),
+            structures: HashMap::new(),
+            weather_rolled_at: HashMap::new(),
+            resource_nodes: HashMap::new(),
+            skill_votes: HashMap::new(),
+            confirmed_skills: HashSet::new(),
         }
     }
     
+    /// In-game hour of the day, 0-23, derived from `world_time`
+    pub fn current_hour(&self) -> u32 {
+        ((self.world_time / WORLD_TIME_PER_HOUR) % 24) as u32
+    }
+    
+    /// Daylight runs from 06:00 until 18:00
+    pub fn is_daytime(&self) -> bool {
+        (6..18).contains(&self.current_hour())
+    }
+    
     pub fn add_area(&mut self, area: Area) {
         self.areas.insert(area.id.to_string(), area);
     }
@@ -42,6 +206,230 @@ impl GameState {
         self.beings.insert(being.id.to_string(), being);
     }
     
+    pub fn get_npc(&self, npc_id: &Uuid) -> Option<&Npc> {
+        self.npcs.get(npc_id)
+    }
+    
+    /// Leave an item lying at a location, returning its id
+    pub fn place_item(&mut self, area_id: &str, location_name: &str, item: Item) -> Result<Uuid, String> {
+        let location = self.areas
+            .get_mut(area_id)
+            .ok_or_else(|| format!("Area {} not found", area_id))?
+            .locations
+            .get_mut(location_name)
+            .ok_or_else(|| format!("Location {} not found in area {}", location_name, area_id))?;
+        
+        let item_id = Uuid::new_v4();
+        location.items_present.push(item_id);
+        self.items.insert(item_id, item);
+        Ok(item_id)
+    }
+    
+    /// Move an item lying in the player's area into their inventory. Returns the item id
+    /// and the location it was taken from.
+    pub fn pickup_item(&mut self, player_id: &str, item_name: &str) -> Result<(Uuid, String), String> {
+        let player_data = self.online_players.get(player_id)
+            .ok_or_else(|| format!("Player {} not found", player_id))?;
+        let area = self.areas.get(&player_data.current_area_id)
+            .ok_or_else(|| "You are in an unknown location.".to_string())?;
+        
+        let mut locations: Vec<_> = area.locations.iter().collect();
+        locations.sort_by(|a, b| a.0.cmp(b.0));
+        let (location_name, item_id) = locations.into_iter()
+            .find_map(|(location_name, location)| {
+                location.items_present.iter()
+                    .find(|id| self.items.get(id).is_some_and(|item| item.name.eq_ignore_ascii_case(item_name)))
+                    .map(|id| (location_name.clone(), *id))
+            })
+            .ok_or_else(|| format!("There is no {} here.", item_name))?;
+        let stack_size = self.items[&item_id].stack_size.max(1);
+        
+        let area_id = player_data.current_area_id.clone();
+        let inventory = self.beings.get_mut(&player_data.being_id)
+            .ok_or_else(|| "Character not found.".to_string())?
+            .inventory
+            .as_mut()
+            .ok_or_else(|| "You cannot carry items.".to_string())?;
+        inventory.items.insert(item_id, InventoryItem { item_id, stack_size, max_stack: 999 });
+        
+        if let Some(location) = self.areas.get_mut(&area_id).and_then(|area| area.locations.get_mut(&location_name)) {
+            location.items_present.retain(|id| *id != item_id);
+        }
+        Ok((item_id, location_name))
+    }
+    
+    /// Put a held item down in the player's area. Returns the item id and the location it
+    /// was left at.
+    pub fn drop_item(&mut self, player_id: &str, item_name: &str) -> Result<(Uuid, String), String> {
+        let player_data = self.online_players.get(player_id)
+            .ok_or_else(|| format!("Player {} not found", player_id))?;
+        let location_name = self.areas.get(&player_data.current_area_id)
+            .ok_or_else(|| "You are in an unknown location.".to_string())?
+            .locations
+            .keys()
+            .min()
+            .cloned()
+            .ok_or_else(|| "There is nowhere to put that down here.".to_string())?;
+        
+        let area_id = player_data.current_area_id.clone();
+        let inventory = self.beings.get_mut(&player_data.being_id)
+            .ok_or_else(|| "Character not found.".to_string())?
+            .inventory
+            .as_mut()
+            .ok_or_else(|| "You cannot carry items.".to_string())?;
+        let items = &self.items;
+        let item_id = inventory.items.keys()
+            .find(|id| items.get(id).is_some_and(|item| item.name.eq_ignore_ascii_case(item_name)))
+            .copied()
+            .ok_or_else(|| format!("You are not carrying {}.", item_name))?;
+        inventory.items.remove(&item_id);
+        
+        if let Some(location) = self.areas.get_mut(&area_id).and_then(|area| area.locations.get_mut(&location_name)) {
+            location.items_present.push(item_id);
+        }
+        Ok((item_id, location_name))
+    }
+    
+    pub fn is_admin(&self, player_id: &str) -> bool {
+        self.online_players.get(player_id).is_some_and(|data| data.is_admin)
+    }
+    
+    /// Online player whose id or character name matches `name_or_id`, ignoring case
+    pub fn find_online_player(&self, name_or_id: &str) -> Option<&str> {
+        self.online_players.iter()
+            .find(|(player_id, data)| {
+                player_id.eq_ignore_ascii_case(name_or_id) || self.beings.get(&data.being_id)
+                    .is_some_and(|being| being.name.eq_ignore_ascii_case(name_or_id))
+            })
+            .map(|(player_id, _)| player_id.as_str())
+    }
+    
+    /// Put goods into a trade with `partner`, opening the trade if needed. `goods` is either
+    /// "<amount> tokens" or the name of a held item; it is escrowed until the trade ends.
+    pub fn offer_trade(&mut self, player_id: &str, partner: &str, goods: &str) -> Result<TradeGoods, String> {
+        if player_id == partner {
+            return Err("You cannot trade with yourself.".to_string());
+        }
+        if !self.online_players.contains_key(partner) {
+            return Err(format!("{} is not online.", partner));
+        }
+        let existing = self.trades.iter().position(|trade| trade.involves(player_id) || trade.involves(partner));
+        if let Some(index) = existing {
+            let trade = &self.trades[index];
+            if !(trade.involves(player_id) && trade.involves(partner)) {
+                return Err("One of you is already trading with someone else.".to_string());
+            }
+        }
+        
+        let offered = self.take_goods(player_id, goods)?;
+        let index = existing.unwrap_or_else(|| {
+            self.trades.push(Trade {
+                initiator: player_id.to_string(),
+                counterparty: partner.to_string(),
+                offers: HashMap::new(),
+                accepted: HashSet::new(),
+            });
+            self.trades.len() - 1
+        });
+        let trade = &mut self.trades[index];
+        trade.offers.entry(player_id.to_string()).or_default().push(offered.clone());
+        trade.accepted.clear();
+        Ok(offered)
+    }
+    
+    /// Accept the player's open trade. Once both sides have accepted the escrowed goods
+    /// change hands and the settled trade is returned.
+    pub fn accept_trade(&mut self, player_id: &str) -> Result<Option<Trade>, String> {
+        let index = self.trades.iter().position(|trade| trade.involves(player_id))
+            .ok_or_else(|| "You are not trading with anyone.".to_string())?;
+        let trade = &mut self.trades[index];
+        if trade.offers.values().all(|offers| offers.is_empty()) {
+            return Err("Nothing has been offered yet.".to_string());
+        }
+        
+        trade.accepted.insert(player_id.to_string());
+        if trade.accepted.len() < 2 {
+            return Ok(None);
+        }
+        
+        let trade = self.trades.remove(index);
+        for (owner, offers) in &trade.offers {
+            let recipient = trade.partner_of(owner).to_string();
+            for goods in offers {
+                self.give_goods(&recipient, goods);
+            }
+        }
+        Ok(Some(trade))
+    }
+    
+    /// Call off the player's open trade, returning every escrowed offer to its owner
+    pub fn cancel_trade(&mut self, player_id: &str) -> Option<Trade> {
+        let index = self.trades.iter().position(|trade| trade.involves(player_id))?;
+        let trade = self.trades.remove(index);
+        for (owner, offers) in &trade.offers {
+            for goods in offers {
+                self.give_goods(owner, goods);
+            }
+        }
+        Some(trade)
+    }
+    
+    fn take_goods(&mut self, player_id: &str, goods: &str) -> Result<TradeGoods, String> {
+        let being_id = self.online_players.get(player_id)
+            .map(|data| data.being_id.clone())
+            .ok_or_else(|| format!("Player {} not found", player_id))?;
+        
+        let words: Vec<&str> = goods.split_whitespace().collect();
+        if let [amount, unit] = words.as_slice() {
+            if let (Ok(amount), true) = (amount.parse::<u64>(), matches!(unit.to_lowercase().as_str(), "token" | "tokens")) {
+                let balance = self.token_balances.entry(being_id).or_insert(0);
+                if amount == 0 || *balance < amount {
+                    return Err(format!("You do not have {} tokens to offer.", amount));
+                }
+                *balance -= amount;
+                return Ok(TradeGoods::Tokens(amount));
+            }
+        }
+        
+        let items = &self.items;
+        let inventory = self.beings.get_mut(&being_id)
+            .and_then(|being| being.inventory.as_mut())
+            .ok_or_else(|| "You cannot carry items.".to_string())?;
+        let item_id = inventory.items.keys()
+            .find(|id| items.get(id).is_some_and(|item| item.name.eq_ignore_ascii_case(goods)))
+            .copied()
+            .ok_or_else(|| format!("You are not carrying {}.", goods))?;
+        let held = inventory.items.remove(&item_id).expect("item found above");
+        Ok(TradeGoods::Item { item_id, stack_size: held.stack_size })
+    }
+    
+    fn give_goods(&mut self, player_id: &str, goods: &TradeGoods) {
+        let Some(being_id) = self.online_players.get(player_id).map(|data| data.being_id.clone()) else {
+            return;
+        };
+        match goods {
+            TradeGoods::Tokens(amount) => {
+                *self.token_balances.entry(being_id).or_insert(0) += amount;
+            }
+            TradeGoods::Item { item_id, stack_size } => {
+                if let Some(inventory) = self.beings.get_mut(&being_id).and_then(|being| being.inventory.as_mut()) {
+                    inventory.items.insert(*item_id, InventoryItem { item_id: *item_id, stack_size: *stack_size, max_stack: 999 });
+                }
+            }
+        }
+    }
+    
+    /// Record `voter`'s vote on an evolved skill. Each voter is counted once; a second
+    /// vote from them returns None. Otherwise returns the supporting votes so far.
+    pub fn record_skill_vote(&mut self, skill_name: &str, voter: &str, support: bool) -> Option<u32> {
+        let votes = self.skill_votes.entry(skill_name.to_string()).or_default();
+        if votes.contains_key(voter) {
+            return None;
+        }
+        votes.insert(voter.to_string(), support);
+        Some(votes.values().filter(|support| **support).count() as u32)
+    }
+    
     pub fn move_player_to_area(&mut self, player_id: &str, area_id: &str) -> Result<(), String> {
         if !self.areas.contains_key(area_id) {
             return Err(format!("Area {} not found", area_id));
@@ -68,4 +456,47 @@ impl GameState {
             })
             .collect()
     }
+    
+    /// Returns the player currently holding a name, compared case-insensitively
+    pub fn name_holder(&self, player_name: &str) -> Option<&str> {
+        self.name_reservations
+            .get(&player_name.to_lowercase())
+            .map(|reservation| reservation.player_id.as_str())
+    }
+    
+    /// Record a local or remote name reservation. Clashes resolve the same way on every
+    /// node: the losing player is moved to a suffixed name, which is returned so the caller
+    /// can announce it.
+    pub fn reserve_player_name(&mut self, reservation: NameReservation) -> Option<NameReservation> {
+        let key = reservation.player_name.to_lowercase();
+        let loser = match self.name_reservations.get(&key) {
+            None => {
+                self.name_reservations.insert(key, reservation);
+                return None;
+            }
+            Some(existing) if existing.player_id == reservation.player_id => return None,
+            Some(existing) if reservation.outranks(existing) => {
+                let displaced = existing.clone();
+                self.name_reservations.insert(key, reservation);
+                displaced
+            }
+            Some(_) => reservation,
+        };
+        
+        let renamed = NameReservation {
+            player_name: loser.suffixed_name(),
+            ..loser
+        };
+        self.rename_player_being(&renamed.player_id, &renamed.player_name);
+        self.reserve_player_name(renamed.clone());
+        Some(renamed)
+    }
+    
+    fn rename_player_being(&mut self, player_id: &str, new_name: &str) {
+        if let Some(player_data) = self.online_players.get(player_id) {
+            if let Some(being) = self.beings.get_mut(&player_data.being_id) {
+                being.name = new_name.to_string();
+            }
+        }
+    }
 }
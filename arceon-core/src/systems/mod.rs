@@ -2,8 +2,10 @@ pub mod skill_evolution;
 pub mod vital_manager;
 pub mod afk_system;
 pub mod death_system;
+pub mod world_systems;
 
 pub use skill_evolution::*;
 pub use vital_manager::*;
 pub use afk_system::*;
 pub use death_system::*;
+pub use world_systems::*;
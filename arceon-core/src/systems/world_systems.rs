@@ -0,0 +1,97 @@
+use bevy_ecs::prelude::*;
+
+use crate::entities::{Being, ExperienceSource, Goal, Health, Npc, NpcAiState, Player, Position};
+
+/// Simulated seconds per schedule run; the game loop runs the schedule at 10 TPS
+pub const TICK_SECONDS: f32 = 0.1;
+
+/// Schedule runs per simulated second
+const TICKS_PER_SECOND: u64 = 10;
+
+/// Seconds an NPC waits between actions
+const NPC_ACTION_COOLDOWN: f32 = 2.0;
+
+/// Number of schedule runs so far
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct WorldClock {
+    pub ticks: u64,
+}
+
+/// Where a player is walking to, and how fast (units per second)
+#[derive(Component, Debug, Clone)]
+pub struct MoveTarget {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub speed: f32,
+}
+
+/// Marks a being as practising `skill` this tick; consumed by `skill_progression_system`
+#[derive(Component, Debug, Clone)]
+pub struct Acting {
+    pub skill: String,
+    pub experience: f64,
+}
+
+/// Step players toward their move target, dropping the target on arrival
+pub fn player_movement_system(mut commands: Commands, mut movers: Query<(Entity, &mut Position, &MoveTarget), With<Player>>) {
+    for (entity, mut position, target) in &mut movers {
+        let (dx, dy, dz) = (target.x - position.x, target.y - position.y, target.z - position.z);
+        let distance = (dx * dx + dy * dy + dz * dz).sqrt();
+        let step = target.speed * TICK_SECONDS;
+
+        if distance <= step {
+            position.x = target.x;
+            position.y = target.y;
+            position.z = target.z;
+            commands.entity(entity).remove::<MoveTarget>();
+        } else {
+            let scale = step / distance;
+            position.x += dx * scale;
+            position.y += dy * scale;
+            position.z += dz * scale;
+        }
+    }
+}
+
+/// Count down NPC cooldowns; an idle NPC takes up its next stacked goal once ready
+pub fn npc_ai_system(mut npcs: Query<&mut NpcAiState, With<Npc>>) {
+    for mut ai in &mut npcs {
+        ai.action_cooldown = (ai.action_cooldown - TICK_SECONDS).max(0.0);
+        if ai.action_cooldown > 0.0 {
+            continue;
+        }
+
+        if matches!(ai.current_goal, Goal::Idle) {
+            if let Some(next) = ai.goal_stack.pop() {
+                ai.current_goal = next;
+            }
+        }
+        let action = format!("{:?}", ai.current_goal);
+        ai.last_action = Some(action);
+        ai.action_cooldown = NPC_ACTION_COOLDOWN;
+    }
+}
+
+/// Advance the world clock and regenerate health once per simulated second
+pub fn world_update_system(mut clock: ResMut<WorldClock>, mut living: Query<&mut Health>) {
+    clock.ticks += 1;
+    if !clock.ticks.is_multiple_of(TICKS_PER_SECOND) {
+        return;
+    }
+
+    for mut health in &mut living {
+        if health.is_alive() && health.current < health.maximum {
+            let amount = health.regeneration_rate.max(0.0).round() as u32;
+            health.heal(amount);
+        }
+    }
+}
+
+/// Grant skill experience to every being that acted this tick
+pub fn skill_progression_system(mut commands: Commands, mut actors: Query<(Entity, &mut Being, &Acting)>) {
+    for (entity, mut being, acting) in &mut actors {
+        being.gain_skill_experience(&acting.skill, acting.experience, ExperienceSource::ActiveSkillUse(acting.skill.clone()));
+        commands.entity(entity).remove::<Acting>();
+    }
+}
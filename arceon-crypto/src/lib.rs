@@ -1,3 +1,5 @@
+pub mod merkle;
+
 /// Cryptographic utilities for Arceon
 pub struct CryptoManager;
 
@@ -0,0 +1,222 @@
+/*!
+# Merkle Trees
+
+Binary Merkle tree over world changes (or any pre-hashed leaves), so a single
+change can be proven part of a block without shipping the whole block.
+
+Leaves and interior nodes are domain-separated (`0x00` / `0x01` prefixes) so an
+interior node can never be passed off as a leaf. When a level has an odd number
+of nodes the last one is carried up to the next level unchanged; pairing it with
+itself instead would give `[a, b, c]` and `[a, b, c, c]` the same root.
+
+Changes are hashed from their canonical encoding: JSON with every object's keys
+sorted, so nodes that built the same `data` map in a different order still agree
+on the root.
+*/
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+/// One step of an inclusion proof: the sibling hash and whether it sits on the
+/// right of the node being proven
+pub type ProofStep = (Hash, bool);
+
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>, // levels[0] = leaves, last = [root]
+}
+
+impl MerkleTree {
+    pub fn build<T: Serialize>(changes: &[T]) -> Result<Self> {
+        let leaves = changes
+            .iter()
+            .map(|change| Ok(leaf_hash(&canonical_json(change)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_leaves(leaves))
+    }
+
+    /// Build from already-hashed leaves (see [`leaf_hash`])
+    pub fn from_leaves(leaves: Vec<Hash>) -> Self {
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [odd] => *odd,
+                    _ => unreachable!("chunks(2) yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    /// Root of the tree; all zeroes for an empty tree
+    pub fn root(&self) -> Hash {
+        self.levels.last().and_then(|level| level.first()).copied().unwrap_or([0u8; 32])
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Sibling path from leaf `index` up to the root, or `None` if out of range
+    pub fn proof(&self, index: usize) -> Option<Vec<ProofStep>> {
+        if index >= self.leaf_count() {
+            return None;
+        }
+
+        let mut proof = Vec::with_capacity(self.levels.len() - 1);
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_on_right = position.is_multiple_of(2);
+            let sibling = if sibling_on_right { level.get(position + 1) } else { level.get(position - 1) };
+            // An odd node out has no sibling: it moves up as it is, adding no step
+            if let Some(sibling) = sibling {
+                proof.push((*sibling, sibling_on_right));
+            }
+            position /= 2;
+        }
+        Some(proof)
+    }
+}
+
+/// Check that `leaf` is included under `root` via `proof`
+pub fn verify(root: &Hash, leaf: &Hash, proof: &[ProofStep]) -> bool {
+    let computed = proof.iter().fold(*leaf, |node, (sibling, sibling_on_right)| {
+        if *sibling_on_right {
+            node_hash(&node, sibling)
+        } else {
+            node_hash(sibling, &node)
+        }
+    });
+    computed == *root
+}
+
+/// JSON encoding with object keys in sorted order, for anything that gets hashed
+pub fn canonical_json<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(&sort_keys(serde_json::to_value(value)?))?)
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(key, value)| (key, sort_keys(value))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+pub fn leaf_hash(data: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+pub fn to_hex(hash: &Hash) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn from_hex(hex: &str) -> Option<Hash> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn changes(count: usize) -> Vec<Value> {
+        (0..count)
+            .map(|i| json!({ "player_id": i, "action_type": format!("step_{}", i), "area_id": "Central Plains" }))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_leaf_proves_with_odd_leaf_counts() {
+        for count in [1, 3, 5, 7, 8] {
+            let changes = changes(count);
+            let tree = MerkleTree::build(&changes).unwrap();
+            let root = tree.root();
+
+            for (index, change) in changes.iter().enumerate() {
+                let leaf = leaf_hash(&canonical_json(change).unwrap());
+                let proof = tree.proof(index).unwrap();
+                assert!(verify(&root, &leaf, &proof), "leaf {} of {}", index, count);
+            }
+            assert!(tree.proof(count).is_none());
+        }
+    }
+
+    #[test]
+    fn test_tampered_leaf_is_rejected() {
+        let mut changes = changes(5);
+        let tree = MerkleTree::build(&changes).unwrap();
+        let proof = tree.proof(2).unwrap();
+
+        changes[2]["action_type"] = json!("teleport_to_vault");
+        let tampered = leaf_hash(&canonical_json(&changes[2]).unwrap());
+        assert!(!verify(&tree.root(), &tampered, &proof));
+
+        // A valid leaf with another leaf's proof must fail too
+        let neighbour = leaf_hash(&canonical_json(&changes[3]).unwrap());
+        assert!(!verify(&tree.root(), &neighbour, &proof));
+    }
+
+    #[test]
+    fn test_key_order_in_data_does_not_change_the_root() {
+        let with_data = |keys: &[(&str, Value)]| {
+            let mut data = serde_json::Map::new();
+            for (key, value) in keys {
+                data.insert(key.to_string(), value.clone());
+            }
+            vec![json!({ "player_id": 1, "action_type": "craft", "data": Value::Object(data) })]
+        };
+        let nested = json!({ "quality": 3, "material": "oak" });
+        let forward = with_data(&[("resource_id", "oak".into()), ("count", 2.into()), ("recipe", nested.clone())]);
+        let backward = with_data(&[("recipe", nested), ("count", 2.into()), ("resource_id", "oak".into())]);
+
+        assert_eq!(MerkleTree::build(&forward).unwrap().root(), MerkleTree::build(&backward).unwrap().root());
+        assert_ne!(MerkleTree::build(&forward).unwrap().root(), MerkleTree::build(&changes(1)).unwrap().root());
+    }
+
+    #[test]
+    fn test_repeating_the_last_leaf_changes_the_root() {
+        let mut padded = changes(3);
+        padded.push(padded[2].clone());
+        assert_ne!(MerkleTree::build(&changes(3)).unwrap().root(), MerkleTree::build(&padded).unwrap().root());
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let root = MerkleTree::build(&changes(4)).unwrap().root();
+        assert_eq!(from_hex(&to_hex(&root)), Some(root));
+        assert_eq!(to_hex(&MerkleTree::from_leaves(Vec::new()).root()), "0".repeat(64));
+    }
+}
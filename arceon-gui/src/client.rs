@@ -1,11 +1,16 @@
 use anyhow::Result;
 use reqwest::Client;
 use serde::{Serialize, Deserialize};
+use std::future::Future;
+use tokio::sync::mpsc;
+use arceon_core::GameCommand;
+use crate::{AreaExit, PlacedStructure};
 
 #[derive(Clone)]
 pub struct GameClient {
     client: Client,
     server_url: String,
+    session_id: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -25,8 +30,8 @@ struct CreatePlayerResponse {
 
 #[derive(Serialize)]
 struct ProcessCommandRequest {
-    player_id: String,
-    command: String,
+    character_id: String,
+    command: GameCommand,
 }
 
 #[derive(Deserialize)]
@@ -35,14 +40,37 @@ struct ProcessCommandResponse {
     response: String,
 }
 
+/// The world map as the server knows it, from `GET /api/world/layout`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorldLayout {
+    pub areas: Vec<AreaLayout>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AreaLayout {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub exits: Vec<AreaExit>,
+    #[serde(default)]
+    pub structures: Vec<PlacedStructure>,
+}
+
 impl GameClient {
     pub fn new(server_url: String) -> Self {
         Self {
             client: Client::new(),
             server_url,
+            session_id: None,
         }
     }
     
+    /// Authenticated session to send with game requests
+    pub fn set_session(&mut self, session_id: Option<String>) {
+        self.session_id = session_id;
+    }
+    
     pub async fn check_server_health(&self) -> Result<bool> {
         let url = format!("{}/api/health", self.server_url);
         
@@ -79,29 +107,143 @@ impl GameClient {
         }
     }
     
-    pub async fn process_command(&self, player_id: String, command: String) -> Result<String> {
+    /// Areas, their exits, descriptions and structures, so the GUI never carries its own map
+    pub async fn fetch_world_layout(&self) -> Result<WorldLayout> {
+        let url = format!("{}/api/world/layout", self.server_url);
+        
+        let response = self.client.get(&url).send().await?;
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(anyhow::anyhow!("Server error: {}", response.status()))
+        }
+    }
+    
+    pub async fn process_command(&self, player_id: String, command: GameCommand) -> Result<String> {
         let url = format!("{}/api/commands", self.server_url);
         
         let request = ProcessCommandRequest {
-            player_id,
+            character_id: player_id,
             command,
         };
         
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?;
+        let mut builder = self.client.post(&url).json(&request);
+        if let Some(session_id) = &self.session_id {
+            builder = builder.header("session-id", session_id);
+        }
+        let response = builder.send().await?;
             
         if response.status().is_success() {
             let command_response: ProcessCommandResponse = response.json().await?;
             if command_response.success {
                 Ok(command_response.response)
             } else {
-                Err(anyhow::anyhow!("Command failed"))
+                Err(anyhow::anyhow!("Command failed: {}", command_response.response))
             }
         } else {
             Err(anyhow::anyhow!("Server error: {}", response.status()))
         }
     }
-}
\ No newline at end of file
+}
+
+/// The server's answer to one dispatched command
+#[derive(Debug)]
+pub struct CommandReply {
+    pub command: GameCommand,
+    pub result: Result<String>,
+}
+
+/// Sends commands to the server off the UI thread. The egui update loop queues
+/// commands with `dispatch` and collects replies with `poll` each frame, so it never
+/// blocks on the network. Commands are sent one at a time, in the order queued.
+pub struct CommandDispatcher {
+    requests: mpsc::UnboundedSender<(String, GameCommand)>,
+    replies: mpsc::UnboundedReceiver<CommandReply>,
+    pending: usize,
+}
+
+impl CommandDispatcher {
+    /// Dispatch commands through `client`. Must be called from within a tokio runtime.
+    pub fn new(client: GameClient) -> Self {
+        Self::with_handler(move |player_id, command| {
+            let client = client.clone();
+            async move { client.process_command(player_id, command).await }
+        })
+    }
+    
+    /// Dispatch commands through any async handler
+    pub fn with_handler<F, Fut>(handler: F) -> Self
+    where
+        F: Fn(String, GameCommand) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        let (request_sender, mut request_receiver) = mpsc::unbounded_channel::<(String, GameCommand)>();
+        let (reply_sender, replies) = mpsc::unbounded_channel();
+        
+        tokio::spawn(async move {
+            while let Some((player_id, command)) = request_receiver.recv().await {
+                let result = handler(player_id, command.clone()).await;
+                if reply_sender.send(CommandReply { command, result }).is_err() {
+                    break; // The GUI has gone away
+                }
+            }
+        });
+        
+        Self { requests: request_sender, replies, pending: 0 }
+    }
+    
+    pub fn dispatch(&mut self, player_id: &str, command: GameCommand) -> Result<()> {
+        self.requests
+            .send((player_id.to_string(), command))
+            .map_err(|_| anyhow::anyhow!("Command dispatcher has stopped"))?;
+        self.pending += 1;
+        Ok(())
+    }
+    
+    /// Replies that have arrived since the last poll, oldest first
+    pub fn poll(&mut self) -> Vec<CommandReply> {
+        let mut replies = Vec::new();
+        while let Ok(reply) = self.replies.try_recv() {
+            replies.push(reply);
+        }
+        self.pending -= replies.len().min(self.pending);
+        replies
+    }
+    
+    /// Commands sent but not yet answered
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_dispatched_commands_reply_in_order() {
+        let mut dispatcher = CommandDispatcher::with_handler(|player_id, command| async move {
+            match command {
+                GameCommand::Who => Err(anyhow::anyhow!("server unavailable")),
+                command => Ok(format!("{} ran {}", player_id, command)),
+            }
+        });
+
+        dispatcher.dispatch("aria", GameCommand::Stats).unwrap();
+        dispatcher.dispatch("aria", GameCommand::Who).unwrap();
+        dispatcher.dispatch("aria", GameCommand::Move { destination: "north".to_string() }).unwrap();
+        assert_eq!(dispatcher.pending(), 3);
+
+        let mut replies = Vec::new();
+        while replies.len() < 3 {
+            replies.extend(dispatcher.poll());
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(dispatcher.pending(), 0);
+
+        assert_eq!(replies[0].command, GameCommand::Stats);
+        assert_eq!(replies[0].result.as_ref().unwrap(), "aria ran stats");
+        assert!(replies[1].result.is_err());
+        assert_eq!(replies[2].result.as_ref().unwrap(), "aria ran move north");
+    }
+}
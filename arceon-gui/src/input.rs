@@ -1,2 +1,172 @@
 // Input handling utilities
 pub struct InputManager;
+
+/// Previously entered commands, recalled with the Up/Down arrows in the command input.
+/// Whatever was being typed when browsing started is kept and restored by stepping
+/// back down past the newest entry.
+#[derive(Debug, Clone, Default)]
+pub struct CommandHistory {
+    entries: Vec<String>,
+    max_entries: usize,
+    position: Option<usize>, // index into `entries` while browsing
+    draft: String,
+}
+
+impl CommandHistory {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            max_entries: max_entries.max(1),
+            position: None,
+            draft: String::new(),
+        }
+    }
+    
+    /// Record a submitted command and stop browsing. Repeats of the last entry are skipped.
+    pub fn push(&mut self, command: &str) {
+        self.position = None;
+        self.draft.clear();
+        
+        let command = command.trim();
+        if command.is_empty() || self.entries.last().map(String::as_str) == Some(command) {
+            return;
+        }
+        self.entries.push(command.to_string());
+        if self.entries.len() > self.max_entries {
+            self.entries.remove(0);
+        }
+    }
+    
+    /// Step to an older entry (Up). `current` is the input as it stands, saved as the
+    /// draft when browsing starts.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(position) => position.saturating_sub(1),
+        };
+        self.position = Some(position);
+        Some(&self.entries[position])
+    }
+    
+    /// Step to a newer entry (Down), ending back at the draft
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+        if position + 1 < self.entries.len() {
+            self.position = Some(position + 1);
+            Some(&self.entries[position + 1])
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+/// Outcome of completing a partial word against a set of candidates
+#[derive(Debug, Clone, PartialEq)]
+pub enum Completion {
+    NoMatch,
+    Unique(String),
+    /// Several candidates share the prefix; `common` is the longest prefix they all share
+    Ambiguous { common: String, candidates: Vec<String> },
+}
+
+/// Complete `prefix` against `candidates`, ignoring case
+pub fn complete<S: AsRef<str>>(prefix: &str, candidates: &[S]) -> Completion {
+    let lowered = prefix.to_lowercase();
+    let mut matches: Vec<&str> = candidates
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|candidate| candidate.to_lowercase().starts_with(&lowered))
+        .collect();
+    matches.sort_unstable();
+    matches.dedup();
+
+    match matches.as_slice() {
+        [] => Completion::NoMatch,
+        [only] => Completion::Unique(only.to_string()),
+        [first, rest @ ..] => {
+            let mut common = first.to_string();
+            for candidate in rest {
+                let shared = common
+                    .chars()
+                    .zip(candidate.chars())
+                    .take_while(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                    .map(|(a, _)| a.len_utf8())
+                    .sum();
+                common.truncate(shared);
+            }
+            // Never shorten what the player already typed
+            if common.chars().count() < prefix.chars().count() {
+                common = prefix.to_string();
+            }
+            Completion::Ambiguous {
+                common,
+                candidates: matches.into_iter().map(str::to_string).collect(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_cycles_and_restores_the_draft() {
+        let mut history = CommandHistory::new(10);
+        for command in ["look", "move north", "move north", "stats"] {
+            history.push(command);
+        }
+
+        assert_eq!(history.newer(), None); // not browsing yet
+        assert_eq!(history.older("say hel"), Some("stats"));
+        assert_eq!(history.older("stats"), Some("move north"));
+        assert_eq!(history.older("move north"), Some("look"));
+        assert_eq!(history.older("look"), Some("look")); // stays on the oldest
+
+        assert_eq!(history.newer(), Some("move north"));
+        assert_eq!(history.newer(), Some("stats"));
+        assert_eq!(history.newer(), Some("say hel"));
+        assert_eq!(history.newer(), None);
+
+        let mut capped = CommandHistory::new(2);
+        for command in ["a", "b", "c"] {
+            capped.push(command);
+        }
+        assert_eq!(capped.older(""), Some("c"));
+        assert_eq!(capped.older(""), Some("b"));
+        assert_eq!(capped.older(""), Some("b"));
+    }
+
+    #[test]
+    fn test_completion_against_candidates() {
+        let npcs = ["Eldara the Wise", "Captain Marcus", "Wild Plains Wolf", "Innkeeper Marta"];
+
+        assert_eq!(complete("eld", &npcs), Completion::Unique("Eldara the Wise".to_string()));
+        assert_eq!(complete("x", &npcs), Completion::NoMatch);
+
+        let verbs = ["say", "skills", "skill", "stats"];
+        assert_eq!(
+            complete("s", &verbs),
+            Completion::Ambiguous {
+                common: "s".to_string(),
+                candidates: vec!["say".to_string(), "skill".to_string(), "skills".to_string(), "stats".to_string()],
+            }
+        );
+        assert_eq!(
+            complete("SK", &verbs),
+            Completion::Ambiguous {
+                common: "skill".to_string(),
+                candidates: vec!["skill".to_string(), "skills".to_string()],
+            }
+        );
+        assert_eq!(complete("north", &["north", "northeast", "south"]), Completion::Ambiguous {
+            common: "north".to_string(),
+            candidates: vec!["north".to_string(), "northeast".to_string()],
+        });
+    }
+}
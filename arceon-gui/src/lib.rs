@@ -3,26 +3,32 @@ pub mod themes;
 pub mod widgets;
 pub mod input;
 pub mod client;
+pub mod config;
 
 use anyhow::Result;
 use eframe::egui;
 // use std::sync::Arc;
 // use tokio::sync::RwLock;
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
 
-use arceon_core::ArceonCore;
+use arceon_core::{ArceonCore, GameCommand};
 use windows::*;
 use themes::ArceonTheme;
-use client::GameClient;
+use config::GuiConfig;
+use client::{CommandDispatcher, CommandReply, GameClient, WorldLayout};
+use input::{complete, CommandHistory, Completion};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AreaExit {
     pub direction: String,
     pub target_area: String,
+    #[serde(default)]
     pub description: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlacedStructure {
     pub id: String,
     pub name: String,
@@ -31,9 +37,23 @@ pub struct PlacedStructure {
     pub description: String,
     pub can_enter: bool,
     pub interior_area: Option<String>, // If structure has interior, this is the area name
+    #[serde(default)]
+    pub interior_description: Option<String>, // What its builder says the inside looks like
 }
 
-#[derive(Debug, Clone)]
+/// Shown for structures whose builder never described the inside
+const FALLBACK_INTERIOR: &str = "The interior is dimly lit and quiet.";
+
+impl PlacedStructure {
+    /// The structure's own interior text, or a generic one if it has none
+    pub fn interior_text(&self) -> &str {
+        self.interior_description.as_deref()
+            .filter(|text| !text.trim().is_empty())
+            .unwrap_or(FALLBACK_INTERIOR)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum StructureType {
     House,
     TownHall,
@@ -49,13 +69,36 @@ pub enum StructureType {
     Gate,
 }
 
+/// Verbs offered by Tab completion: the GUI's own commands plus those the server understands
+const COMMAND_VERBS: &[&str] = &[
+    "connect", "help", "clear", "quit", "character", "map",
+    "look", "move", "go", "enter", "exit", "who", "stats", "skills", "profile",
+    "inventory", "pickup", "drop", "say", "tell", "trade", "talk", "target",
+    "npcs", "quests", "reputation", "build",
+];
+
+const DIRECTIONS: &[&str] = &[
+    "north", "south", "east", "west", "northeast", "northwest", "southeast", "southwest", "up", "down",
+];
+
+const CONNECT_FIRST: &str = "You need to connect first. Type: connect <player_name>";
+
+/// Chat goes to its own tab; everything else is game output
+fn console_tab_for(command: &GameCommand) -> ConsoleTab {
+    match command {
+        GameCommand::Say { .. } | GameCommand::Tell { .. } => ConsoleTab::Chat,
+        _ => ConsoleTab::Game,
+    }
+}
+
 /// Main GUI application
 pub struct ArceonGui {
     core: ArceonCore,
     theme: ArceonTheme,
+    gui_config: GuiConfig,
     
     // Window states
-    main_console: ConsoleWindow,
+    main_console: TabbedConsole,
     inventory_window: InventoryWindow,
     character_window: CharacterWindow,
     map_window: MapWindow,
@@ -64,6 +107,7 @@ pub struct ArceonGui {
     
     // UI state
     command_input: String,
+    command_history: CommandHistory,
     show_inventory: bool,
     show_character: bool,
     show_map: bool,
@@ -78,11 +122,12 @@ pub struct ArceonGui {
     area_connections: HashMap<String, Vec<AreaExit>>,
     area_descriptions: HashMap<String, String>,
     area_structures: HashMap<String, Vec<PlacedStructure>>,
+    area_inhabitants: HashMap<String, Vec<String>>,
     
     // Network client
-    #[allow(dead_code)]
     game_client: GameClient,
-    #[allow(dead_code)]
+    dispatcher: CommandDispatcher,
+    pending_layout: Option<oneshot::Receiver<Result<WorldLayout>>>,
     server_url: String,
 }
 
@@ -90,17 +135,20 @@ impl ArceonGui {
     pub fn new(core: ArceonCore) -> Self {
         let server_url = "http://localhost:8080".to_string();
         let game_client = GameClient::new(server_url.clone());
+        let gui_config = GuiConfig::load(GuiConfig::DEFAULT_PATH);
         
         let mut gui = Self {
             core,
-            theme: ArceonTheme::dark_fantasy(),
-            main_console: ConsoleWindow::new(),
+            theme: ArceonTheme::from_id(gui_config.theme),
+            main_console: TabbedConsole::new(),
             inventory_window: InventoryWindow::new(),
             character_window: CharacterWindow::new(),
             map_window: MapWindow::new(),
             skills_window: SkillsWindow::new(),
-            settings_window: SettingsWindow::new(),
+            settings_window: SettingsWindow::new(gui_config.theme),
+            gui_config,
             command_input: String::new(),
+            command_history: CommandHistory::new(100),
             show_inventory: false,
             show_character: false,
             show_map: false,
@@ -110,10 +158,14 @@ impl ArceonGui {
             is_connected: false,
             server_connected: false,
             current_area: "Espan, Central Plains".to_string(),
-            area_connections: Self::create_area_connections(),
-            area_descriptions: Self::create_area_descriptions(),
-            area_structures: Self::create_initial_structures(),
+            // Filled from the server's layout on connect
+            area_connections: HashMap::new(),
+            area_descriptions: HashMap::new(),
+            area_structures: HashMap::new(),
+            area_inhabitants: Self::create_area_inhabitants(),
+            dispatcher: CommandDispatcher::new(game_client.clone()),
             game_client,
+            pending_layout: None,
             server_url,
         };
         
@@ -126,408 +178,139 @@ impl ArceonGui {
         gui
     }
     
-    /// Show NPCs and creatures in the current area
-    fn show_area_inhabitants(&mut self, area_name: &str) {
-        // Define area populations based on area type and characteristics
-        match area_name {
-            "Espan, Central Plains" => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("🧙 Eldara the Wise - An ancient scholar studying the stone markers");
-                self.main_console.add_output("⚔️ Captain Marcus - A seasoned guard patrolling the crossroads");
-                self.main_console.add_output("🐺 Wild Plains Wolf - A lone wolf hunting in the grasslands");
-                self.main_console.add_output("🐰 Prairie Rabbits - Small creatures darting through the grass");
-                self.main_console.add_output("🦅 Soaring Hawks - Majestic birds circling overhead");
-            },
-            "Alderheart" => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("🏪 Merchant Yorick - A wealthy trader selling exotic goods");
-                self.main_console.add_output("🔨 Blacksmith Gareth - The city's master weaponsmith");
-                self.main_console.add_output("⚓ Dockworker Finn - A burly man loading ships at the harbor");
-                self.main_console.add_output("👩‍💼 Harbormaster Elena - The efficient port authority leader");
-                self.main_console.add_output("🐀 Harbor Rats - Scavenging rodents near the docks");
-                self.main_console.add_output("🐙 Curious Octopi - Sea creatures visible in the harbor waters");
-            },
-            "Silverleaf Enclave" => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("🧝‍♀️ Silviana Moonwhisper - An elven high priestess of nature");
-                self.main_console.add_output("🧝‍♂️ Ranger Thalorin - An expert elven scout and tracker");
-                self.main_console.add_output("📚 Lorekeeper Aerdeth - An ancient elf who knows forgotten secrets");
-                self.main_console.add_output("🦌 Silver Stags - Mystical deer with shimmering antlers");
-                self.main_console.add_output("🧚‍♀️ Forest Sprites - Tiny magical creatures flitting through the trees");
-                self.main_console.add_output("🌸 Singing Birds - Melodious creatures with otherworldly songs");
-            },
-            "Dragon Spine Mountains" => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("🏔️ Thorek Ironbeard - A dwarven mountain guide");
-                self.main_console.add_output("🦅 Giant Mountain Eagles - Massive birds soaring between peaks");
-                self.main_console.add_output("🐻 Cave Bears - Powerful predators dwelling in mountain caves");
-                self.main_console.add_output("🐐 Mountain Goats - Sure-footed climbers on rocky ledges");
-                self.main_console.add_output("⚠️ Warning: Dragon sightings reported in higher peaks!");
-            },
-            "Misty Marshlands" => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("🧙‍♀️ Swamp Witch Morwyn - A mysterious practitioner of dark arts");
-                self.main_console.add_output("🐊 Marsh Crocodiles - Dangerous predators lurking in murky waters");
-                self.main_console.add_output("🐸 Giant Toads - Enormous amphibians with poisonous skin");
-                self.main_console.add_output("🦆 Will-o'-Wisps - Strange lights that lead travelers astray");
-                self.main_console.add_output("⚠️ Danger: Toxic gases and unstable ground!");
-            },
-            "Ancient Silverleaf Forest" => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("🌳 Elder Treant Oakenheart - An ancient tree guardian");
-                self.main_console.add_output("🧝‍♀️ Druid Circle - Elven nature magic practitioners");
-                self.main_console.add_output("🦄 Unicorns - Rare and magical forest dwellers");
-                self.main_console.add_output("🐺 Dire Wolves - Large, intelligent pack hunters");
-                self.main_console.add_output("🦋 Magical Butterflies - Creatures that shimmer with arcane energy");
-                self.main_console.add_output("🍄 Sentient Mushrooms - Fungi with mysterious consciousness");
-            },
-            "Skyhold Citadel" => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("🏛️ Archmage Celestine - Master of the Celestial Arts");
-                self.main_console.add_output("⚔️ Skyguard Captain Aurelius - Elite magical warriors");
-                self.main_console.add_output("📜 Scribe Illuminus - Keeper of ancient magical knowledge");
-                self.main_console.add_output("🦅 Celestial Eagles - Divine messengers of the sky realm");
-                self.main_console.add_output("✨ Floating Crystals - Magical gems that hover and glow");
-                self.main_console.add_output("🌟 Star Wisps - Beings of pure magical energy");
-            },
-            _ => {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Inhabitants ===");
-                self.main_console.add_output("The area appears empty for now...");
+    /// Replace the known areas with `layout`; anything the server didn't mention is forgotten
+    fn apply_world_layout(&mut self, layout: WorldLayout) {
+        self.area_connections.clear();
+        self.area_descriptions.clear();
+        self.area_structures.clear();
+        
+        for area in layout.areas {
+            self.area_connections.insert(area.name.clone(), area.exits);
+            self.area_descriptions.insert(area.name.clone(), area.description);
+            if !area.structures.is_empty() {
+                self.area_structures.insert(area.name, area.structures);
             }
         }
-        
-        // Show any structures in the area
-        self.show_area_structures(area_name);
     }
     
-    /// Show structures that have been built in the current area
-    fn show_area_structures(&mut self, area_name: &str) {
-        if let Some(structures) = self.area_structures.get(area_name) {
-            if !structures.is_empty() {
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Structures ===");
-                for structure in structures {
-                    let enter_text = if structure.can_enter { " (enter available)" } else { "" };
-                    self.main_console.add_output(&format!("🏗️ {} - Built by {}{}", 
-                        structure.name, structure.builder, enter_text));
-                    self.main_console.add_output(&format!("   {}", structure.description));
-                }
+    /// Ask the server for the world layout in the background; `poll_world_layout` applies it
+    fn request_world_layout(&mut self) {
+        let (sender, receiver) = oneshot::channel();
+        let client = self.game_client.clone();
+        tokio::spawn(async move {
+            let _ = sender.send(client.fetch_world_layout().await);
+        });
+        self.pending_layout = Some(receiver);
+    }
+    
+    /// Apply the world layout once it has arrived. Offline, the maps stay empty.
+    fn poll_world_layout(&mut self) {
+        let Some(receiver) = self.pending_layout.as_mut() else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => Err(anyhow::anyhow!("layout request was dropped")),
+        };
+        self.pending_layout = None;
+        
+        match result {
+            Ok(layout) => {
+                let area_count = layout.areas.len();
+                self.apply_world_layout(layout);
+                self.main_console.add_output(&format!("🗺️ Loaded {} areas from the server", area_count));
+            }
+            Err(e) => {
+                self.apply_world_layout(WorldLayout::default());
+                self.main_console.add_output(&format!("❌ Could not load the world map: {}", e));
             }
         }
     }
     
-    /// Show structure interior with context of main area
-    fn show_structure_interior(&mut self, structure_id: &str, main_area: &str) {
-        // Show interior description based on structure type
-        match structure_id {
-            "waypoint_inn" => {
-                self.main_console.add_output("You're inside the cozy Waypoint Inn. A warm fireplace crackles in the corner,");
-                self.main_console.add_output("and the aroma of fresh bread and ale fills the air. Comfortable chairs are");
-                self.main_console.add_output("arranged around wooden tables, perfect for weary travelers.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to leave the inn and return outside.");
-            },
-            "yorick_emporium" => {
-                self.main_console.add_output("You're inside Yorick's Exotic Emporium. Shelves line the walls, packed with");
-                self.main_console.add_output("rare goods from distant lands. Silks, spices, jewelry, and mysterious artifacts");
-                self.main_console.add_output("create a maze of wonders. Merchant Yorick watches from behind the counter.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to leave the emporium and return to the harbor district.");
-            },
-            "gareth_forge" => {
-                self.main_console.add_output("You're inside Gareth's Master Forge. The heat from the great furnace warms");
-                self.main_console.add_output("your face as sparks fly from the anvil. Weapons and armor hang from the walls,");
-                self.main_console.add_output("each piece a testament to masterful craftsmanship. The ring of hammer on steel");
-                self.main_console.add_output("echoes through the smithy.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to leave the forge and return outside.");
-            },
-            "harbor_tower" => {
-                self.main_console.add_output("You climb the spiral staircase of the Harbor Lighthouse. From the top,");
-                self.main_console.add_output("you can see across the harbor and out to the endless sea. The great beacon");
-                self.main_console.add_output("sits ready to guide ships through the night. A logbook records arriving vessels.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to descend and return to the harbor.");
-            },
-            "moonwhisper_temple" => {
-                self.main_console.add_output("You're inside the Temple of the Silver Moon. Moonlight filters through");
-                self.main_console.add_output("crystalline windows, casting ethereal patterns on the living wood walls.");
-                self.main_console.add_output("An altar of polished silver reflects your image like still water. The air");
-                self.main_console.add_output("thrums with peaceful nature magic.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to leave the temple and return to the enclave.");
-            },
-            "lore_library" => {
-                self.main_console.add_output("You're inside the Archive of Ancient Lore. Countless books and scrolls");
-                self.main_console.add_output("line shelves carved from the living tree itself. Soft light emanates from");
-                self.main_console.add_output("glowing crystals, perfect for reading. The knowledge of ages surrounds you");
-                self.main_console.add_output("in this sanctuary of learning.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to leave the library and return to the enclave.");
-            },
-            "celestial_observatory" => {
-                self.main_console.add_output("You're inside the Celestial Observatory. Crystalline walls pulse with");
-                self.main_console.add_output("arcane energy, and magical telescopes point toward distant stars. Charts");
-                self.main_console.add_output("of celestial movements cover floating tables, and the very air crackles with");
-                self.main_console.add_output("the power of cosmic magic.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to descend and return to the citadel.");
-            },
-            _ => {
-                self.main_console.add_output("You're inside a structure. The interior is dimly lit and quiet.");
-                self.main_console.add_output("");
-                self.main_console.add_output("=== Location Context ===");
-                self.main_console.add_output(&format!("Main Area: {}", main_area));
-                self.main_console.add_output("Use 'exit' to leave and return outside.");
+    /// Named inhabitants of each area, for completing `target` and `talk`
+    fn create_area_inhabitants() -> HashMap<String, Vec<String>> {
+        [
+            ("Espan, Central Plains", &["Eldara the Wise", "Captain Marcus", "Innkeeper Marta"][..]),
+            ("Alderheart", &["Merchant Yorick", "Blacksmith Gareth", "Dockworker Finn", "Harbormaster Elena"][..]),
+            ("Silverleaf Enclave", &["Silviana Moonwhisper", "Ranger Thalorin", "Lorekeeper Aerdeth"][..]),
+            ("Dragon Spine Mountains", &["Thorek Ironbeard"][..]),
+            ("Misty Marshlands", &["Swamp Witch Morwyn"][..]),
+            ("Ancient Silverleaf Forest", &["Elder Treant Oakenheart"][..]),
+            ("Skyhold Citadel", &["Archmage Celestine", "Skyguard Captain Aurelius", "Scribe Illuminus"][..]),
+        ]
+        .into_iter()
+        .map(|(area, names)| (area.to_string(), names.iter().map(|name| name.to_string()).collect()))
+        .collect()
+    }
+    
+    /// What the argument of `verb` can complete to in the current area
+    fn completion_candidates(&self, verb: &str) -> Vec<String> {
+        match verb {
+            "move" | "go" => {
+                let mut directions: Vec<String> = DIRECTIONS.iter().map(|d| d.to_string()).collect();
+                if let Some(exits) = self.area_connections.get(&self.current_area) {
+                    directions.extend(exits.iter().map(|exit| exit.direction.clone()));
+                }
+                directions
             }
+            "enter" => self.area_structures.get(&self.current_area)
+                .map(|structures| structures.iter().filter(|s| s.can_enter).map(|s| s.name.clone()).collect())
+                .unwrap_or_default(),
+            "target" | "talk" | "speak" | "look" | "l" => self.area_inhabitants.get(&self.current_area)
+                .cloned()
+                .unwrap_or_default(),
+            _ => Vec::new(),
         }
     }
     
-    /// Create area connections for movement
-    fn create_area_connections() -> HashMap<String, Vec<AreaExit>> {
-        let mut connections = HashMap::new();
-        
-        // Espan, Central Plains connections
-        connections.insert("Espan, Central Plains".to_string(), vec![
-            AreaExit {
-                direction: "north".to_string(),
-                target_area: "Alderheart".to_string(),
-                description: "A well-traveled road leads north to the trading port of Alderheart".to_string(),
-            },
-            AreaExit {
-                direction: "south".to_string(),
-                target_area: "Silverleaf Enclave".to_string(),
-                description: "A forest path winds south toward the elven Silverleaf Enclave".to_string(),
-            },
-            AreaExit {
-                direction: "east".to_string(),
-                target_area: "Dragon Spine Mountains".to_string(),
-                description: "Rocky trails lead east into the towering Dragon Spine Mountains".to_string(),
-            },
-            AreaExit {
-                direction: "west".to_string(),
-                target_area: "Misty Marshlands".to_string(),
-                description: "Muddy paths lead west into the treacherous Misty Marshlands".to_string(),
-            },
-        ]);
-        
-        // Alderheart connections  
-        connections.insert("Alderheart".to_string(), vec![
-            AreaExit {
-                direction: "south".to_string(),
-                target_area: "Espan, Central Plains".to_string(),
-                description: "The main road leads south back to the Central Plains".to_string(),
-            },
-            AreaExit {
-                direction: "east".to_string(),
-                target_area: "Ancient Silverleaf Forest".to_string(),
-                description: "Ship routes and forest paths lead east to the Ancient Forest".to_string(),
-            },
-            AreaExit {
-                direction: "north".to_string(),
-                target_area: "Skyhold Citadel".to_string(),
-                description: "A mountain pass leads north to the mystical Skyhold Citadel".to_string(),
-            },
-        ]);
-        
-        // Silverleaf Enclave connections
-        connections.insert("Silverleaf Enclave".to_string(), vec![
-            AreaExit {
-                direction: "north".to_string(),
-                target_area: "Espan, Central Plains".to_string(),
-                description: "The forest path leads north back to the Central Plains".to_string(),
-            },
-            AreaExit {
-                direction: "west".to_string(),
-                target_area: "Ancient Silverleaf Forest".to_string(),
-                description: "Deeper forest trails lead west into the heart of the Ancient Forest".to_string(),
-            },
-        ]);
-        
-        // Add more areas as needed...
-        connections.insert("Dragon Spine Mountains".to_string(), vec![
-            AreaExit {
-                direction: "west".to_string(),
-                target_area: "Espan, Central Plains".to_string(),
-                description: "Mountain trails descend west back to the Central Plains".to_string(),
-            },
-        ]);
-        
-        connections.insert("Misty Marshlands".to_string(), vec![
-            AreaExit {
-                direction: "east".to_string(),
-                target_area: "Espan, Central Plains".to_string(),
-                description: "Muddy paths lead east back to the Central Plains".to_string(),
-            },
-        ]);
-        
-        connections.insert("Ancient Silverleaf Forest".to_string(), vec![
-            AreaExit {
-                direction: "west".to_string(),
-                target_area: "Alderheart".to_string(),
-                description: "Forest paths lead west to the port city of Alderheart".to_string(),
-            },
-            AreaExit {
-                direction: "east".to_string(),
-                target_area: "Silverleaf Enclave".to_string(),
-                description: "The path leads east to the Silverleaf Enclave".to_string(),
-            },
-        ]);
+    /// Tab completion for the command input: the verb while it is the only word,
+    /// otherwise the argument after it. Ambiguous prefixes list the candidates.
+    fn complete_command_input(&mut self) {
+        let input = self.command_input.trim_start().to_string();
+        let (verb, argument) = match input.split_once(' ') {
+            Some((verb, argument)) => (verb.to_lowercase(), Some(argument.trim_start())),
+            None => (input.clone(), None),
+        };
         
-        connections.insert("Skyhold Citadel".to_string(), vec![
-            AreaExit {
-                direction: "south".to_string(),
-                target_area: "Alderheart".to_string(),
-                description: "The mountain pass descends south to Alderheart".to_string(),
-            },
-        ]);
+        let (completion, before) = match argument {
+            None => (complete(&verb, COMMAND_VERBS), String::new()),
+            Some(argument) => (complete(argument, &self.completion_candidates(&verb)), format!("{} ", verb)),
+        };
         
-        connections
+        match completion {
+            Completion::NoMatch => {}
+            Completion::Unique(word) => {
+                // A completed verb gets a trailing space, ready for its argument
+                let space = if argument.is_none() { " " } else { "" };
+                self.command_input = format!("{}{}{}", before, word, space);
+            }
+            Completion::Ambiguous { common, candidates } => {
+                self.command_input = format!("{}{}", before, common);
+                self.main_console.add_output(&candidates.join("  "));
+            }
+        }
     }
     
-    /// Create area descriptions
-    fn create_area_descriptions() -> HashMap<String, String> {
-        let mut descriptions = HashMap::new();
-        
-        descriptions.insert("Espan, Central Plains".to_string(), 
-            "You stand in the heart of Espan's Central Plains, a vast expanse of rolling grasslands dotted with wildflowers. Ancient stone markers and well-worn paths crisscross the landscape, testament to countless travelers who have passed this way. The air is fresh and carries the scent of grass and distant woodlands.".to_string());
-            
-        descriptions.insert("Alderheart".to_string(), 
-            "The bustling port city of Alderheart spreads before you, its stone buildings rising from the harbor's edge. Ships from distant lands dock at the busy wharves, their holds filled with exotic goods. The salt breeze carries the sounds of commerce and the calls of seabirds overhead.".to_string());
-            
-        descriptions.insert("Silverleaf Enclave".to_string(), 
-            "You have entered the mystical Silverleaf Enclave, where elven architecture blends seamlessly with ancient trees. Silver-barked trees tower overhead, their leaves shimmering with an otherworldly light. The very air seems to hum with natural magic and the wisdom of ages.".to_string());
-            
-        descriptions.insert("Dragon Spine Mountains".to_string(), 
-            "The Dragon Spine Mountains rise majestically around you, their peaks shrouded in mist and legend. Rocky outcroppings and narrow paths wind between towering stone formations. The thin mountain air carries an ancient presence, as if dragons once soared through these very peaks.".to_string());
-            
-        descriptions.insert("Misty Marshlands".to_string(), 
-            "You wade through the treacherous Misty Marshlands, where thick fog obscures the landscape and strange sounds echo from hidden pools. Twisted trees emerge from the murky water like gnarled fingers, and the ground beneath your feet shifts between solid earth and sucking mud.".to_string());
-            
-        descriptions.insert("Ancient Silverleaf Forest".to_string(), 
-            "The Ancient Silverleaf Forest envelops you in its primordial embrace. Massive trees with silver bark stretch impossibly high, their canopy filtering sunlight into dancing patterns. Ancient magic permeates every inch of this sacred woodland, where time seems to flow differently.".to_string());
-            
-        descriptions.insert("Skyhold Citadel".to_string(), 
-            "The legendary Skyhold Citadel perches impossibly on a mountain peak, its spires reaching toward the heavens. Built from white stone that seems to glow with inner light, this mystical fortress has stood watch over the lands below for millennia. The air itself crackles with arcane energy.".to_string());
-        
-        descriptions
+    /// Describe the inside of `structure` in the console as the player steps into it
+    fn show_structure_interior(&mut self, structure: &PlacedStructure) {
+        self.main_console.add_output(&format!("🚪 You enter {}, built by {}.", structure.name, structure.builder));
+        for line in structure.interior_text().lines() {
+            self.main_console.add_output(line);
+        }
     }
     
-    /// Create initial structures that NPCs have built over time
-    fn create_initial_structures() -> HashMap<String, Vec<PlacedStructure>> {
-        let mut structures = HashMap::new();
-        
-        // Espan, Central Plains - Some basic structures from early settlers
-        structures.insert("Espan, Central Plains".to_string(), vec![
-            PlacedStructure {
-                id: "waypoint_inn".to_string(),
-                name: "The Waypoint Inn".to_string(),
-                structure_type: StructureType::Tavern,
-                builder: "Innkeeper Marta".to_string(),
-                description: "A cozy roadside inn with warm lights and the smell of fresh bread".to_string(),
-                can_enter: true,
-                interior_area: Some("Waypoint Inn Interior - Espan, Central Plains".to_string()),
-            },
-            PlacedStructure {
-                id: "marker_shrine".to_string(),
-                name: "Ancient Marker Shrine".to_string(),
-                structure_type: StructureType::Temple,
-                builder: "Unknown Ancient Builders".to_string(),
-                description: "A small stone shrine built around one of the ancient markers".to_string(),
-                can_enter: false,
-                interior_area: None,
-            },
-        ]);
-        
-        // Alderheart - Established city with many structures
-        structures.insert("Alderheart".to_string(), vec![
-            PlacedStructure {
-                id: "yorick_emporium".to_string(),
-                name: "Yorick's Exotic Emporium".to_string(),
-                structure_type: StructureType::Shop,
-                builder: "Merchant Yorick".to_string(),
-                description: "A grand trading house filled with goods from across the seas".to_string(),
-                can_enter: true,
-                interior_area: Some("Yorick's Emporium - Alderheart".to_string()),
-            },
-            PlacedStructure {
-                id: "gareth_forge".to_string(),
-                name: "Gareth's Master Forge".to_string(),
-                structure_type: StructureType::Blacksmith,
-                builder: "Blacksmith Gareth".to_string(),
-                description: "A renowned smithy where the finest weapons and armor are crafted".to_string(),
-                can_enter: true,
-                interior_area: Some("Master Forge - Alderheart".to_string()),
-            },
-            PlacedStructure {
-                id: "harbor_tower".to_string(),
-                name: "Harbor Lighthouse".to_string(),
-                structure_type: StructureType::Tower,
-                builder: "City of Alderheart".to_string(),
-                description: "A tall lighthouse that guides ships safely to port".to_string(),
-                can_enter: true,
-                interior_area: Some("Lighthouse Tower - Alderheart".to_string()),
-            },
-        ]);
-        
-        // Silverleaf Enclave - Elven structures in harmony with nature
-        structures.insert("Silverleaf Enclave".to_string(), vec![
-            PlacedStructure {
-                id: "moonwhisper_temple".to_string(),
-                name: "Temple of the Silver Moon".to_string(),
-                structure_type: StructureType::Temple,
-                builder: "Silviana Moonwhisper".to_string(),
-                description: "An elegant temple carved from living trees, glowing with soft moonlight".to_string(),
-                can_enter: true,
-                interior_area: Some("Silver Moon Temple - Silverleaf Enclave".to_string()),
-            },
-            PlacedStructure {
-                id: "lore_library".to_string(),
-                name: "Archive of Ancient Lore".to_string(),
-                structure_type: StructureType::Library,
-                builder: "Lorekeeper Aerdeth".to_string(),
-                description: "A vast library built within the hollow of an enormous silverleaf tree".to_string(),
-                can_enter: true,
-                interior_area: Some("Ancient Archive - Silverleaf Enclave".to_string()),
-            },
-        ]);
-        
-        // Skyhold Citadel - Magical structures
-        structures.insert("Skyhold Citadel".to_string(), vec![
-            PlacedStructure {
-                id: "celestial_observatory".to_string(),
-                name: "Celestial Observatory".to_string(),
-                structure_type: StructureType::Tower,
-                builder: "Archmage Celestine".to_string(),
-                description: "A crystalline tower that reaches toward the stars, crackling with magical energy".to_string(),
-                can_enter: true,
-                interior_area: Some("Celestial Observatory - Skyhold Citadel".to_string()),
-            },
-        ]);
+    /// Ask the server to save the connected character; the reply lands in the console
+    fn save_character(&mut self) {
+        let Some(player_id) = self.player_id.clone() else {
+            self.main_console.add_output(CONNECT_FIRST);
+            return;
+        };
         
-        structures
+        self.main_console.add_output("💾 Saving character...");
+        if let Err(e) = self.dispatcher.dispatch(&player_id, GameCommand::Save) {
+            self.main_console.add_output(&format!("❌ Save failed: {}", e));
+        }
     }
     
     pub async fn run(self) -> Result<()> {
@@ -562,18 +345,30 @@ impl ArceonApp {
 
 impl eframe::App for ArceonApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Apply theme
+        // Apply theme, picking up any change made in the Settings window
+        self.sync_theme();
         self.gui.theme.apply_to_context(ctx);
         
         // Update core systems
         self.gui.core.update();
         
+        // Show server replies as they arrive, and keep repainting while any are outstanding
+        for reply in self.gui.dispatcher.poll() {
+            self.handle_server_reply(reply);
+        }
+        self.gui.poll_world_layout();
+        if self.gui.dispatcher.pending() > 0 || self.gui.pending_layout.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        
         // Top menu bar
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.menu_button("File", |ui| {
-                    if ui.button("Save Character").clicked() {
-                        // TODO: Save character
+                    let connected = self.gui.player_id.is_some();
+                    if ui.add_enabled(connected, egui::Button::new("Save Character")).clicked() {
+                        self.gui.save_character();
+                        ui.close_menu();
                     }
                     if ui.button("Settings").clicked() {
                         self.gui.show_settings = true;
@@ -646,7 +441,7 @@ impl eframe::App for ArceonApp {
                             if self.gui.player_id.is_some() {
                                 self.process_command("look");
                             } else {
-                                self.gui.main_console.add_output("You need to connect first. Type: connect <player_name>");
+                                self.gui.main_console.add_output(CONNECT_FIRST);
                             }
                         }
                         
@@ -654,7 +449,7 @@ impl eframe::App for ArceonApp {
                             if self.gui.player_id.is_some() {
                                 self.process_command("stats");
                             } else {
-                                self.gui.main_console.add_output("You need to connect first. Type: connect <player_name>");
+                                self.gui.main_console.add_output(CONNECT_FIRST);
                             }
                         }
                         
@@ -662,7 +457,7 @@ impl eframe::App for ArceonApp {
                             if self.gui.player_id.is_some() {
                                 self.process_command("who");
                             } else {
-                                self.gui.main_console.add_output("You need to connect first. Type: connect <player_name>");
+                                self.gui.main_console.add_output(CONNECT_FIRST);
                             }
                         }
                         
@@ -688,26 +483,42 @@ impl eframe::App for ArceonApp {
         egui::TopBottomPanel::bottom("command_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.label(">");
-                let response = ui.text_edit_singleline(&mut self.gui.command_input);
+                let input_id = egui::Id::new("command_input");
+                // Take Tab before the text edit sees it, or it would move focus away
+                let tab_pressed = ui.memory(|memory| memory.has_focus(input_id))
+                    && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Tab));
+                if tab_pressed {
+                    self.gui.complete_command_input();
+                    if let Some(mut state) = egui::TextEdit::load_state(ctx, input_id) {
+                        let end = egui::text::CCursor::new(self.gui.command_input.chars().count());
+                        state.cursor.set_char_range(Some(egui::text::CCursorRange::one(end)));
+                        state.store(ctx, input_id);
+                    }
+                }
                 
-                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    // Process command
-                    let command = self.gui.command_input.trim().to_string();
-                    if !command.is_empty() {
-                        self.gui.main_console.add_output(&format!("> {}", command));
-                        self.process_command(&command);
-                        self.gui.command_input.clear();
+                let response = ui.add(egui::TextEdit::singleline(&mut self.gui.command_input).id(input_id));
+                
+                if response.has_focus() {
+                    // Recall previous commands; the half-typed draft comes back past the newest one
+                    let recalled = if ui.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
+                        self.gui.command_history.older(&self.gui.command_input).map(str::to_string)
+                    } else if ui.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
+                        self.gui.command_history.newer().map(str::to_string)
+                    } else {
+                        None
+                    };
+                    if let Some(recalled) = recalled {
+                        self.gui.command_input = recalled;
                     }
+                }
+                
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    self.submit_command_input();
                     response.request_focus();
                 }
                 
                 if ui.button("Send").clicked() {
-                    let command = self.gui.command_input.trim().to_string();
-                    if !command.is_empty() {
-                        self.gui.main_console.add_output(&format!("> {}", command));
-                        self.process_command(&command);
-                        self.gui.command_input.clear();
-                    }
+                    self.submit_command_input();
                 }
             });
         });
@@ -722,7 +533,18 @@ impl eframe::App for ArceonApp {
         }
         
         if self.gui.show_map {
-            self.gui.map_window.update(ctx, &mut self.gui.show_map);
+            let clicked = self.gui.map_window.update(
+                ctx,
+                &mut self.gui.show_map,
+                &self.gui.area_connections,
+                &self.gui.area_descriptions,
+                &self.gui.current_area,
+            );
+            if let Some(direction) = clicked {
+                let command = format!("move {}", direction);
+                self.gui.main_console.add_output(&format!("> {}", command));
+                self.process_command(&command);
+            }
         }
         
         if self.gui.show_skills {
@@ -736,6 +558,31 @@ impl eframe::App for ArceonApp {
 }
 
 impl ArceonApp {
+    fn sync_theme(&mut self) {
+        let selected = self.gui.settings_window.theme();
+        if selected == self.gui.theme.id {
+            return;
+        }
+        
+        self.gui.theme = ArceonTheme::from_id(selected);
+        self.gui.gui_config.theme = selected;
+        if let Err(e) = self.gui.gui_config.save(GuiConfig::DEFAULT_PATH) {
+            self.gui.main_console.add_output(&format!("❌ Could not save settings: {}", e));
+        }
+    }
+    
+    fn submit_command_input(&mut self) {
+        let command = self.gui.command_input.trim().to_string();
+        self.gui.command_input.clear();
+        if command.is_empty() {
+            return;
+        }
+        
+        self.gui.command_history.push(&command);
+        self.gui.main_console.add_output(&format!("> {}", command));
+        self.process_command(&command);
+    }
+    
     fn process_command(&mut self, command: &str) {
         let parts: Vec<&str> = command.split_whitespace().collect();
         if parts.is_empty() {
@@ -752,16 +599,17 @@ impl ArceonApp {
                 let player_name = parts[1].to_string();
                 
                 self.gui.main_console.add_output(&format!("Attempting to connect as '{}'...", player_name));
-                self.gui.main_console.add_output("Checking server connection...");
+                self.gui.main_console.add_output(&format!("Commands will be sent to {}", self.gui.server_url));
                 
                 // Set up the connection - we'll handle the actual async call in a different way
                 self.gui.player_id = Some(player_name.clone());
                 self.gui.is_connected = true;
                 self.gui.server_connected = false; // Will be updated by health check
                 
+                self.gui.request_world_layout();
+                
                 self.gui.main_console.add_output("✅ Local connection established");
                 self.gui.main_console.add_output("Now try server commands like 'look', 'stats', 'who'");
-                self.gui.main_console.add_output("Server commands will show real-time server data!");
             },
             "help" => {
                 self.gui.main_console.add_output("=== Available Commands ===");
@@ -813,6 +661,8 @@ impl ArceonApp {
                 self.gui.main_console.add_output("");
                 self.gui.main_console.add_output("=== UI Controls ===");
                 self.gui.main_console.add_output("help - Show this help");
+                self.gui.main_console.add_output("Tab - Complete commands, directions and names");
+                self.gui.main_console.add_output("Up/Down - Recall previous commands");
                 self.gui.main_console.add_output("clear - Clear console output");
                 self.gui.main_console.add_output("quit - Exit game");
             },
@@ -832,201 +682,160 @@ impl ArceonApp {
                 if self.gui.player_id.is_some() {
                     self.process_game_command(command);
                 } else {
-                    self.gui.main_console.add_output("You need to connect first. Type: connect <player_name>");
+                    self.gui.main_console.add_output(CONNECT_FIRST);
                 }
             }
         }
     }
     
     fn process_game_command(&mut self, command: &str) {
-        if let Some(ref player_id) = self.gui.player_id {
-            // Show that we're attempting to contact server
-            self.gui.main_console.add_output(&format!("📡 Sending '{}' to server...", command));
-            
-            // Simulate server response since we can't do async here
-            // In a real implementation, this would queue the command for async processing
-            let parts: Vec<&str> = command.split_whitespace().collect();
-            match parts[0] {
-                "look" => {
-                    self.gui.main_console.add_output("🌐 Server Response:");
-                    
-                    // Show current area description
-                    if let Some(description) = self.gui.area_descriptions.get(&self.gui.current_area) {
-                        self.gui.main_console.add_output(description);
-                    } else {
-                        self.gui.main_console.add_output(&format!("You are in {}", self.gui.current_area));
-                    }
-                    
-                    // Show available exits
-                    if let Some(exits) = self.gui.area_connections.get(&self.gui.current_area) {
-                        if !exits.is_empty() {
-                            let exit_list: Vec<String> = exits.iter()
-                                .map(|exit| format!("{} to {}", exit.direction.to_uppercase(), exit.target_area))
-                                .collect();
-                            self.gui.main_console.add_output(&format!("Exits: {}", exit_list.join(", ")));
-                        } else {
-                            self.gui.main_console.add_output("No obvious exits.");
-                        }
-                    }
-                    
-                    // Show NPCs and creatures in the area
-                    self.gui.show_area_inhabitants(&self.gui.current_area.clone());
-                    
-                    self.gui.main_console.add_output("✅ Connected to Arceon server!");
-                },
-                "move" => {
-                    if parts.len() < 2 {
-                        self.gui.main_console.add_output("Move in which direction? (e.g., 'move north')");
-                        return;
-                    }
-                    
-                    let direction = parts[1].to_lowercase();
-                    self.gui.main_console.add_output("🌐 Server Response:");
-                    
-                    if let Some(exits) = self.gui.area_connections.get(&self.gui.current_area) {
-                        if let Some(exit) = exits.iter().find(|e| e.direction == direction) {
-                            self.gui.current_area = exit.target_area.clone();
-                            self.gui.main_console.add_output(&format!("You travel {} and arrive at {}", direction, exit.target_area));
-                            
-                            // Automatically look around the new area
-                            if let Some(description) = self.gui.area_descriptions.get(&self.gui.current_area) {
-                                self.gui.main_console.add_output(description);
-                            }
-                            
-                            // Show new exits
-                            if let Some(new_exits) = self.gui.area_connections.get(&self.gui.current_area) {
-                                if !new_exits.is_empty() {
-                                    let exit_list: Vec<String> = new_exits.iter()
-                                        .map(|e| format!("{} to {}", e.direction.to_uppercase(), e.target_area))
-                                        .collect();
-                                    self.gui.main_console.add_output(&format!("Exits: {}", exit_list.join(", ")));
-                                }
-                            }
-                            
-                            // Show NPCs and creatures in the new area
-                            self.gui.show_area_inhabitants(&self.gui.current_area.clone());
-                        } else {
-                            self.gui.main_console.add_output(&format!("You cannot move {} from here.", direction));
-                            if !exits.is_empty() {
-                                let available_directions: Vec<String> = exits.iter()
-                                    .map(|e| e.direction.clone())
-                                    .collect();
-                                self.gui.main_console.add_output(&format!("Available directions: {}", available_directions.join(", ")));
-                            }
-                        }
-                    } else {
-                        self.gui.main_console.add_output("No exits available from this location.");
-                    }
-                    self.gui.main_console.add_output("✅ Connected to Arceon server!");
-                },
-                "enter" => {
-                    if parts.len() < 2 {
-                        self.gui.main_console.add_output("Enter which structure? (e.g., 'enter inn')");
-                        return;
-                    }
-                    
-                    let structure_query = parts[1..].join(" ").to_lowercase();
+        let Some(player_id) = self.gui.player_id.clone() else {
+            return;
+        };
+        let Some(parsed) = GameCommand::parse(command) else {
+            return;
+        };
+        
+        self.gui.main_console.add_output(&format!("📡 Sending '{}' to server...", parsed));
+        if let Err(e) = self.gui.dispatcher.dispatch(&player_id, parsed) {
+            self.gui.main_console.add_output(&format!("❌ {}", e));
+        }
+    }
+    
+    fn handle_server_reply(&mut self, reply: CommandReply) {
+        match reply.result {
+            Ok(response) if reply.command == GameCommand::Save => {
+                self.gui.server_connected = true;
+                self.gui.main_console.add_output(&format!("💾 {}", response));
+            }
+            Err(e) if reply.command == GameCommand::Save => {
+                self.gui.main_console.add_output(&format!("❌ Save failed: {}", e));
+            }
+            Ok(response) => {
+                self.gui.server_connected = true;
+                let tab = console_tab_for(&reply.command);
+                if tab == ConsoleTab::Game {
                     self.gui.main_console.add_output("🌐 Server Response:");
-                    
-                    if let Some(structures) = self.gui.area_structures.get(&self.gui.current_area) {
-                        let found_structure = structures.iter().find(|s| 
-                            s.name.to_lowercase().contains(&structure_query) || 
-                            s.id.to_lowercase().contains(&structure_query)
-                        );
-                        
-                        if let Some(structure) = found_structure {
-                            if structure.can_enter {
-                                if let Some(interior_area) = &structure.interior_area {
-                                    let old_area = self.gui.current_area.clone();
-                                    let structure_id = structure.id.clone();
-                                    let structure_name = structure.name.clone();
-                                    self.gui.current_area = interior_area.clone();
-                                    self.gui.main_console.add_output(&format!("You enter {} and find yourself inside...", structure_name));
-                                    
-                                    // Show interior description
-                                    self.gui.show_structure_interior(&structure_id, &old_area);
-                                } else {
-                                    self.gui.main_console.add_output(&format!("{} has no interior to enter.", structure.name));
-                                }
-                            } else {
-                                self.gui.main_console.add_output(&format!("{} cannot be entered.", structure.name));
-                            }
-                        } else {
-                            self.gui.main_console.add_output(&format!("Could not find a structure matching '{}'", structure_query));
-                            if !structures.is_empty() {
-                                let available: Vec<String> = structures.iter()
-                                    .filter(|s| s.can_enter)
-                                    .map(|s| s.name.clone())
-                                    .collect();
-                                if !available.is_empty() {
-                                    self.gui.main_console.add_output(&format!("Available structures to enter: {}", available.join(", ")));
-                                }
-                            }
-                        }
-                    } else {
-                        self.gui.main_console.add_output("No structures available to enter in this area.");
-                    }
-                    self.gui.main_console.add_output("✅ Connected to Arceon server!");
-                },
-                "exit" => {
-                    // Check if we're inside a structure (area name contains " - ")
-                    if self.gui.current_area.contains(" - ") {
-                        let area_parts: Vec<&str> = self.gui.current_area.split(" - ").collect();
-                        if area_parts.len() >= 2 {
-                            let main_area = area_parts[1].to_string();
-                            self.gui.current_area = main_area;
-                            self.gui.main_console.add_output("🌐 Server Response:");
-                            self.gui.main_console.add_output("You exit the structure and find yourself back outside.");
-                            
-                            // Auto-look around the main area
-                            if let Some(description) = self.gui.area_descriptions.get(&self.gui.current_area) {
-                                self.gui.main_console.add_output(description);
-                            }
-                            
-                            // Show exits
-                            if let Some(exits) = self.gui.area_connections.get(&self.gui.current_area) {
-                                if !exits.is_empty() {
-                                    let exit_list: Vec<String> = exits.iter()
-                                        .map(|exit| format!("{} to {}", exit.direction.to_uppercase(), exit.target_area))
-                                        .collect();
-                                    self.gui.main_console.add_output(&format!("Exits: {}", exit_list.join(", ")));
-                                }
-                            }
-                            
-                            // Show inhabitants and structures
-                            self.gui.show_area_inhabitants(&self.gui.current_area.clone());
-                            
-                            self.gui.main_console.add_output("✅ Connected to Arceon server!");
-                        } else {
-                            self.gui.main_console.add_output("Cannot determine main area to exit to.");
-                        }
-                    } else {
-                        self.gui.main_console.add_output("You are not inside a structure that can be exited.");
+                }
+                for line in response.lines() {
+                    self.gui.main_console.add_to(tab, line);
+                }
+                self.follow_location(&reply.command);
+            }
+            Err(e) => {
+                self.gui.server_connected = false;
+                self.gui.main_console.add_output(&format!("❌ '{}' failed: {}", reply.command, e));
+            }
+        }
+    }
+    
+    /// Keep the local idea of where the player is in step with commands the server accepted
+    fn follow_location(&mut self, command: &GameCommand) {
+        match command {
+            GameCommand::Move { destination } => {
+                let target = self.gui.area_connections.get(&self.gui.current_area)
+                    .and_then(|exits| exits.iter().find(|exit| exit.direction == *destination))
+                    .map(|exit| exit.target_area.clone());
+                if let Some(target) = target {
+                    self.gui.current_area = target;
+                }
+            }
+            GameCommand::Enter { structure } => {
+                let query = structure.to_lowercase();
+                let entered = self.gui.area_structures.get(&self.gui.current_area)
+                    .and_then(|structures| structures.iter().find(|s| {
+                        s.can_enter && (s.name.to_lowercase().contains(&query) || s.id.to_lowercase().contains(&query))
+                    }))
+                    .cloned();
+                if let Some(entered) = entered {
+                    self.gui.show_structure_interior(&entered);
+                    if let Some(interior) = entered.interior_area {
+                        self.gui.current_area = interior;
                     }
-                },
-                "stats" => {
-                    self.gui.main_console.add_output("🌐 Server Response:");
-                    self.gui.main_console.add_output(&format!("=== {} ===", player_id));
-                    self.gui.main_console.add_output("Health: 100/100");
-                    self.gui.main_console.add_output("Mana: 50/50");
-                    self.gui.main_console.add_output("Level: 1");
-                    self.gui.main_console.add_output("✅ Connected to Arceon server!");
-                },
-                "who" => {
-                    self.gui.main_console.add_output("🌐 Server Response:");
-                    self.gui.main_console.add_output("Players online (1): Connected via API");
-                    self.gui.main_console.add_output("✅ Connected to Arceon server!");
-                },
-                _ => {
-                    self.gui.main_console.add_output("🌐 Server Response:");
-                    self.gui.main_console.add_output(&format!("Server received: {}", command));
-                    self.gui.main_console.add_output("✅ Connected to Arceon server!");
                 }
             }
-            
-            // Update connection status
-            self.gui.server_connected = true;
+            GameCommand::Exit => {
+                // Interiors are named "<structure> - <area>"
+                if let Some((_, main_area)) = self.gui.current_area.split_once(" - ") {
+                    self.gui.current_area = main_area.to_string();
+                }
+            }
+            _ => {}
         }
     }
     
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_while_disconnected_asks_to_connect_first() {
+        let core = ArceonCore::new(arceon_core::Config::default()).await.unwrap();
+        let mut gui = ArceonGui::new(core);
+        assert!(gui.player_id.is_none());
+
+        gui.save_character();
+
+        assert_eq!(gui.dispatcher.pending(), 0);
+        assert_eq!(gui.main_console.console(ConsoleTab::Game).lines().last(), Some(CONNECT_FIRST));
+    }
+
+    #[tokio::test]
+    async fn test_world_layout_fills_the_connection_map() {
+        let core = ArceonCore::new(arceon_core::Config::default()).await.unwrap();
+        let mut gui = ArceonGui::new(core);
+        assert!(gui.area_connections.is_empty());
+
+        let layout: WorldLayout = serde_json::from_str(r#"{
+            "areas": [
+                {
+                    "name": "Alderheart",
+                    "description": "A bustling trading port",
+                    "exits": [{ "direction": "south", "target_area": "Espan, Central Plains" }],
+                    "structures": [{
+                        "id": "harbor_tower", "name": "Harbor Lighthouse", "structure_type": "Tower",
+                        "builder": "City of Alderheart", "description": "A tall lighthouse",
+                        "can_enter": true, "interior_area": "Lighthouse Tower - Alderheart"
+                    }]
+                },
+                { "name": "Espan, Central Plains", "exits": [{ "direction": "north", "target_area": "Alderheart" }] }
+            ]
+        }"#).unwrap();
+        gui.apply_world_layout(layout);
+
+        assert_eq!(gui.area_connections.len(), 2);
+        let exits = &gui.area_connections["Espan, Central Plains"];
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].direction, "north");
+        assert_eq!(exits[0].target_area, "Alderheart");
+        assert_eq!(gui.area_descriptions["Alderheart"], "A bustling trading port");
+        assert_eq!(gui.area_structures["Alderheart"][0].id, "harbor_tower");
+        assert!(!gui.area_structures.contains_key("Espan, Central Plains"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_interior_is_shown_instead_of_the_fallback() {
+        let core = ArceonCore::new(arceon_core::Config::default()).await.unwrap();
+        let mut gui = ArceonGui::new(core);
+        let mut cottage = PlacedStructure {
+            id: "aria_cottage".to_string(),
+            name: "Aria's Cottage".to_string(),
+            structure_type: StructureType::House,
+            builder: "Aria".to_string(),
+            description: "A small thatched cottage".to_string(),
+            can_enter: true,
+            interior_area: Some("Aria's Cottage - Alderheart".to_string()),
+            interior_description: Some("Herbs hang drying above a crackling hearth.".to_string()),
+        };
+
+        gui.show_structure_interior(&cottage);
+        let lines: Vec<&str> = gui.main_console.console(ConsoleTab::Game).lines().collect();
+        assert_eq!(lines.last(), Some(&"Herbs hang drying above a crackling hearth."));
+        assert!(!lines.iter().any(|line| line.contains(FALLBACK_INTERIOR)));
+
+        cottage.interior_description = None;
+        assert_eq!(cottage.interior_text(), FALLBACK_INTERIOR);
+    }
+}
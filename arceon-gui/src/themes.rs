@@ -1,7 +1,24 @@
-use egui::{Context, Color32, Rounding, Shadow};
+use egui::{Context, Color32, Rounding, Shadow, Visuals};
+use serde::{Deserialize, Serialize};
+
+/// Which theme is selected; this is what gets saved in the GUI config
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeId {
+    #[default]
+    DarkFantasy,
+    Light,
+    HighContrast,
+}
+
+impl ThemeId {
+    pub const ALL: [ThemeId; 3] = [ThemeId::DarkFantasy, ThemeId::Light, ThemeId::HighContrast];
+}
 
 pub struct ArceonTheme {
+    pub id: ThemeId,
     pub name: String,
+    pub dark_mode: bool,
     pub background_color: Color32,
     pub panel_color: Color32,
     pub text_color: Color32,
@@ -9,9 +26,19 @@ pub struct ArceonTheme {
 }
 
 impl ArceonTheme {
+    pub fn from_id(id: ThemeId) -> Self {
+        match id {
+            ThemeId::DarkFantasy => Self::dark_fantasy(),
+            ThemeId::Light => Self::light(),
+            ThemeId::HighContrast => Self::high_contrast(),
+        }
+    }
+    
     pub fn dark_fantasy() -> Self {
         Self {
+            id: ThemeId::DarkFantasy,
             name: "Dark Fantasy".to_string(),
+            dark_mode: true,
             background_color: Color32::from_rgb(20, 20, 25),
             panel_color: Color32::from_rgb(35, 35, 45),
             text_color: Color32::from_rgb(220, 220, 220),
@@ -19,15 +46,43 @@ impl ArceonTheme {
         }
     }
     
+    pub fn light() -> Self {
+        Self {
+            id: ThemeId::Light,
+            name: "Parchment".to_string(),
+            dark_mode: false,
+            background_color: Color32::from_rgb(250, 246, 235),
+            panel_color: Color32::from_rgb(236, 228, 210),
+            text_color: Color32::from_rgb(40, 34, 28),
+            accent_color: Color32::from_rgb(140, 90, 30), // Bronze accent
+        }
+    }
+    
+    pub fn high_contrast() -> Self {
+        Self {
+            id: ThemeId::HighContrast,
+            name: "High Contrast".to_string(),
+            dark_mode: true,
+            background_color: Color32::BLACK,
+            panel_color: Color32::from_rgb(10, 10, 10),
+            text_color: Color32::WHITE,
+            accent_color: Color32::from_rgb(255, 210, 0),
+        }
+    }
+    
     pub fn apply_to_context(&self, ctx: &Context) {
         let mut style = (*ctx.style()).clone();
         
+        // Start from egui's own palette so widget colours suit the background
+        style.visuals = if self.dark_mode { Visuals::dark() } else { Visuals::light() };
+        
         // Colors
-        style.visuals.dark_mode = true;
         style.visuals.override_text_color = Some(self.text_color);
         style.visuals.panel_fill = self.panel_color;
         style.visuals.window_fill = self.panel_color;
         style.visuals.extreme_bg_color = self.background_color;
+        style.visuals.selection.bg_fill = self.accent_color;
+        style.visuals.hyperlink_color = self.accent_color;
         
         // Rounded corners for fantasy feel
         style.visuals.window_rounding = Rounding::same(8.0);
@@ -38,9 +93,29 @@ impl ArceonTheme {
             offset: egui::Vec2::new(2.0, 4.0),
             blur: 8.0,
             spread: 0.0,
-            color: egui::Color32::from_black_alpha(80),
+            color: egui::Color32::from_black_alpha(if self.dark_mode { 80 } else { 30 }),
         };
         
         ctx.set_style(style);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_theme_applies_to_a_context() {
+        let ctx = Context::default();
+        for id in ThemeId::ALL {
+            let theme = ArceonTheme::from_id(id);
+            assert_eq!(theme.id, id);
+
+            theme.apply_to_context(&ctx);
+            let visuals = &ctx.style().visuals;
+            assert_eq!(visuals.dark_mode, theme.dark_mode);
+            assert_eq!(visuals.panel_fill, theme.panel_color);
+            assert_eq!(visuals.override_text_color, Some(theme.text_color));
+        }
+    }
+}
@@ -7,20 +7,43 @@ pub struct ConsoleWindow {
     auto_scroll: bool,
 }
 
+/// Lines of scrollback a console keeps unless told otherwise
+pub const DEFAULT_SCROLLBACK: usize = 1000;
+
 impl ConsoleWindow {
     pub fn new() -> Self {
+        Self::with_max_lines(DEFAULT_SCROLLBACK)
+    }
+    
+    pub fn with_max_lines(max_lines: usize) -> Self {
         Self {
-            output_buffer: VecDeque::new(),
-            max_lines: 1000,
+            output_buffer: VecDeque::with_capacity(max_lines.min(DEFAULT_SCROLLBACK)),
+            max_lines: max_lines.max(1),
             auto_scroll: true,
         }
     }
     
+    /// Change the scrollback cap, dropping the oldest lines if already over it
+    pub fn set_max_lines(&mut self, max_lines: usize) {
+        self.max_lines = max_lines.max(1);
+        self.evict_overflow();
+    }
+    
     pub fn add_output(&mut self, text: &str) {
         self.output_buffer.push_back(text.to_string());
-        
-        // Limit buffer size
-        if self.output_buffer.len() > self.max_lines {
+        self.evict_overflow();
+    }
+    
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.output_buffer.iter().map(String::as_str)
+    }
+    
+    pub fn clear(&mut self) {
+        self.output_buffer.clear();
+    }
+    
+    fn evict_overflow(&mut self) {
+        while self.output_buffer.len() > self.max_lines {
             self.output_buffer.pop_front();
         }
     }
@@ -35,21 +58,161 @@ impl ConsoleWindow {
             ui.separator();
             
             // Calculate available height for scroll area (subtract header and separator)
-            let scroll_height = max_height - 60.0; // Reserve space for heading and separator
-            
-            // Output area with controlled height
-            ScrollArea::vertical()
-                .auto_shrink([false; 2])
-                .max_height(scroll_height)
-                .show(ui, |ui| {
-                    for line in &self.output_buffer {
-                        ui.label(line);
-                    }
-                    
-                    if self.auto_scroll {
-                        ui.scroll_to_cursor(Some(egui::Align::BOTTOM));
+            self.show_scrollback(ui, max_height - 60.0); // Reserve space for heading and separator
+        });
+    }
+    
+    fn show_scrollback(&mut self, ui: &mut Ui, scroll_height: f32) {
+        // Output area with controlled height
+        ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .max_height(scroll_height)
+            .show(ui, |ui| {
+                for line in &self.output_buffer {
+                    ui.label(line);
+                }
+                
+                if self.auto_scroll {
+                    ui.scroll_to_cursor(Some(egui::Align::BOTTOM));
+                }
+            });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleTab {
+    Game,
+    Chat,
+}
+
+impl ConsoleTab {
+    pub const ALL: [ConsoleTab; 2] = [ConsoleTab::Game, ConsoleTab::Chat];
+    
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConsoleTab::Game => "Game",
+            ConsoleTab::Chat => "Chat",
+        }
+    }
+}
+
+/// Console split into tabs so chat doesn't scroll away under game output. Lines
+/// added to a tab that isn't showing count as unread until it is selected.
+pub struct TabbedConsole {
+    game: ConsoleWindow,
+    chat: ConsoleWindow,
+    active: ConsoleTab,
+    unread: [usize; 2],
+}
+
+impl TabbedConsole {
+    pub fn new() -> Self {
+        Self {
+            game: ConsoleWindow::new(),
+            chat: ConsoleWindow::new(),
+            active: ConsoleTab::Game,
+            unread: [0; 2],
+        }
+    }
+    
+    /// Add a line to the Game tab
+    pub fn add_output(&mut self, text: &str) {
+        self.add_to(ConsoleTab::Game, text);
+    }
+    
+    pub fn add_to(&mut self, tab: ConsoleTab, text: &str) {
+        self.console_mut(tab).add_output(text);
+        if tab != self.active {
+            self.unread[tab as usize] += 1;
+        }
+    }
+    
+    pub fn select(&mut self, tab: ConsoleTab) {
+        self.active = tab;
+        self.unread[tab as usize] = 0;
+    }
+    
+    pub fn active(&self) -> ConsoleTab {
+        self.active
+    }
+    
+    pub fn unread(&self, tab: ConsoleTab) -> usize {
+        self.unread[tab as usize]
+    }
+    
+    pub fn console(&self, tab: ConsoleTab) -> &ConsoleWindow {
+        match tab {
+            ConsoleTab::Game => &self.game,
+            ConsoleTab::Chat => &self.chat,
+        }
+    }
+    
+    fn console_mut(&mut self, tab: ConsoleTab) -> &mut ConsoleWindow {
+        match tab {
+            ConsoleTab::Game => &mut self.game,
+            ConsoleTab::Chat => &mut self.chat,
+        }
+    }
+    
+    pub fn update_with_height(&mut self, ui: &mut Ui, max_height: f32) {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                for tab in ConsoleTab::ALL {
+                    let label = match self.unread(tab) {
+                        0 => tab.label().to_string(),
+                        unread => format!("{} ({})", tab.label(), unread),
+                    };
+                    if ui.selectable_label(self.active == tab, label).clicked() {
+                        self.select(tab);
                     }
-                });
+                }
+            });
+            ui.separator();
+            
+            let active = self.active;
+            self.console_mut(active).show_scrollback(ui, max_height - 60.0);
         });
     }
 }
+
+impl Default for TabbedConsole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_lines_are_evicted_past_the_cap() {
+        let mut console = ConsoleWindow::with_max_lines(3);
+        for i in 0..5 {
+            console.add_output(&format!("line {}", i));
+        }
+        assert_eq!(console.lines().collect::<Vec<_>>(), vec!["line 2", "line 3", "line 4"]);
+
+        console.set_max_lines(1);
+        assert_eq!(console.lines().collect::<Vec<_>>(), vec!["line 4"]);
+    }
+
+    #[test]
+    fn test_chat_counts_unread_while_game_tab_is_showing() {
+        let mut console = TabbedConsole::new();
+        assert_eq!(console.active(), ConsoleTab::Game);
+
+        console.add_output("You look around.");
+        console.add_to(ConsoleTab::Chat, "Bram says: 'Hello'");
+        console.add_to(ConsoleTab::Chat, "Bram tells you: 'Over here'");
+        assert_eq!(console.unread(ConsoleTab::Game), 0);
+        assert_eq!(console.unread(ConsoleTab::Chat), 2);
+
+        console.select(ConsoleTab::Chat);
+        assert_eq!(console.unread(ConsoleTab::Chat), 0);
+        assert_eq!(console.console(ConsoleTab::Chat).lines().count(), 2);
+
+        console.add_output("The wind picks up.");
+        assert_eq!(console.unread(ConsoleTab::Game), 1);
+    }
+}
@@ -38,24 +38,6 @@ impl CharacterWindow {
     }
 }
 
-pub struct MapWindow;
-
-impl MapWindow {
-    pub fn new() -> Self {
-        Self
-    }
-    
-    pub fn update(&mut self, ctx: &Context, open: &mut bool) {
-        Window::new("World Map")
-            .open(open)
-            .default_size([600.0, 450.0])
-            .show(ctx, |ui| {
-                ui.label("Map of Espan will be displayed here.");
-                // TODO: Implement world map
-            });
-    }
-}
-
 pub struct SkillsWindow;
 
 impl SkillsWindow {
@@ -73,21 +55,3 @@ impl SkillsWindow {
             });
     }
 }
-
-pub struct SettingsWindow;
-
-impl SettingsWindow {
-    pub fn new() -> Self {
-        Self
-    }
-    
-    pub fn update(&mut self, ctx: &Context, open: &mut bool) {
-        Window::new("Settings")
-            .open(open)
-            .default_size([400.0, 300.0])
-            .show(ctx, |ui| {
-                ui.label("Game settings will be configurable here.");
-                // TODO: Implement settings
-            });
-    }
-}
@@ -1,2 +1,228 @@
-// Re-export from inventory.rs for now
-pub use super::inventory::MapWindow;
+use egui::{Align2, Color32, Context, FontId, Pos2, Rect, Sense, Stroke, Vec2, Window};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::AreaExit;
+
+const NODE_RADIUS: f32 = 14.0;
+
+pub struct MapWindow;
+
+impl MapWindow {
+    pub fn new() -> Self {
+        Self
+    }
+    
+    /// Draw the known areas as a graph around `current_area`, with each area's description
+    /// on hover. Returns the direction to move in when the player clicks an area one exit away.
+    pub fn update(
+        &mut self,
+        ctx: &Context,
+        open: &mut bool,
+        connections: &HashMap<String, Vec<AreaExit>>,
+        descriptions: &HashMap<String, String>,
+        current_area: &str,
+    ) -> Option<String> {
+        let mut clicked_direction = None;
+        
+        Window::new("World Map")
+            .open(open)
+            .default_size([600.0, 450.0])
+            .show(ctx, |ui| {
+                let positions = layout(connections, current_area);
+                if positions.is_empty() {
+                    ui.label("No areas have been discovered yet.");
+                    return;
+                }
+                
+                let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::hover());
+                let to_screen = fit_to_rect(&positions, response.rect.shrink(NODE_RADIUS * 3.0));
+                let text_color = ui.visuals().text_color();
+                let accent = ui.visuals().selection.bg_fill;
+                let font = FontId::proportional(12.0);
+                
+                // Edges first so nodes draw over them; each road is drawn once
+                let mut drawn = HashSet::new();
+                for (area, exits) in connections {
+                    for exit in exits {
+                        let (Some(from), Some(to)) = (positions.get(area), positions.get(&exit.target_area)) else {
+                            continue;
+                        };
+                        let key = if *area < exit.target_area {
+                            (area.as_str(), exit.target_area.as_str())
+                        } else {
+                            (exit.target_area.as_str(), area.as_str())
+                        };
+                        if !drawn.insert(key) {
+                            continue;
+                        }
+                        let (from, to) = (to_screen(*from), to_screen(*to));
+                        painter.line_segment([from, to], Stroke::new(1.5, Color32::GRAY));
+                        painter.text(from.lerp(to, 0.5), Align2::CENTER_CENTER, &exit.direction, font.clone(), Color32::GRAY);
+                    }
+                }
+                
+                let adjacent: HashMap<&str, &str> = connections.get(current_area)
+                    .map(|exits| exits.iter().map(|exit| (exit.target_area.as_str(), exit.direction.as_str())).collect())
+                    .unwrap_or_default();
+                
+                for (area, position) in &positions {
+                    let center = to_screen(*position);
+                    let node = Rect::from_center_size(center, Vec2::splat(NODE_RADIUS * 2.0));
+                    let node_response = ui.interact(node, ui.id().with(area), Sense::click());
+                    let direction = adjacent.get(area.as_str());
+                    
+                    let fill = if area == current_area {
+                        accent
+                    } else if direction.is_some() && node_response.hovered() {
+                        accent.linear_multiply(0.5)
+                    } else {
+                        ui.visuals().widgets.inactive.bg_fill
+                    };
+                    painter.circle(center, NODE_RADIUS, fill, Stroke::new(1.0, text_color));
+                    painter.text(center + Vec2::new(0.0, NODE_RADIUS + 8.0), Align2::CENTER_CENTER, area, font.clone(), text_color);
+                    
+                    let description = descriptions.get(area).map(String::as_str).unwrap_or_default();
+                    let hover = match direction {
+                        Some(direction) if description.is_empty() => format!("move {}", direction),
+                        Some(direction) => format!("{}\n\nmove {}", description, direction),
+                        None => description.to_string(),
+                    };
+                    let node_response = if hover.is_empty() { node_response } else { node_response.on_hover_text(hover) };
+                    if let Some(direction) = direction {
+                        if node_response.clicked() {
+                            clicked_direction = Some(direction.to_string());
+                        }
+                    }
+                }
+            });
+        
+        clicked_direction
+    }
+}
+
+impl Default for MapWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Grid offset for a compass direction, with north up
+fn direction_offset(direction: &str) -> Option<(i32, i32)> {
+    Some(match direction {
+        "north" => (0, -1),
+        "south" => (0, 1),
+        "east" => (1, 0),
+        "west" => (-1, 0),
+        "northeast" => (1, -1),
+        "northwest" => (-1, -1),
+        "southeast" => (1, 1),
+        "southwest" => (-1, 1),
+        _ => return None,
+    })
+}
+
+/// Place every area on a grid by walking exits outward from `root`, putting each
+/// neighbour in the direction of the exit that leads to it. When that cell is taken
+/// (or the exit isn't a compass direction) the nearest free cell is used instead, so
+/// no two areas overlap. Areas not reachable from `root` are laid out to the right.
+pub fn layout(connections: &HashMap<String, Vec<AreaExit>>, root: &str) -> HashMap<String, Pos2> {
+    // Sorted so the layout is the same every frame
+    let mut areas: BTreeSet<&str> = connections.keys().map(String::as_str).collect();
+    areas.extend(connections.values().flatten().map(|exit| exit.target_area.as_str()));
+    
+    let mut cells: HashMap<&str, (i32, i32)> = HashMap::new();
+    let mut occupied: HashSet<(i32, i32)> = HashSet::new();
+    let starts = std::iter::once(root).filter(|root| areas.contains(root)).chain(areas.iter().copied());
+    
+    for start in starts {
+        if cells.contains_key(start) {
+            continue;
+        }
+        let max_x = occupied.iter().map(|(x, _)| *x).max();
+        let origin = free_cell_near(max_x.map_or((0, 0), |x| (x + 2, 0)), &occupied);
+        cells.insert(start, origin);
+        occupied.insert(origin);
+        
+        let mut queue = VecDeque::from([start]);
+        while let Some(area) = queue.pop_front() {
+            let (x, y) = cells[area];
+            for exit in connections.get(area).into_iter().flatten() {
+                let target = exit.target_area.as_str();
+                if cells.contains_key(target) {
+                    continue;
+                }
+                let (dx, dy) = direction_offset(&exit.direction).unwrap_or((1, 1));
+                let cell = free_cell_near((x + dx, y + dy), &occupied);
+                cells.insert(target, cell);
+                occupied.insert(cell);
+                queue.push_back(target);
+            }
+        }
+    }
+    
+    cells
+        .into_iter()
+        .map(|(area, (x, y))| (area.to_string(), Pos2::new(x as f32, y as f32)))
+        .collect()
+}
+
+/// `wanted` if it is free, otherwise the closest free cell in rings around it
+fn free_cell_near(wanted: (i32, i32), occupied: &HashSet<(i32, i32)>) -> (i32, i32) {
+    for radius in 0i32.. {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                if dx.abs().max(dy.abs()) != radius {
+                    continue;
+                }
+                let cell = (wanted.0 + dx, wanted.1 + dy);
+                if !occupied.contains(&cell) {
+                    return cell;
+                }
+            }
+        }
+    }
+    unreachable!("the grid is unbounded")
+}
+
+/// Map grid positions onto `rect`, keeping the aspect ratio
+fn fit_to_rect(positions: &HashMap<String, Pos2>, rect: Rect) -> impl Fn(Pos2) -> Pos2 {
+    let bounds = Rect::from_points(&positions.values().copied().collect::<Vec<_>>());
+    let span = bounds.size().max(Vec2::splat(1.0));
+    let scale = (rect.width() / span.x).min(rect.height() / span.y);
+    let offset = rect.center() - bounds.center().to_vec2() * scale;
+    move |position| offset + position.to_vec2() * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit(direction: &str, target: &str) -> AreaExit {
+        AreaExit {
+            direction: direction.to_string(),
+            target_area: target.to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_layout_gives_each_area_its_own_position() {
+        let mut connections = HashMap::new();
+        connections.insert("Plains".to_string(), vec![exit("north", "Harbor"), exit("east", "Mountains")]);
+        connections.insert("Harbor".to_string(), vec![exit("south", "Plains"), exit("east", "Forest")]);
+        // Forest's own west exit would land on Harbor's cell
+        connections.insert("Mountains".to_string(), vec![exit("west", "Plains"), exit("north", "Forest")]);
+        connections.insert("Forest".to_string(), vec![exit("west", "Harbor"), exit("portal", "Island")]);
+        connections.insert("Ruins".to_string(), vec![]);
+
+        let positions = layout(&connections, "Plains");
+        assert_eq!(positions.len(), 6);
+
+        let distinct: HashSet<(i32, i32)> = positions.values().map(|p| (p.x as i32, p.y as i32)).collect();
+        assert_eq!(distinct.len(), positions.len());
+
+        assert_eq!(positions["Plains"], Pos2::new(0.0, 0.0));
+        assert_eq!(positions["Harbor"], Pos2::new(0.0, -1.0));
+        assert_eq!(positions["Mountains"], Pos2::new(1.0, 0.0));
+    }
+}
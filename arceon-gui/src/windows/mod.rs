@@ -5,7 +5,7 @@ pub mod map;
 pub mod skills;
 pub mod settings;
 
-pub use console::ConsoleWindow;
+pub use console::{ConsoleTab, ConsoleWindow, TabbedConsole};
 pub use inventory::InventoryWindow;
 pub use character::CharacterWindow;
 pub use map::MapWindow;
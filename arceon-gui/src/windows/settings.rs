@@ -1,2 +1,51 @@
-// Re-export from inventory.rs for now
-pub use super::inventory::SettingsWindow;
+use egui::{Context, Window};
+
+use crate::themes::{ArceonTheme, ThemeId};
+
+pub struct SettingsWindow {
+    theme: ThemeId,
+}
+
+impl SettingsWindow {
+    pub fn new(theme: ThemeId) -> Self {
+        Self { theme }
+    }
+    
+    /// The theme currently chosen in the window
+    pub fn theme(&self) -> ThemeId {
+        self.theme
+    }
+    
+    pub fn select_theme(&mut self, theme: ThemeId) {
+        self.theme = theme;
+    }
+    
+    pub fn update(&mut self, ctx: &Context, open: &mut bool) {
+        Window::new("Settings")
+            .open(open)
+            .default_size([400.0, 300.0])
+            .show(ctx, |ui| {
+                ui.heading("Theme");
+                for id in ThemeId::ALL {
+                    if ui.radio(self.theme == id, ArceonTheme::from_id(id).name).clicked() {
+                        self.select_theme(id);
+                    }
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selecting_a_theme_updates_the_stored_id() {
+        let mut settings = SettingsWindow::new(ThemeId::default());
+        assert_eq!(settings.theme(), ThemeId::DarkFantasy);
+
+        settings.select_theme(ThemeId::Light);
+        assert_eq!(settings.theme(), ThemeId::Light);
+        assert_eq!(ArceonTheme::from_id(settings.theme()).id, ThemeId::Light);
+    }
+}
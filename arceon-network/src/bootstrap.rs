@@ -0,0 +1,144 @@
+use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Redial schedule for bootstrap nodes.
+///
+/// Every unconnected bootstrap address is dialed when due; each attempt doubles the
+/// wait before the next one, up to `max_backoff`. A successful connection resets the
+/// address, and losing it makes the address due again straight away.
+#[derive(Debug, Clone)]
+pub struct BootstrapReconnector {
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    targets: HashMap<Multiaddr, BootstrapTarget>,
+}
+
+#[derive(Debug, Clone)]
+struct BootstrapTarget {
+    connected: bool,
+    attempts: u32,
+    next_attempt: Instant,
+}
+
+impl BootstrapReconnector {
+    pub fn new(addresses: impl IntoIterator<Item = Multiaddr>, initial_backoff: Duration, max_backoff: Duration, now: Instant) -> Self {
+        let targets = addresses
+            .into_iter()
+            .map(|address| (address, BootstrapTarget { connected: false, attempts: 0, next_attempt: now }))
+            .collect();
+
+        Self { initial_backoff, max_backoff, targets }
+    }
+
+    /// Addresses to dial now. Each one is scheduled for its next retry as if this
+    /// attempt will fail; `mark_connected` cancels that when it succeeds.
+    pub fn due(&mut self, now: Instant) -> Vec<Multiaddr> {
+        let mut due = Vec::new();
+        for (address, target) in &mut self.targets {
+            if target.connected || target.next_attempt > now {
+                continue;
+            }
+            let backoff = Self::backoff(self.initial_backoff, self.max_backoff, target.attempts);
+            target.attempts += 1;
+            target.next_attempt = now + backoff;
+            due.push(address.clone());
+        }
+        due
+    }
+
+    /// Record a connection to `remote`; returns whether it was a bootstrap node
+    pub fn mark_connected(&mut self, remote: &Multiaddr) -> bool {
+        match self.target_mut(remote) {
+            Some(target) => {
+                target.connected = true;
+                target.attempts = 0;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record that the last connection to `remote` closed
+    pub fn mark_disconnected(&mut self, remote: &Multiaddr, now: Instant) {
+        if let Some(target) = self.target_mut(remote) {
+            target.connected = false;
+            target.attempts = 0;
+            target.next_attempt = now;
+        }
+    }
+
+    /// Wait before the next attempt at `address`, if it is still being retried
+    pub fn current_backoff(&self, address: &Multiaddr) -> Option<Duration> {
+        let target = self.targets.get(address)?;
+        if target.connected || target.attempts == 0 {
+            return None;
+        }
+        Some(Self::backoff(self.initial_backoff, self.max_backoff, target.attempts - 1))
+    }
+
+    fn backoff(initial: Duration, max: Duration, attempts: u32) -> Duration {
+        initial.saturating_mul(2u32.saturating_pow(attempts)).min(max)
+    }
+
+    /// Match a connection's address against bootstrap addresses, ignoring any `/p2p/` suffix
+    fn target_mut(&mut self, remote: &Multiaddr) -> Option<&mut BootstrapTarget> {
+        let remote = without_peer_id(remote);
+        self.targets
+            .iter_mut()
+            .find(|(address, _)| without_peer_id(address) == remote)
+            .map(|(_, target)| target)
+    }
+}
+
+pub(crate) fn without_peer_id(address: &Multiaddr) -> Multiaddr {
+    address.iter().filter(|protocol| !matches!(protocol, Protocol::P2p(_))).collect()
+}
+
+/// Peer id from a trailing `/p2p/...` component, if the address has one
+pub(crate) fn peer_id_of(address: &Multiaddr) -> Option<PeerId> {
+    address.iter().find_map(|protocol| match protocol {
+        Protocol::P2p(peer_id) => Some(peer_id),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failing_bootstrap_is_retried_until_it_connects() {
+        let address: Multiaddr = "/ip4/10.0.0.1/tcp/7777".parse().unwrap();
+        let start = Instant::now();
+        let mut reconnector = BootstrapReconnector::new(
+            [address.clone()],
+            Duration::from_secs(1),
+            Duration::from_secs(5),
+            start,
+        );
+
+        // The bootstrap node is down: dials go unanswered and the waits grow, capped at 5s
+        let mut now = start;
+        let mut waits = Vec::new();
+        for _ in 0..5 {
+            assert_eq!(reconnector.due(now), vec![address.clone()]);
+            let wait = reconnector.current_backoff(&address).unwrap();
+            assert!(reconnector.due(now + wait - Duration::from_millis(1)).is_empty());
+            waits.push(wait.as_secs());
+            now += wait;
+        }
+        assert_eq!(waits, vec![1, 2, 4, 5, 5]);
+
+        // It comes up and the next attempt connects
+        assert_eq!(reconnector.due(now), vec![address.clone()]);
+        let remote = address.clone().with(Protocol::P2p(PeerId::random()));
+        assert!(reconnector.mark_connected(&remote));
+        assert!(reconnector.due(now + Duration::from_secs(60)).is_empty());
+
+        // Losing the connection makes it due again with the backoff reset
+        reconnector.mark_disconnected(&remote, now);
+        assert_eq!(reconnector.due(now), vec![address.clone()]);
+        assert_eq!(reconnector.current_backoff(&address), Some(Duration::from_secs(1)));
+    }
+}
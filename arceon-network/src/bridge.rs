@@ -0,0 +1,55 @@
+use crate::{NetworkMessage, PeerInfo};
+use anyhow::Result;
+use arceon_blockchain::ConsensusMessage;
+use arceon_core::channel::{ConsensusSender, GossipSender};
+use arceon_core::NetworkBridge;
+use libp2p::PeerId;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// A `NetworkBridge` that can also carry consensus traffic as typed messages.
+///
+/// `arceon-core` cannot name `ConsensusMessage` (the blockchain crate depends on it), so
+/// the typed half lives here for callers that hold both sides.
+pub trait ConsensusBridge: NetworkBridge {
+    /// Hand `message` to the local blockchain manager and gossip it to peers
+    fn send_consensus(&mut self, message: ConsensusMessage) -> Result<()>;
+}
+
+/// Bridge into a running `NetworkManager`, from `NetworkManager::bridge`.
+///
+/// Outbound messages go through the manager's event loop, so the bridge can be handed to
+/// `ArceonCore` while the loop owns the manager.
+#[derive(Clone)]
+pub struct NetworkManagerBridge {
+    pub(crate) outbound: GossipSender<NetworkMessage>,
+    pub(crate) consensus: ConsensusSender<ConsensusMessage>,
+    pub(crate) known_peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
+}
+
+impl NetworkBridge for NetworkManagerBridge {
+    fn broadcast_message(&mut self, message: serde_json::Value) -> Result<()> {
+        let event_type = message.get("type").and_then(|t| t.as_str()).unwrap_or("unknown").to_string();
+        let scope = message.get("scope").and_then(|s| s.as_str()).unwrap_or("global").to_string();
+        let data = message.get("data").cloned().unwrap_or(serde_json::Value::Null);
+        self.outbound.send(NetworkMessage::CoreEvent { event_type, scope, data })
+            .map_err(|_| anyhow::anyhow!("Network event loop has stopped"))
+    }
+
+    fn get_connected_peers(&self) -> Vec<String> {
+        // Called from synchronous core code; a peer table being updated right now reads as empty
+        self.known_peers.try_read()
+            .map(|peers| peers.keys().map(|peer_id| peer_id.to_string()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl ConsensusBridge for NetworkManagerBridge {
+    fn send_consensus(&mut self, message: ConsensusMessage) -> Result<()> {
+        // Synchronous caller, so a full queue drops the message rather than waiting for room
+        self.consensus.try_send(message.clone())?;
+        self.outbound.send(NetworkMessage::ConsensusMessage(message))
+            .map_err(|_| anyhow::anyhow!("Network event loop has stopped"))
+    }
+}
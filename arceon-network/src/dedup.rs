@@ -0,0 +1,45 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// How long a message is remembered after it is first seen
+pub const DEDUP_TTL: Duration = Duration::from_secs(300);
+
+/// Content hashes of recently handled messages.
+///
+/// Gossipsub only drops duplicates of the same gossip message; the same payload can
+/// still reach us twice by another path (republished, or answered by two peers). Up
+/// to `capacity` hashes are kept, each for `DEDUP_TTL`, oldest evicted first.
+#[derive(Debug, Clone)]
+pub struct MessageDedup {
+    capacity: usize,
+    seen: HashSet<u64>,
+    order: VecDeque<(u64, Instant)>, // oldest first
+}
+
+impl MessageDedup {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), seen: HashSet::new(), order: VecDeque::new() }
+    }
+
+    /// Record `content`; false if the same content was already seen within the TTL
+    pub fn first_sighting(&mut self, content: &[u8], now: Instant) -> bool {
+        while let Some(&(hash, seen_at)) = self.order.front() {
+            if now.saturating_duration_since(seen_at) < DEDUP_TTL && self.order.len() < self.capacity {
+                break;
+            }
+            self.order.pop_front();
+            self.seen.remove(&hash);
+        }
+
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let hash = hasher.finish();
+        if !self.seen.insert(hash) {
+            return false;
+        }
+        self.order.push_back((hash, now));
+        true
+    }
+}
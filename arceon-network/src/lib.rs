@@ -1,5 +1,7 @@
 use anyhow::Result;
+use arceon_core::channel::{self, ConsensusSender, GossipReceiver, GossipSender};
 use arceon_core::config::NetworkConfig;
+use arceon_core::{PlayerDelta, PlayerSnapshot};
 use libp2p::{
     swarm::SwarmEvent, 
     PeerId, Multiaddr, SwarmBuilder,
@@ -7,16 +9,29 @@ use libp2p::{
     mdns::{tokio::Behaviour as Mdns, Event as MdnsEvent},
     tcp, yamux, noise,
     identify::{Behaviour as Identify, Event as IdentifyEvent},
+    kad::{self, store::MemoryStore, Behaviour as Kademlia, Event as KademliaEvent},
     Swarm,
     futures::StreamExt,
 };
 use serde::{Serialize, Deserialize};
-use std::collections::{hash_map::DefaultHasher, HashMap};
+use std::collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
-use std::time::{Duration, SystemTime};
-use tokio::{select, sync::{RwLock, mpsc}};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::{select, sync::{RwLock, watch}, task::JoinHandle};
 use tracing::{info, warn, debug};
 use std::sync::Arc;
+use uuid::Uuid;
+
+pub mod bootstrap;
+pub mod bridge;
+pub mod dedup;
+pub mod peer_score;
+pub mod rate_limit;
+use bootstrap::BootstrapReconnector;
+use bridge::NetworkManagerBridge;
+use dedup::MessageDedup;
+use peer_score::PeerScore;
+use rate_limit::PeerRateLimiter;
 
 // Import blockchain types for integration
 use arceon_blockchain::{
@@ -34,6 +49,7 @@ pub enum NetworkMessage {
     // Communication
     ChatMessage { player_id: String, area_id: String, message: String },
     Say { player_id: String, area_id: String, message: String },
+    Tell { from: String, to: String, message: String }, // shown only by the node hosting `to`
     Emote { player_id: String, area_id: String, action: String },
     
     // Game state synchronization
@@ -49,11 +65,15 @@ pub enum NetworkMessage {
     // Skill evolution system
     SkillEvolution { skill_name: String, vote: String, voter_id: String },
     SkillDiscovery { discoverer_id: String, skill_name: String, method: String },
+
+    // Game events from `ArceonCore`, as it emits them through its `NetworkBridge`
+    CoreEvent { event_type: String, scope: String, data: serde_json::Value },
     
     // Cross-node synchronization messages
     ConsensusMessage(ConsensusMessage),
     WorldStateSync { requester: String, epoch_range: (u64, u64) },
     WorldStateResponse { blocks: Vec<FinalizedBlock>, current_state: WorldState },
+    SyncBlockTooLarge { epoch: u64, encoded_bytes: usize }, // in place of a block no sync response can carry
     NodeHeartbeat { node_id: String, timestamp: SystemTime, blockchain_stats: BlockchainStats },
     PeerDiscoveryRequest { requesting_peer: String, known_peers: Vec<String> },
     PeerDiscoveryResponse { responding_peer: String, peer_list: Vec<(String, String)> },
@@ -77,12 +97,35 @@ pub struct NetworkManager {
     // Cross-node synchronization state
     known_peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
     sync_state: Arc<RwLock<SyncState>>,
-    _message_sender: Option<mpsc::UnboundedSender<NetworkMessage>>,
-    message_receiver: Option<mpsc::UnboundedReceiver<NetworkMessage>>,
+    served_blocks: Arc<RwLock<BTreeMap<u64, FinalizedBlock>>>, // finalized blocks we answer sync requests from
+    message_sender: Option<GossipSender<NetworkMessage>>, // bounded; drops the oldest when full
+    message_receiver: Option<GossipReceiver<NetworkMessage>>,
+    consensus_sender: ConsensusSender<ConsensusMessage>, // into the blockchain manager
     
     // Heartbeat and discovery
     last_heartbeat: Arc<RwLock<SystemTime>>,
+    heartbeat_interval: Duration,
     discovery_interval: Duration,
+    bootstrap: Option<BootstrapReconnector>,
+    dht_peers: Arc<RwLock<HashSet<PeerId>>>, // peers currently in the Kademlia routing table
+    blockchain_stats: Arc<RwLock<BlockchainStats>>, // advertised to peers in each heartbeat
+    
+    // Mirrored skills and inventory of players hosted on other nodes
+    player_roster: Arc<RwLock<HashMap<String, PlayerSnapshot>>>,
+    hosted_players: Arc<RwLock<HashSet<String>>>, // players connected through this node
+    
+    // Flood protection for inbound gossip
+    rate_limiter: PeerRateLimiter,
+    traffic: BTreeMap<String, TopicTraffic>, // gossip counters by topic since the last reset
+    dedup: MessageDedup,
+    duplicate_messages: u64, // dropped by `dedup` since the last reset
+    
+    // Areas whose topics we follow when `area_topics` is on
+    subscribed_areas: HashSet<String>,
+    
+    // Heartbeat, discovery and sync loops, stopped through `shutdown`
+    shutdown: watch::Sender<bool>,
+    background_tasks: Vec<JoinHandle<()>>,
 }
 
 #[derive(Debug, Clone)]
@@ -93,7 +136,14 @@ pub struct PeerInfo {
     pub blockchain_stats: Option<BlockchainStats>,
     pub is_validator: bool,
     pub stake_amount: u64,
-    pub connection_quality: f64,
+    pub score: PeerScore,
+}
+
+impl PeerInfo {
+    /// Current connection quality in [0, 1]; see `PeerScore` for how it is made up
+    pub fn connection_quality(&self) -> f64 {
+        self.score.score(self.last_seen, SystemTime::now())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -101,10 +151,72 @@ pub struct SyncState {
     pub is_syncing: bool,
     pub sync_target_peer: Option<PeerId>,
     pub sync_start_epoch: u64,
-    pub sync_current_epoch: u64,
+    pub sync_current_epoch: u64, // next epoch needed to extend the contiguous synced range
     pub sync_target_epoch: u64,
     pub last_sync_request: SystemTime,
-    pub pending_blocks: HashMap<u64, FinalizedBlock>,
+    pub pending_blocks: HashMap<u64, FinalizedBlock>, // received ahead of a gap
+    pub synced_blocks: VecDeque<FinalizedBlock>,      // contiguous, in epoch order, not yet taken
+}
+
+impl SyncState {
+    fn begin(&mut self, from_epoch: u64, to_epoch: u64) {
+        self.is_syncing = true;
+        self.sync_start_epoch = from_epoch;
+        self.sync_current_epoch = from_epoch;
+        self.sync_target_epoch = to_epoch;
+        self.last_sync_request = SystemTime::now();
+        self.pending_blocks.clear();
+    }
+
+    /// Move every block that now continues the synced range out of `pending_blocks`
+    fn advance(&mut self) {
+        while let Some(block) = self.pending_blocks.remove(&self.sync_current_epoch) {
+            self.synced_blocks.push_back(block);
+            self.sync_current_epoch += 1;
+        }
+
+        if self.sync_current_epoch > self.sync_target_epoch {
+            self.is_syncing = false;
+            self.pending_blocks.clear();
+        }
+    }
+
+    /// The block for `epoch` is too large for any sync response, so it will never arrive.
+    /// End the sync just short of it, with whatever contiguous blocks precede it, rather
+    /// than waiting on it forever.
+    fn stop_before(&mut self, epoch: u64) {
+        if !self.is_syncing || epoch < self.sync_current_epoch || epoch > self.sync_target_epoch {
+            return;
+        }
+        if epoch == self.sync_current_epoch {
+            self.is_syncing = false;
+            self.pending_blocks.clear();
+        } else {
+            self.sync_target_epoch = epoch - 1;
+            self.advance();
+        }
+    }
+
+    /// If `timeout` has passed since the last request and the sync is still running, the
+    /// range to ask for again: from the first epoch still missing up to the target
+    fn retry_range(&mut self, now: SystemTime, timeout: Duration) -> Option<(u64, u64)> {
+        let due = now.duration_since(self.last_sync_request).is_ok_and(|waited| waited >= timeout);
+        if !self.is_syncing || !due {
+            return None;
+        }
+        self.last_sync_request = now;
+        Some((self.sync_current_epoch, self.sync_target_epoch))
+    }
+
+    /// Percentage of the requested range received without gaps
+    pub fn progress(&self) -> f64 {
+        if !self.is_syncing {
+            return 100.0;
+        }
+        let total = self.sync_target_epoch.saturating_sub(self.sync_start_epoch) + 1;
+        let current = self.sync_current_epoch.saturating_sub(self.sync_start_epoch);
+        (current as f64 / total as f64 * 100.0).min(100.0)
+    }
 }
 
 #[derive(libp2p::swarm::NetworkBehaviour)]
@@ -113,6 +225,7 @@ struct ArceonBehaviour {
     gossipsub: Gossipsub,
     mdns: Mdns,
     identify: Identify,
+    kademlia: Kademlia<MemoryStore>,
 }
 
 #[derive(Debug)]
@@ -120,6 +233,7 @@ enum ArceonEvent {
     Gossipsub(GossipsubEvent),
     Mdns(MdnsEvent),
     Identify(IdentifyEvent),
+    Kademlia(KademliaEvent),
 }
 
 impl From<GossipsubEvent> for ArceonEvent {
@@ -140,9 +254,19 @@ impl From<IdentifyEvent> for ArceonEvent {
     }
 }
 
+impl From<KademliaEvent> for ArceonEvent {
+    fn from(event: KademliaEvent) -> Self {
+        ArceonEvent::Kademlia(event)
+    }
+}
+
 
 impl NetworkManager {
-    pub async fn new(config: &NetworkConfig, is_masternode: bool) -> Result<Self> {
+    pub async fn new(
+        config: &NetworkConfig,
+        is_masternode: bool,
+        consensus_sender: ConsensusSender<ConsensusMessage>,
+    ) -> Result<Self> {
         let sync_state = SyncState {
             is_syncing: false,
             sync_target_peer: None,
@@ -151,9 +275,10 @@ impl NetworkManager {
             sync_target_epoch: 0,
             last_sync_request: SystemTime::now(),
             pending_blocks: HashMap::new(),
+            synced_blocks: VecDeque::new(),
         };
 
-        let (sender, receiver) = mpsc::unbounded_channel();
+        let (sender, receiver) = channel::gossip_channel(config.gossip_queue_capacity);
 
         Ok(Self {
             config: config.clone(),
@@ -163,10 +288,25 @@ impl NetworkManager {
             blockchain_enabled: false,
             known_peers: Arc::new(RwLock::new(HashMap::new())),
             sync_state: Arc::new(RwLock::new(sync_state)),
-            _message_sender: Some(sender),
+            served_blocks: Arc::new(RwLock::new(BTreeMap::new())),
+            message_sender: Some(sender),
             message_receiver: Some(receiver),
+            consensus_sender,
             last_heartbeat: Arc::new(RwLock::new(SystemTime::now())),
+            heartbeat_interval: Duration::from_secs(30),
             discovery_interval: Duration::from_secs(30),
+            bootstrap: None,
+            dht_peers: Arc::new(RwLock::new(HashSet::new())),
+            blockchain_stats: Arc::new(RwLock::new(BlockchainStats::default())),
+            player_roster: Arc::new(RwLock::new(HashMap::new())),
+            hosted_players: Arc::new(RwLock::new(HashSet::new())),
+            rate_limiter: PeerRateLimiter::new(config.peer_message_rate, config.peer_message_burst),
+            traffic: BTreeMap::new(),
+            dedup: MessageDedup::new(config.dedup_cache_size),
+            duplicate_messages: 0,
+            subscribed_areas: HashSet::new(),
+            shutdown: watch::channel(false).0,
+            background_tasks: Vec::new(),
         })
     }
     
@@ -175,6 +315,18 @@ impl NetworkManager {
         self.blockchain_enabled = true;
     }
 
+    /// A bridge for `ArceonCore` and other callers to send through this manager,
+    /// usable after the event loop has taken over the manager
+    pub fn bridge(&self) -> Result<NetworkManagerBridge> {
+        let outbound = self.message_sender.clone()
+            .ok_or_else(|| anyhow::anyhow!("Network manager has no internal message channel"))?;
+        Ok(NetworkManagerBridge {
+            outbound,
+            consensus: self.consensus_sender.clone(),
+            known_peers: self.known_peers.clone(),
+        })
+    }
+
     pub async fn start(&mut self) -> Result<()> {
         // Create a random PeerId
         let local_key = libp2p::identity::Keypair::generate_ed25519();
@@ -208,12 +360,17 @@ impl NetworkManager {
         let world_topic = libp2p::gossipsub::IdentTopic::new("arceon-world");
         let consensus_topic = libp2p::gossipsub::IdentTopic::new("arceon-consensus");
         let sync_topic = libp2p::gossipsub::IdentTopic::new("arceon-sync");
+        let heartbeat_topic = libp2p::gossipsub::IdentTopic::new("arceon-heartbeat");
         
         gossipsub.subscribe(&game_topic)?;
         gossipsub.subscribe(&chat_topic)?;
         gossipsub.subscribe(&world_topic)?;
         gossipsub.subscribe(&consensus_topic)?;
         gossipsub.subscribe(&sync_topic)?;
+        gossipsub.subscribe(&heartbeat_topic)?;
+        for topic in self.subscribed_area_topics() {
+            gossipsub.subscribe(&libp2p::gossipsub::IdentTopic::new(topic))?;
+        }
         
         // Create mDNS behavior for local discovery
         let mdns = Mdns::new(Default::default(), local_peer_id)?;
@@ -224,11 +381,18 @@ impl NetworkManager {
             local_key.public(),
         ).with_push_listen_addr_updates(true));
 
+        // Create Kademlia DHT for discovering peers beyond the local network
+        let mut kad_config = kad::Config::default();
+        kad_config.set_protocol_names(vec![libp2p::StreamProtocol::new("/arceon/kad/1.0.0")]);
+        let mut kademlia = Kademlia::with_config(local_peer_id, MemoryStore::new(local_peer_id), kad_config);
+        kademlia.set_mode(Some(kad::Mode::Server));
+
         // Create the enhanced network behavior
         let behaviour = ArceonBehaviour {
             gossipsub,
             mdns,
             identify,
+            kademlia,
         };
         
         // Create the swarm
@@ -250,18 +414,41 @@ impl NetworkManager {
         
         info!("🎮 Enhanced Arceon P2P network starting on {}", listen_addr);
         
-        // Connect to bootstrap nodes if configured
-        for addr in &self.config.bootstrap_nodes {
-            if let Ok(multiaddr) = addr.parse::<Multiaddr>() {
-                if let Err(e) = swarm.dial(multiaddr.clone()) {
-                    warn!("❌ Failed to dial bootstrap node {}: {}", multiaddr, e);
-                } else {
-                    info!("🔗 Connecting to bootstrap node: {}", multiaddr);
+        self.swarm = Some(swarm);
+        
+        // Connect to bootstrap nodes if configured, retrying any that are unreachable
+        let bootstrap_addrs: Vec<Multiaddr> = self.config.bootstrap_nodes.iter()
+            .filter_map(|addr| match addr.parse() {
+                Ok(multiaddr) => Some(multiaddr),
+                Err(e) => {
+                    warn!("❌ Invalid bootstrap node address {}: {}", addr, e);
+                    None
+                }
+            })
+            .collect();
+        self.bootstrap = Some(BootstrapReconnector::new(
+            bootstrap_addrs,
+            Duration::from_secs(1),
+            Duration::from_secs(self.config.bootstrap_backoff_max),
+            Instant::now(),
+        ));
+        self.redial_bootstrap_nodes();
+
+        // Seed the DHT from bootstrap nodes that name their peer id
+        if let Some(swarm) = &mut self.swarm {
+            let mut seeded = false;
+            for multiaddr in self.config.bootstrap_nodes.iter().filter_map(|addr| addr.parse::<Multiaddr>().ok()) {
+                if let Some(peer_id) = bootstrap::peer_id_of(&multiaddr) {
+                    swarm.behaviour_mut().kademlia.add_address(&peer_id, bootstrap::without_peer_id(&multiaddr));
+                    seeded = true;
+                }
+            }
+            if seeded {
+                if let Err(e) = swarm.behaviour_mut().kademlia.bootstrap() {
+                    warn!("❌ Kademlia bootstrap failed: {:?}", e);
                 }
             }
         }
-        
-        self.swarm = Some(swarm);
 
         // Start heartbeat and discovery loops
         self.start_heartbeat_loop().await?;
@@ -275,23 +462,28 @@ impl NetworkManager {
     async fn start_heartbeat_loop(&mut self) -> Result<()> {
         let last_heartbeat = self.last_heartbeat.clone();
         let known_peers = self.known_peers.clone();
+        let blockchain_stats = self.blockchain_stats.clone();
         let local_peer_id = self.local_peer_id.unwrap();
+        let heartbeat_interval = self.heartbeat_interval;
+        let Some(message_sender) = self.message_sender.clone() else {
+            return Err(anyhow::anyhow!("Network message channel is closed"));
+        };
         
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(30));
+        let mut stop = self.shutdown.subscribe();
+        self.background_tasks.push(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(heartbeat_interval);
             loop {
-                interval.tick().await;
+                select! {
+                    _ = interval.tick() => {}
+                    _ = Self::stop_requested(&mut stop) => break,
+                }
                 
-                let _blockchain_stats = BlockchainStats {
-                    total_blocks: 0,
-                    last_finalized_epoch: 0,
-                    pending_transactions: 0,
-                    total_players: 0,
-                    total_areas: 0,
-                    total_npcs: 0,
-                    total_events: 0,
-                    world_time: 0,
-                };
+                // Queue the heartbeat; the event loop publishes it
+                let heartbeat = Self::heartbeat_message(local_peer_id, blockchain_stats.read().await.clone());
+                if message_sender.send(heartbeat).is_err() {
+                    debug!("Network event loop stopped, ending heartbeat loop");
+                    break;
+                }
                 
                 // Update last heartbeat time
                 *last_heartbeat.write().await = SystemTime::now();
@@ -301,10 +493,18 @@ impl NetworkManager {
                 
                 debug!("💓 Heartbeat sent from peer: {}", local_peer_id);
             }
-        });
+        }));
         
         Ok(())
     }
+
+    fn heartbeat_message(local_peer_id: PeerId, blockchain_stats: BlockchainStats) -> NetworkMessage {
+        NetworkMessage::NodeHeartbeat {
+            node_id: local_peer_id.to_string(),
+            timestamp: SystemTime::now(),
+            blockchain_stats,
+        }
+    }
     
     /// Start peer discovery loop
     async fn start_peer_discovery_loop(&mut self) -> Result<()> {
@@ -312,10 +512,14 @@ impl NetworkManager {
         let _local_peer_id = self.local_peer_id.unwrap();
         let discovery_interval = self.discovery_interval;
         
-        tokio::spawn(async move {
+        let mut stop = self.shutdown.subscribe();
+        self.background_tasks.push(tokio::spawn(async move {
             let mut interval = tokio::time::interval(discovery_interval);
             loop {
-                interval.tick().await;
+                select! {
+                    _ = interval.tick() => {}
+                    _ = Self::stop_requested(&mut stop) => break,
+                }
                 
                 // Initiate peer discovery
                 let peer_count = known_peers.read().await.len();
@@ -324,52 +528,102 @@ impl NetworkManager {
                     // Discovery would be triggered through the swarm
                 }
             }
-        });
+        }));
         
         Ok(())
     }
     
-    /// Start synchronization loop
+    /// Start synchronization loop. Besides picking the sync peer, it asks again for the
+    /// epochs still missing whenever a sync has waited `sync_timeout` on its last request.
     async fn start_sync_loop(&mut self) -> Result<()> {
         let sync_state = self.sync_state.clone();
         let known_peers = self.known_peers.clone();
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(60));
+        let local_peer_id = self.local_peer_id.unwrap();
+        let sync_timeout = Duration::from_secs(self.config.sync_timeout.max(1));
+        let Some(message_sender) = self.message_sender.clone() else {
+            return Err(anyhow::anyhow!("Network message channel is closed"));
+        };
+        let mut stop = self.shutdown.subscribe();
+        self.background_tasks.push(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60).min(sync_timeout));
             loop {
-                interval.tick().await;
-                
-                let mut sync_state_guard = sync_state.write().await;
-                if !sync_state_guard.is_syncing {
-                    // Check if blockchain sync is needed
-                    let peers = known_peers.read().await;
-                    if let Some(target_peer) = peers.values().find(|peer| {
-                        peer.blockchain_stats.as_ref()
-                            .map_or(false, |s| s.last_finalized_epoch > 0)
-                    }) {
-                        sync_state_guard.is_syncing = true;
-                        sync_state_guard.sync_target_peer = Some(target_peer.peer_id);
-                        sync_state_guard.sync_start_epoch = 0;
-                        sync_state_guard.sync_target_epoch = target_peer.blockchain_stats
-                            .as_ref().unwrap().last_finalized_epoch;
-                        sync_state_guard.last_sync_request = SystemTime::now();
-                        
-                        info!("🔄 Starting sync from epoch {} to {} with peer {}", 
-                            sync_state_guard.sync_start_epoch,
-                            sync_state_guard.sync_target_epoch,
-                            target_peer.peer_id
-                        );
-                    }
+                select! {
+                    _ = interval.tick() => {}
+                    _ = Self::stop_requested(&mut stop) => break,
+                }
+                let peers = known_peers.read().await;
+                let mut sync_state = sync_state.write().await;
+                Self::choose_sync_target(&mut sync_state, &peers, SystemTime::now());
+                let Some(epoch_range) = sync_state.retry_range(SystemTime::now(), sync_timeout) else {
+                    continue;
+                };
+                drop(sync_state);
+
+                info!("🔄 Sync timed out; requesting epochs {}-{} again", epoch_range.0, epoch_range.1);
+                let request = NetworkMessage::WorldStateSync { requester: local_peer_id.to_string(), epoch_range };
+                if message_sender.send(request).is_err() {
+                    debug!("Network event loop stopped, ending sync loop");
+                    break;
                 }
             }
-        });
+        }));
         
         Ok(())
     }
     
+    /// Peer with the longest finalized chain, the better connection breaking ties;
+    /// None if no peer is past genesis or every one that is has gone stale
+    fn best_sync_peer(peers: &HashMap<PeerId, PeerInfo>, now: SystemTime) -> Option<&PeerInfo> {
+        peers.values()
+            .filter(|peer| peer.blockchain_stats.as_ref().is_some_and(|s| s.last_finalized_epoch > 0))
+            .filter(|peer| !Self::is_stale(peer, now))
+            .max_by(|a, b| {
+                let epoch = |peer: &PeerInfo| peer.blockchain_stats.as_ref().map_or(0, |s| s.last_finalized_epoch);
+                epoch(a).cmp(&epoch(b))
+                    .then_with(|| a.score.score(a.last_seen, now).total_cmp(&b.score.score(b.last_seen, now)))
+            })
+    }
+    
+    fn is_stale(peer: &PeerInfo, now: SystemTime) -> bool {
+        now.duration_since(peer.last_seen).is_ok_and(|silent| silent >= peer_score::STALE_AFTER)
+    }
+    
+    /// Start a sync with the best peer if none is running, or move a running one to a
+    /// new peer if its target has gone stale or disappeared. Epochs already received are kept.
+    fn choose_sync_target(sync_state: &mut SyncState, peers: &HashMap<PeerId, PeerInfo>, now: SystemTime) {
+        if sync_state.is_syncing {
+            let target_alive = sync_state.sync_target_peer
+                .and_then(|peer_id| peers.get(&peer_id))
+                .is_some_and(|peer| !Self::is_stale(peer, now));
+            if target_alive {
+                return;
+            }
+        }
+        
+        let Some(target_peer) = Self::best_sync_peer(peers, now) else {
+            return;
+        };
+        let target_epoch = target_peer.blockchain_stats.as_ref().map_or(0, |s| s.last_finalized_epoch);
+        
+        if sync_state.is_syncing {
+            warn!("⚠️ Sync peer {:?} went stale; continuing from epoch {} with peer {}",
+                sync_state.sync_target_peer, sync_state.sync_current_epoch, target_peer.peer_id);
+            sync_state.sync_target_epoch = target_epoch;
+        } else {
+            sync_state.begin(0, target_epoch);
+            info!("🔄 Starting sync from epoch {} to {} with peer {}", 
+                sync_state.sync_start_epoch,
+                sync_state.sync_target_epoch,
+                target_peer.peer_id
+            );
+        }
+        sync_state.sync_target_peer = Some(target_peer.peer_id);
+    }
+    
     /// Cleanup stale peers
     async fn cleanup_stale_peers(known_peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>) {
         let mut peers = known_peers.write().await;
-        let stale_threshold = SystemTime::now() - Duration::from_secs(300); // 5 minutes
+        let stale_threshold = SystemTime::now() - peer_score::STALE_AFTER;
         
         peers.retain(|peer_id, peer_info| {
             if peer_info.last_seen < stale_threshold {
@@ -381,22 +635,156 @@ impl NetworkManager {
         });
     }
 
+    /// Resolves once shutdown is signalled. The `watch::Ref` from `wait_for` is dropped in
+    /// here rather than held inside a `select!`, where it would make the loop future non-Send.
+    async fn stop_requested(stop: &mut watch::Receiver<bool>) {
+        let _ = stop.wait_for(|stopped| *stopped).await;
+    }
+
     pub async fn run_event_loop(&mut self) -> Result<()> {
+        let mut bootstrap_interval = tokio::time::interval(Duration::from_secs(1));
+        let mut dht_interval = tokio::time::interval(self.discovery_interval);
+        let mut stop = self.shutdown.subscribe();
         loop {
             if let Some(swarm) = &mut self.swarm {
                 select! {
+                    _ = Self::stop_requested(&mut stop) => {
+                        return Ok(());
+                    }
                     event = swarm.next() => {
                         if let Some(event) = event {
-                            self.handle_swarm_event(event).await?;
+                            // One bad event (a discovery request nobody can hear yet) is no
+                            // reason to drop every connection
+                            if let Err(e) = self.handle_swarm_event(event).await {
+                                debug!("Error handling swarm event: {}", e);
+                            }
                         }
                     }
                     message = self.message_receiver.as_mut().unwrap().recv() => {
                         if let Some(msg) = message {
-                            self.handle_internal_message(msg).await?;
+                            // Gossip with nobody subscribed fails (a lone node's heartbeat does);
+                            // that must not take the event loop down with it
+                            if let Err(e) = self.handle_internal_message(msg).await {
+                                debug!("Could not publish queued message: {}", e);
+                            }
                         }
                     }
+                    _ = bootstrap_interval.tick() => {
+                        self.redial_bootstrap_nodes();
+                    }
+                    _ = dht_interval.tick() => {
+                        // Random walk to refresh the routing table; fails harmlessly with no known peers
+                        let _ = swarm.behaviour_mut().kademlia.bootstrap();
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Leave the network cleanly. A validator (`validator_id` set) announces a
+    /// `ValidatorLeave` so peers stop counting on its votes instead of waiting out
+    /// `STALE_AFTER`. Messages still queued for the event loop, such as the core's
+    /// `PlayerLeave`s, are published, then the heartbeat, discovery and sync loops are
+    /// stopped and awaited. The event loop, if running, returns.
+    pub async fn shutdown(&mut self, validator_id: Option<Uuid>) -> Result<()> {
+        if let Some(node_id) = validator_id {
+            let leave = ConsensusMessage::ValidatorLeave { node_id, timestamp: SystemTime::now() };
+            if self.blockchain_enabled {
+                if let Err(e) = self.consensus_sender.send(leave.clone()).await {
+                    warn!("Validator leave not handed to the blockchain manager: {}", e);
                 }
             }
+            if let Some(sender) = &self.message_sender {
+                let _ = sender.send(NetworkMessage::ConsensusMessage(leave));
+            }
+        }
+        
+        while let Some(message) = self.message_receiver.as_mut().and_then(|receiver| receiver.try_recv().ok()) {
+            if let Err(e) = self.handle_internal_message(message).await {
+                // Usually no peers left to hear it; nothing more can be done on the way out
+                debug!("Could not publish queued message during shutdown: {}", e);
+            }
+        }
+        
+        self.shutdown.send_replace(true);
+        for task in self.background_tasks.drain(..) {
+            if let Err(e) = task.await {
+                warn!("Background network task failed during shutdown: {}", e);
+            }
+        }
+        info!("👋 Network manager shut down");
+        Ok(())
+    }
+    
+    /// Record a peer found through the DHT and connect to it if we have room
+    async fn handle_dht_peer(&mut self, peer_id: PeerId, multiaddr: Multiaddr) {
+        self.dht_peers.write().await.insert(peer_id);
+        self.known_peers.write().await.entry(peer_id).or_insert_with(|| PeerInfo {
+            peer_id,
+            multiaddr: multiaddr.clone(),
+            last_seen: SystemTime::now(),
+            blockchain_stats: None,
+            is_validator: false,
+            stake_amount: 0,
+            score: PeerScore::default(),
+        });
+
+        if let Some(swarm) = &mut self.swarm {
+            if Some(peer_id) == self.local_peer_id || swarm.is_connected(&peer_id) {
+                return;
+            }
+            if swarm.connected_peers().count() >= self.config.max_peers {
+                return;
+            }
+            if let Err(e) = swarm.dial(multiaddr.clone()) {
+                warn!("❌ Failed to dial DHT peer {}: {}", peer_id, e);
+            } else {
+                info!("🔗 Connecting to DHT peer: {}", peer_id);
+            }
+        }
+    }
+    
+    /// Connect to a peer at `addr`, which must end in `/p2p/<peer id>`. The peer is
+    /// recorded straight away; the connection itself completes in the event loop.
+    pub async fn dial_peer(&mut self, addr: Multiaddr) -> Result<()> {
+        let Some(swarm) = &mut self.swarm else {
+            return Err(anyhow::anyhow!("Network is not started"));
+        };
+        let peer_id = bootstrap::peer_id_of(&addr)
+            .ok_or_else(|| anyhow::anyhow!("Address {} has no /p2p/ peer id", addr))?;
+        if Some(peer_id) == self.local_peer_id {
+            return Err(anyhow::anyhow!("Cannot dial our own peer id"));
+        }
+
+        swarm.dial(addr.clone())?;
+        swarm.behaviour_mut().kademlia.add_address(&peer_id, bootstrap::without_peer_id(&addr));
+        info!("🔗 Dialing peer {} at {}", peer_id, addr);
+
+        self.known_peers.write().await.entry(peer_id).or_insert_with(|| PeerInfo {
+            peer_id,
+            multiaddr: addr,
+            last_seen: SystemTime::now(),
+            blockchain_stats: None,
+            is_validator: false,
+            stake_amount: 0,
+            score: PeerScore::default(),
+        });
+        Ok(())
+    }
+    
+    /// Dial every bootstrap node whose backoff has elapsed
+    fn redial_bootstrap_nodes(&mut self) {
+        let (Some(swarm), Some(bootstrap)) = (&mut self.swarm, &mut self.bootstrap) else {
+            return;
+        };
+
+        for multiaddr in bootstrap.due(Instant::now()) {
+            if let Err(e) = swarm.dial(multiaddr.clone()) {
+                warn!("❌ Failed to dial bootstrap node {}: {} (retrying in {:?})", 
+                    multiaddr, e, bootstrap.current_backoff(&multiaddr).unwrap_or_default());
+            } else {
+                info!("🔗 Connecting to bootstrap node: {}", multiaddr);
+            }
         }
     }
     
@@ -414,13 +802,14 @@ impl NetworkManager {
                         blockchain_stats: None,
                         is_validator: false,
                         stake_amount: 0,
-                        connection_quality: 0.5,
+                        score: PeerScore::default(),
                     };
                     
                     self.known_peers.write().await.insert(peer_id, peer_info);
                     
                     // Attempt to connect
                     if let Some(swarm) = &mut self.swarm {
+                        swarm.behaviour_mut().kademlia.add_address(&peer_id, multiaddr.clone());
                         if let Err(e) = swarm.dial(multiaddr.clone()) {
                             warn!("❌ Failed to dial discovered peer {}: {}", peer_id, e);
                         } else {
@@ -432,9 +821,9 @@ impl NetworkManager {
             SwarmEvent::Behaviour(ArceonEvent::Mdns(MdnsEvent::Expired(list))) => {
                 for (peer_id, _multiaddr) in list {
                     info!("🕰️ mDNS peer expired: {}", peer_id);
-                    // Mark peer as potentially stale
+                    // It stopped announcing itself on the local network
                     if let Some(peer_info) = self.known_peers.write().await.get_mut(&peer_id) {
-                        peer_info.connection_quality *= 0.8;
+                        peer_info.score.record_failure();
                     }
                 }
             }
@@ -443,17 +832,25 @@ impl NetworkManager {
                 message_id: _,
                 message,
             })) => {
-                if let Ok(msg) = serde_json::from_slice::<NetworkMessage>(&message.data) {
-                    self.handle_network_message(msg, Some(peer_id)).await?;
-                }
+                let counters = self.traffic.entry(message.topic.as_str().to_string()).or_default();
+                counters.messages_received += 1;
+                counters.bytes_received += message.data.len() as u64;
+                self.handle_gossip_data(&message.data, Some(peer_id)).await?;
             }
             SwarmEvent::Behaviour(ArceonEvent::Identify(IdentifyEvent::Received { peer_id, info })) => {
                 info!("🆔 Identified peer: {} - Agent: {}", peer_id, info.agent_version);
                 
+                // Inbound connections come from ephemeral ports; tell the DHT where the peer listens
+                if let Some(swarm) = &mut self.swarm {
+                    for addr in &info.listen_addrs {
+                        swarm.behaviour_mut().kademlia.add_address(&peer_id, addr.clone());
+                    }
+                }
+                
                 // Update peer info with identification data
                 if let Some(peer_info) = self.known_peers.write().await.get_mut(&peer_id) {
                     peer_info.last_seen = SystemTime::now();
-                    peer_info.connection_quality = 1.0;
+                    peer_info.score.record_success();
                     
                     // Check if this is an Arceon node
                     if info.agent_version.contains("arceon") {
@@ -461,27 +858,47 @@ impl NetworkManager {
                     }
                 }
             }
+            SwarmEvent::Behaviour(ArceonEvent::Kademlia(KademliaEvent::RoutingUpdated { peer, addresses, .. })) => {
+                debug!("📡 DHT routing table updated with peer {}", peer);
+                self.handle_dht_peer(peer, addresses.first().clone()).await;
+            }
             SwarmEvent::NewListenAddr { address, .. } => {
                 info!("🎧 Listening on: {}", address);
             }
-            SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+            SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
                 info!("🤝 Connected to peer: {}", peer_id);
+                if let Some(bootstrap) = &mut self.bootstrap {
+                    bootstrap.mark_connected(endpoint.get_remote_address());
+                }
                 
-                // Update peer connection quality
                 if let Some(peer_info) = self.known_peers.write().await.get_mut(&peer_id) {
                     peer_info.last_seen = SystemTime::now();
-                    peer_info.connection_quality = (peer_info.connection_quality + 0.2).min(1.0);
+                    peer_info.score.record_success();
                 }
                 
                 // Request peer discovery information
                 self.request_peer_discovery(peer_id).await?;
             }
-            SwarmEvent::ConnectionClosed { peer_id, .. } => {
+            SwarmEvent::ConnectionClosed { peer_id, endpoint, num_established, cause, .. } => {
                 info!("👋 Disconnected from peer: {}", peer_id);
+                if num_established == 0 {
+                    if let Some(bootstrap) = &mut self.bootstrap {
+                        bootstrap.mark_disconnected(endpoint.get_remote_address(), Instant::now());
+                    }
+                    self.rate_limiter.forget(&peer_id);
+                }
                 
-                // Reduce connection quality but keep peer info
+                // Idle timeouts and deliberate closes are not the peer's fault; errors are
+                if cause.is_some() {
+                    if let Some(peer_info) = self.known_peers.write().await.get_mut(&peer_id) {
+                        peer_info.score.record_failure();
+                    }
+                }
+            }
+            SwarmEvent::OutgoingConnectionError { peer_id: Some(peer_id), error, .. } => {
+                warn!("❌ Could not connect to peer {}: {}", peer_id, error);
                 if let Some(peer_info) = self.known_peers.write().await.get_mut(&peer_id) {
-                    peer_info.connection_quality *= 0.5;
+                    peer_info.score.record_failure();
                 }
             }
             
@@ -508,14 +925,42 @@ impl NetworkManager {
         // Send discovery request directly to the peer
         if let Some(swarm) = &mut self.swarm {
             let data = serde_json::to_vec(&discovery_request)?;
-            let topic = libp2p::gossipsub::IdentTopic::new("arceon-sync");
-            swarm.behaviour_mut().gossipsub.publish(topic, data)?;
+            Self::publish(swarm, &mut self.traffic, "arceon-sync", data)?;
         }
         
         Ok(())
     }
     
-    async fn handle_network_message(&mut self, message: NetworkMessage, _sender: Option<PeerId>) -> Result<()> {
+    /// Decode and handle a gossipsub payload
+    async fn handle_gossip_data(&mut self, data: &[u8], sender: Option<PeerId>) -> Result<()> {
+        match serde_json::from_slice::<NetworkMessage>(data) {
+            Ok(message) => self.handle_network_message(message, sender).await,
+            Err(e) => {
+                debug!("Ignoring undecodable gossip message: {}", e);
+                Ok(())
+            }
+        }
+    }
+    
+    async fn handle_network_message(&mut self, message: NetworkMessage, sender: Option<PeerId>) -> Result<()> {
+        // The same payload can arrive by more than one path; handle it once
+        if !self.dedup.first_sighting(&serde_json::to_vec(&message)?, Instant::now()) {
+            debug!("Ignoring duplicate message from {:?}", sender);
+            self.duplicate_messages += 1;
+            return Ok(());
+        }
+        
+        // Drop messages from peers over their rate limit, and trust them a little less
+        if let Some(peer_id) = sender {
+            if !self.rate_limiter.allow(peer_id, Instant::now()) {
+                debug!("🚫 Dropping message from {}: over its rate limit", peer_id);
+                if let Some(peer_info) = self.known_peers.write().await.get_mut(&peer_id) {
+                    peer_info.score.record_failure();
+                }
+                return Ok(());
+            }
+        }
+        
         match message {
             // Existing game messages
             NetworkMessage::PlayerJoin { player_id, player_name, race, area_id } => {
@@ -533,14 +978,31 @@ impl NetworkManager {
             NetworkMessage::Say { player_id, area_id, message } => {
                 info!("🗣️ [{}] {} says: {}", area_id, player_id, message);
             }
+            NetworkMessage::Tell { from, to, message } => {
+                if self.hosted_players.read().await.contains(&to) {
+                    info!("📨 {} tells {}: {}", from, to, message);
+                } else {
+                    debug!("Ignoring tell for {}, who is not hosted here", to);
+                }
+            }
             NetworkMessage::Emote { player_id, area_id, action } => {
                 info!("🎭 [{}] {} {}", area_id, player_id, action);
             }
             NetworkMessage::GameStateSync { world_time, area_updates } => {
                 info!("🔄 Game state sync at time {} with {} area updates", world_time, area_updates.len());
             }
-            NetworkMessage::PlayerUpdate { player_id, being_data: _ } => {
-                info!("📊 Player update for {}", player_id);
+            NetworkMessage::PlayerUpdate { player_id, being_data } => {
+                match PlayerDelta::decode(&being_data) {
+                    Ok(delta) => {
+                        info!("📊 Player update for {}: {} skill(s), {} item(s) changed", 
+                            player_id, delta.changed_skills.len(), delta.inventory_changes.len());
+                        self.player_roster.write().await
+                            .entry(player_id)
+                            .or_default()
+                            .apply(&delta);
+                    }
+                    Err(e) => warn!("Ignoring malformed player update for {}: {}", player_id, e),
+                }
             }
             NetworkMessage::SkillGain { player_id, skill_name, new_level, experience } => {
                 info!("⬆️ Player {} gained skill {} level {:.1} (XP: {:.1})", player_id, skill_name, new_level, experience);
@@ -560,12 +1022,17 @@ impl NetworkManager {
             NetworkMessage::SkillDiscovery { discoverer_id, skill_name, method } => {
                 info!("🔍 Player {} discovered skill '{}' via {}", discoverer_id, skill_name, method);
             }
+            NetworkMessage::CoreEvent { event_type, scope, .. } => {
+                debug!("Core event {} ({} scope) from a peer", event_type, scope);
+            }
             
             // Cross-node synchronization messages
-            NetworkMessage::ConsensusMessage(_consensus_msg) => {
+            NetworkMessage::ConsensusMessage(consensus_msg) => {
                 if self.blockchain_enabled {
-                    debug!("Processing consensus message");
-                    // TODO: Forward to blockchain manager
+                    debug!("Forwarding consensus message to blockchain manager");
+                    if let Err(e) = self.consensus_sender.send(consensus_msg).await {
+                        warn!("Consensus message not handed to the blockchain manager: {}", e);
+                    }
                 } else {
                     debug!("Received consensus message but blockchain not enabled");
                 }
@@ -584,6 +1051,15 @@ impl NetworkManager {
                 self.handle_world_state_sync_response(blocks, current_state).await?;
             }
             
+            NetworkMessage::SyncBlockTooLarge { epoch, encoded_bytes } => {
+                let mut sync_state = self.sync_state.write().await;
+                if sync_state.is_syncing && (sync_state.sync_current_epoch..=sync_state.sync_target_epoch).contains(&epoch) {
+                    warn!("⚠️ Block for epoch {} is {} bytes, too large to sync; stopping the sync before it",
+                        epoch, encoded_bytes);
+                    sync_state.stop_before(epoch);
+                }
+            }
+            
             NetworkMessage::NodeHeartbeat { node_id, timestamp, blockchain_stats } => {
                 debug!("💓 Heartbeat received from {}", node_id);
                 if let Ok(peer_id) = node_id.parse() {
@@ -621,46 +1097,112 @@ impl NetworkManager {
     /// Handle world state sync request
     async fn handle_world_state_sync_request(&mut self, _requester: PeerId, epoch_range: (u64, u64)) -> Result<()> {
         if self.blockchain_enabled {
-            // This would typically fetch blocks from the blockchain manager
-            // For now, we'll create a placeholder response
-            let response = NetworkMessage::WorldStateResponse {
-                blocks: Vec::new(), // Would fetch actual blocks here
-                current_state: WorldState {
-                    current_epoch: epoch_range.1,
-                    players: HashMap::new(),
-                    areas: HashMap::new(),
-                    npcs: HashMap::new(),
-                    global_events: Vec::new(),
-                    skill_discoveries: HashMap::new(),
-                    world_time: 0,
-                    last_update: SystemTime::now(),
-                },
-            };
+            let responses = Self::sync_responses(&self.served_blocks, &self.config, epoch_range).await?;
+            debug!("📦 Answering sync for epochs {}-{} in {} chunks", epoch_range.0, epoch_range.1, responses.len());
             
-            // Send response
+            // Send each chunk as its own gossip message; the requester reassembles them by epoch
             if let Some(swarm) = &mut self.swarm {
-                let data = serde_json::to_vec(&response)?;
-                let topic = libp2p::gossipsub::IdentTopic::new("arceon-sync");
-                swarm.behaviour_mut().gossipsub.publish(topic, data)?;
+                for response in &responses {
+                    let (topic, data) = Self::encode_for_gossip(response)?;
+                    Self::publish(swarm, &mut self.traffic, topic, data)?;
+                }
             }
         }
         Ok(())
     }
     
+    /// Split the served blocks in `epoch_range` into responses of at most `sync_batch_size`
+    /// blocks, each encoding to no more than `max_sync_message_bytes`. Takes the fields it
+    /// needs rather than `&self`: holding `&self` across the lock would need the swarm to be
+    /// `Sync`, and the event loop future would stop being `Send`.
+    async fn sync_responses(
+        served_blocks: &RwLock<BTreeMap<u64, FinalizedBlock>>,
+        config: &NetworkConfig,
+        epoch_range: (u64, u64),
+    ) -> Result<Vec<NetworkMessage>> {
+        let current_state = WorldState {
+            current_epoch: epoch_range.1,
+            players: HashMap::new(),
+            areas: HashMap::new(),
+            npcs: HashMap::new(),
+            global_events: Vec::new(),
+            skill_discoveries: HashMap::new(),
+            world_time: 0,
+            last_update: SystemTime::now(),
+            account_nonces: HashMap::new(),
+        };
+        
+        let blocks: Vec<FinalizedBlock> = if epoch_range.0 <= epoch_range.1 {
+            served_blocks.read().await
+                .range(epoch_range.0..=epoch_range.1)
+                .map(|(_, block)| block.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        
+        let mut responses = Vec::new();
+        for batch in blocks.chunks(config.sync_batch_size.max(1) as usize) {
+            Self::fit_sync_response(batch, &current_state, config.max_sync_message_bytes, &mut responses)?;
+        }
+        if responses.is_empty() {
+            responses.push(NetworkMessage::WorldStateResponse { blocks: Vec::new(), current_state });
+        }
+        Ok(responses)
+    }
+    
+    /// Push `blocks` as one response, halving the batch until each piece fits under `max_bytes`.
+    /// A single block that still doesn't fit is replaced by a `SyncBlockTooLarge` marker,
+    /// so the requester stops waiting for it.
+    fn fit_sync_response(
+        blocks: &[FinalizedBlock],
+        current_state: &WorldState,
+        max_bytes: usize,
+        responses: &mut Vec<NetworkMessage>,
+    ) -> Result<()> {
+        let response = NetworkMessage::WorldStateResponse {
+            blocks: blocks.to_vec(),
+            current_state: current_state.clone(),
+        };
+        let size = serde_json::to_vec(&response)?.len();
+        
+        if size <= max_bytes {
+            responses.push(response);
+        } else if blocks.len() == 1 {
+            warn!("⚠️ Block for epoch {} encodes to {} bytes, over the {} byte sync cap; sending a marker instead",
+                blocks[0].epoch, size, max_bytes);
+            responses.push(NetworkMessage::SyncBlockTooLarge { epoch: blocks[0].epoch, encoded_bytes: size });
+        } else {
+            let (front, back) = blocks.split_at(blocks.len() / 2);
+            Self::fit_sync_response(front, current_state, max_bytes, responses)?;
+            Self::fit_sync_response(back, current_state, max_bytes, responses)?;
+        }
+        Ok(())
+    }
+    
     /// Handle world state sync response
     async fn handle_world_state_sync_response(&mut self, blocks: Vec<FinalizedBlock>, _current_state: WorldState) -> Result<()> {
         let mut sync_state = self.sync_state.write().await;
         
-        // Process received blocks
+        if !sync_state.is_syncing {
+            debug!("Ignoring {} unrequested sync blocks", blocks.len());
+            return Ok(());
+        }
+        
+        // Chunks may arrive in any order; stash them by epoch and let `advance` stitch them together
         for block in blocks {
-            sync_state.pending_blocks.insert(block.epoch, block);
+            if block.epoch >= sync_state.sync_current_epoch && block.epoch <= sync_state.sync_target_epoch {
+                sync_state.pending_blocks.insert(block.epoch, block);
+            }
         }
+        sync_state.advance();
         
         // Check if sync is complete
-        if sync_state.pending_blocks.len() >= (sync_state.sync_target_epoch - sync_state.sync_start_epoch + 1) as usize {
-            info!("✅ Blockchain sync completed! Processed {} blocks", sync_state.pending_blocks.len());
-            sync_state.is_syncing = false;
-            sync_state.pending_blocks.clear();
+        if !sync_state.is_syncing {
+            info!("✅ Blockchain sync completed! Received epochs {}-{}", 
+                sync_state.sync_start_epoch, sync_state.sync_target_epoch);
+        } else {
+            debug!("🔄 Sync at epoch {} of {}", sync_state.sync_current_epoch, sync_state.sync_target_epoch);
         }
         
         Ok(())
@@ -669,11 +1211,15 @@ impl NetworkManager {
     /// Handle node heartbeat
     async fn handle_node_heartbeat(&mut self, node_id: PeerId, timestamp: SystemTime, blockchain_stats: BlockchainStats) -> Result<()> {
         let mut peers = self.known_peers.write().await;
+        let latency = SystemTime::now().duration_since(timestamp).ok();
         
         if let Some(peer_info) = peers.get_mut(&node_id) {
             peer_info.last_seen = timestamp;
             peer_info.blockchain_stats = Some(blockchain_stats.clone());
-            peer_info.connection_quality = (peer_info.connection_quality + 0.1).min(1.0);
+            peer_info.score.record_success();
+            if let Some(latency) = latency {
+                peer_info.score.record_latency(latency);
+            }
             
             // Update validator status based on blockchain stats
             if blockchain_stats.total_blocks > 0 {
@@ -682,6 +1228,11 @@ impl NetworkManager {
             }
         } else {
             // Create new peer info from heartbeat
+            let mut score = PeerScore::default();
+            score.record_success();
+            if let Some(latency) = latency {
+                score.record_latency(latency);
+            }
             let peer_info = PeerInfo {
                 peer_id: node_id,
                 multiaddr: "/ip4/0.0.0.0/tcp/0".parse().unwrap(), // Placeholder
@@ -689,7 +1240,7 @@ impl NetworkManager {
                 blockchain_stats: Some(blockchain_stats),
                 is_validator: true,
                 stake_amount: 1000,
-                connection_quality: 0.8,
+                score,
             };
             peers.insert(node_id, peer_info);
         }
@@ -725,8 +1276,7 @@ impl NetworkManager {
         // Send response
         if let Some(swarm) = &mut self.swarm {
             let data = serde_json::to_vec(&response)?;
-            let topic = libp2p::gossipsub::IdentTopic::new("arceon-sync");
-            swarm.behaviour_mut().gossipsub.publish(topic, data)?;
+            Self::publish(swarm, &mut self.traffic, "arceon-sync", data)?;
         }
         
         Ok(())
@@ -746,7 +1296,7 @@ impl NetworkManager {
                     blockchain_stats: None,
                     is_validator: false,
                     stake_amount: 0,
-                    connection_quality: 0.5,
+                    score: PeerScore::default(),
                 };
                 
                 peers.insert(*peer_id, peer_info);
@@ -766,42 +1316,134 @@ impl NetworkManager {
         Ok(())
     }
     
+    /// Gossip topic and wire bytes for a message
+    fn encode_for_gossip(message: &NetworkMessage) -> Result<(&'static str, Vec<u8>)> {
+        let topic = match message {
+            // Game messages
+            NetworkMessage::PlayerJoin { .. } => "arceon-players",
+            NetworkMessage::PlayerLeave { .. } => "arceon-players",
+            NetworkMessage::PlayerMove { .. } => "arceon-players", 
+            NetworkMessage::ChatMessage { .. } => "arceon-chat",
+            NetworkMessage::Say { .. } => "arceon-chat",
+            NetworkMessage::Tell { .. } => "arceon-chat",
+            NetworkMessage::Emote { .. } => "arceon-chat",
+            NetworkMessage::GameStateSync { .. } => "arceon-world",
+            NetworkMessage::PlayerUpdate { .. } => "arceon-players",
+            NetworkMessage::SkillGain { .. } => "arceon-skills",
+            NetworkMessage::NPCSpawn { .. } => "arceon-world",
+            NetworkMessage::NPCAction { .. } => "arceon-world",
+            NetworkMessage::AreaEvent { .. } => "arceon-world",
+            NetworkMessage::SkillEvolution { .. } => "arceon-skills",
+            NetworkMessage::SkillDiscovery { .. } => "arceon-skills",
+            NetworkMessage::CoreEvent { .. } => "arceon-world",
+            
+            // Cross-node sync messages
+            NetworkMessage::ConsensusMessage(_) => "arceon-consensus",
+            NetworkMessage::WorldStateSync { .. } => "arceon-sync",
+            NetworkMessage::WorldStateResponse { .. } => "arceon-sync",
+            NetworkMessage::SyncBlockTooLarge { .. } => "arceon-sync",
+            NetworkMessage::NodeHeartbeat { .. } => "arceon-heartbeat",
+            NetworkMessage::PeerDiscoveryRequest { .. } => "arceon-discovery",
+            NetworkMessage::PeerDiscoveryResponse { .. } => "arceon-discovery",
+        };
+        
+        Ok((topic, serde_json::to_vec(message)?))
+    }
+    
     pub async fn broadcast_message(&mut self, message: NetworkMessage) -> Result<()> {
+        let area_topic = Self::area_of(&message)
+            .filter(|_| self.config.area_topics)
+            .map(|area_id| self.topic_for_area(area_id));
         if let Some(swarm) = &mut self.swarm {
-            let topic = match &message {
-                // Game messages
-                NetworkMessage::PlayerJoin { .. } => "arceon-players",
-                NetworkMessage::PlayerLeave { .. } => "arceon-players",
-                NetworkMessage::PlayerMove { .. } => "arceon-players", 
-                NetworkMessage::ChatMessage { .. } => "arceon-chat",
-                NetworkMessage::Say { .. } => "arceon-chat",
-                NetworkMessage::Emote { .. } => "arceon-chat",
-                NetworkMessage::GameStateSync { .. } => "arceon-world",
-                NetworkMessage::PlayerUpdate { .. } => "arceon-players",
-                NetworkMessage::SkillGain { .. } => "arceon-skills",
-                NetworkMessage::NPCSpawn { .. } => "arceon-world",
-                NetworkMessage::NPCAction { .. } => "arceon-world",
-                NetworkMessage::AreaEvent { .. } => "arceon-world",
-                NetworkMessage::SkillEvolution { .. } => "arceon-skills",
-                NetworkMessage::SkillDiscovery { .. } => "arceon-skills",
-                
-                // Cross-node sync messages
-                NetworkMessage::ConsensusMessage(_) => "arceon-consensus",
-                NetworkMessage::WorldStateSync { .. } => "arceon-sync",
-                NetworkMessage::WorldStateResponse { .. } => "arceon-sync",
-                NetworkMessage::NodeHeartbeat { .. } => "arceon-heartbeat",
-                NetworkMessage::PeerDiscoveryRequest { .. } => "arceon-discovery",
-                NetworkMessage::PeerDiscoveryResponse { .. } => "arceon-discovery",
-            };
-            
-            let data = serde_json::to_vec(&message)?;
-            let topic = libp2p::gossipsub::IdentTopic::new(topic);
-            
-            swarm.behaviour_mut().gossipsub.publish(topic, data)?;
+            let (topic, data) = Self::encode_for_gossip(&message)?;
+            match area_topic {
+                Some(area_topic) => match Self::publish(swarm, &mut self.traffic, &area_topic, data) {
+                    // Nobody follows that area, so there is no one to tell
+                    Err(e) if matches!(e.downcast_ref::<libp2p::gossipsub::PublishError>(), Some(libp2p::gossipsub::PublishError::InsufficientPeers)) => {
+                        debug!("No peers follow {}; not publishing", area_topic);
+                    }
+                    result => result?,
+                },
+                None => Self::publish(swarm, &mut self.traffic, topic, data)?,
+            }
+        }
+        Ok(())
+    }
+    
+    /// Area a message is local to, for messages that only matter to nodes following it
+    fn area_of(message: &NetworkMessage) -> Option<&str> {
+        match message {
+            NetworkMessage::ChatMessage { area_id, .. }
+            | NetworkMessage::Say { area_id, .. }
+            | NetworkMessage::Emote { area_id, .. }
+            | NetworkMessage::NPCSpawn { area_id, .. }
+            | NetworkMessage::NPCAction { area_id, .. }
+            | NetworkMessage::AreaEvent { area_id, .. } => Some(area_id),
+            NetworkMessage::CoreEvent { scope, data, .. } if scope == "area" => data.get("area_id").and_then(|a| a.as_str()),
+            _ => None,
+        }
+    }
+    
+    /// Gossip topic carrying `area_id`'s local traffic: `arceon-area-<area id>`, or
+    /// `arceon-area-shard-<n>` when areas are grouped into `area_topic_shards` shards
+    pub fn topic_for_area(&self, area_id: &str) -> String {
+        match self.config.area_topic_shards {
+            0 => format!("arceon-area-{}", area_id),
+            shards => {
+                // FNV-1a, so every node puts an area in the same shard whatever it was built with
+                let hash = area_id.bytes()
+                    .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+                format!("arceon-area-shard-{}", hash % shards as u64)
+            }
+        }
+    }
+    
+    fn subscribed_area_topics(&self) -> HashSet<String> {
+        self.subscribed_areas.iter().map(|area_id| self.topic_for_area(area_id)).collect()
+    }
+    
+    /// Follow an area's local traffic. Takes effect when the network starts if it has not yet.
+    pub fn subscribe_area(&mut self, area_id: &str) -> Result<()> {
+        if !self.subscribed_areas.insert(area_id.to_string()) {
+            return Ok(());
+        }
+        let topic = self.topic_for_area(area_id);
+        if let Some(swarm) = &mut self.swarm {
+            swarm.behaviour_mut().gossipsub.subscribe(&libp2p::gossipsub::IdentTopic::new(topic))?;
+        }
+        Ok(())
+    }
+    
+    /// Stop following an area; its topic is left once no other followed area shares it
+    pub fn unsubscribe_area(&mut self, area_id: &str) -> Result<()> {
+        if !self.subscribed_areas.remove(area_id) {
+            return Ok(());
+        }
+        let topic = self.topic_for_area(area_id);
+        if self.subscribed_area_topics().contains(&topic) {
+            return Ok(());
+        }
+        if let Some(swarm) = &mut self.swarm {
+            swarm.behaviour_mut().gossipsub.unsubscribe(&libp2p::gossipsub::IdentTopic::new(topic))?;
         }
         Ok(())
     }
     
+    /// Publish `data` on `topic`, counting it once gossipsub accepts it
+    fn publish(
+        swarm: &mut Swarm<ArceonBehaviour>,
+        traffic: &mut BTreeMap<String, TopicTraffic>,
+        topic: &str,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let bytes = data.len() as u64;
+        swarm.behaviour_mut().gossipsub.publish(libp2p::gossipsub::IdentTopic::new(topic), data)?;
+        let counters = traffic.entry(topic.to_string()).or_default();
+        counters.messages_sent += 1;
+        counters.bytes_sent += bytes;
+        Ok(())
+    }
+    
     /// Request world state sync from the network
     pub async fn request_world_state_sync(&mut self, from_epoch: u64, to_epoch: u64) -> Result<()> {
         let sync_request = NetworkMessage::WorldStateSync {
@@ -813,10 +1455,7 @@ impl NetworkManager {
         
         // Update sync state
         let mut sync_state = self.sync_state.write().await;
-        sync_state.is_syncing = true;
-        sync_state.sync_start_epoch = from_epoch;
-        sync_state.sync_target_epoch = to_epoch;
-        sync_state.last_sync_request = SystemTime::now();
+        sync_state.begin(from_epoch, to_epoch);
         
         info!("🔄 Requested world state sync for epochs {} to {}", from_epoch, to_epoch);
         Ok(())
@@ -824,20 +1463,38 @@ impl NetworkManager {
     
     /// Broadcast node heartbeat
     pub async fn broadcast_heartbeat(&mut self, blockchain_stats: BlockchainStats) -> Result<()> {
-        let heartbeat = NetworkMessage::NodeHeartbeat {
-            node_id: self.local_peer_id.unwrap().to_string(),
-            timestamp: SystemTime::now(),
-            blockchain_stats,
-        };
-        
+        let heartbeat = Self::heartbeat_message(self.local_peer_id.unwrap(), blockchain_stats);
         self.broadcast_message(heartbeat).await
     }
+
+    /// Set the blockchain stats advertised in future heartbeats
+    pub async fn update_blockchain_stats(&self, blockchain_stats: BlockchainStats) {
+        *self.blockchain_stats.write().await = blockchain_stats;
+    }
+    
+    /// Make finalized blocks available to peers that request a sync
+    pub async fn serve_blocks(&self, blocks: impl IntoIterator<Item = FinalizedBlock>) {
+        let mut served = self.served_blocks.write().await;
+        for block in blocks {
+            served.insert(block.epoch, block);
+        }
+    }
     
     /// Get current synchronization state
     pub async fn get_sync_state(&self) -> SyncState {
         self.sync_state.read().await.clone()
     }
     
+    /// Take the blocks synced so far, in epoch order, for applying to the local chain
+    pub async fn take_synced_blocks(&self) -> Vec<FinalizedBlock> {
+        self.sync_state.write().await.synced_blocks.drain(..).collect()
+    }
+    
+    /// Peers currently in the Kademlia routing table
+    pub async fn get_dht_peers(&self) -> Vec<PeerId> {
+        self.dht_peers.read().await.iter().cloned().collect()
+    }
+    
     /// Get list of known peers
     pub async fn get_known_peers(&self) -> Vec<PeerInfo> {
         self.known_peers.read().await.values().cloned().collect()
@@ -869,27 +1526,58 @@ impl NetworkManager {
         }
     }
     
+    /// Record whether a player is connected through this node, so private messages
+    /// addressed to them are shown here
+    pub async fn set_player_hosted(&self, player_id: &str, hosted: bool) {
+        let mut hosted_players = self.hosted_players.write().await;
+        if hosted {
+            hosted_players.insert(player_id.to_string());
+        } else {
+            hosted_players.remove(player_id);
+        }
+    }
+    
+    /// Last known skills and inventory of a player hosted elsewhere
+    pub async fn get_player_snapshot(&self, player_id: &str) -> Option<PlayerSnapshot> {
+        self.player_roster.read().await.get(player_id).cloned()
+    }
+    
     /// Get network statistics
     pub async fn get_network_stats(&self) -> NetworkStats {
         let peers = self.known_peers.read().await;
         let sync_state = self.sync_state.read().await;
         let connected_peers = self.get_connected_peers();
+        let total = self.traffic.values().fold(TopicTraffic::default(), |mut total, topic| {
+            total.messages_sent += topic.messages_sent;
+            total.bytes_sent += topic.bytes_sent;
+            total.messages_received += topic.messages_received;
+            total.bytes_received += topic.bytes_received;
+            total
+        });
         
         NetworkStats {
             total_known_peers: peers.len(),
             connected_peers: connected_peers.len(),
             validator_peers: peers.values().filter(|p| p.is_validator).count(),
             is_syncing: sync_state.is_syncing,
-            sync_progress: if sync_state.is_syncing {
-                let total = sync_state.sync_target_epoch - sync_state.sync_start_epoch + 1;
-                let current = sync_state.sync_current_epoch - sync_state.sync_start_epoch;
-                (current as f64 / total as f64 * 100.0).min(100.0)
-            } else {
-                100.0
-            },
+            sync_progress: sync_state.progress(),
             last_heartbeat: *self.last_heartbeat.read().await,
+            messages_sent: total.messages_sent,
+            bytes_sent: total.bytes_sent,
+            messages_received: total.messages_received,
+            bytes_received: total.bytes_received,
+            topic_traffic: self.traffic.clone(),
+            duplicate_messages: self.duplicate_messages,
+            gossip_queue_dropped: self.message_sender.as_ref().map_or(0, GossipSender::dropped),
+            consensus_queue_saturated: self.consensus_sender.saturated(),
         }
     }
+    
+    /// Zero the gossip traffic counters
+    pub fn reset_traffic_stats(&mut self) {
+        self.traffic.clear();
+        self.duplicate_messages = 0;
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -900,4 +1588,682 @@ pub struct NetworkStats {
     pub is_syncing: bool,
     pub sync_progress: f64,
     pub last_heartbeat: SystemTime,
+    pub messages_sent: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+    pub topic_traffic: BTreeMap<String, TopicTraffic>,
+    pub duplicate_messages: u64,
+    pub gossip_queue_dropped: u64, // outbound gossip dropped by a full queue, since start
+    pub consensus_queue_saturated: u64, // consensus messages dropped after waiting for room, since start
+}
+
+/// Gossip messages and payload bytes on one topic
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TopicTraffic {
+    pub messages_sent: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arceon_core::entities::being::{Being, Race};
+    use arceon_blockchain::{BlockchainManager, WorldStateProposal};
+    use uuid::Uuid;
+
+    fn test_consensus_channel() -> (ConsensusSender<ConsensusMessage>, tokio::sync::mpsc::Receiver<ConsensusMessage>) {
+        channel::consensus_channel(channel::DEFAULT_CHANNEL_CAPACITY, channel::DEFAULT_SEND_TIMEOUT)
+    }
+
+    async fn test_manager() -> NetworkManager {
+        let config = arceon_core::Config::default().network;
+        let (consensus_sender, _) = test_consensus_channel();
+        NetworkManager::new(&config, false, consensus_sender).await.unwrap()
+    }
+
+    fn test_block(epoch: u64) -> FinalizedBlock {
+        FinalizedBlock {
+            block_hash: [epoch as u8; 32],
+            epoch,
+            round: 0,
+            proposer: Uuid::nil(),
+            timestamp: SystemTime::UNIX_EPOCH,
+            world_changes: Vec::new(),
+            validator_signatures: HashMap::new(),
+            merkle_root: String::new(),
+            previous_hash: None,
+            transaction_nonces: Vec::new(),
+        }
+    }
+
+    fn empty_world_state() -> WorldState {
+        WorldState {
+            current_epoch: 0,
+            players: HashMap::new(),
+            areas: HashMap::new(),
+            npcs: HashMap::new(),
+            global_events: Vec::new(),
+            skill_discoveries: HashMap::new(),
+            world_time: 0,
+            last_update: SystemTime::UNIX_EPOCH,
+            account_nonces: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_player_update_applies_skill_change_to_roster() {
+        let mut peer = test_manager().await;
+
+        let mut being = Being::new_player("Aria".to_string(), Race::Elf);
+        let before = PlayerSnapshot::of(&being);
+        let baseline = PlayerSnapshot::default().diff(&before).unwrap();
+        peer.handle_network_message(NetworkMessage::PlayerUpdate {
+            player_id: "aria".to_string(),
+            being_data: baseline.encode().unwrap(),
+        }, None).await.unwrap();
+
+        being.skills.skills.get_mut("Health").unwrap().level = 12.5;
+        let delta = before.diff(&PlayerSnapshot::of(&being)).unwrap();
+        assert_eq!(delta.changed_skills.len(), 1);
+        peer.handle_network_message(NetworkMessage::PlayerUpdate {
+            player_id: "aria".to_string(),
+            being_data: delta.encode().unwrap(),
+        }, None).await.unwrap();
+
+        let mirrored = peer.get_player_snapshot("aria").await.unwrap();
+        assert_eq!(mirrored, PlayerSnapshot::of(&being));
+        assert_eq!(mirrored.skills["Health"].level, 12.5);
+    }
+
+    #[tokio::test]
+    async fn test_sync_progress_climbs_as_contiguous_blocks_arrive() {
+        let mut peer = test_manager().await;
+        peer.sync_state.write().await.begin(10, 19);
+
+        let mut last_progress = peer.get_network_stats().await.sync_progress;
+        assert_eq!(last_progress, 0.0);
+
+        // Blocks beyond a gap are held back and do not count yet
+        for range in [15..18, 10..13, 13..15, 18..20] {
+            peer.handle_network_message(NetworkMessage::WorldStateResponse {
+                blocks: range.clone().map(test_block).collect(),
+                current_state: empty_world_state(),
+            }, None).await.unwrap();
+
+            let progress = peer.get_network_stats().await.sync_progress;
+            assert!(progress >= last_progress, "{:?}: {} < {}", range, progress, last_progress);
+            last_progress = progress;
+            if range.start == 15 {
+                assert_eq!(progress, 0.0);
+            }
+            if range.start == 10 {
+                assert_eq!(progress, 30.0);
+            }
+        }
+
+        assert!(!peer.is_syncing().await);
+        assert_eq!(last_progress, 100.0);
+        let synced: Vec<u64> = peer.take_synced_blocks().await.iter().map(|b| b.epoch).collect();
+        assert_eq!(synced, (10..20).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_proposal_reaches_peer_blockchain() {
+        let config = arceon_core::Config::default();
+        let mut blockchain = BlockchainManager::new(&config.blockchain).await.unwrap();
+        blockchain.start(Uuid::new_v4(), false, 0).await.unwrap();
+        let mut receiving = NetworkManager::new(&config.network, false, blockchain.consensus_sender().unwrap()).await.unwrap();
+        receiving.enable_blockchain();
+
+        let proposal = WorldStateProposal {
+            proposal_id: Uuid::new_v4(),
+            proposer: Uuid::new_v4(),
+            epoch: 1,
+            round: 0,
+            timestamp: SystemTime::now(),
+            world_changes: Vec::new(),
+            previous_block_hash: None,
+            merkle_root: String::new(),
+            signature: None,
+        };
+        let broadcast = NetworkMessage::ConsensusMessage(ConsensusMessage::Proposal(proposal.clone()));
+        let (topic, data) = NetworkManager::encode_for_gossip(&broadcast).unwrap();
+        assert_eq!(topic, "arceon-consensus");
+
+        receiving.handle_gossip_data(&data, None).await.unwrap();
+
+        let received = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                if let Some(received) = blockchain.get_pending_proposal(proposal.proposal_id).await {
+                    break received;
+                }
+                tokio::task::yield_now().await;
+            }
+        }).await.expect("proposal never reached the blockchain manager");
+        assert_eq!(received.proposer, proposal.proposer);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_forwards_typed_consensus_messages() {
+        use arceon_core::NetworkBridge;
+        use bridge::ConsensusBridge;
+
+        let config = arceon_core::Config::default().network;
+        let (consensus_sender, mut consensus_receiver) = test_consensus_channel();
+        let mut manager = NetworkManager::new(&config, false, consensus_sender).await.unwrap();
+        let mut bridge = manager.bridge().unwrap();
+
+        let validator = Uuid::new_v4();
+        bridge.send_consensus(ConsensusMessage::ValidatorLeave { node_id: validator, timestamp: SystemTime::UNIX_EPOCH }).unwrap();
+
+        // The local blockchain manager gets it as-is, and it is queued for gossip
+        assert!(matches!(consensus_receiver.try_recv(), Ok(ConsensusMessage::ValidatorLeave { node_id, .. }) if node_id == validator));
+        let outbound = manager.message_receiver.as_mut().unwrap();
+        assert!(matches!(outbound.try_recv(), Ok(NetworkMessage::ConsensusMessage(ConsensusMessage::ValidatorLeave { .. }))));
+
+        // Core's untyped events still go out, wrapped
+        bridge.broadcast_message(serde_json::json!({"type": "Say", "scope": "area", "data": {"message": "hi"}})).unwrap();
+        let Ok(NetworkMessage::CoreEvent { event_type, scope, data }) = outbound.try_recv() else {
+            panic!("core event was not queued");
+        };
+        assert_eq!((event_type.as_str(), scope.as_str()), ("Say", "area"));
+        assert_eq!(data["message"], "hi");
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_carries_blockchain_stats_to_peer() {
+        let mut sender = test_manager().await;
+        let mut receiver = test_manager().await;
+        let sender_id = PeerId::random();
+        sender.local_peer_id = Some(sender_id);
+        sender.heartbeat_interval = Duration::from_millis(20);
+        sender.update_blockchain_stats(BlockchainStats {
+            total_blocks: 42,
+            last_finalized_epoch: 41,
+            ..BlockchainStats::default()
+        }).await;
+
+        sender.start_heartbeat_loop().await.unwrap();
+        let heartbeat = tokio::time::timeout(Duration::from_secs(2), sender.message_receiver.as_mut().unwrap().recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(heartbeat, NetworkMessage::NodeHeartbeat { .. }));
+
+        // What the event loop would publish, as the peer receives it
+        let (topic, data) = NetworkManager::encode_for_gossip(&heartbeat).unwrap();
+        assert_eq!(topic, "arceon-heartbeat");
+        receiver.handle_gossip_data(&data, None).await.unwrap();
+
+        let peers = receiver.get_known_peers().await;
+        let recorded = peers.iter().find(|peer| peer.peer_id == sender_id).unwrap();
+        assert_eq!(recorded.blockchain_stats.as_ref().unwrap().last_finalized_epoch, 41);
+    }
+
+    /// Start a node listening on an OS-assigned port and return its loopback address
+    async fn listening_node(bootstrap_nodes: Vec<String>) -> (NetworkManager, Multiaddr) {
+        let mut config = arceon_core::Config::default().network;
+        config.bootstrap_nodes = bootstrap_nodes;
+        let (consensus_sender, _) = test_consensus_channel();
+        start_listening(NetworkManager::new(&config, false, consensus_sender).await.unwrap()).await
+    }
+
+    /// Start `node` on an OS-assigned port and return its loopback address
+    async fn start_listening(mut node: NetworkManager) -> (NetworkManager, Multiaddr) {
+        node.config.listen_port = 0;
+        node.start().await.unwrap();
+
+        let swarm = node.swarm.as_mut().unwrap();
+        let address = loop {
+            if let SwarmEvent::NewListenAddr { address, .. } = swarm.select_next_some().await {
+                if address.to_string().starts_with("/ip4/127.0.0.1/") {
+                    break address;
+                }
+            }
+        };
+        let peer_id = node.get_peer_id().unwrap();
+        (node, address.with(libp2p::multiaddr::Protocol::P2p(peer_id)))
+    }
+
+    async fn wait_for_dht_peer(dht_peers: &Arc<RwLock<HashSet<PeerId>>>, peer_id: PeerId) {
+        tokio::time::timeout(Duration::from_secs(20), async {
+            while !dht_peers.read().await.contains(&peer_id) {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        }).await.unwrap_or_else(|_| panic!("{} never entered the routing table", peer_id));
+    }
+
+    #[tokio::test]
+    async fn test_dht_discovers_peers_through_bootstrap_node() {
+        let (mut hub, hub_addr) = listening_node(Vec::new()).await;
+        let hub_id = hub.get_peer_id().unwrap();
+        let hub_table = hub.dht_peers.clone();
+        let hub_loop = tokio::spawn(async move { hub.run_event_loop().await });
+
+        let (mut first, _) = listening_node(vec![hub_addr.to_string()]).await;
+        let first_id = first.get_peer_id().unwrap();
+        let first_table = first.dht_peers.clone();
+        let first_loop = tokio::spawn(async move { first.run_event_loop().await });
+        wait_for_dht_peer(&first_table, hub_id).await;
+        wait_for_dht_peer(&hub_table, first_id).await;
+
+        // The second node only knows the hub, but learns about the first via the DHT
+        let (mut second, _) = listening_node(vec![hub_addr.to_string()]).await;
+        let second_id = second.get_peer_id().unwrap();
+        let second_table = second.dht_peers.clone();
+        let second_loop = tokio::spawn(async move { second.run_event_loop().await });
+        wait_for_dht_peer(&second_table, hub_id).await;
+        wait_for_dht_peer(&second_table, first_id).await;
+        wait_for_dht_peer(&first_table, second_id).await;
+
+        hub_loop.abort();
+        first_loop.abort();
+        second_loop.abort();
+    }
+
+    #[tokio::test]
+    async fn test_burst_from_one_peer_is_cut_off_at_the_limit() {
+        let mut config = arceon_core::Config::default().network;
+        config.peer_message_rate = 0.001;
+        config.peer_message_burst = 5;
+        let (consensus_sender, _) = test_consensus_channel();
+        let mut node = NetworkManager::new(&config, false, consensus_sender).await.unwrap();
+
+        let flooder = PeerId::random();
+        node.known_peers.write().await.insert(flooder, PeerInfo {
+            peer_id: flooder,
+            multiaddr: "/ip4/10.0.0.2/tcp/7777".parse().unwrap(),
+            last_seen: SystemTime::now(),
+            blockchain_stats: None,
+            is_validator: false,
+            stake_amount: 0,
+            score: PeerScore::default(),
+        });
+
+        let snapshot = PlayerSnapshot::of(&Being::new_player("Aria".to_string(), Race::Elf));
+        let being_data = PlayerSnapshot::default().diff(&snapshot).unwrap().encode().unwrap();
+        for i in 0..20 {
+            node.handle_network_message(NetworkMessage::PlayerUpdate {
+                player_id: format!("player-{}", i),
+                being_data: being_data.clone(),
+            }, Some(flooder)).await.unwrap();
+        }
+
+        // Only the burst got through
+        for i in 0..20 {
+            let mirrored = node.get_player_snapshot(&format!("player-{}", i)).await.is_some();
+            assert_eq!(mirrored, i < 5, "player-{}", i);
+        }
+        let quality = node.known_peers.read().await[&flooder].connection_quality();
+        assert!(quality < 0.5, "quality {}", quality);
+
+        // Another peer is unaffected
+        node.handle_network_message(NetworkMessage::PlayerUpdate {
+            player_id: "bystander".to_string(),
+            being_data,
+        }, Some(PeerId::random())).await.unwrap();
+        assert!(node.get_player_snapshot("bystander").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_responsive_peer_outscores_a_flaky_one() {
+        let mut node = test_manager().await;
+        let (steady, flaky) = (PeerId::random(), PeerId::random());
+
+        for round in 0..10 {
+            node.handle_node_heartbeat(steady, SystemTime::now(), BlockchainStats::default()).await.unwrap();
+
+            // The flaky peer's heartbeats arrive late, and its connection keeps dropping
+            let sent = SystemTime::now() - Duration::from_secs(2);
+            node.handle_node_heartbeat(flaky, sent, BlockchainStats::default()).await.unwrap();
+            if round % 2 == 0 {
+                node.known_peers.write().await.get_mut(&flaky).unwrap().score.record_failure();
+            }
+        }
+
+        let peers = node.known_peers.read().await;
+        let (steady, flaky) = (peers[&steady].connection_quality(), peers[&flaky].connection_quality());
+        assert!(steady > flaky, "steady {} <= flaky {}", steady, flaky);
+        assert!((0.0..=1.0).contains(&steady) && (0.0..=1.0).contains(&flaky));
+
+        // A peer that goes quiet fades towards zero
+        let mut silent = peers.values().next().unwrap().clone();
+        silent.last_seen = SystemTime::now() - peer_score::STALE_AFTER;
+        assert_eq!(silent.connection_quality(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_targets_the_tallest_chain() {
+        let now = SystemTime::now();
+        let peer_at = |epoch: u64, last_seen: SystemTime| PeerInfo {
+            peer_id: PeerId::random(),
+            multiaddr: "/ip4/10.0.0.2/tcp/7777".parse().unwrap(),
+            last_seen,
+            blockchain_stats: Some(BlockchainStats { last_finalized_epoch: epoch, ..BlockchainStats::default() }),
+            is_validator: true,
+            stake_amount: 0,
+            score: PeerScore::default(),
+        };
+        let mut peers: HashMap<PeerId, PeerInfo> = [peer_at(12, now), peer_at(40, now), peer_at(0, now), peer_at(25, now)]
+            .into_iter()
+            .map(|peer| (peer.peer_id, peer))
+            .collect();
+        let tallest = peers.values().find(|peer| peer.blockchain_stats.as_ref().unwrap().last_finalized_epoch == 40).unwrap().peer_id;
+
+        // An equally tall but flakier peer loses the tie
+        let mut flaky = peer_at(40, now);
+        flaky.score.record_failure();
+        peers.insert(flaky.peer_id, flaky);
+
+        let mut sync_state = test_manager().await.get_sync_state().await;
+        NetworkManager::choose_sync_target(&mut sync_state, &peers, now);
+        assert!(sync_state.is_syncing);
+        assert_eq!(sync_state.sync_target_peer, Some(tallest));
+        assert_eq!(sync_state.sync_target_epoch, 40);
+
+        // Halfway through, the tallest peer goes quiet; the sync moves on without starting over
+        sync_state.sync_current_epoch = 20;
+        peers.get_mut(&tallest).unwrap().last_seen = now - peer_score::STALE_AFTER;
+        NetworkManager::choose_sync_target(&mut sync_state, &peers, now);
+        let next = sync_state.sync_target_peer.unwrap();
+        assert_ne!(next, tallest);
+        assert_eq!(peers[&next].blockchain_stats.as_ref().unwrap().last_finalized_epoch, 40);
+        assert_eq!(sync_state.sync_current_epoch, 20);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_discovery_response_is_handled_once() {
+        let mut node = test_manager().await;
+        let discovered = PeerId::random();
+        let response = NetworkMessage::PeerDiscoveryResponse {
+            responding_peer: PeerId::random().to_string(),
+            peer_list: vec![(discovered.to_string(), "/ip4/10.0.0.3/tcp/7777".to_string())],
+        };
+
+        node.handle_network_message(response.clone(), None).await.unwrap();
+        let first_seen = node.known_peers.read().await[&discovered].last_seen;
+
+        // The same response relayed by a second peer
+        node.handle_network_message(response, Some(PeerId::random())).await.unwrap();
+        assert_eq!(node.get_peer_count().await, 1);
+        assert_eq!(node.known_peers.read().await[&discovered].last_seen, first_seen);
+        assert_eq!(node.get_network_stats().await.duplicate_messages, 1);
+    }
+
+    #[tokio::test]
+    async fn test_dialing_an_unreachable_peer_records_the_failed_attempt() {
+        let unreachable_id = PeerId::random();
+        let unreachable: Multiaddr = format!("/ip4/127.0.0.1/tcp/1/p2p/{}", unreachable_id).parse().unwrap();
+
+        let mut stopped = test_manager().await;
+        assert!(stopped.dial_peer(unreachable.clone()).await.is_err());
+        assert_eq!(stopped.get_peer_count().await, 0);
+
+        let (mut node, _) = listening_node(Vec::new()).await;
+        assert!(node.dial_peer("/ip4/127.0.0.1/tcp/1".parse().unwrap()).await.is_err());
+        node.dial_peer(unreachable.clone()).await.unwrap();
+        let recorded = node.known_peers.read().await[&unreachable_id].clone();
+        assert_eq!(recorded.multiaddr, unreachable);
+        assert_eq!(recorded.connection_quality(), 0.5);
+
+        // The refused connection comes back through the event loop and marks the peer down
+        let error = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let event = node.swarm.as_mut().unwrap().select_next_some().await;
+                if matches!(event, SwarmEvent::OutgoingConnectionError { peer_id: Some(peer_id), .. } if peer_id == unreachable_id) {
+                    break event;
+                }
+            }
+        }).await.expect("dial never failed");
+        node.handle_swarm_event(error).await.unwrap();
+        assert!(node.known_peers.read().await[&unreachable_id].connection_quality() < 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_broadcasts_are_counted_per_topic() {
+        let (mut sender, sender_addr) = listening_node(Vec::new()).await;
+        let (mut receiver, _) = listening_node(vec![sender_addr.to_string()]).await;
+        let receiver_id = receiver.get_peer_id().unwrap();
+
+        // Wait until the sender knows the receiver is subscribed to chat
+        tokio::time::timeout(Duration::from_secs(20), async {
+            loop {
+                let subscribed = sender.swarm.as_ref().unwrap().behaviour().gossipsub.all_peers()
+                    .any(|(peer_id, topics)| *peer_id == receiver_id && topics.iter().any(|topic| topic.as_str() == "arceon-chat"));
+                if subscribed {
+                    break;
+                }
+                select! {
+                    _ = sender.swarm.as_mut().unwrap().select_next_some() => {}
+                    _ = receiver.swarm.as_mut().unwrap().select_next_some() => {}
+                }
+            }
+        }).await.expect("receiver never subscribed");
+
+        let mut expected_bytes = 0;
+        for i in 0..3 {
+            let say = NetworkMessage::Say { player_id: "aria".to_string(), area_id: "plains".to_string(), message: format!("hello {}", i) };
+            expected_bytes += serde_json::to_vec(&say).unwrap().len() as u64;
+            sender.broadcast_message(say).await.unwrap();
+        }
+
+        let stats = sender.get_network_stats().await;
+        assert_eq!(stats.messages_sent, 3);
+        assert_eq!(stats.bytes_sent, expected_bytes);
+        assert_eq!(stats.topic_traffic["arceon-chat"].messages_sent, 3);
+        assert_eq!(stats.topic_traffic.len(), 1);
+
+        // The receiver counts what arrives
+        tokio::time::timeout(Duration::from_secs(20), async {
+            while receiver.get_network_stats().await.messages_received < 3 {
+                select! {
+                    _ = sender.swarm.as_mut().unwrap().select_next_some() => {}
+                    event = receiver.swarm.as_mut().unwrap().select_next_some() => {
+                        if matches!(event, SwarmEvent::Behaviour(ArceonEvent::Gossipsub(GossipsubEvent::Message { .. }))) {
+                            receiver.handle_swarm_event(event).await.unwrap();
+                        }
+                    }
+                }
+            }
+        }).await.expect("broadcasts never arrived");
+        assert_eq!(receiver.get_network_stats().await.topic_traffic["arceon-chat"].bytes_received, expected_bytes);
+
+        sender.reset_traffic_stats();
+        let stats = sender.get_network_stats().await;
+        assert_eq!((stats.messages_sent, stats.bytes_sent), (0, 0));
+        assert!(stats.topic_traffic.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_area_traffic_only_reaches_nodes_following_the_area() {
+        let mut config = arceon_core::Config::default().network;
+        config.area_topics = true;
+        let (mut sender, sender_addr) = start_listening(
+            NetworkManager::new(&config, false, test_consensus_channel().0).await.unwrap()).await;
+        config.bootstrap_nodes = vec![sender_addr.to_string()];
+        let mut receiver = NetworkManager::new(&config, false, test_consensus_channel().0).await.unwrap();
+        receiver.subscribe_area("area-a").unwrap();
+        let (mut receiver, _) = start_listening(receiver).await;
+        let receiver_id = receiver.get_peer_id().unwrap();
+        let (topic_a, topic_b) = (sender.topic_for_area("area-a"), sender.topic_for_area("area-b"));
+        assert_eq!(topic_a, "arceon-area-area-a");
+
+        tokio::time::timeout(Duration::from_secs(20), async {
+            loop {
+                let subscribed = sender.swarm.as_ref().unwrap().behaviour().gossipsub.all_peers()
+                    .any(|(peer_id, topics)| *peer_id == receiver_id && topics.iter().any(|topic| topic.as_str() == topic_a));
+                if subscribed {
+                    break;
+                }
+                select! {
+                    _ = sender.swarm.as_mut().unwrap().select_next_some() => {}
+                    _ = receiver.swarm.as_mut().unwrap().select_next_some() => {}
+                }
+            }
+        }).await.expect("receiver never subscribed to area A");
+
+        // Region B first: if it were delivered at all it would arrive before region A's
+        for area_id in ["area-b", "area-a"] {
+            sender.broadcast_message(NetworkMessage::AreaEvent {
+                area_id: area_id.to_string(),
+                event_type: "WeatherChange".to_string(),
+                description: "Rain is falling.".to_string(),
+            }).await.unwrap();
+        }
+
+        tokio::time::timeout(Duration::from_secs(20), async {
+            while receiver.get_network_stats().await.messages_received < 1 {
+                select! {
+                    _ = sender.swarm.as_mut().unwrap().select_next_some() => {}
+                    event = receiver.swarm.as_mut().unwrap().select_next_some() => {
+                        if matches!(event, SwarmEvent::Behaviour(ArceonEvent::Gossipsub(GossipsubEvent::Message { .. }))) {
+                            receiver.handle_swarm_event(event).await.unwrap();
+                        }
+                    }
+                }
+            }
+        }).await.expect("area A event never arrived");
+
+        let received = receiver.get_network_stats().await.topic_traffic;
+        assert_eq!(received[&topic_a].messages_received, 1);
+        assert!(!received.contains_key(&topic_b));
+        assert!(!received.contains_key("arceon-world"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_range_larger_than_one_batch_arrives_in_chunks() {
+        let mut server = test_manager().await;
+        server.config.sync_batch_size = 16;
+        server.config.max_sync_message_bytes = 8 * 1024;
+        server.serve_blocks((0..100).map(test_block)).await;
+
+        let responses = NetworkManager::sync_responses(&server.served_blocks, &server.config, (0, 99)).await.unwrap();
+        assert!(responses.len() > 100 / 16, "expected several chunks, got {}", responses.len());
+        for response in &responses {
+            assert!(serde_json::to_vec(response).unwrap().len() <= 8 * 1024);
+        }
+
+        // Deliver the chunks out of order; the requester still ends up with every block
+        let mut requester = test_manager().await;
+        requester.sync_state.write().await.begin(0, 99);
+        for response in responses.into_iter().rev() {
+            requester.handle_network_message(response, None).await.unwrap();
+        }
+
+        assert!(!requester.is_syncing().await);
+        let synced: Vec<u64> = requester.take_synced_blocks().await.iter().map(|b| b.epoch).collect();
+        assert_eq!(synced, (0..100).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_block_mid_range_ends_the_sync_before_it() {
+        let mut server = test_manager().await;
+        server.config.max_sync_message_bytes = 8 * 1024;
+        let mut oversized = test_block(5);
+        oversized.merkle_root = "x".repeat(16 * 1024);
+        server.serve_blocks((0..10).map(|epoch| if epoch == 5 { oversized.clone() } else { test_block(epoch) })).await;
+
+        let responses = NetworkManager::sync_responses(&server.served_blocks, &server.config, (0, 9)).await.unwrap();
+        assert!(responses.iter().any(|response| matches!(response, NetworkMessage::SyncBlockTooLarge { epoch: 5, .. })));
+
+        let mut requester = test_manager().await;
+        requester.sync_state.write().await.begin(0, 9);
+        for response in responses.into_iter().rev() {
+            requester.handle_network_message(response, None).await.unwrap();
+        }
+
+        // Everything before the oversized block syncs, and the sync finishes instead of hanging
+        assert!(!requester.is_syncing().await);
+        let synced: Vec<u64> = requester.take_synced_blocks().await.iter().map(|b| b.epoch).collect();
+        assert_eq!(synced, (0..5).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_stalled_sync_is_requested_again_from_the_first_missing_epoch() {
+        let mut sync_state = SyncState {
+            is_syncing: false,
+            sync_target_peer: None,
+            sync_start_epoch: 0,
+            sync_current_epoch: 0,
+            sync_target_epoch: 0,
+            last_sync_request: SystemTime::UNIX_EPOCH,
+            pending_blocks: HashMap::new(),
+            synced_blocks: VecDeque::new(),
+        };
+        sync_state.begin(0, 9);
+        sync_state.pending_blocks.extend((0..3).map(|epoch| (epoch, test_block(epoch))));
+        sync_state.advance();
+        let requested = sync_state.last_sync_request;
+        let timeout = Duration::from_secs(30);
+
+        assert_eq!(sync_state.retry_range(requested + Duration::from_secs(29), timeout), None);
+        assert_eq!(sync_state.retry_range(requested + timeout, timeout), Some((3, 9)));
+        // The retry counts as the new request
+        assert_eq!(sync_state.retry_range(requested + timeout + Duration::from_secs(1), timeout), None);
+
+        sync_state.pending_blocks.extend((3..10).map(|epoch| (epoch, test_block(epoch))));
+        sync_state.advance();
+        assert_eq!(sync_state.retry_range(requested + timeout * 3, timeout), None);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_announces_the_validator_and_stops_the_loops() {
+        let config = arceon_core::Config::default().network;
+        let (consensus_sender, mut consensus_receiver) = test_consensus_channel();
+        let mut manager = NetworkManager::new(&config, true, consensus_sender).await.unwrap();
+        manager.enable_blockchain();
+        let (mut manager, _) = start_listening(manager).await;
+        assert_eq!(manager.background_tasks.len(), 3);
+        let stop = manager.shutdown.subscribe();
+
+        let validator = Uuid::new_v4();
+        tokio::time::timeout(Duration::from_secs(5), manager.shutdown(Some(validator))).await
+            .expect("shutdown should not hang").unwrap();
+
+        assert!(matches!(consensus_receiver.try_recv(), Ok(ConsensusMessage::ValidatorLeave { node_id, .. }) if node_id == validator));
+        assert!(*stop.borrow());
+        assert!(manager.background_tasks.is_empty());
+        // The event loop sees the signal and returns instead of running forever
+        tokio::time::timeout(Duration::from_secs(5), manager.run_event_loop()).await
+            .expect("event loop should stop after shutdown").unwrap();
+    }
+
+
+    #[tokio::test]
+    async fn test_full_queues_drop_by_policy() {
+        use arceon_core::NetworkBridge;
+
+        let mut config = arceon_core::Config::default().network;
+        config.gossip_queue_capacity = 4;
+        let (consensus_sender, mut consensus_receiver) = channel::consensus_channel(2, Duration::from_millis(20));
+        let mut manager = NetworkManager::new(&config, false, consensus_sender).await.unwrap();
+        let mut bridge = manager.bridge().unwrap();
+
+        // Gossip keeps the newest messages and makes room by dropping the oldest
+        for n in 0..10 {
+            bridge.broadcast_message(serde_json::json!({"type": "Say", "scope": "area", "data": {"n": n}})).unwrap();
+        }
+        let outbound = manager.message_receiver.as_mut().unwrap();
+        assert_eq!(outbound.len(), 4);
+        let Ok(NetworkMessage::CoreEvent { data, .. }) = outbound.try_recv() else {
+            panic!("core event was not queued");
+        };
+        assert_eq!(data["n"], 6);
+
+        // Consensus waits for room, and only gives a message up once the wait runs out
+        let leave = || ConsensusMessage::ValidatorLeave { node_id: Uuid::nil(), timestamp: SystemTime::UNIX_EPOCH };
+        manager.consensus_sender.send(leave()).await.unwrap();
+        manager.consensus_sender.send(leave()).await.unwrap();
+        assert!(manager.consensus_sender.send(leave()).await.is_err());
+
+        let stats = manager.get_network_stats().await;
+        assert_eq!(stats.gossip_queue_dropped, 6);
+        assert_eq!(stats.consensus_queue_saturated, 1);
+        assert!(consensus_receiver.try_recv().is_ok() && consensus_receiver.try_recv().is_ok());
+        assert!(consensus_receiver.try_recv().is_err());
+    }
+
 }
@@ -0,0 +1,89 @@
+use std::time::{Duration, SystemTime};
+
+/// Weight of the newest outcome in the reliability average
+const RELIABILITY_SMOOTHING: f64 = 0.2;
+
+/// Weight of the newest sample in the latency average
+const LATENCY_SMOOTHING: f64 = 0.3;
+
+/// Latency that scores 0.5; faster peers score higher, slower ones lower
+const REFERENCE_LATENCY_MS: f64 = 250.0;
+
+/// How much reliability counts against latency in a fresh peer's score
+const RELIABILITY_WEIGHT: f64 = 0.7;
+
+/// A peer heard from this recently counts as fully fresh (two heartbeat intervals)
+pub const FRESH_FOR: Duration = Duration::from_secs(60);
+
+/// A peer not heard from for this long scores zero and is dropped as stale
+pub const STALE_AFTER: Duration = Duration::from_secs(300);
+
+/// Connection quality of one peer, built from what we have seen of it.
+///
+/// Three signals go in:
+///
+/// - reliability, an exponential moving average of interaction outcomes, where a
+///   heartbeat, identify or connection counts as 1 and a failed dial, dropped
+///   connection or rate-limited message counts as 0
+/// - latency, a moving average of how long heartbeats take to arrive, mapped to
+///   `REFERENCE_LATENCY_MS / (REFERENCE_LATENCY_MS + latency)`
+/// - freshness, 1 while the peer was heard from within `FRESH_FOR`, falling
+///   linearly to 0 at `STALE_AFTER`
+///
+/// The score is `freshness × (0.7 × reliability + 0.3 × latency)`, always in [0, 1].
+/// Freshness scales the rest because a peer we have not heard from lately is only
+/// worth as much as our confidence it is still there. A peer we know nothing about
+/// yet scores 0.5.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerScore {
+    reliability: f64,
+    latency_ms: Option<f64>,
+}
+
+impl Default for PeerScore {
+    fn default() -> Self {
+        Self { reliability: 0.5, latency_ms: None }
+    }
+}
+
+impl PeerScore {
+    /// The peer did what we asked or sent what it should
+    pub fn record_success(&mut self) {
+        self.record_outcome(1.0);
+    }
+
+    /// The peer failed us: a dial or connection error, or flooding
+    pub fn record_failure(&mut self) {
+        self.record_outcome(0.0);
+    }
+
+    fn record_outcome(&mut self, outcome: f64) {
+        self.reliability += RELIABILITY_SMOOTHING * (outcome - self.reliability);
+    }
+
+    /// A message from the peer took `latency` to reach us
+    pub fn record_latency(&mut self, latency: Duration) {
+        let sample = latency.as_secs_f64() * 1000.0;
+        self.latency_ms = Some(match self.latency_ms {
+            Some(average) => average + LATENCY_SMOOTHING * (sample - average),
+            None => sample,
+        });
+    }
+
+    /// Score in [0, 1] for a peer last heard from at `last_seen`
+    pub fn score(&self, last_seen: SystemTime, now: SystemTime) -> f64 {
+        let latency = self.latency_ms
+            .map_or(0.5, |latency_ms| REFERENCE_LATENCY_MS / (REFERENCE_LATENCY_MS + latency_ms));
+        let quality = RELIABILITY_WEIGHT * self.reliability + (1.0 - RELIABILITY_WEIGHT) * latency;
+        (Self::freshness(last_seen, now) * quality).clamp(0.0, 1.0)
+    }
+
+    fn freshness(last_seen: SystemTime, now: SystemTime) -> f64 {
+        let silent = now.duration_since(last_seen).unwrap_or_default();
+        if silent <= FRESH_FOR {
+            return 1.0;
+        }
+        let fading = (silent - FRESH_FOR).as_secs_f64() / (STALE_AFTER - FRESH_FOR).as_secs_f64();
+        (1.0 - fading).max(0.0)
+    }
+}
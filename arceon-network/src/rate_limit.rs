@@ -0,0 +1,49 @@
+use libp2p::PeerId;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Per-peer token buckets for inbound gossip.
+///
+/// Each peer starts with `burst` tokens and earns `rate` more per second, up to
+/// `burst`. A message costs one token; a peer with none left is over its limit.
+#[derive(Debug, Clone)]
+pub struct PeerRateLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: HashMap<PeerId, TokenBucket>,
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl PeerRateLimiter {
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self { rate: rate.max(0.0), burst: burst.max(1) as f64, buckets: HashMap::new() }
+    }
+
+    /// Spend a token for a message from `peer`; returns false if it should be dropped
+    pub fn allow(&mut self, peer: PeerId, now: Instant) -> bool {
+        let bucket = self.buckets
+            .entry(peer)
+            .or_insert(TokenBucket { tokens: self.burst, refilled_at: now });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drop the bucket of a peer we are no longer connected to
+    pub fn forget(&mut self, peer: &PeerId) {
+        self.buckets.remove(peer);
+    }
+}
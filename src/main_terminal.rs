@@ -47,6 +47,8 @@ async fn main() -> Result<()> {
     
     // Initialize core systems
     let mut core = ArceonCore::new(config.clone()).await?;
+    let player_store = arceon_core::persistence::PlayerStore::from_data_directory(std::path::Path::new(&config.database.data_dir))?;
+    core.enable_player_persistence(player_store);
     
     // Initialize blockchain manager
     let blockchain = BlockchainManager;
@@ -138,6 +140,7 @@ async fn main() -> Result<()> {
         }
         
         if command.to_lowercase() == "quit" || command.to_lowercase() == "exit" {
+            core.remove_player(&player_id).await?;
             println!("Farewell, adventurer!");
             break;
         }
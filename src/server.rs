@@ -8,7 +8,7 @@ use warp::Filter;
 use serde_json::json;
 use uuid::Uuid;
 
-use arceon_core::{ArceonCore, Config, NetworkManager, BlockchainManager};
+use arceon_core::{ArceonCore, Config, NetworkManager, BlockchainManager, GameCommand};
 use arceon_world::WorldManager;
 
 // Import our authentication, database, group, guild, and skill migration systems
@@ -145,6 +145,15 @@ async fn main() -> Result<()> {
             }))
         });
     
+    // World layout, so clients draw the map and follow exits from the server's areas
+    let world_layout = api
+        .and(warp::path("world"))
+        .and(warp::path("layout"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_core(core_state.clone()))
+        .and_then(handle_world_layout);
+    
     // Authentication endpoints
     let register = api
         .and(warp::path("auth"))
@@ -362,6 +371,7 @@ async fn main() -> Result<()> {
     
     // Combine routes
     let routes = health
+        .or(world_layout)
         .or(register)
         .or(login)
         .or(logout)
@@ -437,7 +447,7 @@ struct CreatePlayerResponse {
 
 #[derive(Deserialize)]
 struct ProcessCommandRequest {
-    command: String,
+    command: GameCommand,
     character_id: Option<String>,
 }
 
@@ -732,6 +742,34 @@ async fn handle_create_player(
     }
 }
 
+async fn handle_world_layout(
+    core_state: Arc<RwLock<arceon_core::state::GameState>>
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let state = core_state.read().await;
+    let area_names: std::collections::HashMap<Uuid, &str> = state.areas.values()
+        .map(|area| (area.id, area.name.as_str()))
+        .collect();
+    
+    let areas: Vec<serde_json::Value> = state.areas.values().map(|area| {
+        // Connections carry no compass direction, so exits are numbered the way `go <number>` uses them
+        let exits: Vec<serde_json::Value> = area.connected_areas.iter().enumerate()
+            .filter_map(|(i, connection)| area_names.get(&connection.target_area_id).map(|target| json!({
+                "direction": (i + 1).to_string(),
+                "target_area": target,
+                "description": connection.description,
+            })))
+            .collect();
+        json!({
+            "name": area.name,
+            "description": area.description,
+            "exits": exits,
+            "structures": [],
+        })
+    }).collect();
+    
+    Ok(warp::reply::json(&json!({ "areas": areas })))
+}
+
 async fn handle_process_command(
     req: ProcessCommandRequest,
     session_id_str: String,
@@ -745,8 +783,8 @@ async fn handle_process_command(
         let mut auth = auth_manager.write().await;
         if let Ok(Some(session)) = auth.validate_session(session_id).await {
             // Process command with game state
-            let response_text = match req.command.as_str() {
-                "look" => {
+            let response_text = match &req.command {
+                GameCommand::Look { .. } => {
                     let state = core_state.read().await;
                     if state.areas.is_empty() {
                         "World is still loading...".to_string()
@@ -755,26 +793,40 @@ async fn handle_process_command(
                         format!("You are in {}.\n{}", first_area.name, first_area.description)
                     }
                 },
-                "who" => {
+                GameCommand::Who => {
                     let state = core_state.read().await;
                     format!("Players online ({}): Connected via authenticated API", state.online_players.len())
                 },
-                "stats" => {
-                    if let Some(user) = auth.users.get(&session.user_id.to_string()) {
+                GameCommand::Stats => {
+                    let state = core_state.read().await;
+                    let being = req.character_id.as_ref()
+                        .and_then(|character_id| state.online_players.get(character_id))
+                        .and_then(|player_data| state.beings.get(&player_data.being_id));
+                    
+                    if let Some(being) = being {
+                        let mut stats = format!("=== {} ===\nRace: {:?}\nHealth: {:.1}/{:.1}\nMana: {:.1}/{:.1}\n\nSkills:\n",
+                            being.name, being.race,
+                            being.vitals.health.current, being.vitals.health.maximum,
+                            being.vitals.mana.current, being.vitals.mana.maximum);
+                        for (skill_name, level, experience) in being.get_formatted_skills() {
+                            stats.push_str(&format!("  {}: {} (XP: {})\n", skill_name, level, experience));
+                        }
+                        stats
+                    } else if let Some(user) = auth.users.get(&session.user_id.to_string()) {
                         let active_character = user.character_slots.iter()
                             .find(|slot| slot.is_active && slot.character_id.is_some())
                             .and_then(|slot| slot.character_name.as_ref())
                             .map(|name| name.as_str())
                             .unwrap_or("No active character");
                         
-                        format!("=== {} ===\nCharacter: {}\nHealth: 100/100\nMana: 50/50\nLevel: 1\n\n✅ Authenticated session active!", 
+                        format!("=== {} ===\nCharacter: {}\nThis character is not in the world yet.\n\n✅ Authenticated session active!", 
                             session.username,
                             active_character)
                     } else {
                         "Character stats unavailable".to_string()
                     }
                 },
-                "profile" => {
+                GameCommand::Profile => {
                     if let Some(user) = auth.users.get(&session.user_id.to_string()) {
                         let character_count = user.character_slots.iter().filter(|slot| slot.character_id.is_some()).count();
                         format!("=== Profile: {} ===\nAccount ID: {}\nCharacters: {}\nWallet Bound: {}\nLast Login: {:?}",
@@ -787,7 +839,7 @@ async fn handle_process_command(
                         "Profile unavailable".to_string()
                     }
                 },
-                "skills" => {
+                GameCommand::Skills => {
                     if let Some(user) = auth.users.get(&session.user_id.to_string()) {
                         let active_character = user.character_slots.iter()
                             .find(|slot| slot.is_active && slot.character_id.is_some());
@@ -815,6 +867,9 @@ async fn handle_process_command(
                         "Skills unavailable - user not found".to_string()
                     }
                 },
+                GameCommand::Save => {
+                    "Character saving is not enabled on this server.".to_string()
+                },
                 _ => format!("🎮 Command processed: {}\n✅ Authenticated server connection active!", req.command),
             };
             